@@ -0,0 +1,75 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use mech3ax_anim::{AnimDefIndex, AnimDefLookup as _};
+use mech3ax_api_types::anim::{AnimActivation, AnimDef, Execution, NamePad, NamePtr};
+
+const NODE_COUNT: usize = 1000;
+
+fn anim_def() -> AnimDef {
+    AnimDef {
+        name: "bench".to_string(),
+        anim_name: NamePad {
+            name: "bench".to_string(),
+            pad: Vec::new(),
+        },
+        anim_root: NamePad {
+            name: "bench".to_string(),
+            pad: Vec::new(),
+        },
+        file_name: "bench.flt".to_string(),
+        auto_reset_node_states: true,
+        activation: AnimActivation::OnCall,
+        execution: Execution::None,
+        network_log: None,
+        save_log: None,
+        has_callbacks: false,
+        reset_time: None,
+        health: 0.0,
+        proximity_damage: true,
+        activ_prereq_min_to_satisfy: 0,
+        objects: None,
+        nodes: Some(
+            (0..NODE_COUNT)
+                .map(|i| NamePtr {
+                    name: format!("node{}", i),
+                    pointer: 0,
+                })
+                .collect(),
+        ),
+        lights: None,
+        puffers: None,
+        dynamic_sounds: None,
+        static_sounds: None,
+        activ_prereqs: None,
+        anim_refs: None,
+        reset_state: None,
+        sequences: Vec::new(),
+    }
+}
+
+/// Look up every node once, like writing an event per node would.
+fn lookup_all(c: &mut Criterion) {
+    let anim_def = anim_def();
+    let names: Vec<String> = (0..NODE_COUNT).map(|i| format!("node{}", i)).collect();
+
+    let mut group = c.benchmark_group("node_to_index");
+    group.bench_function("scan", |b| {
+        b.iter(|| {
+            for name in &names {
+                black_box(anim_def.node_to_index(name).unwrap());
+            }
+        })
+    });
+    group.bench_function("index", |b| {
+        b.iter(|| {
+            // includes building the index, which happens once per anim def
+            let index = AnimDefIndex::new(&anim_def);
+            for name in &names {
+                black_box(index.node_to_index(name).unwrap());
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, lookup_all);
+criterion_main!(benches);
@@ -2,3 +2,5 @@
 #![allow(clippy::identity_op)]
 pub mod mw;
 mod types;
+
+pub use types::{AnimDefIndex, AnimDefLookup};
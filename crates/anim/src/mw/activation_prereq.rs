@@ -1,7 +1,7 @@
 use bytemuck::{AnyBitPattern, NoUninit};
 use mech3ax_api_types::anim::{ActivationPrereq, PrereqAnimation, PrereqObject, PrereqParent};
 use mech3ax_common::assert::assert_utf8;
-use mech3ax_common::io_ext::{CountingReader, CountingWriter};
+use mech3ax_common::io_ext::{read_array, CountingReader, CountingWriter};
 use mech3ax_common::{assert_that, Result};
 use mech3ax_types::{impl_as_bytes, primitive_enum, Ascii, Bool32, Maybe};
 use std::io::{Read, Write};
@@ -21,7 +21,7 @@ struct ActivPrereqAnimC {
     zero32: u32,     // 32
     zero36: u32,     // 36
 }
-impl_as_bytes!(ActivPrereqAnimC, 40);
+impl_as_bytes!(ActivPrereqAnimC, 40, LeStruct);
 
 #[derive(Debug, Clone, Copy, NoUninit, AnyBitPattern)]
 #[repr(C)]
@@ -30,7 +30,7 @@ struct ActivPrereqObjC {
     name: Ascii<32>, // 32
     pointer: u32,    // 36
 }
-impl_as_bytes!(ActivPrereqObjC, 40);
+impl_as_bytes!(ActivPrereqObjC, 40, LeStruct);
 
 fn read_activ_prereq_anim(read: &mut CountingReader<impl Read>) -> Result<ActivationPrereq> {
     let prereq: ActivPrereqAnimC = read.read_struct()?;
@@ -121,11 +121,13 @@ pub fn read_activ_prereqs(
     read: &mut CountingReader<impl Read>,
     count: u8,
 ) -> Result<Vec<ActivationPrereq>> {
-    (0..count).map(|_| read_activ_prereq(read)).collect()
+    read_array(read, count.into(), |read, _index| read_activ_prereq(read))
 }
 
 fn write_activ_prereq_anim(write: &mut CountingWriter<impl Write>, name: &str) -> Result<()> {
-    let fill = Ascii::from_str_padded(name);
+    let fill = assert_utf8("anim def activ prereq a name", write.offset + 8, || {
+        Ascii::from_str_padded(name)
+    })?;
     // always required (not optional)
     write.write_u32(false.into())?;
     write.write_u32(ActivPrereqType::Animation as u32)?;
@@ -142,7 +144,9 @@ fn write_activ_prereq_object(
     object: &PrereqObject,
     prereq_type: ActivPrereqType,
 ) -> Result<()> {
-    let name = Ascii::from_str_padded(&object.name);
+    let name = assert_utf8("anim def activ prereq o name", write.offset + 12, || {
+        Ascii::from_str_padded(&object.name)
+    })?;
     write.write_u32((!object.required).into())?;
     write.write_u32(prereq_type as u32)?;
     write.write_struct(&ActivPrereqObjC {
@@ -158,7 +162,9 @@ fn write_activ_prereq_parent(
     parent: &PrereqParent,
     prereq_type: ActivPrereqType,
 ) -> Result<()> {
-    let name = Ascii::from_str_padded(&parent.name);
+    let name = assert_utf8("anim def activ prereq p name", write.offset + 12, || {
+        Ascii::from_str_padded(&parent.name)
+    })?;
     write.write_u32((!parent.required).into())?;
     write.write_u32(prereq_type as u32)?;
     write.write_struct(&ActivPrereqObjC {
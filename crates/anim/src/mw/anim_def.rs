@@ -1,6 +1,7 @@
 use super::activation_prereq::{read_activ_prereqs, write_activ_prereqs};
 use super::sequence_event::{read_events, size_events, write_events};
 use super::support::*;
+use crate::types::AnimDefIndex;
 use bytemuck::{AnyBitPattern, NoUninit};
 use log::trace;
 use mech3ax_api_types::anim::events::EventData;
@@ -80,7 +81,7 @@ struct AnimDefC {
     anim_refs_ptr: u32,              // 308
     zero312: u32,                    // 312
 }
-impl_as_bytes!(AnimDefC, 316);
+impl_as_bytes!(AnimDefC, 316, LeStruct);
 
 #[derive(Debug, Clone, Copy, NoUninit, AnyBitPattern)]
 #[repr(C)]
@@ -91,7 +92,7 @@ struct SeqDefInfoC {
     pointer: u32,      // 56
     size: u32,         // 60
 }
-impl_as_bytes!(SeqDefInfoC, 64);
+impl_as_bytes!(SeqDefInfoC, 64, LeStruct);
 const RESET_SEQUENCE: Ascii<32> = Ascii::new(b"RESET_SEQUENCE\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0");
 
 pub fn read_anim_def_zero(read: &mut CountingReader<impl Read>) -> Result<()> {
@@ -610,7 +611,7 @@ pub fn write_anim_def_zero(write: &mut CountingWriter<impl Write>) -> Result<()>
 
 fn write_reset_state(
     write: &mut CountingWriter<impl Write>,
-    anim_def: &AnimDef,
+    anim_def: &AnimDefIndex<'_>,
     size: u32,
 ) -> Result<()> {
     write.write_struct(&SeqDefInfoC {
@@ -631,9 +632,14 @@ fn write_reset_state(
     Ok(())
 }
 
-fn write_sequence_defs(write: &mut CountingWriter<impl Write>, anim_def: &AnimDef) -> Result<()> {
+fn write_sequence_defs(
+    write: &mut CountingWriter<impl Write>,
+    anim_def: &AnimDefIndex<'_>,
+) -> Result<()> {
     for seq_def in &anim_def.sequences {
-        let name = Ascii::from_str_padded(&seq_def.name);
+        let name = assert_utf8("anim def seq def name", write.offset, || {
+            Ascii::from_str_padded(&seq_def.name)
+        })?;
         let flags = if seq_def.activation == SeqActivation::OnCall {
             0x0303
         } else {
@@ -658,7 +664,9 @@ pub fn write_anim_def(
     anim_ptr: &AnimPtr,
 ) -> Result<()> {
     let anim_name = Ascii::from_str_garbage(&anim_def.anim_name.name, &anim_def.anim_name.pad);
-    let name = Ascii::from_str_padded(&anim_def.name);
+    let name = assert_utf8("anim def name", write.offset + 32, || {
+        Ascii::from_str_padded(&anim_def.name)
+    })?;
     let anim_root = Ascii::from_str_garbage(&anim_def.anim_root.name, &anim_def.anim_root.pad);
 
     let mut flags = AnimDefFlags::empty();
@@ -812,8 +820,10 @@ pub fn write_anim_def(
     if let Some(anim_refs) = &anim_def.anim_refs {
         write_anim_refs(write, anim_refs)?;
     }
-    write_reset_state(write, anim_def, reset_state_size)?;
-    write_sequence_defs(write, anim_def)?;
+    // events look up names many times, so build the indices once
+    let anim_def = AnimDefIndex::new(anim_def);
+    write_reset_state(write, &anim_def, reset_state_size)?;
+    write_sequence_defs(write, &anim_def)?;
 
     Ok(())
 }
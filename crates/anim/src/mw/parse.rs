@@ -26,7 +26,7 @@ struct AnimNameC {
     name: Ascii<80>,
     unknown: u32,
 }
-impl_as_bytes!(AnimNameC, 84);
+impl_as_bytes!(AnimNameC, 84, LeStruct);
 
 #[derive(Debug, Clone, Copy, NoUninit, AnyBitPattern)]
 #[repr(C)]
@@ -50,14 +50,14 @@ struct AnimInfoC {
     one60: u32,
     zero64: u32,
 }
-impl_as_bytes!(AnimInfoC, 68);
+impl_as_bytes!(AnimInfoC, 68, LeStruct);
 
 fn read_anim_header(read: &mut CountingReader<impl Read>) -> Result<Vec<AnimName>> {
     trace!("Reading anim header at {}", read.offset);
     let signature = read.read_u32()?;
     assert_that!("signature", signature == SIGNATURE, read.prev)?;
     let version = read.read_u32()?;
-    assert_that!("version", version == VERSION_MW, read.prev)?;
+    assert_that!("version", version version == VERSION_MW, read.prev)?;
     let count = read.read_u32()?;
 
     trace!("Reading anim names at {}", read.offset);
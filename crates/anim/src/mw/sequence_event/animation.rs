@@ -1,4 +1,5 @@
 use super::ScriptObject;
+use crate::types::AnimDefIndex;
 use bytemuck::{AnyBitPattern, NoUninit};
 use mech3ax_api_types::anim::events::{InvalidateAnimation, ResetAnimation, StopAnimation};
 use mech3ax_api_types::anim::AnimDef;
@@ -14,7 +15,7 @@ struct AnimationC {
     name: Ascii<32>,
     zero32: u32,
 }
-impl_as_bytes!(AnimationC, 36);
+impl_as_bytes!(AnimationC, 36, LeStruct);
 
 fn read_animation(read: &mut CountingReader<impl Read>) -> Result<String> {
     let animation: AnimationC = read.read_struct()?;
@@ -26,7 +27,9 @@ fn read_animation(read: &mut CountingReader<impl Read>) -> Result<String> {
 }
 
 fn write_animation(write: &mut CountingWriter<impl Write>, name: &str) -> Result<()> {
-    let fill = Ascii::from_str_padded(name);
+    let fill = assert_utf8("animation name", write.offset, || {
+        Ascii::from_str_padded(name)
+    })?;
     write.write_struct(&AnimationC {
         name: fill,
         zero32: 0,
@@ -38,13 +41,16 @@ impl ScriptObject for StopAnimation {
     const INDEX: u8 = 25;
     const SIZE: u32 = AnimationC::SIZE;
 
-    fn read(read: &mut CountingReader<impl Read>, _anim_def: &AnimDef, size: u32) -> Result<Self> {
-        assert_that!("stop animation size", size == Self::SIZE, read.offset)?;
+    fn read(read: &mut CountingReader<impl Read>, _anim_def: &AnimDef, _size: u32) -> Result<Self> {
         let name = read_animation(read)?;
         Ok(Self { name })
     }
 
-    fn write(&self, write: &mut CountingWriter<impl Write>, _anim_def: &AnimDef) -> Result<()> {
+    fn write(
+        &self,
+        write: &mut CountingWriter<impl Write>,
+        _anim_def: &AnimDefIndex<'_>,
+    ) -> Result<()> {
         write_animation(write, &self.name)
     }
 }
@@ -53,13 +59,16 @@ impl ScriptObject for ResetAnimation {
     const INDEX: u8 = 26;
     const SIZE: u32 = AnimationC::SIZE;
 
-    fn read(read: &mut CountingReader<impl Read>, _anim_def: &AnimDef, size: u32) -> Result<Self> {
-        assert_that!("reset animation size", size == Self::SIZE, read.offset)?;
+    fn read(read: &mut CountingReader<impl Read>, _anim_def: &AnimDef, _size: u32) -> Result<Self> {
         let name = read_animation(read)?;
         Ok(Self { name })
     }
 
-    fn write(&self, write: &mut CountingWriter<impl Write>, _anim_def: &AnimDef) -> Result<()> {
+    fn write(
+        &self,
+        write: &mut CountingWriter<impl Write>,
+        _anim_def: &AnimDefIndex<'_>,
+    ) -> Result<()> {
         write_animation(write, &self.name)
     }
 }
@@ -68,13 +77,16 @@ impl ScriptObject for InvalidateAnimation {
     const INDEX: u8 = 27;
     const SIZE: u32 = AnimationC::SIZE;
 
-    fn read(read: &mut CountingReader<impl Read>, _anim_def: &AnimDef, size: u32) -> Result<Self> {
-        assert_that!("invalidate animation size", size == Self::SIZE, read.offset)?;
+    fn read(read: &mut CountingReader<impl Read>, _anim_def: &AnimDef, _size: u32) -> Result<Self> {
         let name = read_animation(read)?;
         Ok(Self { name })
     }
 
-    fn write(&self, write: &mut CountingWriter<impl Write>, _anim_def: &AnimDef) -> Result<()> {
+    fn write(
+        &self,
+        write: &mut CountingWriter<impl Write>,
+        _anim_def: &AnimDefIndex<'_>,
+    ) -> Result<()> {
         write_animation(write, &self.name)
     }
 }
@@ -1,6 +1,6 @@
 use super::types::INPUT_NODE;
 use super::ScriptObject;
-use crate::types::AnimDefLookup as _;
+use crate::types::{AnimDefIndex, AnimDefLookup as _};
 use bytemuck::{AnyBitPattern, NoUninit};
 use mech3ax_api_types::anim::events::{
     CallAnimation, CallAnimationAtNode, CallAnimationParameters, CallAnimationTargetNode,
@@ -10,7 +10,7 @@ use mech3ax_api_types::anim::AnimDef;
 use mech3ax_api_types::Vec3;
 use mech3ax_common::assert::assert_utf8;
 use mech3ax_common::io_ext::{CountingReader, CountingWriter};
-use mech3ax_common::{assert_that, Result};
+use mech3ax_common::{assert_that, assert_with_msg, Result};
 use mech3ax_types::{bitflags, impl_as_bytes, AsBytes as _, Ascii, Maybe};
 use std::io::{Read, Write};
 
@@ -45,14 +45,13 @@ struct CallAnimationC {
     translation: Vec3,        // 44
     rotation: Vec3,           // 56
 }
-impl_as_bytes!(CallAnimationC, 68);
+impl_as_bytes!(CallAnimationC, 68, LeStruct);
 
 impl ScriptObject for CallAnimation {
     const INDEX: u8 = 24;
     const SIZE: u32 = CallAnimationC::SIZE;
 
-    fn read(read: &mut CountingReader<impl Read>, anim_def: &AnimDef, size: u32) -> Result<Self> {
-        assert_that!("call animation size", size == Self::SIZE, read.offset)?;
+    fn read(read: &mut CountingReader<impl Read>, anim_def: &AnimDef, _size: u32) -> Result<Self> {
         let call_animation: CallAnimationC = read.read_struct()?;
 
         let name = assert_utf8("call animation name", read.prev + 0, || {
@@ -68,8 +67,19 @@ impl ScriptObject for CallAnimation {
         )?;
 
         let wait_for_completion = if flags.contains(CallAnimationFlags::WAIT_FOR) {
-            let max_prev_ref = anim_def.anim_refs.as_ref().map(|v| v.len()).unwrap_or(0) as u16 - 1;
-            assert_that!("call animation wait for", 0 <= call_animation.wait_for_completion <= max_prev_ref, read.prev + 38)?;
+            // the animation waited for must be a previous call
+            let max_prev_ref = anim_def
+                .anim_refs
+                .as_ref()
+                .and_then(|anim_refs| anim_refs.len().checked_sub(1))
+                .ok_or_else(|| {
+                    assert_with_msg!(
+                        "Expected `call animation wait for` to have an anim ref to wait for, but there are none (at {})",
+                        read.prev + 38
+                    )
+                })?;
+            let wait_for = usize::from(call_animation.wait_for_completion);
+            assert_that!("call animation wait for", 0 <= wait_for <= max_prev_ref, read.prev + 38)?;
             Some(call_animation.wait_for_completion)
         } else {
             assert_that!(
@@ -177,8 +187,14 @@ impl ScriptObject for CallAnimation {
         })
     }
 
-    fn write(&self, write: &mut CountingWriter<impl Write>, anim_def: &AnimDef) -> Result<()> {
-        let name = Ascii::from_str_padded(&self.name);
+    fn write(
+        &self,
+        write: &mut CountingWriter<impl Write>,
+        anim_def: &AnimDefIndex<'_>,
+    ) -> Result<()> {
+        let name = assert_utf8("call animation name", write.offset, || {
+            Ascii::from_str_padded(&self.name)
+        })?;
         let mut flags = CallAnimationFlags::empty();
         if self.wait_for_completion.is_some() {
             flags |= CallAnimationFlags::WAIT_FOR;
@@ -1,6 +1,6 @@
 use super::types::INPUT_NODE;
 use super::ScriptObject;
-use crate::types::AnimDefLookup as _;
+use crate::types::{AnimDefIndex, AnimDefLookup as _};
 use bytemuck::{AnyBitPattern, NoUninit};
 use mech3ax_api_types::anim::events::CallObjectConnector;
 use mech3ax_api_types::anim::AnimDef;
@@ -29,18 +29,13 @@ struct CallObjectConnectorC {
     from_pos: Vec3,
     to_pos: Vec3,
 }
-impl_as_bytes!(CallObjectConnectorC, 68);
+impl_as_bytes!(CallObjectConnectorC, 68, LeStruct);
 
 impl ScriptObject for CallObjectConnector {
     const INDEX: u8 = 19;
     const SIZE: u32 = CallObjectConnectorC::SIZE;
 
-    fn read(read: &mut CountingReader<impl Read>, anim_def: &AnimDef, size: u32) -> Result<Self> {
-        assert_that!(
-            "call object connector size",
-            size == Self::SIZE,
-            read.offset
-        )?;
+    fn read(read: &mut CountingReader<impl Read>, anim_def: &AnimDef, _size: u32) -> Result<Self> {
         let call_obj_connector: CallObjectConnectorC = read.read_struct()?;
         assert_that!(
             "call object connector flags",
@@ -87,8 +82,14 @@ impl ScriptObject for CallObjectConnector {
         })
     }
 
-    fn write(&self, write: &mut CountingWriter<impl Write>, anim_def: &AnimDef) -> Result<()> {
-        let node = Ascii::from_str_padded(&self.node);
+    fn write(
+        &self,
+        write: &mut CountingWriter<impl Write>,
+        anim_def: &AnimDefIndex<'_>,
+    ) -> Result<()> {
+        let node = assert_utf8("call object connector node name", write.offset + 4, || {
+            Ascii::from_str_padded(&self.node)
+        })?;
         let from_index = anim_def.node_to_index(&self.from_node)? as u16;
         write.write_struct(&CallObjectConnectorC {
             flags: FLAGS,
@@ -1,4 +1,5 @@
 use super::ScriptObject;
+use crate::types::AnimDefIndex;
 use bytemuck::{AnyBitPattern, NoUninit};
 use mech3ax_api_types::anim::events::{Callback, Else, ElseIf, EndIf, If, Loop};
 use mech3ax_api_types::anim::AnimDef;
@@ -13,14 +14,13 @@ struct LoopC {
     start: i32,
     loop_count: i32,
 }
-impl_as_bytes!(LoopC, 8);
+impl_as_bytes!(LoopC, 8, LeStruct);
 
 impl ScriptObject for Loop {
     const INDEX: u8 = 30;
     const SIZE: u32 = LoopC::SIZE;
 
-    fn read(read: &mut CountingReader<impl Read>, _anim_def: &AnimDef, size: u32) -> Result<Self> {
-        assert_that!("loop size", size == LoopC::SIZE, read.offset)?;
+    fn read(read: &mut CountingReader<impl Read>, _anim_def: &AnimDef, _size: u32) -> Result<Self> {
         let loop_: LoopC = read.read_struct()?;
         assert_that!("loop start", loop_.start == 1, read.prev + 0)?;
         Ok(Loop {
@@ -29,7 +29,11 @@ impl ScriptObject for Loop {
         })
     }
 
-    fn write(&self, write: &mut CountingWriter<impl Write>, _anim_def: &AnimDef) -> Result<()> {
+    fn write(
+        &self,
+        write: &mut CountingWriter<impl Write>,
+        _anim_def: &AnimDefIndex<'_>,
+    ) -> Result<()> {
         write.write_struct(&LoopC {
             start: self.start,
             loop_count: self.loop_count,
@@ -82,14 +86,13 @@ struct IfC {
     zero4: u32,
     value: [u8; 4],
 }
-impl_as_bytes!(IfC, 12);
+impl_as_bytes!(IfC, 12, LeStruct);
 
 impl ScriptObject for If {
     const INDEX: u8 = 31;
     const SIZE: u32 = IfC::SIZE;
 
-    fn read(read: &mut CountingReader<impl Read>, _anim_def: &AnimDef, size: u32) -> Result<Self> {
-        assert_that!("if size", size == Self::SIZE, read.offset)?;
+    fn read(read: &mut CountingReader<impl Read>, _anim_def: &AnimDef, _size: u32) -> Result<Self> {
         let if_: IfC = read.read_struct()?;
 
         let condition = assert_that!("if cond", enum if_.condition, read.prev + 0)?;
@@ -112,7 +115,11 @@ impl ScriptObject for If {
         }
     }
 
-    fn write(&self, write: &mut CountingWriter<impl Write>, _anim_def: &AnimDef) -> Result<()> {
+    fn write(
+        &self,
+        write: &mut CountingWriter<impl Write>,
+        _anim_def: &AnimDefIndex<'_>,
+    ) -> Result<()> {
         let (condition, value) = match self {
             If::RandomWeight(value) => (Condition::RandomWeight, value.to_le_bytes()),
             If::PlayerRange(value) => (Condition::PlayerRange, value.to_le_bytes()),
@@ -139,8 +146,7 @@ impl ScriptObject for ElseIf {
     const INDEX: u8 = 33;
     const SIZE: u32 = IfC::SIZE;
 
-    fn read(read: &mut CountingReader<impl Read>, _anim_def: &AnimDef, size: u32) -> Result<Self> {
-        assert_that!("else if size", size == Self::SIZE, read.offset)?;
+    fn read(read: &mut CountingReader<impl Read>, _anim_def: &AnimDef, _size: u32) -> Result<Self> {
         let if_: IfC = read.read_struct()?;
 
         let condition = assert_that!("else if cond", enum if_.condition, read.prev + 0)?;
@@ -167,7 +173,11 @@ impl ScriptObject for ElseIf {
         }
     }
 
-    fn write(&self, write: &mut CountingWriter<impl Write>, _anim_def: &AnimDef) -> Result<()> {
+    fn write(
+        &self,
+        write: &mut CountingWriter<impl Write>,
+        _anim_def: &AnimDefIndex<'_>,
+    ) -> Result<()> {
         let (condition, value) = match self {
             ElseIf::RandomWeight(value) => (Condition::RandomWeight, value.to_le_bytes()),
             ElseIf::PlayerRange(value) => (Condition::PlayerRange, value.to_le_bytes()),
@@ -194,12 +204,19 @@ impl ScriptObject for Else {
     const INDEX: u8 = 32;
     const SIZE: u32 = 0;
 
-    fn read(read: &mut CountingReader<impl Read>, _anim_def: &AnimDef, size: u32) -> Result<Self> {
-        assert_that!("else size", size == Self::SIZE, read.offset)?;
+    fn read(
+        _read: &mut CountingReader<impl Read>,
+        _anim_def: &AnimDef,
+        _size: u32,
+    ) -> Result<Self> {
         Ok(Self {})
     }
 
-    fn write(&self, _write: &mut CountingWriter<impl Write>, _anim_def: &AnimDef) -> Result<()> {
+    fn write(
+        &self,
+        _write: &mut CountingWriter<impl Write>,
+        _anim_def: &AnimDefIndex<'_>,
+    ) -> Result<()> {
         Ok(())
     }
 }
@@ -208,12 +225,19 @@ impl ScriptObject for EndIf {
     const INDEX: u8 = 34;
     const SIZE: u32 = 0;
 
-    fn read(read: &mut CountingReader<impl Read>, _anim_def: &AnimDef, size: u32) -> Result<Self> {
-        assert_that!("end if size", size == Self::SIZE, read.offset)?;
+    fn read(
+        _read: &mut CountingReader<impl Read>,
+        _anim_def: &AnimDef,
+        _size: u32,
+    ) -> Result<Self> {
         Ok(Self {})
     }
 
-    fn write(&self, _write: &mut CountingWriter<impl Write>, _anim_def: &AnimDef) -> Result<()> {
+    fn write(
+        &self,
+        _write: &mut CountingWriter<impl Write>,
+        _anim_def: &AnimDefIndex<'_>,
+    ) -> Result<()> {
         Ok(())
     }
 }
@@ -222,8 +246,7 @@ impl ScriptObject for Callback {
     const INDEX: u8 = 35;
     const SIZE: u32 = 4;
 
-    fn read(read: &mut CountingReader<impl Read>, anim_def: &AnimDef, size: u32) -> Result<Self> {
-        assert_that!("callback size", size == Self::SIZE, read.offset)?;
+    fn read(read: &mut CountingReader<impl Read>, anim_def: &AnimDef, _size: u32) -> Result<Self> {
         assert_that!(
             "anim def has callbacks",
             anim_def.has_callbacks == true,
@@ -233,7 +256,11 @@ impl ScriptObject for Callback {
         Ok(Self { value })
     }
 
-    fn write(&self, write: &mut CountingWriter<impl Write>, _anim_def: &AnimDef) -> Result<()> {
+    fn write(
+        &self,
+        write: &mut CountingWriter<impl Write>,
+        _anim_def: &AnimDefIndex<'_>,
+    ) -> Result<()> {
         write.write_u32(self.value)?;
         Ok(())
     }
@@ -1,12 +1,12 @@
 use super::ScriptObject;
-use crate::types::AnimDefLookup as _;
+use crate::types::{AnimDefIndex, AnimDefLookup as _};
 use bytemuck::{AnyBitPattern, NoUninit};
 use mech3ax_api_types::anim::events::{AtNode, DetonateWeapon};
 use mech3ax_api_types::anim::AnimDef;
 use mech3ax_api_types::Vec3;
 use mech3ax_common::assert::assert_utf8;
 use mech3ax_common::io_ext::{CountingReader, CountingWriter};
-use mech3ax_common::{assert_that, Result};
+use mech3ax_common::Result;
 use mech3ax_types::{impl_as_bytes, AsBytes as _, Ascii};
 use std::io::{Read, Write};
 
@@ -17,14 +17,13 @@ struct DetonateWeaponC {
     node_index: u16,
     translation: Vec3,
 }
-impl_as_bytes!(DetonateWeaponC, 24);
+impl_as_bytes!(DetonateWeaponC, 24, LeStruct);
 
 impl ScriptObject for DetonateWeapon {
     const INDEX: u8 = 41;
     const SIZE: u32 = DetonateWeaponC::SIZE;
 
-    fn read(read: &mut CountingReader<impl Read>, anim_def: &AnimDef, size: u32) -> Result<Self> {
-        assert_that!("detonate weapon size", size == Self::SIZE, read.offset)?;
+    fn read(read: &mut CountingReader<impl Read>, anim_def: &AnimDef, _size: u32) -> Result<Self> {
         let detonate_weapon: DetonateWeaponC = read.read_struct()?;
         let name = assert_utf8("detonate weapon name", read.prev + 0, || {
             detonate_weapon.name.to_str_padded()
@@ -39,8 +38,14 @@ impl ScriptObject for DetonateWeapon {
         })
     }
 
-    fn write(&self, write: &mut CountingWriter<impl Write>, anim_def: &AnimDef) -> Result<()> {
-        let name = Ascii::from_str_padded(&self.name);
+    fn write(
+        &self,
+        write: &mut CountingWriter<impl Write>,
+        anim_def: &AnimDefIndex<'_>,
+    ) -> Result<()> {
+        let name = assert_utf8("detonate weapon name", write.offset, || {
+            Ascii::from_str_padded(&self.name)
+        })?;
         write.write_struct(&DetonateWeaponC {
             name,
             node_index: anim_def.node_to_index(&self.at_node.node)? as u16,
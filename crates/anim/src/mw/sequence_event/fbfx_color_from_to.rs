@@ -1,5 +1,6 @@
 use super::delta::{dec_f32, delta};
 use super::ScriptObject;
+use crate::types::AnimDefIndex;
 use bytemuck::{AnyBitPattern, NoUninit};
 use mech3ax_api_types::anim::events::{FrameBufferEffectColor, Rgba};
 use mech3ax_api_types::anim::AnimDef;
@@ -25,14 +26,13 @@ struct FbFxColorFromToC {
     delta_alpha: f32,
     runtime: f32,
 }
-impl_as_bytes!(FbFxColorFromToC, 52);
+impl_as_bytes!(FbFxColorFromToC, 52, LeStruct);
 
 impl ScriptObject for FrameBufferEffectColor {
     const INDEX: u8 = 36;
     const SIZE: u32 = FbFxColorFromToC::SIZE;
 
-    fn read(read: &mut CountingReader<impl Read>, _anim_def: &AnimDef, size: u32) -> Result<Self> {
-        assert_that!("fbfx color from to size", size == Self::SIZE, read.offset)?;
+    fn read(read: &mut CountingReader<impl Read>, _anim_def: &AnimDef, _size: u32) -> Result<Self> {
         let fbfx: FbFxColorFromToC = read.read_struct()?;
 
         assert_that!("fbfx color from red", 0.0 <= fbfx.from_red <= 1.0, read.prev + 0)?;
@@ -114,7 +114,11 @@ impl ScriptObject for FrameBufferEffectColor {
         })
     }
 
-    fn write(&self, write: &mut CountingWriter<impl Write>, _anim_def: &AnimDef) -> Result<()> {
+    fn write(
+        &self,
+        write: &mut CountingWriter<impl Write>,
+        _anim_def: &AnimDefIndex<'_>,
+    ) -> Result<()> {
         let delta_red = delta(self.to.r, self.from.r, self.runtime);
         let delta_green = delta(self.to.g, self.from.g, self.runtime);
         let delta_blue = delta(self.to.b, self.from.b, self.runtime);
@@ -1,9 +1,11 @@
 use super::utils::assert_color;
 use super::ScriptObject;
+use crate::types::AnimDefIndex;
 use bytemuck::{AnyBitPattern, NoUninit};
 use mech3ax_api_types::anim::events::{FogState, FogType};
 use mech3ax_api_types::anim::AnimDef;
 use mech3ax_api_types::{Color, Range};
+use mech3ax_common::assert::assert_utf8;
 use mech3ax_common::io_ext::{CountingReader, CountingWriter};
 use mech3ax_common::{assert_that, Result};
 use mech3ax_types::{bitflags, impl_as_bytes, AsBytes as _, Ascii, Maybe};
@@ -37,17 +39,18 @@ struct FogStateC {
     altitude: Range, // 52
     range: Range,    // 60
 }
-impl_as_bytes!(FogStateC, 68);
+impl_as_bytes!(FogStateC, 68, LeStruct);
 
 impl ScriptObject for FogState {
     const INDEX: u8 = 28;
     const SIZE: u32 = FogStateC::SIZE;
 
-    fn read(read: &mut CountingReader<impl Read>, _anim_def: &AnimDef, size: u32) -> Result<Self> {
-        assert_that!("fog state size", size == Self::SIZE, read.offset)?;
+    fn read(read: &mut CountingReader<impl Read>, _anim_def: &AnimDef, _size: u32) -> Result<Self> {
         let fog_state: FogStateC = read.read_struct()?;
 
-        let name = Ascii::from_str_padded(DEFAULT_FOG_NAME);
+        let name = assert_utf8("fog state name", read.prev + 0, || {
+            Ascii::from_str_padded(DEFAULT_FOG_NAME)
+        })?;
         assert_that!("fog state name", fog_state.name == name, read.prev + 0)?;
 
         assert_that!(
@@ -72,8 +75,14 @@ impl ScriptObject for FogState {
         })
     }
 
-    fn write(&self, write: &mut CountingWriter<impl Write>, _anim_def: &AnimDef) -> Result<()> {
-        let name = Ascii::from_str_padded(DEFAULT_FOG_NAME);
+    fn write(
+        &self,
+        write: &mut CountingWriter<impl Write>,
+        _anim_def: &AnimDefIndex<'_>,
+    ) -> Result<()> {
+        let name = assert_utf8("fog state name", write.offset, || {
+            Ascii::from_str_padded(DEFAULT_FOG_NAME)
+        })?;
         let fog_type = match &self.fog_type {
             FogType::Off => FogType::Off as u32,
             FogType::Linear => FogType::Linear as u32,
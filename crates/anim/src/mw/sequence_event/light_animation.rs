@@ -1,5 +1,5 @@
 use super::ScriptObject;
-use crate::types::AnimDefLookup as _;
+use crate::types::{AnimDefIndex, AnimDefLookup as _};
 use bytemuck::{AnyBitPattern, NoUninit};
 use mech3ax_api_types::anim::events::LightAnimation;
 use mech3ax_api_types::anim::AnimDef;
@@ -29,14 +29,13 @@ struct LightAnimationC {
     zero92: f32,
     runtime: f32,
 }
-impl_as_bytes!(LightAnimationC, 100);
+impl_as_bytes!(LightAnimationC, 100, LeStruct);
 
 impl ScriptObject for LightAnimation {
     const INDEX: u8 = 5;
     const SIZE: u32 = LightAnimationC::SIZE;
 
-    fn read(read: &mut CountingReader<impl Read>, anim_def: &AnimDef, size: u32) -> Result<Self> {
-        assert_that!("light animation size", size == Self::SIZE, read.offset)?;
+    fn read(read: &mut CountingReader<impl Read>, anim_def: &AnimDef, _size: u32) -> Result<Self> {
         let light_anim: LightAnimationC = read.read_struct()?;
 
         // not sure why this information is duplicated?
@@ -135,8 +134,14 @@ impl ScriptObject for LightAnimation {
         })
     }
 
-    fn write(&self, write: &mut CountingWriter<impl Write>, anim_def: &AnimDef) -> Result<()> {
-        let name = Ascii::from_str_padded(&self.name);
+    fn write(
+        &self,
+        write: &mut CountingWriter<impl Write>,
+        anim_def: &AnimDefIndex<'_>,
+    ) -> Result<()> {
+        let name = assert_utf8("light anim name", write.offset, || {
+            Ascii::from_str_padded(&self.name)
+        })?;
         let light_index = anim_def.light_to_index(&self.name)? as u32;
 
         write.write_struct(&LightAnimationC {
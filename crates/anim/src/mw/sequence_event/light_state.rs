@@ -1,7 +1,7 @@
 use super::types::INPUT_NODE;
 use super::utils::assert_color;
 use super::ScriptObject;
-use crate::types::AnimDefLookup as _;
+use crate::types::{AnimDefIndex, AnimDefLookup as _};
 use bytemuck::{AnyBitPattern, NoUninit};
 use mech3ax_api_types::anim::events::{AtNode, LightState};
 use mech3ax_api_types::anim::AnimDef;
@@ -35,14 +35,13 @@ struct LightStateC {
     ambient: f32,         // 112
     diffuse: f32,         // 116
 }
-impl_as_bytes!(LightStateC, 120);
+impl_as_bytes!(LightStateC, 120, LeStruct);
 
 impl ScriptObject for LightState {
     const INDEX: u8 = 4;
     const SIZE: u32 = LightStateC::SIZE;
 
-    fn read(read: &mut CountingReader<impl Read>, anim_def: &AnimDef, size: u32) -> Result<Self> {
-        assert_that!("light state size", size == Self::SIZE, read.offset)?;
+    fn read(read: &mut CountingReader<impl Read>, anim_def: &AnimDef, _size: u32) -> Result<Self> {
         let light_state: LightStateC = read.read_struct()?;
 
         // not sure why this information is duplicated?
@@ -246,8 +245,14 @@ impl ScriptObject for LightState {
         })
     }
 
-    fn write(&self, write: &mut CountingWriter<impl Write>, anim_def: &AnimDef) -> Result<()> {
-        let name = Ascii::from_str_padded(&self.name);
+    fn write(
+        &self,
+        write: &mut CountingWriter<impl Write>,
+        anim_def: &AnimDefIndex<'_>,
+    ) -> Result<()> {
+        let name = assert_utf8("light state name", write.offset, || {
+            Ascii::from_str_padded(&self.name)
+        })?;
         let light_index = anim_def.light_to_index(&self.name)? as u32;
 
         let mut flags = LightFlags::empty();
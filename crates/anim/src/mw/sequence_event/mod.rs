@@ -1,3 +1,4 @@
+use crate::types::AnimDefIndex;
 mod animation;
 mod call_animation;
 mod call_object_connector;
@@ -39,5 +40,12 @@ pub trait ScriptObject: Sized {
     const INDEX: u8;
     const SIZE: u32;
     fn read(read: &mut CountingReader<impl Read>, anim_def: &AnimDef, size: u32) -> Result<Self>;
-    fn write(&self, write: &mut CountingWriter<impl Write>, anim_def: &AnimDef) -> Result<()>;
+    fn write(
+        &self,
+        write: &mut CountingWriter<impl Write>,
+        anim_def: &AnimDefIndex<'_>,
+    ) -> Result<()>;
 }
+
+#[cfg(test)]
+mod tests;
@@ -1,5 +1,5 @@
 use super::ScriptObject;
-use crate::types::AnimDefLookup as _;
+use crate::types::{AnimDefIndex, AnimDefLookup as _};
 use bytemuck::{AnyBitPattern, NoUninit};
 use mech3ax_api_types::anim::events::ObjectActiveState;
 use mech3ax_api_types::anim::AnimDef;
@@ -14,14 +14,13 @@ struct ObjectActiveStateC {
     state: Bool32,
     node_index: u32,
 }
-impl_as_bytes!(ObjectActiveStateC, 8);
+impl_as_bytes!(ObjectActiveStateC, 8, LeStruct);
 
 impl ScriptObject for ObjectActiveState {
     const INDEX: u8 = 6;
     const SIZE: u32 = ObjectActiveStateC::SIZE;
 
-    fn read(read: &mut CountingReader<impl Read>, anim_def: &AnimDef, size: u32) -> Result<Self> {
-        assert_that!("object active state size", size == Self::SIZE, read.offset)?;
+    fn read(read: &mut CountingReader<impl Read>, anim_def: &AnimDef, _size: u32) -> Result<Self> {
         let object_active_state: ObjectActiveStateC = read.read_struct()?;
         let state =
             assert_that!("object active state", bool object_active_state.state, read.prev + 0)?;
@@ -31,7 +30,11 @@ impl ScriptObject for ObjectActiveState {
         Ok(Self { node, state })
     }
 
-    fn write(&self, write: &mut CountingWriter<impl Write>, anim_def: &AnimDef) -> Result<()> {
+    fn write(
+        &self,
+        write: &mut CountingWriter<impl Write>,
+        anim_def: &AnimDefIndex<'_>,
+    ) -> Result<()> {
         write.write_struct(&ObjectActiveStateC {
             state: self.state.into(),
             node_index: anim_def.node_to_index(&self.node)? as u32,
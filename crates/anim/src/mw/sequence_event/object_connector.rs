@@ -1,6 +1,6 @@
 use super::types::INPUT_NODE;
 use super::ScriptObject;
-use crate::types::AnimDefLookup as _;
+use crate::types::{AnimDefIndex, AnimDefLookup as _};
 use bytemuck::{AnyBitPattern, NoUninit};
 use mech3ax_api_types::anim::events::ObjectConnector;
 use mech3ax_api_types::anim::AnimDef;
@@ -47,17 +47,17 @@ struct ObjectConnectorC {
     zero68: f32,
     max_length: f32,
 }
-impl_as_bytes!(ObjectConnectorC, 76);
+impl_as_bytes!(ObjectConnectorC, 76, LeStruct);
 
 impl ScriptObject for ObjectConnector {
     const INDEX: u8 = 18;
     const SIZE: u32 = ObjectConnectorC::SIZE;
 
-    fn read(read: &mut CountingReader<impl Read>, anim_def: &AnimDef, size: u32) -> Result<Self> {
-        assert_that!("object connector size", size == Self::SIZE, read.offset)?;
+    fn read(read: &mut CountingReader<impl Read>, anim_def: &AnimDef, _size: u32) -> Result<Self> {
         let object_connector: ObjectConnectorC = read.read_struct()?;
-        let flags =
-            assert_that!("object connector flags", flags object_connector.flags, read.prev + 0)?;
+        // keep any flags that aren't known, so the event can be written back
+        let flags = ObjectConnectorFlags::from_bits_truncate(object_connector.flags.value);
+        let unknown_flags = object_connector.flags.value & !flags.bits();
 
         assert_that!(
             "object connector field 10",
@@ -235,10 +235,21 @@ impl ScriptObject for ObjectConnector {
             from_pos,
             to_pos,
             max_length,
+            unknown_flags,
         })
     }
 
-    fn write(&self, write: &mut CountingWriter<impl Write>, anim_def: &AnimDef) -> Result<()> {
+    fn write(
+        &self,
+        write: &mut CountingWriter<impl Write>,
+        anim_def: &AnimDefIndex<'_>,
+    ) -> Result<()> {
+        let known = ObjectConnectorFlags::from_bits_truncate(self.unknown_flags);
+        assert_that!(
+            "object connector unknown flags",
+            known == ObjectConnectorFlags::empty(),
+            write.offset
+        )?;
         let node_index = anim_def.node_to_index(&self.node)? as u16;
         let mut flags = ObjectConnectorFlags::empty();
 
@@ -279,7 +290,7 @@ impl ScriptObject for ObjectConnector {
         }
 
         write.write_struct(&ObjectConnectorC {
-            flags: flags.maybe(),
+            flags: Flags::new(flags.bits() | self.unknown_flags),
             node_index,
             from_index,
             to_index,
@@ -1,5 +1,5 @@
 use super::ScriptObject;
-use crate::types::AnimDefLookup as _;
+use crate::types::{AnimDefIndex, AnimDefLookup as _};
 use bytemuck::{AnyBitPattern, NoUninit};
 use mech3ax_api_types::anim::events::ObjectCycleTexture;
 use mech3ax_api_types::anim::AnimDef;
@@ -16,14 +16,13 @@ struct ObjectCycleTextureC {
     node_index: u16,
     reset: u16,
 }
-impl_as_bytes!(ObjectCycleTextureC, 8);
+impl_as_bytes!(ObjectCycleTextureC, 8, LeStruct);
 
 impl ScriptObject for ObjectCycleTexture {
     const INDEX: u8 = 17;
     const SIZE: u32 = ObjectCycleTextureC::SIZE;
 
-    fn read(read: &mut CountingReader<impl Read>, anim_def: &AnimDef, size: u32) -> Result<Self> {
-        assert_that!("object cycle texture size", size == Self::SIZE, read.offset)?;
+    fn read(read: &mut CountingReader<impl Read>, anim_def: &AnimDef, _size: u32) -> Result<Self> {
         let object_cycle_texture: ObjectCycleTextureC = read.read_struct()?;
         assert_that!(
             "object cycle texture increment",
@@ -44,7 +43,11 @@ impl ScriptObject for ObjectCycleTexture {
         })
     }
 
-    fn write(&self, write: &mut CountingWriter<impl Write>, anim_def: &AnimDef) -> Result<()> {
+    fn write(
+        &self,
+        write: &mut CountingWriter<impl Write>,
+        anim_def: &AnimDefIndex<'_>,
+    ) -> Result<()> {
         let node_index = anim_def.node_to_index(&self.node)? as u16;
         write.write_struct(&ObjectCycleTextureC {
             increment: 1,
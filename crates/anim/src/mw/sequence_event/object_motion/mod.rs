@@ -1,5 +1,5 @@
 use super::ScriptObject;
-use crate::types::AnimDefLookup as _;
+use crate::types::{AnimDefIndex, AnimDefLookup as _};
 use bytemuck::{AnyBitPattern, NoUninit};
 use mech3ax_api_types::anim::events::{
     BounceSequence, BounceSound, ForwardRotation, ForwardRotationDistance, ForwardRotationTime,
@@ -89,17 +89,71 @@ struct ObjectMotionC {
     // RUNTIME
     runtime: f32, // 316
 }
-impl_as_bytes!(ObjectMotionC, 320);
+impl_as_bytes!(ObjectMotionC, 320, LeStruct);
+
+fn validate_bounce_seq_name(name: &str, field: &str, pos: usize) -> Result<()> {
+    // empty names are read back as `None`, and names must fit with a
+    // terminator
+    if name.is_empty() || name.len() > 31 || !name.is_ascii() {
+        return Err(assert_with_msg!(
+            "Expected `{}` to be 1 to 31 ASCII characters, but was `{}` (at {})",
+            field,
+            name,
+            pos
+        ));
+    }
+    Ok(())
+}
+
+/// Enforce the invariants checked when reading, so that a hand-edited
+/// object motion can't be written in a form that can't be read back.
+fn validate(object_motion: &ObjectMotion, pos: usize) -> Result<()> {
+    if let Some(bounce_seq) = &object_motion.bounce_sequence {
+        match &bounce_seq.seq_name0 {
+            Some(name) => validate_bounce_seq_name(name, "object motion bounce seq 0 name", pos)?,
+            None => {
+                return Err(assert_with_msg!(
+                    "Expected at least one bounce sequence (at {})",
+                    pos
+                ))
+            }
+        }
+        if let Some(name) = &bounce_seq.seq_name1 {
+            validate_bounce_seq_name(name, "object motion bounce seq 1 name", pos)?;
+        }
+        if let Some(name) = &bounce_seq.seq_name2 {
+            validate_bounce_seq_name(name, "object motion bounce seq 2 name", pos)?;
+        }
+    }
+    if let Some(bounce_sound) = &object_motion.bounce_sound {
+        assert_that!(
+            "object motion bounce snd 0 vol",
+            bounce_sound.volume > 0.0,
+            pos
+        )?;
+    }
+    if let Some(runtime) = object_motion.runtime {
+        assert_that!("object motion runtime", runtime > 0.0, pos)?;
+    }
+    let known = ObjectMotionFlags::from_bits_truncate(object_motion.unknown_flags);
+    assert_that!(
+        "object motion unknown flags",
+        known == ObjectMotionFlags::empty(),
+        pos
+    )?;
+    Ok(())
+}
 
 impl ScriptObject for ObjectMotion {
     const INDEX: u8 = 10;
     const SIZE: u32 = ObjectMotionC::SIZE;
 
-    fn read(read: &mut CountingReader<impl Read>, anim_def: &AnimDef, size: u32) -> Result<Self> {
-        assert_that!("object motion size", size == Self::SIZE, read.offset)?;
+    fn read(read: &mut CountingReader<impl Read>, anim_def: &AnimDef, _size: u32) -> Result<Self> {
         let object_motion: ObjectMotionC = read.read_struct()?;
 
-        let flags = assert_that!("object motion flags", flags object_motion.flags, read.prev + 0)?;
+        // keep any flags that aren't known, so the event can be written back
+        let flags = ObjectMotionFlags::from_bits_truncate(object_motion.flags.value);
+        let unknown_flags = object_motion.flags.value & !flags.bits();
         let node = anim_def.node_from_index(object_motion.node_index as usize, read.prev + 4)?;
 
         assert_that!(
@@ -334,7 +388,7 @@ impl ScriptObject for ObjectMotion {
         assert_that!(
             "object motion scale copy",
             object_motion.scale_copy == Vec3::DEFAULT,
-            read.prev + 182
+            read.prev + 184
         )?;
 
         assert_that!(
@@ -488,10 +542,16 @@ impl ScriptObject for ObjectMotion {
             bounce_sequence,
             bounce_sound,
             runtime,
+            unknown_flags,
         })
     }
 
-    fn write(&self, write: &mut CountingWriter<impl Write>, anim_def: &AnimDef) -> Result<()> {
+    fn write(
+        &self,
+        write: &mut CountingWriter<impl Write>,
+        anim_def: &AnimDefIndex<'_>,
+    ) -> Result<()> {
+        validate(self, write.offset)?;
         let node_index = anim_def.node_to_index(&self.node)? as u32;
         let mut flags = ObjectMotionFlags::empty();
         let gravity = if let Some(gravity_info) = &self.gravity {
@@ -570,13 +630,25 @@ impl ScriptObject for ObjectMotion {
             flags |= ObjectMotionFlags::BOUNCE_SEQ;
 
             if let Some(name) = bounce_seq.seq_name0.as_ref() {
-                bounce_seq0_name = Ascii::from_str_padded(name);
+                bounce_seq0_name = assert_utf8(
+                    "object motion bounce seq 0 name",
+                    write.offset + 196,
+                    || Ascii::from_str_padded(name),
+                )?;
             }
             if let Some(name) = bounce_seq.seq_name1.as_ref() {
-                bounce_seq1_name = Ascii::from_str_padded(name);
+                bounce_seq1_name = assert_utf8(
+                    "object motion bounce seq 1 name",
+                    write.offset + 236,
+                    || Ascii::from_str_padded(name),
+                )?;
             }
             if let Some(name) = bounce_seq.seq_name2.as_ref() {
-                bounce_seq2_name = Ascii::from_str_padded(name);
+                bounce_seq2_name = assert_utf8(
+                    "object motion bounce seq 2 name",
+                    write.offset + 276,
+                    || Ascii::from_str_padded(name),
+                )?;
             }
         }
 
@@ -594,7 +666,7 @@ impl ScriptObject for ObjectMotion {
         }
 
         write.write_struct(&ObjectMotionC {
-            flags: flags.maybe(),
+            flags: Flags::new(flags.bits() | self.unknown_flags),
             node_index,
             zero008: 0.0,
             gravity,
@@ -638,3 +710,6 @@ impl ScriptObject for ObjectMotion {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests;
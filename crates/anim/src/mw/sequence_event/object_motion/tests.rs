@@ -0,0 +1,236 @@
+use super::{validate, ScriptObject as _};
+use crate::types::AnimDefIndex;
+use mech3ax_api_types::anim::events::{BounceSequence, BounceSound, ObjectMotion};
+use mech3ax_api_types::anim::{AnimActivation, AnimDef, Execution, NamePad, NamePtr};
+use mech3ax_common::io_ext::{CountingReader, CountingWriter};
+use mech3ax_common::Error;
+
+fn anim_def() -> AnimDef {
+    AnimDef {
+        name: "test".to_string(),
+        anim_name: NamePad {
+            name: "test".to_string(),
+            pad: Vec::new(),
+        },
+        anim_root: NamePad {
+            name: "test".to_string(),
+            pad: Vec::new(),
+        },
+        file_name: "test.flt".to_string(),
+        auto_reset_node_states: true,
+        activation: AnimActivation::OnCall,
+        execution: Execution::None,
+        network_log: None,
+        save_log: None,
+        has_callbacks: false,
+        reset_time: None,
+        health: 0.0,
+        proximity_damage: true,
+        activ_prereq_min_to_satisfy: 0,
+        objects: None,
+        nodes: Some(vec![NamePtr {
+            name: "node".to_string(),
+            pointer: 0,
+        }]),
+        lights: None,
+        puffers: None,
+        dynamic_sounds: None,
+        static_sounds: Some(vec![NamePad {
+            name: "sound".to_string(),
+            pad: Vec::new(),
+        }]),
+        activ_prereqs: None,
+        anim_refs: None,
+        reset_state: None,
+        sequences: Vec::new(),
+    }
+}
+
+fn object_motion() -> ObjectMotion {
+    ObjectMotion {
+        node: "node".to_string(),
+        impact_force: false,
+        gravity: None,
+        translation_range_min: None,
+        translation_range_max: None,
+        translation: None,
+        forward_rotation: None,
+        xyz_rotation: None,
+        scale: None,
+        bounce_sequence: None,
+        bounce_sound: None,
+        runtime: None,
+        unknown_flags: 0,
+    }
+}
+
+fn bounce_sequence(
+    seq_name0: Option<&str>,
+    seq_name1: Option<&str>,
+    seq_name2: Option<&str>,
+) -> Option<BounceSequence> {
+    Some(BounceSequence {
+        seq_name0: seq_name0.map(str::to_string),
+        seq_name1: seq_name1.map(str::to_string),
+        seq_name2: seq_name2.map(str::to_string),
+    })
+}
+
+#[test]
+fn valid() {
+    let mut motion = object_motion();
+    validate(&motion, 0).unwrap();
+
+    motion.bounce_sequence = bounce_sequence(Some("bounce"), None, Some("bounce2"));
+    motion.bounce_sound = Some(BounceSound {
+        name: "sound".to_string(),
+        volume: 1.0,
+    });
+    motion.runtime = Some(1.0);
+    validate(&motion, 0).unwrap();
+}
+
+#[test]
+fn bounce_sequence_without_first_name() {
+    let mut motion = object_motion();
+    motion.bounce_sequence = bounce_sequence(None, Some("bounce"), None);
+    let err = validate(&motion, 42).unwrap_err();
+    assert_eq!(
+        format!("{:#?}", err),
+        "Assert(\n    Expected at least one bounce sequence (at 42),\n)"
+    );
+}
+
+#[test]
+fn bounce_sequence_empty_name() {
+    let mut motion = object_motion();
+    motion.bounce_sequence = bounce_sequence(Some("bounce"), Some(""), None);
+    let err = validate(&motion, 42).unwrap_err();
+    assert!(format!("{:#?}", err).contains("`object motion bounce seq 1 name`"));
+}
+
+#[test]
+fn bounce_sequence_long_name() {
+    let name = "b".repeat(32);
+    let mut motion = object_motion();
+    motion.bounce_sequence = bounce_sequence(Some("bounce"), None, Some(&name));
+    let err = validate(&motion, 42).unwrap_err();
+    assert!(format!("{:#?}", err).contains("`object motion bounce seq 2 name`"));
+}
+
+#[test]
+fn bounce_sequence_non_ascii_name() {
+    let mut motion = object_motion();
+    motion.bounce_sequence = bounce_sequence(Some("bounce€"), None, None);
+    let err = validate(&motion, 42).unwrap_err();
+    assert!(format!("{:#?}", err).contains("`object motion bounce seq 0 name`"));
+}
+
+#[test]
+fn bounce_sound_zero_volume() {
+    let mut motion = object_motion();
+    motion.bounce_sound = Some(BounceSound {
+        name: "sound".to_string(),
+        volume: 0.0,
+    });
+    let err = validate(&motion, 42).unwrap_err();
+    assert_eq!(
+        format!("{:#?}", err),
+        "Assert(\n    Expected `object motion bounce snd 0 vol` > 0.0, but was 0.0 (at 42),\n)"
+    );
+}
+
+#[test]
+fn runtime_zero() {
+    let mut motion = object_motion();
+    motion.runtime = Some(0.0);
+    let err = validate(&motion, 42).unwrap_err();
+    assert_eq!(
+        format!("{:#?}", err),
+        "Assert(\n    Expected `object motion runtime` > 0.0, but was 0.0 (at 42),\n)"
+    );
+}
+
+#[test]
+fn write_validates() {
+    let anim_def = anim_def();
+    let mut motion = object_motion();
+    motion.runtime = Some(-1.0);
+    let mut write = CountingWriter::new(Vec::new(), 100);
+    let err = motion
+        .write(&mut write, &AnimDefIndex::new(&anim_def))
+        .unwrap_err();
+    assert_eq!(
+        format!("{:#?}", err),
+        "Assert(\n    Expected `object motion runtime` > 0.0, but was -1.0 (at 100),\n)"
+    );
+    assert!(write.into_inner().is_empty());
+}
+
+fn object_motion_bytes() -> Vec<u8> {
+    let mut buf = vec![0u8; 320];
+    // node index
+    buf[4..8].copy_from_slice(&1u32.to_le_bytes());
+    // bounce sequence sentinels
+    for offset in [228, 268, 308] {
+        buf[offset..offset + 2].copy_from_slice(&(-1i16).to_le_bytes());
+    }
+    buf
+}
+
+#[test]
+fn read_valid() {
+    let anim_def = anim_def();
+    let buf = object_motion_bytes();
+    let mut read = CountingReader::new(&buf[..]);
+    let motion = ObjectMotion::read(&mut read, &anim_def, 320).unwrap();
+    assert_eq!(motion.node, "node");
+    assert!(motion.scale.is_none());
+}
+
+#[test]
+fn read_scale_copy_non_zero() {
+    let anim_def = anim_def();
+    let mut buf = object_motion_bytes();
+    // scale copy (y)
+    buf[188..192].copy_from_slice(&1.0f32.to_le_bytes());
+    let mut read = CountingReader::new(&buf[..]);
+    read.offset = 1000;
+    let err = ObjectMotion::read(&mut read, &anim_def, 320).unwrap_err();
+    match err {
+        Error::Assert(err) => {
+            assert_eq!(err.name(), Some("object motion scale copy"));
+            assert_eq!(err.pos(), Some(1184));
+        }
+        other => panic!("unexpected error {:?}", other),
+    }
+}
+
+#[test]
+fn unknown_flags_round_trip() {
+    let anim_def = anim_def();
+    let mut buf = object_motion_bytes();
+    // flags: an unknown high bit
+    buf[0..4].copy_from_slice(&(1u32 << 31).to_le_bytes());
+    let mut read = CountingReader::new(&buf[..]);
+    let motion = ObjectMotion::read(&mut read, &anim_def, 320).unwrap();
+    assert_eq!(motion.unknown_flags, 1 << 31);
+
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    motion
+        .write(&mut write, &AnimDefIndex::new(&anim_def))
+        .unwrap();
+    assert_eq!(write.into_inner(), buf);
+}
+
+#[test]
+fn unknown_flags_known_bit() {
+    let mut motion = object_motion();
+    // impact force
+    motion.unknown_flags = 1 << 1;
+    let err = validate(&motion, 42).unwrap_err();
+    assert_eq!(
+        format!("{:#?}", err),
+        "Assert(\n    Expected `object motion unknown flags` == {}, but was {IMPACT_FORCE} (at 42),\n)"
+    );
+}
@@ -1,5 +1,5 @@
 use super::ScriptObject;
-use crate::types::AnimDefLookup as _;
+use crate::types::{AnimDefIndex, AnimDefLookup as _};
 use bytemuck::{AnyBitPattern, NoUninit};
 use mech3ax_api_types::anim::events::{FloatFromTo, ObjectMotionFromTo, Vec3FromTo};
 use mech3ax_api_types::anim::AnimDef;
@@ -39,20 +39,17 @@ struct ObjectMotionFromToC {
     scale_delta: Vec3,     // 116
     run_time: f32,         // 128
 }
-impl_as_bytes!(ObjectMotionFromToC, 132);
+impl_as_bytes!(ObjectMotionFromToC, 132, LeStruct);
 
 impl ScriptObject for ObjectMotionFromTo {
     const INDEX: u8 = 11;
     const SIZE: u32 = ObjectMotionFromToC::SIZE;
 
-    fn read(read: &mut CountingReader<impl Read>, anim_def: &AnimDef, size: u32) -> Result<Self> {
-        assert_that!(
-            "object motion from to size",
-            size == Self::SIZE,
-            read.offset
-        )?;
+    fn read(read: &mut CountingReader<impl Read>, anim_def: &AnimDef, _size: u32) -> Result<Self> {
         let motion: ObjectMotionFromToC = read.read_struct()?;
-        let flags = assert_that!("object motion from to flags", flags motion.flags, read.prev + 0)?;
+        // keep any flags that aren't known, so the event can be written back
+        let flags = ObjectMotionFromToFlags::from_bits_truncate(motion.flags.value);
+        let unknown_flags = motion.flags.value & !flags.bits();
         let node = anim_def.node_from_index(motion.node_index as usize, read.prev + 4)?;
 
         let morph = if flags.contains(ObjectMotionFromToFlags::MORPH) {
@@ -168,10 +165,21 @@ impl ScriptObject for ObjectMotionFromTo {
             translate,
             rotate,
             scale,
+            unknown_flags,
         })
     }
 
-    fn write(&self, write: &mut CountingWriter<impl Write>, anim_def: &AnimDef) -> Result<()> {
+    fn write(
+        &self,
+        write: &mut CountingWriter<impl Write>,
+        anim_def: &AnimDefIndex<'_>,
+    ) -> Result<()> {
+        let known = ObjectMotionFromToFlags::from_bits_truncate(self.unknown_flags);
+        assert_that!(
+            "object motion from to unknown flags",
+            known == ObjectMotionFromToFlags::empty(),
+            write.offset
+        )?;
         let mut flags = ObjectMotionFromToFlags::empty();
         if self.translate.is_some() {
             flags |= ObjectMotionFromToFlags::TRANSLATE;
@@ -214,7 +222,7 @@ impl ScriptObject for ObjectMotionFromTo {
         };
 
         write.write_struct(&ObjectMotionFromToC {
-            flags: flags.maybe(),
+            flags: Flags::new(flags.bits() | self.unknown_flags),
             node_index,
             morph_from,
             morph_to,
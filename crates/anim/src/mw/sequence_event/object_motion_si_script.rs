@@ -1,5 +1,5 @@
 use super::ScriptObject;
-use crate::types::AnimDefLookup as _;
+use crate::types::{AnimDefIndex, AnimDefLookup as _};
 use bytemuck::{AnyBitPattern, NoUninit};
 use mech3ax_api_types::anim::events::{
     ObjectMotionSiFrame, ObjectMotionSiScript, RotateData, ScaleData, TranslateData,
@@ -21,7 +21,7 @@ struct ScriptHeaderC {
     zero16: u32,     // 16
     zero20: u32,     // 20
 }
-impl_as_bytes!(ScriptHeaderC, 24);
+impl_as_bytes!(ScriptHeaderC, 24, LeStruct);
 
 bitflags! {
     struct FrameFlags: u32 {
@@ -40,7 +40,7 @@ struct FrameC {
     start_time: f32,
     end_time: f32,
 }
-impl_as_bytes!(FrameC, 12);
+impl_as_bytes!(FrameC, 12, LeStruct);
 
 #[derive(Debug, Clone, Copy, NoUninit, AnyBitPattern)]
 #[repr(C)]
@@ -48,7 +48,7 @@ struct TranslateDataC {
     value: Vec3,
     unk: Bytes<64>,
 }
-impl_as_bytes!(TranslateDataC, 76);
+impl_as_bytes!(TranslateDataC, 76, LeStruct);
 
 #[derive(Debug, Clone, Copy, NoUninit, AnyBitPattern)]
 #[repr(C)]
@@ -56,7 +56,7 @@ struct RotateDataC {
     value: Quaternion,
     unk: Bytes<60>,
 }
-impl_as_bytes!(RotateDataC, 76);
+impl_as_bytes!(RotateDataC, 76, LeStruct);
 
 #[derive(Debug, Clone, Copy, NoUninit, AnyBitPattern)]
 #[repr(C)]
@@ -64,7 +64,7 @@ struct ScaleDataC {
     value: Vec3,
     unk: Bytes<64>,
 }
-impl_as_bytes!(ScaleDataC, 76);
+impl_as_bytes!(ScaleDataC, 76, LeStruct);
 
 fn read_frame(read: &mut CountingReader<impl Read>) -> Result<ObjectMotionSiFrame> {
     let frame: FrameC = read.read_struct()?;
@@ -166,7 +166,11 @@ impl ScriptObject for ObjectMotionSiScript {
         Ok(ObjectMotionSiScript { node, frames })
     }
 
-    fn write(&self, write: &mut CountingWriter<impl Write>, anim_def: &AnimDef) -> Result<()> {
+    fn write(
+        &self,
+        write: &mut CountingWriter<impl Write>,
+        anim_def: &AnimDefIndex<'_>,
+    ) -> Result<()> {
         let node_index = anim_def.node_to_index(&self.node)? as u32;
         let count = self.frames.len() as u32;
         write.write_struct(&ScriptHeaderC {
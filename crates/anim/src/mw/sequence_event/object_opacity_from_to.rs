@@ -1,6 +1,6 @@
 use super::delta::{dec_f32, delta};
 use super::ScriptObject;
-use crate::types::AnimDefLookup as _;
+use crate::types::{AnimDefIndex, AnimDefLookup as _};
 use bytemuck::{AnyBitPattern, NoUninit};
 use mech3ax_api_types::anim::events::{ObjectOpacity, ObjectOpacityFromTo};
 use mech3ax_api_types::anim::AnimDef;
@@ -20,18 +20,13 @@ struct ObjectOpacityFromToC {
     delta_value: f32,
     runtime: f32,
 }
-impl_as_bytes!(ObjectOpacityFromToC, 24);
+impl_as_bytes!(ObjectOpacityFromToC, 24, LeStruct);
 
 impl ScriptObject for ObjectOpacityFromTo {
     const INDEX: u8 = 14;
     const SIZE: u32 = ObjectOpacityFromToC::SIZE;
 
-    fn read(read: &mut CountingReader<impl Read>, anim_def: &AnimDef, size: u32) -> Result<Self> {
-        assert_that!(
-            "object opacity from to size",
-            size == Self::SIZE,
-            read.offset
-        )?;
+    fn read(read: &mut CountingReader<impl Read>, anim_def: &AnimDef, _size: u32) -> Result<Self> {
         let object_opacity: ObjectOpacityFromToC = read.read_struct()?;
         let node = anim_def.node_from_index(object_opacity.node_index as usize, read.prev + 0)?;
         // the opacity value is independent from the state; e.g. for -1, opacity is not necessarily 0.0
@@ -84,7 +79,11 @@ impl ScriptObject for ObjectOpacityFromTo {
         })
     }
 
-    fn write(&self, write: &mut CountingWriter<impl Write>, anim_def: &AnimDef) -> Result<()> {
+    fn write(
+        &self,
+        write: &mut CountingWriter<impl Write>,
+        anim_def: &AnimDefIndex<'_>,
+    ) -> Result<()> {
         let node_index = anim_def.node_to_index(&self.node)? as u32;
         let runtime = if self.fudge {
             dec_f32(self.runtime)
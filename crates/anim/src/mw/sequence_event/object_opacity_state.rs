@@ -1,5 +1,5 @@
 use super::ScriptObject;
-use crate::types::AnimDefLookup as _;
+use crate::types::{AnimDefIndex, AnimDefLookup as _};
 use bytemuck::{AnyBitPattern, NoUninit};
 use mech3ax_api_types::anim::events::ObjectOpacityState;
 use mech3ax_api_types::anim::AnimDef;
@@ -16,14 +16,13 @@ struct ObjectOpacityStateC {
     opacity: f32,
     node_index: u32,
 }
-impl_as_bytes!(ObjectOpacityStateC, 12);
+impl_as_bytes!(ObjectOpacityStateC, 12, LeStruct);
 
 impl ScriptObject for ObjectOpacityState {
     const INDEX: u8 = 13;
     const SIZE: u32 = ObjectOpacityStateC::SIZE;
 
-    fn read(read: &mut CountingReader<impl Read>, anim_def: &AnimDef, size: u32) -> Result<Self> {
-        assert_that!("object opacity state size", size == Self::SIZE, read.offset)?;
+    fn read(read: &mut CountingReader<impl Read>, anim_def: &AnimDef, _size: u32) -> Result<Self> {
         let object_opacity_state: ObjectOpacityStateC = read.read_struct()?;
         let is_set = assert_that!("object opacity state is set", bool object_opacity_state.is_set, read.prev + 0)?;
         let state = assert_that!("object opacity state state", bool object_opacity_state.state, read.prev + 2)?;
@@ -38,7 +37,11 @@ impl ScriptObject for ObjectOpacityState {
         })
     }
 
-    fn write(&self, write: &mut CountingWriter<impl Write>, anim_def: &AnimDef) -> Result<()> {
+    fn write(
+        &self,
+        write: &mut CountingWriter<impl Write>,
+        anim_def: &AnimDefIndex<'_>,
+    ) -> Result<()> {
         write.write_struct(&ObjectOpacityStateC {
             is_set: self.is_set.into(),
             state: self.state.into(),
@@ -1,5 +1,5 @@
 use super::ScriptObject;
-use crate::types::AnimDefLookup as _;
+use crate::types::{AnimDefIndex, AnimDefLookup as _};
 use bytemuck::{AnyBitPattern, NoUninit};
 use mech3ax_api_types::anim::events::{ObjectRotateState, RotateState};
 use mech3ax_api_types::anim::AnimDef;
@@ -20,14 +20,13 @@ struct ObjectRotateStateC {
     node_index: u16,    // 16
     at_node_index: u16, // 18
 }
-impl_as_bytes!(ObjectRotateStateC, 20);
+impl_as_bytes!(ObjectRotateStateC, 20, LeStruct);
 
 impl ScriptObject for ObjectRotateState {
     const INDEX: u8 = 9;
     const SIZE: u32 = ObjectRotateStateC::SIZE;
 
-    fn read(read: &mut CountingReader<impl Read>, anim_def: &AnimDef, size: u32) -> Result<Self> {
-        assert_that!("object rotate state size", size == Self::SIZE, read.offset)?;
+    fn read(read: &mut CountingReader<impl Read>, anim_def: &AnimDef, _size: u32) -> Result<Self> {
         let object_rotate_state: ObjectRotateStateC = read.read_struct()?;
         // FLAGS (mutually exclusive)
         // if this is a camera:
@@ -79,7 +78,11 @@ impl ScriptObject for ObjectRotateState {
         Ok(Self { node, rotate })
     }
 
-    fn write(&self, write: &mut CountingWriter<impl Write>, anim_def: &AnimDef) -> Result<()> {
+    fn write(
+        &self,
+        write: &mut CountingWriter<impl Write>,
+        anim_def: &AnimDefIndex<'_>,
+    ) -> Result<()> {
         let (flags, rotate, at_node_index) = match &self.rotate {
             RotateState::Absolute(rotate) => {
                 let rotate = Vec3 {
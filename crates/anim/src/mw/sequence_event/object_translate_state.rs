@@ -1,6 +1,6 @@
 use super::types::INPUT_NODE;
 use super::ScriptObject;
-use crate::types::AnimDefLookup as _;
+use crate::types::{AnimDefIndex, AnimDefLookup as _};
 use bytemuck::{AnyBitPattern, NoUninit};
 use mech3ax_api_types::anim::events::ObjectTranslateState;
 use mech3ax_api_types::anim::AnimDef;
@@ -20,18 +20,13 @@ struct ObjectTranslateStateC {
     node_index: u16,    // 16
     at_node_index: u16, // 18
 }
-impl_as_bytes!(ObjectTranslateStateC, 20);
+impl_as_bytes!(ObjectTranslateStateC, 20, LeStruct);
 
 impl ScriptObject for ObjectTranslateState {
     const INDEX: u8 = 7;
     const SIZE: u32 = ObjectTranslateStateC::SIZE;
 
-    fn read(read: &mut CountingReader<impl Read>, anim_def: &AnimDef, size: u32) -> Result<Self> {
-        assert_that!(
-            "object translate state size",
-            size == Self::SIZE,
-            read.offset
-        )?;
+    fn read(read: &mut CountingReader<impl Read>, anim_def: &AnimDef, _size: u32) -> Result<Self> {
         let object_translate_state: ObjectTranslateStateC = read.read_struct()?;
         assert_that!(
             "object translate state field 00",
@@ -59,7 +54,11 @@ impl ScriptObject for ObjectTranslateState {
         })
     }
 
-    fn write(&self, write: &mut CountingWriter<impl Write>, anim_def: &AnimDef) -> Result<()> {
+    fn write(
+        &self,
+        write: &mut CountingWriter<impl Write>,
+        anim_def: &AnimDefIndex<'_>,
+    ) -> Result<()> {
         let node_index = anim_def.node_to_index(&self.node)? as u16;
         let at_node_index = if let Some(at_node) = &self.at_node {
             assert_that!("object translate state at node", at_node eq INPUT_NODE, 0)?;
@@ -1,4 +1,5 @@
 use super::*;
+use crate::types::AnimDefIndex;
 use bytemuck::{AnyBitPattern, NoUninit};
 use log::trace;
 use mech3ax_api_types::anim::events::{
@@ -25,7 +26,121 @@ struct EventHeaderC {
     size: u32,
     start_time: f32,
 }
-impl_as_bytes!(EventHeaderC, 12);
+impl_as_bytes!(EventHeaderC, 12, LeStruct);
+
+/// Read event data of a fixed size. The size from the event header is
+/// checked before anything is read.
+fn read_sized<T: ScriptObject>(
+    read: &mut CountingReader<impl Read>,
+    anim_def: &AnimDef,
+    size: u32,
+    name: &str,
+) -> Result<T> {
+    assert_that!(name, size == T::SIZE, read.offset)?;
+    T::read(read, anim_def, size)
+}
+
+/// Read the event data for the event type, which is the index of a
+/// [`ScriptObject`]. New events only need to be added here, and to the size
+/// and write functions below.
+pub fn read_event(
+    event_type: u8,
+    read: &mut CountingReader<impl Read>,
+    anim_def: &AnimDef,
+    size: u32,
+) -> Result<EventData> {
+    match event_type {
+        Sound::INDEX => read_sized(read, anim_def, size, "sound size").map(EventData::Sound),
+        SoundNode::INDEX => {
+            read_sized(read, anim_def, size, "sound node size").map(EventData::SoundNode)
+        }
+        LightState::INDEX => {
+            read_sized(read, anim_def, size, "light state size").map(EventData::LightState)
+        }
+        LightAnimation::INDEX => {
+            read_sized(read, anim_def, size, "light animation size").map(EventData::LightAnimation)
+        }
+        ObjectActiveState::INDEX => read_sized(read, anim_def, size, "object active state size")
+            .map(EventData::ObjectActiveState),
+        ObjectTranslateState::INDEX => {
+            read_sized(read, anim_def, size, "object translate state size")
+                .map(EventData::ObjectTranslateState)
+        }
+        ObjectScaleState::INDEX => read_sized(read, anim_def, size, "object scale state size")
+            .map(EventData::ObjectScaleState),
+        ObjectRotateState::INDEX => read_sized(read, anim_def, size, "object rotate state size")
+            .map(EventData::ObjectRotateState),
+        ObjectMotion::INDEX => {
+            read_sized(read, anim_def, size, "object motion size").map(EventData::ObjectMotion)
+        }
+        ObjectMotionFromTo::INDEX => read_sized(read, anim_def, size, "object motion from to size")
+            .map(EventData::ObjectMotionFromTo),
+        ObjectOpacityState::INDEX => read_sized(read, anim_def, size, "object opacity state size")
+            .map(EventData::ObjectOpacityState),
+        ObjectOpacityFromTo::INDEX => {
+            read_sized(read, anim_def, size, "object opacity from to size")
+                .map(EventData::ObjectOpacityFromTo)
+        }
+        ObjectAddChild::INDEX => {
+            read_sized(read, anim_def, size, "object add child size").map(EventData::ObjectAddChild)
+        }
+        ObjectCycleTexture::INDEX => read_sized(read, anim_def, size, "object cycle texture size")
+            .map(EventData::ObjectCycleTexture),
+        ObjectConnector::INDEX => read_sized(read, anim_def, size, "object connector size")
+            .map(EventData::ObjectConnector),
+        CallObjectConnector::INDEX => {
+            read_sized(read, anim_def, size, "call object connector size")
+                .map(EventData::CallObjectConnector)
+        }
+        CallSequence::INDEX => {
+            read_sized(read, anim_def, size, "call sequence size").map(EventData::CallSequence)
+        }
+        StopSequence::INDEX => {
+            read_sized(read, anim_def, size, "stop sequence size").map(EventData::StopSequence)
+        }
+        CallAnimation::INDEX => {
+            read_sized(read, anim_def, size, "call animation size").map(EventData::CallAnimation)
+        }
+        StopAnimation::INDEX => {
+            read_sized(read, anim_def, size, "stop animation size").map(EventData::StopAnimation)
+        }
+        ResetAnimation::INDEX => {
+            read_sized(read, anim_def, size, "reset animation size").map(EventData::ResetAnimation)
+        }
+        InvalidateAnimation::INDEX => read_sized(read, anim_def, size, "invalidate animation size")
+            .map(EventData::InvalidateAnimation),
+        FogState::INDEX => {
+            read_sized(read, anim_def, size, "fog state size").map(EventData::FogState)
+        }
+        Loop::INDEX => read_sized(read, anim_def, size, "loop size").map(EventData::Loop),
+        If::INDEX => read_sized(read, anim_def, size, "if size").map(EventData::If),
+        Else::INDEX => read_sized(read, anim_def, size, "else size").map(EventData::Else),
+        ElseIf::INDEX => read_sized(read, anim_def, size, "else if size").map(EventData::Elif),
+        EndIf::INDEX => read_sized(read, anim_def, size, "end if size").map(EventData::Endif),
+        Callback::INDEX => {
+            read_sized(read, anim_def, size, "callback size").map(EventData::Callback)
+        }
+        FrameBufferEffectColor::INDEX => {
+            read_sized(read, anim_def, size, "fbfx color from to size")
+                .map(EventData::FrameBufferEffectColorFromTo)
+        }
+        DetonateWeapon::INDEX => {
+            read_sized(read, anim_def, size, "detonate weapon size").map(EventData::DetonateWeapon)
+        }
+        PufferState::INDEX => {
+            read_sized(read, anim_def, size, "puffer state size").map(EventData::PufferState)
+        }
+        // the size depends on the frames, and is checked by the script
+        ObjectMotionSiScript::INDEX => {
+            ObjectMotionSiScript::read(read, anim_def, size).map(EventData::ObjectMotionSIScript)
+        }
+        _ => Err(assert_with_msg!(
+            "Expected valid event type, but was {} (at {})",
+            event_type,
+            read.prev + 0
+        )),
+    }
+}
 
 pub fn read_events(
     read: &mut CountingReader<impl Read>,
@@ -54,105 +169,14 @@ pub fn read_events(
             })
         };
 
+        assert_that!(
+            "event header size",
+            header.size >= EventHeaderC::SIZE,
+            read.prev + 4
+        )?;
         let actual_size = header.size - EventHeaderC::SIZE;
-        assert_that!("event header actual size", actual_size >= 0, read.prev + 4)?;
 
-        let data = match header.event_type {
-            Sound::INDEX => EventData::Sound(Sound::read(read, anim_def, actual_size)?),
-            SoundNode::INDEX => EventData::SoundNode(SoundNode::read(read, anim_def, actual_size)?),
-            LightState::INDEX => {
-                EventData::LightState(LightState::read(read, anim_def, actual_size)?)
-            }
-            LightAnimation::INDEX => {
-                EventData::LightAnimation(LightAnimation::read(read, anim_def, actual_size)?)
-            }
-            ObjectActiveState::INDEX => {
-                EventData::ObjectActiveState(ObjectActiveState::read(read, anim_def, actual_size)?)
-            }
-            ObjectTranslateState::INDEX => EventData::ObjectTranslateState(
-                ObjectTranslateState::read(read, anim_def, actual_size)?,
-            ),
-            ObjectScaleState::INDEX => {
-                EventData::ObjectScaleState(ObjectScaleState::read(read, anim_def, actual_size)?)
-            }
-            ObjectRotateState::INDEX => {
-                EventData::ObjectRotateState(ObjectRotateState::read(read, anim_def, actual_size)?)
-            }
-            ObjectMotion::INDEX => {
-                EventData::ObjectMotion(ObjectMotion::read(read, anim_def, actual_size)?)
-            }
-            ObjectMotionFromTo::INDEX => EventData::ObjectMotionFromTo(ObjectMotionFromTo::read(
-                read,
-                anim_def,
-                actual_size,
-            )?),
-            ObjectOpacityState::INDEX => EventData::ObjectOpacityState(ObjectOpacityState::read(
-                read,
-                anim_def,
-                actual_size,
-            )?),
-            ObjectOpacityFromTo::INDEX => EventData::ObjectOpacityFromTo(
-                ObjectOpacityFromTo::read(read, anim_def, actual_size)?,
-            ),
-            ObjectAddChild::INDEX => {
-                EventData::ObjectAddChild(ObjectAddChild::read(read, anim_def, actual_size)?)
-            }
-            ObjectCycleTexture::INDEX => EventData::ObjectCycleTexture(ObjectCycleTexture::read(
-                read,
-                anim_def,
-                actual_size,
-            )?),
-            ObjectConnector::INDEX => {
-                EventData::ObjectConnector(ObjectConnector::read(read, anim_def, actual_size)?)
-            }
-            CallObjectConnector::INDEX => EventData::CallObjectConnector(
-                CallObjectConnector::read(read, anim_def, actual_size)?,
-            ),
-            CallSequence::INDEX => {
-                EventData::CallSequence(CallSequence::read(read, anim_def, actual_size)?)
-            }
-            StopSequence::INDEX => {
-                EventData::StopSequence(StopSequence::read(read, anim_def, actual_size)?)
-            }
-            CallAnimation::INDEX => {
-                EventData::CallAnimation(CallAnimation::read(read, anim_def, actual_size)?)
-            }
-            StopAnimation::INDEX => {
-                EventData::StopAnimation(StopAnimation::read(read, anim_def, actual_size)?)
-            }
-            ResetAnimation::INDEX => {
-                EventData::ResetAnimation(ResetAnimation::read(read, anim_def, actual_size)?)
-            }
-            InvalidateAnimation::INDEX => EventData::InvalidateAnimation(
-                InvalidateAnimation::read(read, anim_def, actual_size)?,
-            ),
-            FogState::INDEX => EventData::FogState(FogState::read(read, anim_def, actual_size)?),
-            Loop::INDEX => EventData::Loop(Loop::read(read, anim_def, actual_size)?),
-            If::INDEX => EventData::If(If::read(read, anim_def, actual_size)?),
-            Else::INDEX => EventData::Else(Else::read(read, anim_def, actual_size)?),
-            ElseIf::INDEX => EventData::Elif(ElseIf::read(read, anim_def, actual_size)?),
-            EndIf::INDEX => EventData::Endif(EndIf::read(read, anim_def, actual_size)?),
-            Callback::INDEX => EventData::Callback(Callback::read(read, anim_def, actual_size)?),
-            FrameBufferEffectColor::INDEX => EventData::FrameBufferEffectColorFromTo(
-                FrameBufferEffectColor::read(read, anim_def, actual_size)?,
-            ),
-            DetonateWeapon::INDEX => {
-                EventData::DetonateWeapon(DetonateWeapon::read(read, anim_def, actual_size)?)
-            }
-            PufferState::INDEX => {
-                EventData::PufferState(PufferState::read(read, anim_def, actual_size)?)
-            }
-            ObjectMotionSiScript::INDEX => EventData::ObjectMotionSIScript(
-                ObjectMotionSiScript::read(read, anim_def, actual_size)?,
-            ),
-            _ => {
-                return Err(assert_with_msg!(
-                    "Expected valid event type, but was {} (at {})",
-                    header.event_type,
-                    read.prev + 0
-                ));
-            }
-        };
+        let data = read_event(header.event_type, read, anim_def, actual_size)?;
 
         events.push(Event { data, start })
     }
@@ -163,7 +187,7 @@ pub fn read_events(
 
 pub fn write_events(
     write: &mut CountingWriter<impl Write>,
-    anim_def: &AnimDef,
+    anim_def: &AnimDefIndex<'_>,
     events: &[Event],
 ) -> Result<()> {
     for event in events {
@@ -264,7 +288,7 @@ fn size_event(event: &Event) -> u32 {
 
 fn write_event(
     write: &mut CountingWriter<impl Write>,
-    anim_def: &AnimDef,
+    anim_def: &AnimDefIndex<'_>,
     event: &Event,
 ) -> Result<()> {
     match &event.data {
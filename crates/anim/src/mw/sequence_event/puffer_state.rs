@@ -1,5 +1,5 @@
 use super::ScriptObject;
-use crate::types::AnimDefLookup as _;
+use crate::types::{AnimDefIndex, AnimDefLookup as _};
 use bytemuck::{AnyBitPattern, NoUninit};
 use mech3ax_api_types::anim::events::{
     AtNode, Interval, IntervalType, PufferState, PufferStateCycleTextures,
@@ -81,14 +81,13 @@ struct PufferStateC {
     growth_factor: f32,        // 544
     zero548: Zeros<32>,        // 548
 }
-impl_as_bytes!(PufferStateC, 580);
+impl_as_bytes!(PufferStateC, 580, LeStruct);
 
 impl ScriptObject for PufferState {
     const INDEX: u8 = 42;
     const SIZE: u32 = PufferStateC::SIZE;
 
-    fn read(read: &mut CountingReader<impl Read>, anim_def: &AnimDef, size: u32) -> Result<Self> {
-        assert_that!("puffer state size", size == Self::SIZE, read.offset)?;
+    fn read(read: &mut CountingReader<impl Read>, anim_def: &AnimDef, _size: u32) -> Result<Self> {
         let puffer_state: PufferStateC = read.read_struct()?;
         let name = assert_utf8("puffer state name", read.prev + 0, || {
             puffer_state.name.to_str_padded()
@@ -585,8 +584,14 @@ impl ScriptObject for PufferState {
         })
     }
 
-    fn write(&self, write: &mut CountingWriter<impl Write>, anim_def: &AnimDef) -> Result<()> {
-        let name = Ascii::from_str_padded(&self.name);
+    fn write(
+        &self,
+        write: &mut CountingWriter<impl Write>,
+        anim_def: &AnimDefIndex<'_>,
+    ) -> Result<()> {
+        let name = assert_utf8("puffer state name", write.offset, || {
+            Ascii::from_str_padded(&self.name)
+        })?;
         let puffer_index = anim_def.puffer_to_index(&self.name)? as u32;
         let mut flags = PufferStateFlags::empty();
         if self.active_state.is_some() {
@@ -663,22 +668,34 @@ impl ScriptObject for PufferState {
         if let Some(textures) = &self.textures {
             flags |= PufferStateFlags::CYCLE_TEXTURE;
             if let Some(tex) = &textures.texture1 {
-                tex192 = Ascii::from_str_padded(tex);
+                tex192 = assert_utf8("puffer state texture 192", write.offset + 192, || {
+                    Ascii::from_str_padded(tex)
+                })?;
             }
             if let Some(tex) = &textures.texture2 {
-                tex228 = Ascii::from_str_padded(tex);
+                tex228 = assert_utf8("puffer state texture 228", write.offset + 228, || {
+                    Ascii::from_str_padded(tex)
+                })?;
             }
             if let Some(tex) = &textures.texture3 {
-                tex264 = Ascii::from_str_padded(tex);
+                tex264 = assert_utf8("puffer state texture 264", write.offset + 264, || {
+                    Ascii::from_str_padded(tex)
+                })?;
             }
             if let Some(tex) = &textures.texture4 {
-                tex300 = Ascii::from_str_padded(tex);
+                tex300 = assert_utf8("puffer state texture 300", write.offset + 300, || {
+                    Ascii::from_str_padded(tex)
+                })?;
             }
             if let Some(tex) = &textures.texture5 {
-                tex336 = Ascii::from_str_padded(tex);
+                tex336 = assert_utf8("puffer state texture 336", write.offset + 336, || {
+                    Ascii::from_str_padded(tex)
+                })?;
             }
             if let Some(tex) = &textures.texture6 {
-                tex372 = Ascii::from_str_padded(tex);
+                tex372 = assert_utf8("puffer state texture 372", write.offset + 372, || {
+                    Ascii::from_str_padded(tex)
+                })?;
             }
         }
         write.write_struct(&PufferStateC {
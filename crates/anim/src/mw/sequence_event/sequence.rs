@@ -1,4 +1,5 @@
 use super::ScriptObject;
+use crate::types::AnimDefIndex;
 use bytemuck::{AnyBitPattern, NoUninit};
 use mech3ax_api_types::anim::events::{CallSequence, StopSequence};
 use mech3ax_api_types::anim::AnimDef;
@@ -14,7 +15,7 @@ struct SequenceC {
     name: Ascii<32>,
     sentinel: i32,
 }
-impl_as_bytes!(SequenceC, 36);
+impl_as_bytes!(SequenceC, 36, LeStruct);
 
 fn read_sequence(read: &mut CountingReader<impl Read>) -> Result<String> {
     let sequence: SequenceC = read.read_struct()?;
@@ -26,7 +27,9 @@ fn read_sequence(read: &mut CountingReader<impl Read>) -> Result<String> {
 }
 
 fn write_sequence(write: &mut CountingWriter<impl Write>, name: &str) -> Result<()> {
-    let fill = Ascii::from_str_padded(name);
+    let fill = assert_utf8("sequence name", write.offset, || {
+        Ascii::from_str_padded(name)
+    })?;
     write.write_struct(&SequenceC {
         name: fill,
         sentinel: -1,
@@ -38,13 +41,16 @@ impl ScriptObject for CallSequence {
     const INDEX: u8 = 22;
     const SIZE: u32 = SequenceC::SIZE;
 
-    fn read(read: &mut CountingReader<impl Read>, _anim_def: &AnimDef, size: u32) -> Result<Self> {
-        assert_that!("call sequence size", size == Self::SIZE, read.offset)?;
+    fn read(read: &mut CountingReader<impl Read>, _anim_def: &AnimDef, _size: u32) -> Result<Self> {
         let name = read_sequence(read)?;
         Ok(Self { name })
     }
 
-    fn write(&self, write: &mut CountingWriter<impl Write>, _anim_def: &AnimDef) -> Result<()> {
+    fn write(
+        &self,
+        write: &mut CountingWriter<impl Write>,
+        _anim_def: &AnimDefIndex<'_>,
+    ) -> Result<()> {
         write_sequence(write, &self.name)
     }
 }
@@ -53,13 +59,16 @@ impl ScriptObject for StopSequence {
     const INDEX: u8 = 23;
     const SIZE: u32 = SequenceC::SIZE;
 
-    fn read(read: &mut CountingReader<impl Read>, _anim_def: &AnimDef, size: u32) -> Result<Self> {
-        assert_that!("stop sequence size", size == Self::SIZE, read.offset)?;
+    fn read(read: &mut CountingReader<impl Read>, _anim_def: &AnimDef, _size: u32) -> Result<Self> {
         let name = read_sequence(read)?;
         Ok(Self { name })
     }
 
-    fn write(&self, write: &mut CountingWriter<impl Write>, _anim_def: &AnimDef) -> Result<()> {
+    fn write(
+        &self,
+        write: &mut CountingWriter<impl Write>,
+        _anim_def: &AnimDefIndex<'_>,
+    ) -> Result<()> {
         write_sequence(write, &self.name)
     }
 }
@@ -1,11 +1,11 @@
 use super::ScriptObject;
-use crate::types::AnimDefLookup as _;
+use crate::types::{AnimDefIndex, AnimDefLookup as _};
 use bytemuck::{AnyBitPattern, NoUninit};
 use mech3ax_api_types::anim::events::{AtNode, Sound};
 use mech3ax_api_types::anim::AnimDef;
 use mech3ax_api_types::Vec3;
 use mech3ax_common::io_ext::{CountingReader, CountingWriter};
-use mech3ax_common::{assert_that, Result};
+use mech3ax_common::Result;
 use mech3ax_types::{impl_as_bytes, AsBytes as _};
 use std::io::{Read, Write};
 
@@ -16,14 +16,13 @@ struct SoundC {
     node_index: u16,
     translation: Vec3,
 }
-impl_as_bytes!(SoundC, 16);
+impl_as_bytes!(SoundC, 16, LeStruct);
 
 impl ScriptObject for Sound {
     const INDEX: u8 = 1;
     const SIZE: u32 = SoundC::SIZE;
 
-    fn read(read: &mut CountingReader<impl Read>, anim_def: &AnimDef, size: u32) -> Result<Self> {
-        assert_that!("sound size", size == Self::SIZE, read.offset)?;
+    fn read(read: &mut CountingReader<impl Read>, anim_def: &AnimDef, _size: u32) -> Result<Self> {
         let sound: SoundC = read.read_struct()?;
         let name = anim_def.sound_from_index(sound.sound_index as usize, read.prev + 0)?;
         let node = anim_def.node_from_index(sound.node_index as usize, read.prev + 2)?;
@@ -36,7 +35,11 @@ impl ScriptObject for Sound {
         })
     }
 
-    fn write(&self, write: &mut CountingWriter<impl Write>, anim_def: &AnimDef) -> Result<()> {
+    fn write(
+        &self,
+        write: &mut CountingWriter<impl Write>,
+        anim_def: &AnimDefIndex<'_>,
+    ) -> Result<()> {
         write.write_struct(&SoundC {
             sound_index: anim_def.sound_to_index(&self.name)? as u16,
             node_index: anim_def.node_to_index(&self.at_node.node)? as u16,
@@ -1,5 +1,5 @@
 use super::ScriptObject;
-use crate::types::AnimDefLookup as _;
+use crate::types::{AnimDefIndex, AnimDefLookup as _};
 use bytemuck::{AnyBitPattern, NoUninit};
 use mech3ax_api_types::anim::events::{AtNode, SoundNode};
 use mech3ax_api_types::anim::AnimDef;
@@ -20,14 +20,13 @@ struct SoundNodeC {
     node_index: u32,          // 44
     translation: Vec3,        // 48
 }
-impl_as_bytes!(SoundNodeC, 60);
+impl_as_bytes!(SoundNodeC, 60, LeStruct);
 
 impl ScriptObject for SoundNode {
     const INDEX: u8 = 2;
     const SIZE: u32 = SoundNodeC::SIZE;
 
-    fn read(read: &mut CountingReader<impl Read>, anim_def: &AnimDef, size: u32) -> Result<Self> {
-        assert_that!("sound node size", size == Self::SIZE, read.offset)?;
+    fn read(read: &mut CountingReader<impl Read>, anim_def: &AnimDef, _size: u32) -> Result<Self> {
         let sound_node: SoundNodeC = read.read_struct()?;
         let name = assert_utf8("sound node name", read.prev + 0, || {
             sound_node.name.to_str_padded()
@@ -63,8 +62,14 @@ impl ScriptObject for SoundNode {
         })
     }
 
-    fn write(&self, write: &mut CountingWriter<impl Write>, anim_def: &AnimDef) -> Result<()> {
-        let name = Ascii::from_str_padded(&self.name);
+    fn write(
+        &self,
+        write: &mut CountingWriter<impl Write>,
+        anim_def: &AnimDefIndex<'_>,
+    ) -> Result<()> {
+        let name = assert_utf8("sound node name", write.offset, || {
+            Ascii::from_str_padded(&self.name)
+        })?;
 
         let (inherit_translation, node_index, translation) = if let Some(at_node) = &self.at_node {
             let node_index = anim_def.node_to_index(&at_node.node)? as u32;
@@ -0,0 +1,930 @@
+use super::parse::read_event;
+use super::types::INPUT_NODE;
+use super::{object_motion_si_script_size, read_events, size_events, write_events, ScriptObject};
+use crate::types::AnimDefIndex;
+use mech3ax_api_types::anim::events::*;
+use mech3ax_api_types::anim::{AnimActivation, AnimDef, Execution, NamePad, NamePtr, NamePtrFlags};
+use mech3ax_api_types::{Color, Quaternion, Range, Vec3};
+use mech3ax_common::io_ext::{CountingReader, CountingWriter};
+use mech3ax_common::test_utils::assert_truncated_fails;
+use mech3ax_common::Error;
+use mech3ax_types::u32_to_usize;
+use proptest::collection::vec;
+use proptest::option;
+use proptest::prelude::*;
+use std::io::Cursor;
+
+fn name_ptr(name: &str) -> NamePtr {
+    NamePtr {
+        name: name.to_string(),
+        pointer: 0,
+    }
+}
+
+fn anim_def() -> AnimDef {
+    AnimDef {
+        name: "test".to_string(),
+        anim_name: NamePad {
+            name: "test".to_string(),
+            pad: Vec::new(),
+        },
+        anim_root: NamePad {
+            name: "test".to_string(),
+            pad: Vec::new(),
+        },
+        file_name: "test.flt".to_string(),
+        auto_reset_node_states: true,
+        activation: AnimActivation::OnCall,
+        execution: Execution::None,
+        network_log: None,
+        save_log: None,
+        has_callbacks: true,
+        reset_time: None,
+        health: 0.0,
+        proximity_damage: true,
+        activ_prereq_min_to_satisfy: 0,
+        objects: None,
+        nodes: Some(vec![name_ptr("node"), name_ptr("other")]),
+        lights: Some(vec![name_ptr("light")]),
+        puffers: Some(vec![NamePtrFlags {
+            name: "puffer".to_string(),
+            pointer: 0,
+            flags: 0,
+        }]),
+        dynamic_sounds: None,
+        static_sounds: Some(vec![NamePad {
+            name: "sound".to_string(),
+            pad: Vec::new(),
+        }]),
+        activ_prereqs: None,
+        anim_refs: None,
+        reset_state: None,
+        sequences: Vec::new(),
+    }
+}
+
+const POS: Vec3 = Vec3 {
+    x: 1.0,
+    y: 2.0,
+    z: 3.0,
+};
+
+fn at_node() -> AtNode {
+    AtNode {
+        node: "node".to_string(),
+        translation: POS,
+    }
+}
+
+fn vec3_from_to() -> Option<Vec3FromTo> {
+    Some(Vec3FromTo {
+        from: Vec3::DEFAULT,
+        to: POS,
+        delta: POS,
+    })
+}
+
+/// One of every event, with as many optional parts set as possible.
+fn events() -> Vec<EventData> {
+    vec![
+        EventData::Sound(Sound {
+            name: "sound".to_string(),
+            at_node: at_node(),
+        }),
+        EventData::SoundNode(SoundNode {
+            name: "sound".to_string(),
+            active_state: true,
+            at_node: Some(at_node()),
+        }),
+        EventData::LightState(LightState {
+            name: "light".to_string(),
+            active_state: true,
+            directional: Some(true),
+            saturated: Some(false),
+            subdivide: Some(true),
+            static_: Some(false),
+            at_node: Some(at_node()),
+            range: Some(Range {
+                min: 1.0,
+                max: 10.0,
+            }),
+            color: Some(Color::WHITE_NORM),
+            ambient: Some(0.5),
+            diffuse: Some(0.5),
+        }),
+        EventData::LightAnimation(LightAnimation {
+            name: "light".to_string(),
+            range: Range {
+                min: 1.0,
+                max: 10.0,
+            },
+            color: Color::WHITE_NORM,
+            runtime: 2.0,
+        }),
+        EventData::ObjectActiveState(ObjectActiveState {
+            node: "node".to_string(),
+            state: true,
+        }),
+        EventData::ObjectTranslateState(ObjectTranslateState {
+            node: "node".to_string(),
+            translate: POS,
+            at_node: Some(INPUT_NODE.to_string()),
+        }),
+        EventData::ObjectScaleState(ObjectScaleState {
+            node: "node".to_string(),
+            scale: POS,
+        }),
+        EventData::ObjectRotateState(ObjectRotateState {
+            node: "node".to_string(),
+            rotate: RotateState::Absolute(POS),
+        }),
+        EventData::ObjectMotion(ObjectMotion {
+            node: "node".to_string(),
+            impact_force: true,
+            gravity: Some(Gravity {
+                mode: GravityMode::Local,
+                value: -9.8,
+            }),
+            translation_range_min: None,
+            translation_range_max: None,
+            translation: Some(ObjectMotionTranslation {
+                delta: POS,
+                initial: Vec3::DEFAULT,
+                unk: Vec3::DEFAULT,
+            }),
+            forward_rotation: Some(ForwardRotation::Distance(ForwardRotationDistance {
+                v1: 1.0,
+            })),
+            xyz_rotation: None,
+            scale: None,
+            bounce_sequence: None,
+            bounce_sound: Some(BounceSound {
+                name: "sound".to_string(),
+                volume: 1.0,
+            }),
+            runtime: Some(3.0),
+            unknown_flags: 0,
+        }),
+        EventData::ObjectMotionFromTo(ObjectMotionFromTo {
+            node: "node".to_string(),
+            run_time: 1.0,
+            morph: Some(FloatFromTo {
+                from: 0.0,
+                to: 1.0,
+                delta: 1.0,
+            }),
+            translate: vec3_from_to(),
+            rotate: vec3_from_to(),
+            scale: vec3_from_to(),
+            unknown_flags: 0,
+        }),
+        EventData::ObjectMotionSIScript(ObjectMotionSiScript {
+            node: "node".to_string(),
+            frames: vec![
+                ObjectMotionSiFrame {
+                    start_time: 0.0,
+                    end_time: 1.0,
+                    translation: Some(TranslateData {
+                        value: POS,
+                        unk: vec![0; 64],
+                    }),
+                    rotation: Some(RotateData {
+                        value: Quaternion::DEFAULT,
+                        unk: vec![0; 60],
+                    }),
+                    scale: Some(ScaleData {
+                        value: POS,
+                        unk: vec![0; 64],
+                    }),
+                },
+                ObjectMotionSiFrame {
+                    start_time: 1.0,
+                    end_time: 2.0,
+                    translation: None,
+                    rotation: None,
+                    scale: Some(ScaleData {
+                        value: POS,
+                        unk: vec![0; 64],
+                    }),
+                },
+            ],
+        }),
+        EventData::ObjectOpacityState(ObjectOpacityState {
+            node: "node".to_string(),
+            is_set: true,
+            state: true,
+            opacity: 0.5,
+        }),
+        EventData::ObjectOpacityFromTo(ObjectOpacityFromTo {
+            node: "node".to_string(),
+            opacity_from: ObjectOpacity {
+                value: 0.0,
+                state: 1,
+            },
+            opacity_to: ObjectOpacity {
+                value: 1.0,
+                state: 1,
+            },
+            runtime: 1.0,
+            fudge: false,
+        }),
+        EventData::ObjectAddChild(ObjectAddChild {
+            parent: "node".to_string(),
+            child: "other".to_string(),
+        }),
+        EventData::ObjectCycleTexture(ObjectCycleTexture {
+            node: "node".to_string(),
+            reset: 1,
+        }),
+        EventData::ObjectConnector(ObjectConnector {
+            node: "node".to_string(),
+            from_node: Some("other".to_string()),
+            to_node: Some(INPUT_NODE.to_string()),
+            from_pos: None,
+            to_pos: Some(POS),
+            max_length: Some(10.0),
+            unknown_flags: 0,
+        }),
+        EventData::CallObjectConnector(CallObjectConnector {
+            node: "connector".to_string(),
+            from_node: "node".to_string(),
+            to_node: "".to_string(),
+            to_pos: POS,
+        }),
+        EventData::CallSequence(CallSequence {
+            name: "sequence".to_string(),
+        }),
+        EventData::StopSequence(StopSequence {
+            name: "sequence".to_string(),
+        }),
+        EventData::CallAnimation(CallAnimation {
+            name: "animation".to_string(),
+            wait_for_completion: None,
+            parameters: CallAnimationParameters::AtNode(CallAnimationAtNode {
+                node: "node".to_string(),
+                translation: Some(POS),
+                rotation: Some(POS),
+            }),
+        }),
+        EventData::StopAnimation(StopAnimation {
+            name: "animation".to_string(),
+        }),
+        EventData::ResetAnimation(ResetAnimation {
+            name: "animation".to_string(),
+        }),
+        EventData::InvalidateAnimation(InvalidateAnimation {
+            name: "animation".to_string(),
+        }),
+        EventData::FogState(FogState {
+            name: "fog".to_string(),
+            fog_type: FogType::Linear,
+            color: Color::WHITE_NORM,
+            altitude: Range {
+                min: 0.0,
+                max: 100.0,
+            },
+            range: Range {
+                min: 10.0,
+                max: 1000.0,
+            },
+        }),
+        EventData::Loop(Loop {
+            start: 1,
+            loop_count: 2,
+        }),
+        EventData::If(If::PlayerRange(PlayerRangeCond::from(100.0))),
+        EventData::Elif(ElseIf::HwRender(HwRenderCond::from(true))),
+        EventData::Else(Else {}),
+        EventData::Endif(EndIf {}),
+        EventData::Callback(Callback { value: 1 }),
+        EventData::FrameBufferEffectColorFromTo(FrameBufferEffectColor {
+            from: Rgba {
+                r: 0.0,
+                g: 0.0,
+                b: 0.0,
+                a: 0.0,
+            },
+            to: Rgba {
+                r: 1.0,
+                g: 1.0,
+                b: 1.0,
+                a: 1.0,
+            },
+            runtime: 1.0,
+            fudge_alpha: false,
+        }),
+        EventData::DetonateWeapon(DetonateWeapon {
+            name: "weapon".to_string(),
+            at_node: at_node(),
+        }),
+        EventData::PufferState(PufferState {
+            name: "puffer".to_string(),
+            state: true,
+            translate: true,
+            active_state: Some(1),
+            at_node: Some(at_node()),
+            local_velocity: Some(POS),
+            world_velocity: None,
+            min_random_velocity: None,
+            max_random_velocity: None,
+            world_acceleration: Some(POS),
+            interval: Interval {
+                interval_type: IntervalType::Time,
+                interval_value: 0.5,
+                flag: true,
+            },
+            size_range: Some(Range { min: 1.0, max: 2.0 }),
+            lifetime_range: None,
+            start_age_range: None,
+            deviation_distance: Some(1.0),
+            fade_range: None,
+            friction: Some(0.5),
+            textures: Some(PufferStateCycleTextures {
+                texture1: Some("smoke1".to_string()),
+                texture2: Some("smoke2".to_string()),
+                texture3: None,
+                texture4: None,
+                texture5: None,
+                texture6: None,
+            }),
+            growth_factor: None,
+        }),
+    ]
+}
+
+/// Write the value, and assert the number of bytes written is the expected
+/// size declared for it.
+fn assert_written_size<T: ScriptObject>(value: &T, expected: u32) {
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    value
+        .write(&mut write, &AnimDefIndex::new(&anim_def()))
+        .unwrap();
+    assert_eq!(
+        write.offset,
+        u32_to_usize(expected),
+        "{}",
+        std::any::type_name::<T>()
+    );
+}
+
+#[test]
+fn event_data_written_size_matches_declared_size() {
+    for data in events() {
+        match &data {
+            EventData::Sound(value) => assert_written_size(value, Sound::SIZE),
+            EventData::SoundNode(value) => assert_written_size(value, SoundNode::SIZE),
+            EventData::LightState(value) => assert_written_size(value, LightState::SIZE),
+            EventData::LightAnimation(value) => assert_written_size(value, LightAnimation::SIZE),
+            EventData::ObjectActiveState(value) => {
+                assert_written_size(value, ObjectActiveState::SIZE)
+            }
+            EventData::ObjectTranslateState(value) => {
+                assert_written_size(value, ObjectTranslateState::SIZE)
+            }
+            EventData::ObjectScaleState(value) => {
+                assert_written_size(value, ObjectScaleState::SIZE)
+            }
+            EventData::ObjectRotateState(value) => {
+                assert_written_size(value, ObjectRotateState::SIZE)
+            }
+            EventData::ObjectMotion(value) => assert_written_size(value, ObjectMotion::SIZE),
+            EventData::ObjectMotionFromTo(value) => {
+                assert_written_size(value, ObjectMotionFromTo::SIZE)
+            }
+            EventData::ObjectMotionSIScript(value) => {
+                // the size depends on the frames
+                assert_written_size(value, object_motion_si_script_size(value))
+            }
+            EventData::ObjectOpacityState(value) => {
+                assert_written_size(value, ObjectOpacityState::SIZE)
+            }
+            EventData::ObjectOpacityFromTo(value) => {
+                assert_written_size(value, ObjectOpacityFromTo::SIZE)
+            }
+            EventData::ObjectAddChild(value) => assert_written_size(value, ObjectAddChild::SIZE),
+            EventData::ObjectCycleTexture(value) => {
+                assert_written_size(value, ObjectCycleTexture::SIZE)
+            }
+            EventData::ObjectConnector(value) => assert_written_size(value, ObjectConnector::SIZE),
+            EventData::CallObjectConnector(value) => {
+                assert_written_size(value, CallObjectConnector::SIZE)
+            }
+            EventData::CallSequence(value) => assert_written_size(value, CallSequence::SIZE),
+            EventData::StopSequence(value) => assert_written_size(value, StopSequence::SIZE),
+            EventData::CallAnimation(value) => assert_written_size(value, CallAnimation::SIZE),
+            EventData::StopAnimation(value) => assert_written_size(value, StopAnimation::SIZE),
+            EventData::ResetAnimation(value) => assert_written_size(value, ResetAnimation::SIZE),
+            EventData::InvalidateAnimation(value) => {
+                assert_written_size(value, InvalidateAnimation::SIZE)
+            }
+            EventData::FogState(value) => assert_written_size(value, FogState::SIZE),
+            EventData::Loop(value) => assert_written_size(value, Loop::SIZE),
+            EventData::If(value) => assert_written_size(value, If::SIZE),
+            EventData::Else(value) => assert_written_size(value, Else::SIZE),
+            EventData::Elif(value) => assert_written_size(value, ElseIf::SIZE),
+            EventData::Endif(value) => assert_written_size(value, EndIf::SIZE),
+            EventData::Callback(value) => assert_written_size(value, Callback::SIZE),
+            EventData::FrameBufferEffectColorFromTo(value) => {
+                assert_written_size(value, FrameBufferEffectColor::SIZE)
+            }
+            EventData::DetonateWeapon(value) => assert_written_size(value, DetonateWeapon::SIZE),
+            EventData::PufferState(value) => assert_written_size(value, PufferState::SIZE),
+        }
+    }
+}
+
+#[test]
+fn events_written_size_matches_size_events() {
+    let events = events()
+        .into_iter()
+        .map(|data| Event { data, start: None })
+        .collect::<Vec<_>>();
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    write_events(&mut write, &AnimDefIndex::new(&anim_def()), &events).unwrap();
+    assert_eq!(write.offset, u32_to_usize(size_events(&events)));
+}
+
+#[test]
+fn read_event_unknown_type() {
+    let mut read = CountingReader::new(Cursor::new(Vec::new()));
+    let err = read_event(0, &mut read, &anim_def(), 0).unwrap_err();
+    let Error::Assert(e) = err else {
+        panic!("expected an assertion error");
+    };
+    assert_eq!(e.message(), "Expected valid event type, but was 0 (at 0)");
+}
+
+#[test]
+fn read_event_wrong_size_is_rejected_before_reading() {
+    // the data is empty, so any read would fail with an I/O error
+    let mut read = CountingReader::new(Cursor::new(Vec::new()));
+    let err = read_event(Sound::INDEX, &mut read, &anim_def(), Sound::SIZE + 4).unwrap_err();
+    let Error::Assert(e) = err else {
+        panic!("expected an assertion error");
+    };
+    assert_eq!(e.name(), Some("sound size"));
+    assert_eq!(e.pos(), Some(0));
+    assert_eq!(read.offset, 0);
+}
+
+/// A sound event with the given sound index, at the first node.
+fn sound_event(sound_index: u16) -> Vec<u8> {
+    let mut buf = Vec::new();
+    buf.extend_from_slice(&sound_index.to_le_bytes());
+    buf.extend_from_slice(&1u16.to_le_bytes());
+    buf.extend_from_slice(&[0; 12]);
+    buf
+}
+
+#[test]
+fn read_sound_index_boundaries() {
+    // sound indices are one-based, so the last valid index is the length
+    let mut read = CountingReader::new(Cursor::new(sound_event(1)));
+    let data = read_event(Sound::INDEX, &mut read, &anim_def(), Sound::SIZE).unwrap();
+    let EventData::Sound(sound) = data else {
+        panic!("expected a sound event");
+    };
+    assert_eq!(sound.name, "sound");
+
+    for sound_index in [0, 2] {
+        let mut read = CountingReader::new(Cursor::new(sound_event(sound_index)));
+        let err = read_event(Sound::INDEX, &mut read, &anim_def(), Sound::SIZE).unwrap_err();
+        let Error::Assert(e) = err else {
+            panic!("expected an assertion error");
+        };
+        assert_eq!(e.name(), Some("sound index"));
+        assert_eq!(e.pos(), Some(0));
+    }
+}
+
+#[test]
+fn read_event_round_trip() {
+    for data in events() {
+        let event = Event { data, start: None };
+        let mut write = CountingWriter::new(Vec::new(), 0);
+        write_events(
+            &mut write,
+            &AnimDefIndex::new(&anim_def()),
+            std::slice::from_ref(&event),
+        )
+        .unwrap();
+        let buf = write.into_inner();
+
+        // skip the event header
+        let mut read = CountingReader::new(Cursor::new(buf[12..].to_vec()));
+        let size = size_events(std::slice::from_ref(&event)) - 12;
+        let data = read_event(buf[0], &mut read, &anim_def(), size).unwrap();
+        assert_eq!(read.offset, u32_to_usize(size));
+
+        let event = Event { data, start: None };
+        let mut write = CountingWriter::new(Vec::new(), 0);
+        write_events(&mut write, &AnimDefIndex::new(&anim_def()), &[event]).unwrap();
+        assert_eq!(write.into_inner(), buf);
+    }
+}
+
+fn write_all_events(anim_def: &AnimDef, events: &[Event]) -> Vec<u8> {
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    write_events(&mut write, &AnimDefIndex::new(anim_def), events).unwrap();
+    write.into_inner()
+}
+
+fn read_all_events(data: &[u8], length: usize, anim_def: &AnimDef) -> Result<Vec<Event>, Error> {
+    let mut read = CountingReader::new(Cursor::new(data));
+    read_events(&mut read, length.try_into().unwrap(), anim_def)
+}
+
+#[test]
+fn read_events_truncated_fails() {
+    let events = events()
+        .into_iter()
+        .map(|data| Event { data, start: None })
+        .collect::<Vec<_>>();
+    let anim_def = anim_def();
+    let data = write_all_events(&anim_def, &events);
+    // the length of the events is stored separately, and not truncated
+    assert_truncated_fails(&data, |prefix| {
+        read_all_events(prefix, data.len(), &anim_def)
+    });
+}
+
+#[test]
+fn read_events_header_size_too_small() {
+    let anim_def = anim_def();
+    let event = Event {
+        data: EventData::Sound(Sound {
+            name: "sound".to_string(),
+            at_node: at_node(),
+        }),
+        start: None,
+    };
+    let mut data = write_all_events(&anim_def, &[event]);
+    data[4..8].copy_from_slice(&4u32.to_le_bytes());
+
+    let err = read_all_events(&data, data.len(), &anim_def).unwrap_err();
+    let Error::Assert(e) = err else {
+        panic!("expected an assertion error");
+    };
+    assert_eq!(e.name(), Some("event header size"));
+    assert_eq!(e.pos(), Some(4));
+}
+
+#[test]
+fn read_call_animation_wait_for_without_anim_refs() {
+    let call_animation = CallAnimation {
+        name: "animation".to_string(),
+        wait_for_completion: Some(0),
+        parameters: CallAnimationParameters::None,
+    };
+    let mut with_anim_refs = anim_def();
+    with_anim_refs.anim_refs = Some(vec![NamePad {
+        name: "animation".to_string(),
+        pad: Vec::new(),
+    }]);
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    call_animation
+        .write(&mut write, &AnimDefIndex::new(&with_anim_refs))
+        .unwrap();
+    let data = write.into_inner();
+
+    let mut read = CountingReader::new(Cursor::new(data.clone()));
+    let read_back = CallAnimation::read(&mut read, &with_anim_refs, CallAnimation::SIZE).unwrap();
+    assert_eq!(read_back.wait_for_completion, Some(0));
+
+    // without anim refs, there is nothing to wait for
+    let mut read = CountingReader::new(Cursor::new(data));
+    let err = CallAnimation::read(&mut read, &anim_def(), CallAnimation::SIZE).unwrap_err();
+    let Error::Assert(e) = err else {
+        panic!("expected an assertion error");
+    };
+    assert_eq!(
+        e.message(),
+        "Expected `call animation wait for` to have an anim ref to wait for, but there are none (at 38)"
+    );
+}
+
+fn connector() -> ObjectConnector {
+    ObjectConnector {
+        node: "node".to_string(),
+        from_node: Some("other".to_string()),
+        to_node: Some(INPUT_NODE.to_string()),
+        from_pos: None,
+        to_pos: Some(POS),
+        max_length: Some(10.0),
+        unknown_flags: 0,
+    }
+}
+
+fn motion_from_to() -> ObjectMotionFromTo {
+    ObjectMotionFromTo {
+        node: "node".to_string(),
+        run_time: 1.0,
+        morph: None,
+        translate: vec3_from_to(),
+        rotate: None,
+        scale: None,
+        unknown_flags: 0,
+    }
+}
+
+/// Set an unknown bit in the flags of the written event, and check that it's
+/// read into the unknown flags and written back unchanged.
+fn assert_unknown_flags_round_trip<T: ScriptObject>(
+    value: &T,
+    bit: u32,
+    unknown_flags: fn(&T) -> u32,
+) {
+    let anim_def = anim_def();
+    let index = AnimDefIndex::new(&anim_def);
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    value.write(&mut write, &index).unwrap();
+    let mut buf = write.into_inner();
+    // the flags are the first field
+    let flags = u32::from_le_bytes(buf[0..4].try_into().unwrap());
+    buf[0..4].copy_from_slice(&(flags | bit).to_le_bytes());
+
+    let mut read = CountingReader::new(Cursor::new(buf.clone()));
+    let read_back = T::read(&mut read, &anim_def, T::SIZE).unwrap();
+    assert_eq!(unknown_flags(&read_back), bit);
+
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    read_back.write(&mut write, &index).unwrap();
+    assert_eq!(write.into_inner(), buf);
+}
+
+fn assert_unknown_flags_known_bit<T: ScriptObject>(value: &T, name: &str) {
+    let anim_def = anim_def();
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    let err = value
+        .write(&mut write, &AnimDefIndex::new(&anim_def))
+        .unwrap_err();
+    let Error::Assert(e) = err else {
+        panic!("expected an assertion error");
+    };
+    assert_eq!(e.name(), Some(name));
+}
+
+#[test]
+fn object_connector_unknown_flags_round_trip() {
+    // between the from and to flags
+    assert_unknown_flags_round_trip(&connector(), 1 << 2, |value| value.unknown_flags);
+    assert_unknown_flags_round_trip(&connector(), 1 << 31, |value| value.unknown_flags);
+}
+
+#[test]
+fn object_connector_unknown_flags_known_bit() {
+    let mut value = connector();
+    // max length
+    value.unknown_flags = 1 << 15;
+    assert_unknown_flags_known_bit(&value, "object connector unknown flags");
+}
+
+#[test]
+fn object_motion_from_to_unknown_flags_round_trip() {
+    assert_unknown_flags_round_trip(&motion_from_to(), 1 << 4, |value| value.unknown_flags);
+    assert_unknown_flags_round_trip(&motion_from_to(), 1 << 31, |value| value.unknown_flags);
+}
+
+#[test]
+fn object_motion_from_to_unknown_flags_known_bit() {
+    let mut value = motion_from_to();
+    // morph
+    value.unknown_flags = 1 << 3;
+    assert_unknown_flags_known_bit(&value, "object motion from to unknown flags");
+}
+
+fn float() -> impl Strategy<Value = f32> {
+    // any bit pattern round-trips, including NaNs and infinities
+    proptest::num::f32::ANY
+}
+
+fn positive() -> impl Strategy<Value = f32> {
+    use proptest::num::f32::{INFINITE, NORMAL, POSITIVE, SUBNORMAL};
+    POSITIVE | NORMAL | SUBNORMAL | INFINITE
+}
+
+fn vec3() -> impl Strategy<Value = Vec3> {
+    (float(), float(), float()).prop_map(|(x, y, z)| Vec3 { x, y, z })
+}
+
+fn quaternion() -> impl Strategy<Value = Quaternion> {
+    (float(), float(), float(), float()).prop_map(|(x, y, z, w)| Quaternion { x, y, z, w })
+}
+
+fn node() -> impl Strategy<Value = String> {
+    prop_oneof![Just("node".to_string()), Just("other".to_string())]
+}
+
+fn bounce_seq_name() -> impl Strategy<Value = String> {
+    "[a-zA-Z0-9_]{1,31}"
+}
+
+fn vec3_from_to_strategy() -> impl Strategy<Value = Vec3FromTo> {
+    (vec3(), vec3(), vec3()).prop_map(|(from, to, delta)| Vec3FromTo { from, to, delta })
+}
+
+fn object_motion() -> impl Strategy<Value = ObjectMotion> {
+    let gravity_mode = prop_oneof![
+        Just(GravityMode::Local),
+        Just(GravityMode::Complex),
+        Just(GravityMode::NoAltitude),
+    ];
+    let gravity = (gravity_mode, float()).prop_map(|(mode, value)| Gravity { mode, value });
+    let translation =
+        (vec3(), vec3(), vec3()).prop_map(|(delta, initial, unk)| ObjectMotionTranslation {
+            delta,
+            initial,
+            unk,
+        });
+    let forward_rotation = prop_oneof![
+        (float(), float())
+            .prop_map(|(v1, v2)| ForwardRotation::Time(ForwardRotationTime { v1, v2 })),
+        float().prop_map(|v1| ForwardRotation::Distance(ForwardRotationDistance { v1 })),
+    ];
+    let xyz_rotation = (vec3(), vec3()).prop_map(|(value, unk)| XyzRotation { value, unk });
+    let scale = (vec3(), vec3()).prop_map(|(value, unk)| ObjectMotionScale { value, unk });
+    let bounce_sequence = (
+        bounce_seq_name(),
+        option::of(bounce_seq_name()),
+        option::of(bounce_seq_name()),
+    )
+        .prop_map(|(seq_name0, seq_name1, seq_name2)| BounceSequence {
+            seq_name0: Some(seq_name0),
+            seq_name1,
+            seq_name2,
+        });
+    let bounce_sound = positive().prop_map(|volume| BounceSound {
+        name: "sound".to_string(),
+        volume,
+    });
+    (
+        (
+            node(),
+            any::<bool>(),
+            option::of(gravity),
+            option::of(quaternion()),
+            option::of(quaternion()),
+            option::of(translation),
+        ),
+        (
+            option::of(forward_rotation),
+            option::of(xyz_rotation),
+            option::of(scale),
+            option::of(bounce_sequence),
+            option::of(bounce_sound),
+            option::of(positive()),
+        ),
+    )
+        .prop_map(
+            |(
+                (
+                    node,
+                    impact_force,
+                    gravity,
+                    translation_range_min,
+                    translation_range_max,
+                    translation,
+                ),
+                (forward_rotation, xyz_rotation, scale, bounce_sequence, bounce_sound, runtime),
+            )| ObjectMotion {
+                node,
+                impact_force,
+                gravity,
+                translation_range_min,
+                translation_range_max,
+                translation,
+                forward_rotation,
+                xyz_rotation,
+                scale,
+                bounce_sequence,
+                bounce_sound,
+                runtime,
+                unknown_flags: 0,
+            },
+        )
+}
+
+fn object_connector() -> impl Strategy<Value = ObjectConnector> {
+    // without a node, the position comes from the input, so a fixed
+    // position can't be given as well
+    let from = prop_oneof![
+        Just((None, None)),
+        (
+            prop_oneof![node(), Just(INPUT_NODE.to_string())],
+            option::of(vec3())
+        )
+            .prop_map(|(node, pos)| (Some(node), pos)),
+    ];
+    let to = prop_oneof![
+        Just((None, None)),
+        (
+            prop_oneof![node(), Just(INPUT_NODE.to_string())],
+            option::of(vec3())
+        )
+            .prop_map(|(node, pos)| (Some(node), pos)),
+    ];
+    (node(), from, to, option::of(positive())).prop_map(
+        |(node, (from_node, from_pos), (to_node, to_pos), max_length)| ObjectConnector {
+            node,
+            from_node,
+            to_node,
+            from_pos,
+            to_pos,
+            max_length,
+            unknown_flags: 0,
+        },
+    )
+}
+
+fn object_motion_from_to() -> impl Strategy<Value = ObjectMotionFromTo> {
+    let morph =
+        (float(), float(), float()).prop_map(|(from, to, delta)| FloatFromTo { from, to, delta });
+    (
+        node(),
+        positive(),
+        option::of(morph),
+        option::of(vec3_from_to_strategy()),
+        option::of(vec3_from_to_strategy()),
+        option::of(vec3_from_to_strategy()),
+    )
+        .prop_map(
+            |(node, run_time, morph, translate, rotate, scale)| ObjectMotionFromTo {
+                node,
+                run_time,
+                morph,
+                translate,
+                rotate,
+                scale,
+                unknown_flags: 0,
+            },
+        )
+}
+
+/// Write the event, read it back, and check that the value and the bytes
+/// written for it are unchanged.
+fn assert_round_trip<T: ScriptObject + std::fmt::Debug>(value: &T) -> Result<(), TestCaseError> {
+    let anim_def = anim_def();
+    let index = AnimDefIndex::new(&anim_def);
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    value.write(&mut write, &index).unwrap();
+    let buf = write.into_inner();
+    prop_assert_eq!(buf.len(), u32_to_usize(T::SIZE));
+
+    let mut read = CountingReader::new(Cursor::new(buf.clone()));
+    let read_back = T::read(&mut read, &anim_def, T::SIZE).unwrap();
+    prop_assert_eq!(read.offset, buf.len());
+    // the events don't implement `PartialEq`, and floats would need bitwise
+    // comparison anyway
+    prop_assert_eq!(format!("{:?}", read_back), format!("{:?}", value));
+
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    read_back.write(&mut write, &index).unwrap();
+    prop_assert_eq!(write.into_inner(), buf);
+    Ok(())
+}
+
+/// Reading arbitrary bytes may fail, but must never panic.
+fn read_arbitrary<T: ScriptObject>(data: Vec<u8>) {
+    let mut read = CountingReader::new(Cursor::new(data));
+    let _ = read_event(T::INDEX, &mut read, &anim_def(), T::SIZE);
+}
+
+proptest! {
+    #[test]
+    fn object_motion_round_trip(value in object_motion()) {
+        assert_round_trip(&value)?;
+    }
+
+    #[test]
+    fn object_connector_round_trip(value in object_connector()) {
+        assert_round_trip(&value)?;
+    }
+
+    #[test]
+    fn object_motion_from_to_round_trip(value in object_motion_from_to()) {
+        assert_round_trip(&value)?;
+    }
+
+    #[test]
+    fn object_motion_read_arbitrary(data in vec(any::<u8>(), u32_to_usize(ObjectMotion::SIZE))) {
+        read_arbitrary::<ObjectMotion>(data);
+    }
+
+    #[test]
+    fn object_connector_read_arbitrary(data in vec(any::<u8>(), u32_to_usize(ObjectConnector::SIZE))) {
+        read_arbitrary::<ObjectConnector>(data);
+    }
+
+    #[test]
+    fn read_events_arbitrary(data in vec(any::<u8>(), 0..256)) {
+        // reading may fail, but must never panic
+        let _ = read_all_events(&data, data.len(), &anim_def());
+    }
+
+    #[test]
+    fn object_motion_from_to_read_arbitrary(
+        data in vec(any::<u8>(), u32_to_usize(ObjectMotionFromTo::SIZE))
+    ) {
+        read_arbitrary::<ObjectMotionFromTo>(data);
+    }
+}
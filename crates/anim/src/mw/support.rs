@@ -14,7 +14,7 @@ struct ObjectC {
     zero32: u32,     // 32
     unk: Bytes<60>,  // 36
 }
-impl_as_bytes!(ObjectC, 96);
+impl_as_bytes!(ObjectC, 96, LeStruct);
 
 pub fn read_objects(read: &mut CountingReader<impl Read>, count: u8) -> Result<Vec<NamePad>> {
     trace!("Reading anim def object 0 at {}", read.offset);
@@ -70,7 +70,7 @@ struct NodeInfoC {
     zero32: u32,     // 32
     pointer: u32,    // 36
 }
-impl_as_bytes!(NodeInfoC, 40);
+impl_as_bytes!(NodeInfoC, 40, LeStruct);
 
 pub fn read_nodes(read: &mut CountingReader<impl Read>, count: u8) -> Result<Vec<NamePtr>> {
     trace!("Reading anim def node 0 at {}", read.offset);
@@ -135,7 +135,7 @@ struct ReaderLookupC {
     pointer: u32,    // 36
     in_world: u32,   // 40
 }
-impl_as_bytes!(ReaderLookupC, 44);
+impl_as_bytes!(ReaderLookupC, 44, LeStruct);
 
 pub fn read_lights(read: &mut CountingReader<impl Read>, count: u8) -> Result<Vec<NamePtr>> {
     trace!("Reading anim def light 0 at {}", read.offset);
@@ -204,7 +204,7 @@ struct PufferRefC {
     pointer: u32,    // 36
     zero40: u32,     // 40
 }
-impl_as_bytes!(PufferRefC, 44);
+impl_as_bytes!(PufferRefC, 44, LeStruct);
 
 pub fn read_puffers(read: &mut CountingReader<impl Read>, count: u8) -> Result<Vec<NamePtrFlags>> {
     trace!("Reading anim def puffer 0 at {}", read.offset);
@@ -254,7 +254,9 @@ pub fn write_puffers(
     // the first entry is always zero
     write.write_zeros(ReaderLookupC::SIZE)?;
     for puffer in puffers {
-        let name = Ascii::from_str_padded(&puffer.name);
+        let name = assert_utf8("anim def puffer name", write.offset, || {
+            Ascii::from_str_padded(&puffer.name)
+        })?;
         let flags = puffer.flags << 24;
         write.write_struct(&ReaderLookupC {
             name,
@@ -348,7 +350,7 @@ struct StaticSoundC {
     name: Ascii<32>, // 00
     zero32: u32,     // 32
 }
-impl_as_bytes!(StaticSoundC, 36);
+impl_as_bytes!(StaticSoundC, 36, LeStruct);
 
 pub fn read_static_sounds(read: &mut CountingReader<impl Read>, count: u8) -> Result<Vec<NamePad>> {
     trace!("Reading anim def static sound 0 at {}", read.offset);
@@ -401,7 +403,7 @@ struct AnimRefC {
     zero64: u32,     // 64
     pointer: u32,    // 68
 }
-impl_as_bytes!(AnimRefC, 72);
+impl_as_bytes!(AnimRefC, 72, LeStruct);
 
 pub fn read_anim_refs(read: &mut CountingReader<impl Read>, count: u8) -> Result<Vec<NamePad>> {
     // the first entry... is not zero! as this is not a node list
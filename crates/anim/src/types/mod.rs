@@ -1,5 +1,7 @@
 use mech3ax_api_types::anim::AnimDef;
 use mech3ax_common::{assert_that, assert_with_msg, Result};
+use std::collections::HashMap;
+use std::ops::Deref;
 
 pub trait AnimDefLookup {
     fn node_from_index(&self, index: usize, offset: usize) -> Result<String>;
@@ -125,3 +127,135 @@ impl AnimDefLookup for AnimDef {
         }
     }
 }
+
+type NameIndex<'a> = HashMap<&'a str, usize>;
+
+/// Build an index from name to (one-based) index. If a name is listed more
+/// than once, the first one is used, like a linear scan would.
+fn name_index<'a, T>(
+    items: Option<&'a [T]>,
+    name: impl Fn(&'a T) -> &'a str,
+) -> Option<NameIndex<'a>> {
+    items.map(|items| {
+        let mut index = HashMap::with_capacity(items.len());
+        for (pos, item) in items.iter().enumerate() {
+            index.entry(name(item)).or_insert(pos + 1);
+        }
+        index
+    })
+}
+
+/// An anim def with indices to look up names, for writing.
+///
+/// Looking up a name in the anim def scans the names, which is slow for
+/// anim defs with many events. Looking up an index is unchanged.
+#[derive(Debug)]
+pub struct AnimDefIndex<'a> {
+    anim_def: &'a AnimDef,
+    nodes: Option<NameIndex<'a>>,
+    sounds: Option<NameIndex<'a>>,
+    lights: Option<NameIndex<'a>>,
+    puffers: Option<NameIndex<'a>>,
+}
+
+impl<'a> AnimDefIndex<'a> {
+    pub fn new(anim_def: &'a AnimDef) -> Self {
+        Self {
+            anim_def,
+            nodes: name_index(anim_def.nodes.as_deref(), |node| &node.name),
+            sounds: name_index(anim_def.static_sounds.as_deref(), |sound| &sound.name),
+            lights: name_index(anim_def.lights.as_deref(), |light| &light.name),
+            puffers: name_index(anim_def.puffers.as_deref(), |puffer| &puffer.name),
+        }
+    }
+}
+
+impl Deref for AnimDefIndex<'_> {
+    type Target = AnimDef;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.anim_def
+    }
+}
+
+impl AnimDefLookup for AnimDefIndex<'_> {
+    #[inline]
+    fn node_from_index(&self, index: usize, offset: usize) -> Result<String> {
+        self.anim_def.node_from_index(index, offset)
+    }
+
+    fn node_to_index(&self, name: &str) -> Result<usize> {
+        if let Some(nodes) = &self.nodes {
+            nodes
+                .get(name)
+                .copied()
+                .ok_or_else(|| assert_with_msg!("Expected to find node `{}`, but didn't", name))
+        } else {
+            Err(assert_with_msg!(
+                "Expected to find node `{}`, but anim def has no nodes",
+                name
+            ))
+        }
+    }
+
+    #[inline]
+    fn sound_from_index(&self, index: usize, offset: usize) -> Result<String> {
+        self.anim_def.sound_from_index(index, offset)
+    }
+
+    fn sound_to_index(&self, name: &str) -> Result<usize> {
+        if let Some(sounds) = &self.sounds {
+            sounds
+                .get(name)
+                .copied()
+                .ok_or_else(|| assert_with_msg!("Expected to find sound `{}`, but didn't", name))
+        } else {
+            Err(assert_with_msg!(
+                "Expected to find sound `{}`, but anim def has no sounds",
+                name
+            ))
+        }
+    }
+
+    #[inline]
+    fn light_from_index(&self, index: usize, offset: usize) -> Result<String> {
+        self.anim_def.light_from_index(index, offset)
+    }
+
+    fn light_to_index(&self, name: &str) -> Result<usize> {
+        if let Some(lights) = &self.lights {
+            lights
+                .get(name)
+                .copied()
+                .ok_or_else(|| assert_with_msg!("Expected to find light `{}`, but didn't", name))
+        } else {
+            Err(assert_with_msg!(
+                "Expected to find light `{}`, but anim def has no lights",
+                name
+            ))
+        }
+    }
+
+    #[inline]
+    fn puffer_from_index(&self, index: usize, offset: usize) -> Result<String> {
+        self.anim_def.puffer_from_index(index, offset)
+    }
+
+    fn puffer_to_index(&self, name: &str) -> Result<usize> {
+        if let Some(puffers) = &self.puffers {
+            puffers
+                .get(name)
+                .copied()
+                .ok_or_else(|| assert_with_msg!("Expected to find puffer `{}`, but didn't", name))
+        } else {
+            Err(assert_with_msg!(
+                "Expected to find puffer `{}`, but anim def has no puffers",
+                name
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,104 @@
+use super::*;
+use mech3ax_api_types::anim::{AnimActivation, Execution, NamePad, NamePtr, NamePtrFlags};
+
+fn name_ptr(name: String) -> NamePtr {
+    NamePtr { name, pointer: 0 }
+}
+
+fn anim_def(count: usize) -> AnimDef {
+    let mut nodes: Vec<NamePtr> = (0..count).map(|i| name_ptr(format!("node{}", i))).collect();
+    // a duplicate name must resolve to the first one
+    nodes.push(name_ptr("node0".to_string()));
+    AnimDef {
+        name: "test".to_string(),
+        anim_name: NamePad {
+            name: "test".to_string(),
+            pad: Vec::new(),
+        },
+        anim_root: NamePad {
+            name: "test".to_string(),
+            pad: Vec::new(),
+        },
+        file_name: "test.flt".to_string(),
+        auto_reset_node_states: true,
+        activation: AnimActivation::OnCall,
+        execution: Execution::None,
+        network_log: None,
+        save_log: None,
+        has_callbacks: false,
+        reset_time: None,
+        health: 0.0,
+        proximity_damage: true,
+        activ_prereq_min_to_satisfy: 0,
+        objects: None,
+        nodes: Some(nodes),
+        lights: Some(
+            (0..count)
+                .map(|i| name_ptr(format!("light{}", i)))
+                .collect(),
+        ),
+        puffers: Some(
+            (0..count)
+                .map(|i| NamePtrFlags {
+                    name: format!("puffer{}", i),
+                    pointer: 0,
+                    flags: 0,
+                })
+                .collect(),
+        ),
+        dynamic_sounds: None,
+        static_sounds: None,
+        activ_prereqs: None,
+        anim_refs: None,
+        reset_state: None,
+        sequences: Vec::new(),
+    }
+}
+
+#[test]
+fn index_matches_scan() {
+    let anim_def = anim_def(50);
+    let index = AnimDefIndex::new(&anim_def);
+
+    let nodes = anim_def.nodes.as_ref().unwrap();
+    for (pos, node) in nodes.iter().enumerate() {
+        let expected = anim_def.node_to_index(&node.name).unwrap();
+        assert_eq!(index.node_to_index(&node.name).unwrap(), expected);
+        // only the duplicate doesn't round-trip
+        if pos < 50 {
+            assert_eq!(expected, pos + 1);
+        }
+        assert_eq!(index.node_from_index(expected, 0).unwrap(), node.name);
+    }
+    assert_eq!(index.node_to_index("node0").unwrap(), 1);
+
+    for light in anim_def.lights.as_ref().unwrap() {
+        let expected = anim_def.light_to_index(&light.name).unwrap();
+        assert_eq!(index.light_to_index(&light.name).unwrap(), expected);
+        assert_eq!(index.light_from_index(expected, 0).unwrap(), light.name);
+    }
+    for puffer in anim_def.puffers.as_ref().unwrap() {
+        let expected = anim_def.puffer_to_index(&puffer.name).unwrap();
+        assert_eq!(index.puffer_to_index(&puffer.name).unwrap(), expected);
+        assert_eq!(index.puffer_from_index(expected, 0).unwrap(), puffer.name);
+    }
+}
+
+#[test]
+fn index_errors_match_scan() {
+    let anim_def = anim_def(3);
+    let index = AnimDefIndex::new(&anim_def);
+
+    let expected = anim_def.node_to_index("missing").unwrap_err();
+    let actual = index.node_to_index("missing").unwrap_err();
+    assert_eq!(actual.to_string(), expected.to_string());
+
+    // no sounds at all
+    let expected = anim_def.sound_to_index("sound").unwrap_err();
+    let actual = index.sound_to_index("sound").unwrap_err();
+    assert_eq!(actual.to_string(), expected.to_string());
+
+    let expected = anim_def.light_from_index(4, 10).unwrap_err();
+    let actual = index.light_from_index(4, 10).unwrap_err();
+    assert_eq!(actual.to_string(), expected.to_string());
+}
@@ -1,4 +1,4 @@
-use crate::serde::{bool_false, bytes};
+use crate::serde::{bool_false, bytes, u32_zero};
 use crate::{Color, Quaternion, Range, Vec3};
 use ::serde::{Deserialize, Serialize};
 use mech3ax_metadata_proc_macro::{Enum, Struct, Union};
@@ -6,27 +6,32 @@ use mech3ax_types::primitive_enum;
 use std::ops::Deref;
 
 #[derive(Debug, Serialize, Deserialize, Clone, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct AtNode {
     pub node: String,
     pub translation: Vec3,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct StopAnimation {
     pub name: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ResetAnimation {
     pub name: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct InvalidateAnimation {
     pub name: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CallAnimationAtNode {
     pub node: String,
     pub translation: Option<Vec3>,
@@ -34,17 +39,20 @@ pub struct CallAnimationAtNode {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CallAnimationWithNode {
     pub node: String,
     pub translation: Option<Vec3>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CallAnimationTargetNode {
     pub operand_node: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Union)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum CallAnimationParameters {
     AtNode(CallAnimationAtNode),
     WithNode(CallAnimationWithNode),
@@ -53,6 +61,7 @@ pub enum CallAnimationParameters {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CallAnimation {
     pub name: String,
     #[serde(skip_serializing_if = "Option::is_none", default)]
@@ -61,6 +70,7 @@ pub struct CallAnimation {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CallObjectConnector {
     pub node: String,
     pub from_node: String,
@@ -69,12 +79,14 @@ pub struct CallObjectConnector {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Loop {
     pub start: i32,
     pub loop_count: i32,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[dotnet(val_struct)]
 pub struct RandomWeightCond {
     pub value: f32,
@@ -95,6 +107,7 @@ impl Deref for RandomWeightCond {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[dotnet(val_struct)]
 pub struct PlayerRangeCond {
     pub value: f32,
@@ -115,6 +128,7 @@ impl Deref for PlayerRangeCond {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[dotnet(val_struct)]
 pub struct AnimationLodCond {
     pub value: u32,
@@ -135,6 +149,7 @@ impl Deref for AnimationLodCond {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[dotnet(val_struct)]
 pub struct HwRenderCond {
     pub value: bool,
@@ -155,6 +170,7 @@ impl Deref for HwRenderCond {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[dotnet(val_struct)]
 pub struct PlayerFirstPersonCond {
     pub value: bool,
@@ -175,6 +191,7 @@ impl Deref for PlayerFirstPersonCond {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Union)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum If {
     RandomWeight(RandomWeightCond),
     PlayerRange(PlayerRangeCond),
@@ -184,6 +201,7 @@ pub enum If {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Union)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum ElseIf {
     RandomWeight(RandomWeightCond),
     PlayerRange(PlayerRangeCond),
@@ -193,23 +211,28 @@ pub enum ElseIf {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Else {}
 
 #[derive(Debug, Serialize, Deserialize, Clone, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct EndIf {}
 
 #[derive(Debug, Serialize, Deserialize, Clone, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Callback {
     pub value: u32,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct DetonateWeapon {
     pub name: String,
     pub at_node: AtNode,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[dotnet(val_struct)]
 pub struct Rgba {
     pub r: f32,
@@ -219,6 +242,7 @@ pub struct Rgba {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct FrameBufferEffectColor {
     pub from: Rgba,
     pub to: Rgba,
@@ -229,6 +253,7 @@ pub struct FrameBufferEffectColor {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Enum)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[repr(u32)]
 pub enum FogType {
     Off = 0,
@@ -237,6 +262,7 @@ pub enum FogType {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct FogState {
     pub name: String,
     pub fog_type: FogType,
@@ -246,6 +272,7 @@ pub struct FogState {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct LightAnimation {
     pub name: String,
     pub range: Range,
@@ -254,6 +281,7 @@ pub struct LightAnimation {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct LightState {
     pub name: String,
     pub active_state: bool,
@@ -278,12 +306,14 @@ pub struct LightState {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ObjectActiveState {
     pub node: String,
     pub state: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ObjectAddChild {
     // in the reader zbd, both values are fused into a list (PARENT_CHILD)
     pub parent: String,
@@ -291,6 +321,7 @@ pub struct ObjectAddChild {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ObjectConnector {
     pub node: String,
     #[serde(skip_serializing_if = "Option::is_none", default)]
@@ -303,15 +334,20 @@ pub struct ObjectConnector {
     pub to_pos: Option<Vec3>,
     #[serde(skip_serializing_if = "Option::is_none", default)]
     pub max_length: Option<f32>,
+    // flag bits that aren't known yet, which are written back as they were
+    #[serde(skip_serializing_if = "u32_zero", default)]
+    pub unknown_flags: u32,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ObjectCycleTexture {
     pub node: String,
     pub reset: u16,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct FloatFromTo {
     pub from: f32,
     pub to: f32,
@@ -319,6 +355,7 @@ pub struct FloatFromTo {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Vec3FromTo {
     pub from: Vec3,
     pub to: Vec3,
@@ -326,6 +363,7 @@ pub struct Vec3FromTo {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ObjectMotionFromTo {
     pub node: String,
     pub run_time: f32,
@@ -337,30 +375,40 @@ pub struct ObjectMotionFromTo {
     pub rotate: Option<Vec3FromTo>,
     #[serde(skip_serializing_if = "Option::is_none", default)]
     pub scale: Option<Vec3FromTo>,
+    // flag bits that aren't known yet, which are written back as they were
+    #[serde(skip_serializing_if = "u32_zero", default)]
+    pub unknown_flags: u32,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct TranslateData {
     pub value: Vec3,
     #[serde(with = "bytes")]
+    #[cfg_attr(feature = "schemars", schemars(with = "String"))]
     pub unk: Vec<u8>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct RotateData {
     pub value: Quaternion,
     #[serde(with = "bytes")]
+    #[cfg_attr(feature = "schemars", schemars(with = "String"))]
     pub unk: Vec<u8>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ScaleData {
     pub value: Vec3,
     #[serde(with = "bytes")]
+    #[cfg_attr(feature = "schemars", schemars(with = "String"))]
     pub unk: Vec<u8>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ObjectMotionSiFrame {
     pub start_time: f32,
     pub end_time: f32,
@@ -373,12 +421,14 @@ pub struct ObjectMotionSiFrame {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ObjectMotionSiScript {
     pub node: String,
     pub frames: Vec<ObjectMotionSiFrame>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Enum)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum GravityMode {
     Local,
     Complex,
@@ -386,6 +436,7 @@ pub enum GravityMode {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[dotnet(val_struct)]
 pub struct Gravity {
     pub mode: GravityMode,
@@ -393,6 +444,7 @@ pub struct Gravity {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[dotnet(val_struct)]
 pub struct ForwardRotationTime {
     pub v1: f32,
@@ -400,18 +452,21 @@ pub struct ForwardRotationTime {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[dotnet(val_struct)]
 pub struct ForwardRotationDistance {
     pub v1: f32,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Union)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum ForwardRotation {
     Time(ForwardRotationTime),
     Distance(ForwardRotationDistance),
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct BounceSequence {
     pub seq_name0: Option<String>,
     pub seq_name1: Option<String>,
@@ -419,6 +474,7 @@ pub struct BounceSequence {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ObjectMotionTranslation {
     pub delta: Vec3,
     pub initial: Vec3,
@@ -426,24 +482,28 @@ pub struct ObjectMotionTranslation {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct XyzRotation {
     pub value: Vec3,
     pub unk: Vec3,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ObjectMotionScale {
     pub value: Vec3,
     pub unk: Vec3,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct BounceSound {
     pub name: String,
     pub volume: f32,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ObjectMotion {
     pub node: String,
     pub impact_force: bool,
@@ -467,9 +527,13 @@ pub struct ObjectMotion {
     pub bounce_sound: Option<BounceSound>,
     #[serde(skip_serializing_if = "Option::is_none", default)]
     pub runtime: Option<f32>,
+    // flag bits that aren't known yet, which are written back as they were
+    #[serde(skip_serializing_if = "u32_zero", default)]
+    pub unknown_flags: u32,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[dotnet(val_struct)]
 pub struct ObjectOpacity {
     pub value: f32,
@@ -477,6 +541,7 @@ pub struct ObjectOpacity {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ObjectOpacityFromTo {
     pub node: String,
     pub opacity_from: ObjectOpacity,
@@ -488,6 +553,7 @@ pub struct ObjectOpacityFromTo {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ObjectOpacityState {
     pub node: String,
     pub is_set: bool,
@@ -496,6 +562,7 @@ pub struct ObjectOpacityState {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Union)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum RotateState {
     Absolute(Vec3),
     AtNodeXYZ,
@@ -503,18 +570,21 @@ pub enum RotateState {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ObjectRotateState {
     pub node: String,
     pub rotate: RotateState,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ObjectScaleState {
     pub node: String,
     pub scale: Vec3,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ObjectTranslateState {
     pub node: String,
     pub translate: Vec3,
@@ -523,6 +593,7 @@ pub struct ObjectTranslateState {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Enum)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum IntervalType {
     Unset,
     Time,
@@ -530,6 +601,7 @@ pub enum IntervalType {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Interval {
     pub interval_type: IntervalType,
     pub interval_value: f32,
@@ -537,6 +609,7 @@ pub struct Interval {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct PufferStateCycleTextures {
     pub texture1: Option<String>,
     pub texture2: Option<String>,
@@ -547,6 +620,7 @@ pub struct PufferStateCycleTextures {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct PufferState {
     pub name: String,
     pub state: bool,
@@ -585,16 +659,19 @@ pub struct PufferState {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CallSequence {
     pub name: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct StopSequence {
     pub name: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct SoundNode {
     pub name: String,
     pub active_state: bool,
@@ -603,12 +680,14 @@ pub struct SoundNode {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Sound {
     pub name: String,
     pub at_node: AtNode,
 }
 
 #[derive(Debug, Serialize, Deserialize, Union)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum EventData {
     Sound(Sound),
     SoundNode(SoundNode),
@@ -647,6 +726,7 @@ pub enum EventData {
 
 primitive_enum! {
     #[derive(Serialize, Deserialize, Enum)]
+    #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
     pub enum StartOffset: u8 {
         Animation = 1,
         Sequence = 2,
@@ -655,6 +735,7 @@ primitive_enum! {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[dotnet(val_struct)]
 pub struct EventStart {
     pub offset: StartOffset,
@@ -662,6 +743,7 @@ pub struct EventStart {
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Event {
     pub data: EventData,
     pub start: Option<EventStart>,
@@ -8,14 +8,17 @@ use mech3ax_metadata_proc_macro::{Enum, Struct, Union};
 use mech3ax_types::primitive_enum;
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct AnimName {
     pub name: String,
     #[serde(with = "bytes")]
+    #[cfg_attr(feature = "schemars", schemars(with = "String"))]
     pub pad: Vec<u8>,
     pub unknown: u32,
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct AnimPtr {
     pub file_name: String,
 
@@ -33,6 +36,7 @@ pub struct AnimPtr {
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct AnimMetadata {
     pub base_ptr: u32,
     pub world_ptr: u32,
@@ -42,6 +46,7 @@ pub struct AnimMetadata {
 
 primitive_enum! {
     #[derive(Serialize, Deserialize, Enum)]
+    #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
     pub enum AnimActivation: u8 {
         WeaponHit = 0,
         CollideHit = 1,
@@ -52,6 +57,7 @@ primitive_enum! {
 }
 
 #[derive(Debug, Serialize, Deserialize, Union)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum Execution {
     ByRange(Range),
     ByZone,
@@ -59,14 +65,17 @@ pub enum Execution {
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[dotnet(val_struct)]
 pub struct NamePad {
     pub name: String,
     #[serde(with = "bytes")]
+    #[cfg_attr(feature = "schemars", schemars(with = "String"))]
     pub pad: Vec<u8>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[dotnet(val_struct)]
 pub struct NamePtr {
     pub name: String,
@@ -74,6 +83,7 @@ pub struct NamePtr {
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[dotnet(val_struct)]
 pub struct NamePtrFlags {
     pub name: String,
@@ -83,6 +93,7 @@ pub struct NamePtrFlags {
 
 primitive_enum! {
     #[derive(Serialize, Deserialize, Enum)]
+    #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
     pub enum SeqActivation: u32 {
         Initial = 0,
         OnCall = 3,
@@ -90,12 +101,14 @@ primitive_enum! {
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[dotnet(val_struct)]
 pub struct PrereqAnimation {
     pub name: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[dotnet(val_struct)]
 pub struct PrereqObject {
     pub name: String,
@@ -105,6 +118,7 @@ pub struct PrereqObject {
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[dotnet(val_struct)]
 pub struct PrereqParent {
     pub name: String,
@@ -114,6 +128,7 @@ pub struct PrereqParent {
 }
 
 #[derive(Debug, Serialize, Deserialize, Union)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum ActivationPrereq {
     Animation(PrereqAnimation),
     Parent(PrereqParent),
@@ -121,12 +136,14 @@ pub enum ActivationPrereq {
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ResetState {
     pub events: Vec<Event>,
     pub pointer: u32,
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct SeqDef {
     pub name: String,
     pub activation: SeqActivation,
@@ -135,6 +152,7 @@ pub struct SeqDef {
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct AnimDef {
     pub name: String,
     pub anim_name: NamePad,
@@ -5,6 +5,7 @@ use mech3ax_metadata_proc_macro::{Struct, Union};
 use mech3ax_timestamp::DateTime;
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ArchiveEntry {
     pub name: String,
     #[serde(skip_serializing_if = "Option::is_none", default)]
@@ -14,20 +15,24 @@ pub struct ArchiveEntry {
 }
 
 #[derive(Debug, Serialize, Deserialize, Union)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum ArchiveEntryInfo {
     Valid(ArchiveEntryInfoValid),
     Invalid(ArchiveEntryInfoInvalid),
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ArchiveEntryInfoValid {
     pub comment: String,
     pub datetime: DateTime,
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ArchiveEntryInfoInvalid {
     #[serde(with = "bytes")]
+    #[cfg_attr(feature = "schemars", schemars(with = "String"))]
     pub comment: Vec<u8>,
     pub filetime: u64,
 }
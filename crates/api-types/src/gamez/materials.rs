@@ -5,6 +5,7 @@ use mech3ax_metadata_proc_macro::{Enum, Struct, Union};
 use mech3ax_types::primitive_enum;
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CycleData {
     pub textures: Vec<String>,
     pub unk00: bool,
@@ -16,6 +17,7 @@ pub struct CycleData {
 
 primitive_enum! {
     #[derive(Serialize, Deserialize, Enum)]
+    #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
     pub enum Soil: u32 {
         Default = 0,
         Water = 1,
@@ -49,6 +51,7 @@ impl Default for Soil {
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct TexturedMaterial {
     pub texture: String,
     // the GameZ data doesn't use the pointer (it stores the texture name index)
@@ -63,6 +66,7 @@ pub struct TexturedMaterial {
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ColoredMaterial {
     pub color: Color,
     pub alpha: u8,
@@ -71,7 +75,22 @@ pub struct ColoredMaterial {
 }
 
 #[derive(Debug, Serialize, Deserialize, Union)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum Material {
     Textured(TexturedMaterial),
     Colored(ColoredMaterial),
 }
+
+/// Resolve the texture of a material against the texture list.
+///
+/// Returns `None` for colored materials, and for textured materials whose
+/// texture isn't in the list.
+pub fn material_texture_name<'a>(material: &Material, textures: &'a [String]) -> Option<&'a str> {
+    match material {
+        Material::Textured(textured) => textures
+            .iter()
+            .find(|texture| **texture == textured.texture)
+            .map(String::as_str),
+        Material::Colored(_) => None,
+    }
+}
@@ -1,10 +1,12 @@
 use crate::gamez::mesh::{MeshMw, MeshNg};
 use crate::nodes::mw::NodeMw;
 use crate::nodes::pm::NodePm;
+use crate::Vec3;
 use ::serde::{Deserialize, Serialize};
 use mech3ax_metadata_proc_macro::Struct;
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ModelMw {
     pub nodes: Vec<NodeMw>,
     pub meshes: Vec<MeshMw>,
@@ -12,8 +14,20 @@ pub struct ModelMw {
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ModelPm {
     pub nodes: Vec<NodePm>,
     pub meshes: Vec<MeshNg>,
     pub mesh_ptrs: Vec<i32>,
 }
+
+/// The axis-aligned bounding box of a model's geometry, after the node
+/// transforms are applied.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ModelBounds {
+    pub min: Vec3,
+    pub max: Vec3,
+    /// The model has no vertices, and the box is zero.
+    pub empty: bool,
+}
@@ -1,6 +1,8 @@
 mod mw;
 mod ng;
+mod normals;
 mod rc;
+mod triangles;
 
 use crate::{Color, Vec3};
 use ::serde::{Deserialize, Serialize};
@@ -20,15 +22,17 @@ use mech3ax_types::impl_as_bytes;
     AnyBitPattern,
     Struct,
 )]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[dotnet(val_struct)]
 #[repr(C)]
 pub struct UvCoord {
     pub u: f32,
     pub v: f32,
 }
-impl_as_bytes!(UvCoord, 8);
+impl_as_bytes!(UvCoord, 8, LeStruct);
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct MeshLight {
     pub unk00: u32,
     pub unk04: u32,
@@ -49,4 +53,9 @@ pub struct MeshLight {
 
 pub use mw::*;
 pub use ng::*;
+pub use normals::{recompute_normals, MeshNormals, NormalWeight};
 pub use rc::*;
+pub use triangles::{mesh_triangles, triangulate_polygon, triangulate_strip, MeshPolygons};
+
+#[cfg(test)]
+mod tests;
@@ -4,6 +4,7 @@ use ::serde::{Deserialize, Serialize};
 use mech3ax_metadata_proc_macro::Struct;
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct PolygonMw {
     pub vertex_indices: Vec<u32>,
     pub vertex_colors: Vec<Color>,
@@ -22,6 +23,7 @@ pub struct PolygonMw {
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct MeshMw {
     pub vertices: Vec<Vec3>,
     pub normals: Vec<Vec3>,
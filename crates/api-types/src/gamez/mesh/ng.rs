@@ -1,5 +1,5 @@
 use super::{MeshLight, UvCoord};
-use crate::serde::bool_false;
+use crate::serde::{bool_false, u32_zero};
 use crate::{Color, Vec3};
 use ::serde::{Deserialize, Serialize};
 use bytemuck::{AnyBitPattern, NoUninit};
@@ -7,6 +7,7 @@ use mech3ax_metadata_proc_macro::Struct;
 use mech3ax_types::impl_as_bytes;
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct PolygonFlags {
     #[serde(skip_serializing_if = "bool_false", default)]
     pub unk2: bool,
@@ -19,21 +20,24 @@ pub struct PolygonFlags {
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, NoUninit, AnyBitPattern, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[repr(C)]
 pub struct MeshMaterialInfo {
     pub material_index: u32,      // 00
     pub polygon_usage_count: u32, // 04
     pub unk_ptr: u32,             // 08
 }
-impl_as_bytes!(MeshMaterialInfo, 12);
+impl_as_bytes!(MeshMaterialInfo, 12, LeStruct);
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct PolygonMaterialNg {
     pub material_index: u32,
     pub uv_coords: Vec<UvCoord>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct PolygonNg {
     pub flags: PolygonFlags,
     pub vertex_indices: Vec<u32>,
@@ -41,6 +45,8 @@ pub struct PolygonNg {
     pub normal_indices: Option<Vec<u32>>,
     pub materials: Vec<PolygonMaterialNg>,
 
+    #[serde(skip_serializing_if = "u32_zero", default)]
+    pub unknown_flags: u32,
     pub unk04: i32,
     pub vertices_ptr: u32,
     pub normals_ptr: u32,
@@ -52,6 +58,7 @@ pub struct PolygonNg {
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct MeshNg {
     pub vertices: Vec<Vec3>,
     pub normals: Vec<Vec3>,
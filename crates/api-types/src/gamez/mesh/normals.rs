@@ -0,0 +1,162 @@
+use super::{MeshMw, MeshNg, MeshPolygons, MeshRc};
+use crate::Vec3;
+use mech3ax_types::u32_to_usize;
+
+/// How the face normals are weighted when averaging them per vertex.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NormalWeight {
+    /// Every face adjacent to a vertex counts the same.
+    #[default]
+    Equal,
+    /// Larger faces count more, so small faces (e.g. bevels) bend the
+    /// normal less.
+    Area,
+}
+
+/// A mesh with vertices and normals that can be replaced.
+pub trait MeshNormals: MeshPolygons {
+    fn vertices(&self) -> &[Vec3];
+    /// Replace the normals with one normal per vertex, and set the normal
+    /// indices of each polygon to its vertex indices.
+    fn set_vertex_normals(&mut self, normals: Vec<Vec3>);
+}
+
+impl MeshNormals for MeshMw {
+    fn vertices(&self) -> &[Vec3] {
+        &self.vertices
+    }
+
+    fn set_vertex_normals(&mut self, normals: Vec<Vec3>) {
+        self.normals = normals;
+        for polygon in &mut self.polygons {
+            polygon.normal_indices = Some(polygon.vertex_indices.clone());
+        }
+    }
+}
+
+impl MeshNormals for MeshRc {
+    fn vertices(&self) -> &[Vec3] {
+        &self.vertices
+    }
+
+    fn set_vertex_normals(&mut self, normals: Vec<Vec3>) {
+        self.normals = normals;
+        for polygon in &mut self.polygons {
+            polygon.normal_indices = Some(polygon.vertex_indices.clone());
+        }
+    }
+}
+
+impl MeshNormals for MeshNg {
+    fn vertices(&self) -> &[Vec3] {
+        &self.vertices
+    }
+
+    fn set_vertex_normals(&mut self, normals: Vec<Vec3>) {
+        self.normals = normals;
+        for polygon in &mut self.polygons {
+            polygon.normal_indices = Some(polygon.vertex_indices.clone());
+        }
+    }
+}
+
+fn sub(a: Vec3, b: Vec3) -> Vec3 {
+    Vec3 {
+        x: a.x - b.x,
+        y: a.y - b.y,
+        z: a.z - b.z,
+    }
+}
+
+fn add(a: Vec3, b: Vec3) -> Vec3 {
+    Vec3 {
+        x: a.x + b.x,
+        y: a.y + b.y,
+        z: a.z + b.z,
+    }
+}
+
+fn cross(a: Vec3, b: Vec3) -> Vec3 {
+    Vec3 {
+        x: a.y * b.z - a.z * b.y,
+        y: a.z * b.x - a.x * b.z,
+        z: a.x * b.y - a.y * b.x,
+    }
+}
+
+/// Scale the vector to unit length, or `None` if it has no length.
+fn normalize(v: Vec3) -> Option<Vec3> {
+    let len = (v.x * v.x + v.y * v.y + v.z * v.z).sqrt();
+    if len > 0.0 && len.is_finite() {
+        Some(Vec3 {
+            x: v.x / len,
+            y: v.y / len,
+            z: v.z / len,
+        })
+    } else {
+        None
+    }
+}
+
+/// The normal of a polygon, with a length of twice its area.
+///
+/// This is the sum of the cross products of its triangles, so the normal
+/// points to the side the vertices wind counter-clockwise around.
+///
+/// Returns `None` if any vertex index is out of range.
+fn face_normal(vertices: &[Vec3], indices: &[u32], triangles: &[[u32; 3]]) -> Option<Vec3> {
+    if indices
+        .iter()
+        .any(|&index| u32_to_usize(index) >= vertices.len())
+    {
+        return None;
+    }
+    let vertex = |index: u32| vertices[u32_to_usize(index)];
+    let mut normal = Vec3::DEFAULT;
+    for &[a, b, c] in triangles {
+        let (a, b, c) = (vertex(a), vertex(b), vertex(c));
+        normal = add(normal, cross(sub(b, a), sub(c, a)));
+    }
+    Some(normal)
+}
+
+/// Compute the vertex normals from the polygons, replacing any existing
+/// normals.
+///
+/// Each polygon's face normal is added to the normals of its vertices, which
+/// are then normalized. The face normal points to the side the polygon's
+/// vertices wind counter-clockwise around. Vertices that aren't part of any
+/// polygon with an area get a zero normal. Polygons with vertex indices out
+/// of range are skipped.
+///
+/// This is meant for exporting meshes with missing or zeroed normals. The
+/// normals are indexed per vertex afterwards, which isn't how the games
+/// store them, so the mesh may not be written back identically.
+pub fn recompute_normals(mesh: &mut impl MeshNormals, weight: NormalWeight) {
+    let vertices = mesh.vertices();
+    let mut sums = vec![Vec3::DEFAULT; vertices.len()];
+
+    for (indices, triangles) in mesh.polygon_vertex_indices().zip(mesh.polygon_triangles()) {
+        let Some(normal) = face_normal(vertices, indices, &triangles) else {
+            continue;
+        };
+        let normal = match weight {
+            NormalWeight::Equal => match normalize(normal) {
+                Some(normal) => normal,
+                None => continue,
+            },
+            NormalWeight::Area => normal,
+        };
+        for &index in indices {
+            // the indices were checked by `face_normal`
+            let sum = &mut sums[u32_to_usize(index)];
+            *sum = add(*sum, normal);
+        }
+    }
+
+    let normals = sums
+        .into_iter()
+        .map(|sum| normalize(sum).unwrap_or(Vec3::DEFAULT))
+        .collect();
+    mesh.set_vertex_normals(normals);
+}
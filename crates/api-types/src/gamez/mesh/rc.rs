@@ -1,9 +1,11 @@
 use super::{MeshLight, UvCoord};
+use crate::serde::u32_zero;
 use crate::Vec3;
 use ::serde::{Deserialize, Serialize};
 use mech3ax_metadata_proc_macro::Struct;
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct PolygonRc {
     pub vertex_indices: Vec<u32>,
     pub normal_indices: Option<Vec<u32>>,
@@ -11,6 +13,8 @@ pub struct PolygonRc {
     pub material_index: u32,
 
     pub unk0_flag: bool,
+    #[serde(skip_serializing_if = "u32_zero", default)]
+    pub unknown_flags: u32,
     pub unk04: i32,
     pub unk24: u32,
 
@@ -20,6 +24,7 @@ pub struct PolygonRc {
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct MeshRc {
     pub vertices: Vec<Vec3>,
     pub normals: Vec<Vec3>,
@@ -0,0 +1,271 @@
+use super::{
+    mesh_triangles, recompute_normals, triangulate_polygon, triangulate_strip, MeshNg, MeshRc,
+    NormalWeight, PolygonFlags, PolygonNg, PolygonRc,
+};
+use crate::Vec3;
+
+fn polygon(vertex_indices: &[u32]) -> PolygonRc {
+    PolygonRc {
+        vertex_indices: vertex_indices.to_vec(),
+        normal_indices: None,
+        uv_coords: None,
+        material_index: 0,
+        unk0_flag: false,
+        unknown_flags: 0,
+        unk04: 0,
+        unk24: 0,
+        vertices_ptr: 0,
+        normals_ptr: 0,
+        uvs_ptr: 0,
+    }
+}
+
+fn mesh(polygons: Vec<PolygonRc>) -> MeshRc {
+    MeshRc {
+        vertices: Vec::new(),
+        normals: Vec::new(),
+        morphs: Vec::new(),
+        lights: Vec::new(),
+        polygons,
+        polygons_ptr: 0,
+        vertices_ptr: 0,
+        normals_ptr: 0,
+        lights_ptr: 0,
+        morphs_ptr: 0,
+        file_ptr: false,
+        unk04: 0,
+        parent_count: 0,
+        unk68: 0.0,
+        unk72: 0.0,
+        unk76: 0.0,
+        unk80: 0.0,
+    }
+}
+
+#[test]
+fn triangulate_triangle() {
+    assert_eq!(triangulate_polygon(&[4, 5, 6]), [[4, 5, 6]]);
+}
+
+#[test]
+fn triangulate_quad() {
+    assert_eq!(triangulate_polygon(&[0, 1, 2, 3]), [[0, 1, 2], [0, 2, 3]]);
+}
+
+#[test]
+fn triangulate_pentagon() {
+    assert_eq!(
+        triangulate_polygon(&[9, 7, 5, 3, 1]),
+        [[9, 7, 5], [9, 5, 3], [9, 3, 1]]
+    );
+}
+
+#[test]
+fn triangulate_degenerate() {
+    assert!(triangulate_polygon(&[]).is_empty());
+    assert!(triangulate_polygon(&[0]).is_empty());
+    assert!(triangulate_polygon(&[0, 1]).is_empty());
+}
+
+#[test]
+fn mesh_triangles_in_polygon_order() {
+    let mesh = mesh(vec![
+        polygon(&[0, 1, 2, 3]),
+        polygon(&[0, 1]),
+        polygon(&[4, 5, 6, 7, 8]),
+    ]);
+    assert_eq!(
+        mesh_triangles(&mesh),
+        [[0, 1, 2], [0, 2, 3], [4, 5, 6], [4, 6, 7], [4, 7, 8]]
+    );
+}
+
+#[test]
+fn triangulate_strip_alternates_winding() {
+    assert_eq!(triangulate_strip(&[4, 5, 6]), [[4, 5, 6]]);
+    assert_eq!(
+        triangulate_strip(&[0, 1, 2, 3, 4]),
+        [[0, 1, 2], [2, 1, 3], [2, 3, 4]]
+    );
+    assert!(triangulate_strip(&[0, 1]).is_empty());
+}
+
+fn polygon_ng(vertex_indices: &[u32], triangle_strip: bool) -> PolygonNg {
+    PolygonNg {
+        flags: PolygonFlags {
+            unk2: false,
+            unk3: false,
+            triangle_strip,
+            unk6: false,
+        },
+        vertex_indices: vertex_indices.to_vec(),
+        vertex_colors: Vec::new(),
+        normal_indices: None,
+        materials: Vec::new(),
+        unknown_flags: 0,
+        unk04: 0,
+        vertices_ptr: 0,
+        normals_ptr: 0,
+        uvs_ptr: 0,
+        colors_ptr: 0,
+        unk28: 0,
+        unk32: 0,
+        unk36: 0,
+    }
+}
+
+fn mesh_ng(vertices: Vec<Vec3>, polygons: Vec<PolygonNg>) -> MeshNg {
+    MeshNg {
+        vertices,
+        normals: Vec::new(),
+        morphs: Vec::new(),
+        lights: Vec::new(),
+        polygons,
+        material_infos: Vec::new(),
+        polygons_ptr: 0,
+        vertices_ptr: 0,
+        normals_ptr: 0,
+        lights_ptr: 0,
+        morphs_ptr: 0,
+        materials_ptr: 0,
+        file_ptr: false,
+        unk04: 0,
+        unk08: 0,
+        parent_count: 0,
+        unk40: 0.0,
+        unk44: 0.0,
+        unk72: 0.0,
+        unk76: 0.0,
+        unk80: 0.0,
+        unk84: 0.0,
+    }
+}
+
+#[test]
+fn mesh_triangles_ng_strips() {
+    let mesh = mesh_ng(
+        Vec::new(),
+        vec![
+            polygon_ng(&[0, 1, 2, 3], true),
+            polygon_ng(&[4, 5, 6, 7], false),
+        ],
+    );
+    assert_eq!(
+        mesh_triangles(&mesh),
+        [[0, 1, 2], [2, 1, 3], [4, 5, 6], [4, 6, 7]]
+    );
+}
+
+/// A cube from -1 to 1, with vertex `i` at bit 0 = x, bit 1 = y, bit 2 = z.
+/// The faces wind counter-clockwise seen from the outside.
+fn cube() -> MeshRc {
+    let mut mesh = mesh(vec![
+        polygon(&[1, 3, 7, 5]),
+        polygon(&[0, 4, 6, 2]),
+        polygon(&[2, 6, 7, 3]),
+        polygon(&[0, 1, 5, 4]),
+        polygon(&[4, 5, 7, 6]),
+        polygon(&[0, 2, 3, 1]),
+    ]);
+    let coord = |bit: bool| if bit { 1.0 } else { -1.0 };
+    mesh.vertices = (0..8)
+        .map(|i| Vec3 {
+            x: coord(i & 1 != 0),
+            y: coord(i & 2 != 0),
+            z: coord(i & 4 != 0),
+        })
+        .collect();
+    mesh
+}
+
+fn assert_close(actual: Vec3, expected: Vec3) {
+    let close = |a: f32, b: f32| (a - b).abs() < 1e-6;
+    assert!(
+        close(actual.x, expected.x) && close(actual.y, expected.y) && close(actual.z, expected.z),
+        "{:?} != {:?}",
+        actual,
+        expected
+    );
+}
+
+#[test]
+fn recompute_normals_cube_outward() {
+    for weight in [NormalWeight::Equal, NormalWeight::Area] {
+        let mut mesh = cube();
+        recompute_normals(&mut mesh, weight);
+
+        assert_eq!(mesh.normals.len(), 8);
+        let inv_sqrt3 = 1.0 / 3.0f32.sqrt();
+        for (vertex, normal) in mesh.vertices.iter().zip(&mesh.normals) {
+            // the corner normal points away from the center
+            let expected = Vec3 {
+                x: vertex.x * inv_sqrt3,
+                y: vertex.y * inv_sqrt3,
+                z: vertex.z * inv_sqrt3,
+            };
+            assert_close(*normal, expected);
+        }
+        for polygon in &mesh.polygons {
+            assert_eq!(
+                polygon.normal_indices.as_ref(),
+                Some(&polygon.vertex_indices)
+            );
+        }
+    }
+}
+
+#[test]
+fn recompute_normals_area_weight() {
+    let mut mesh = mesh(vec![
+        // area 2, facing +z
+        polygon(&[0, 1, 2]),
+        // area 0.5, facing +y
+        polygon(&[0, 3, 4]),
+        // out of range, skipped
+        polygon(&[0, 1, 9]),
+    ]);
+    let v = |x, y, z| Vec3 { x, y, z };
+    mesh.vertices = vec![
+        v(0.0, 0.0, 0.0),
+        v(2.0, 0.0, 0.0),
+        v(0.0, 2.0, 0.0),
+        v(0.0, 0.0, 1.0),
+        v(1.0, 0.0, 0.0),
+        // unused
+        v(5.0, 5.0, 5.0),
+    ];
+
+    recompute_normals(&mut mesh, NormalWeight::Equal);
+    let inv_sqrt2 = 1.0 / 2.0f32.sqrt();
+    assert_close(mesh.normals[0], v(0.0, inv_sqrt2, inv_sqrt2));
+    assert_close(mesh.normals[1], v(0.0, 0.0, 1.0));
+    assert_close(mesh.normals[5], Vec3::DEFAULT);
+
+    recompute_normals(&mut mesh, NormalWeight::Area);
+    let inv_sqrt17 = 1.0 / 17.0f32.sqrt();
+    assert_close(mesh.normals[0], v(0.0, inv_sqrt17, 4.0 * inv_sqrt17));
+    assert_close(mesh.normals[3], v(0.0, 1.0, 0.0));
+    assert_close(mesh.normals[5], Vec3::DEFAULT);
+}
+
+#[test]
+fn recompute_normals_ng_strip() {
+    // a flat quad as a strip, which winds counter-clockwise seen from +Z
+    let vertex = |x, y| Vec3 { x, y, z: 0.0 };
+    let vertices = vec![
+        vertex(0.0, 0.0),
+        vertex(1.0, 0.0),
+        vertex(0.0, 1.0),
+        vertex(1.0, 1.0),
+    ];
+    let mut mesh = mesh_ng(vertices, vec![polygon_ng(&[0, 1, 2, 3], true)]);
+    recompute_normals(&mut mesh, NormalWeight::Equal);
+    let up = Vec3 {
+        x: 0.0,
+        y: 0.0,
+        z: 1.0,
+    };
+    for normal in &mesh.normals {
+        assert_close(*normal, up);
+    }
+}
@@ -0,0 +1,90 @@
+use super::{MeshMw, MeshNg, MeshRc};
+
+/// Split a polygon into triangles, using a fan from the first index.
+///
+/// Triangle `k` is made from the polygon corners `0`, `k + 1`, and `k + 2`.
+/// So per-corner attributes (e.g. normal indices, UV coordinates, or vertex
+/// colors) can be split the same way, by triangulating the corner indices
+/// `0..n`. Polygons with fewer than three indices have no triangles.
+///
+/// A fan is only correct for convex polygons, which the meshes seem to be.
+pub fn triangulate_polygon(indices: &[u32]) -> Vec<[u32; 3]> {
+    match indices {
+        [first, rest @ ..] => rest
+            .windows(2)
+            .map(|pair| [*first, pair[0], pair[1]])
+            .collect(),
+        [] => Vec::new(),
+    }
+}
+
+/// Split a triangle strip into triangles.
+///
+/// Triangle `k` is made from the strip corners `k`, `k + 1`, and `k + 2`,
+/// with the first two swapped for odd `k`, so every triangle has the same
+/// winding. Strips with fewer than three indices have no triangles.
+pub fn triangulate_strip(indices: &[u32]) -> Vec<[u32; 3]> {
+    indices
+        .windows(3)
+        .enumerate()
+        .map(|(k, corners)| {
+            if k % 2 == 0 {
+                [corners[0], corners[1], corners[2]]
+            } else {
+                [corners[1], corners[0], corners[2]]
+            }
+        })
+        .collect()
+}
+
+/// A mesh made of polygons with vertex indices.
+pub trait MeshPolygons {
+    /// The vertex indices of each polygon.
+    fn polygon_vertex_indices(&self) -> impl Iterator<Item = &[u32]>;
+
+    /// The vertex indices of each polygon, split into triangles. By default,
+    /// polygons are split as fans, see [`triangulate_polygon`].
+    fn polygon_triangles(&self) -> impl Iterator<Item = Vec<[u32; 3]>> {
+        self.polygon_vertex_indices().map(triangulate_polygon)
+    }
+}
+
+impl MeshPolygons for MeshMw {
+    fn polygon_vertex_indices(&self) -> impl Iterator<Item = &[u32]> {
+        self.polygons
+            .iter()
+            .map(|polygon| polygon.vertex_indices.as_slice())
+    }
+}
+
+impl MeshPolygons for MeshRc {
+    fn polygon_vertex_indices(&self) -> impl Iterator<Item = &[u32]> {
+        self.polygons
+            .iter()
+            .map(|polygon| polygon.vertex_indices.as_slice())
+    }
+}
+
+impl MeshPolygons for MeshNg {
+    fn polygon_vertex_indices(&self) -> impl Iterator<Item = &[u32]> {
+        self.polygons
+            .iter()
+            .map(|polygon| polygon.vertex_indices.as_slice())
+    }
+
+    fn polygon_triangles(&self) -> impl Iterator<Item = Vec<[u32; 3]>> {
+        self.polygons.iter().map(|polygon| {
+            if polygon.flags.triangle_strip {
+                triangulate_strip(&polygon.vertex_indices)
+            } else {
+                triangulate_polygon(&polygon.vertex_indices)
+            }
+        })
+    }
+}
+
+/// The vertex indices of all polygons of a mesh, split into triangles in
+/// polygon order. See [`MeshPolygons::polygon_triangles`].
+pub fn mesh_triangles(mesh: &impl MeshPolygons) -> Vec<[u32; 3]> {
+    mesh.polygon_triangles().flatten().collect()
+}
@@ -12,14 +12,41 @@ use mech3ax_metadata_proc_macro::Struct;
 use mech3ax_timestamp::DateTime;
 use mesh::{MeshMw, MeshNg, MeshRc};
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct TextureInfoMw {
+    pub state: u32,
+    pub index: u32,
+    pub unk36: i32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct TextureInfoNg {
+    pub state: u32,
+    pub index: u32,
+    pub unk40: i32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct TextureInfoRc {
+    pub state: u32,
+    pub unk32: i32,
+}
+
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct GameZMetadataMw {
     pub meshes_array_size: i32,
     pub node_array_size: u32,
     pub node_data_count: u32,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub texture_infos: Option<Vec<TextureInfoMw>>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[dotnet(partial, namespace = "Mech3DotNet.Zbd")]
 pub struct GameZDataMw {
     pub textures: Vec<String>,
@@ -30,14 +57,18 @@ pub struct GameZDataMw {
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct GameZMetadataPm {
     pub datetime: DateTime,
     pub meshes_array_size: i32,
     pub node_data_count: u32,
     pub texture_ptrs: Vec<Option<u32>>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub texture_infos: Option<Vec<TextureInfoNg>>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[dotnet(partial, namespace = "Mech3DotNet.Zbd")]
 pub struct GameZDataPm {
     pub textures: Vec<String>,
@@ -48,18 +79,23 @@ pub struct GameZDataPm {
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct GameZMetadataCs {
     pub datetime: DateTime,
     pub texture_ptrs: Vec<Option<u32>>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub texture_infos: Option<Vec<TextureInfoNg>>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct TextureName {
     pub original: String,
     pub renamed: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[dotnet(partial, namespace = "Mech3DotNet.Zbd")]
 pub struct GameZDataCs {
     pub textures: Vec<TextureName>,
@@ -70,10 +106,13 @@ pub struct GameZDataCs {
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[dotnet(partial, namespace = "Mech3DotNet.Zbd")]
 pub struct GameZDataRc {
     pub textures: Vec<String>,
     pub materials: Vec<Material>,
     pub meshes: Vec<MeshRc>,
     pub nodes: Vec<NodeRc>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub texture_infos: Option<Vec<TextureInfoRc>>,
 }
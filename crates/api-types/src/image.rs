@@ -5,6 +5,7 @@ use mech3ax_metadata_proc_macro::{Enum, Struct, Union};
 use mech3ax_types::primitive_enum;
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Enum)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum TextureAlpha {
     None,
     Simple,
@@ -13,6 +14,7 @@ pub enum TextureAlpha {
 
 primitive_enum! {
     #[derive(Serialize, Deserialize, Enum)]
+    #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
     pub enum TextureStretch: u16 {
         None = 0,
         Vertical = 1,
@@ -28,18 +30,22 @@ primitive_enum! {
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct PaletteData {
     #[serde(with = "bytes")]
+    #[cfg_attr(feature = "schemars", schemars(with = "String"))]
     pub data: Vec<u8>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct GlobalPalette {
     pub index: u32,
     pub count: u16,
 }
 
 #[derive(Debug, Serialize, Deserialize, Union)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[repr(u16)]
 pub enum TexturePalette {
     None,
@@ -48,8 +54,13 @@ pub enum TexturePalette {
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct TextureInfo {
     pub name: String,
+    /// The position of the texture in the original file. If set for every
+    /// texture, they are written in this order.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub index: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none", default)]
     pub rename: Option<String>,
     pub alpha: TextureAlpha,
@@ -62,8 +73,22 @@ pub struct TextureInfo {
     pub palette: TexturePalette,
 }
 
+/// The file format of the extracted texture images.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Enum)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum TextureFileFormat {
+    Png,
+    Tga,
+    Bmp,
+}
+
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct TextureManifest {
     pub texture_infos: Vec<TextureInfo>,
     pub global_palettes: Vec<PaletteData>,
+    /// The file format the images were extracted as. If not set, they are
+    /// PNGs.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub image_format: Option<TextureFileFormat>,
 }
@@ -4,6 +4,7 @@ use mech3ax_metadata_proc_macro::Struct;
 use mech3ax_timestamp::DateTime;
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Script {
     pub name: String,
     pub last_modified: DateTime,
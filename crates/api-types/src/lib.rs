@@ -29,6 +29,7 @@ use mech3ax_types::impl_as_bytes;
     AnyBitPattern,
     Struct,
 )]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[dotnet(val_struct)]
 #[repr(C)]
 pub struct Range {
@@ -54,6 +55,7 @@ impl Range {
     Struct,
     Default,
 )]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[dotnet(val_struct)]
 #[repr(C)]
 pub struct Vec3 {
@@ -61,7 +63,7 @@ pub struct Vec3 {
     pub y: f32,
     pub z: f32,
 }
-impl_as_bytes!(Vec3, 12);
+impl_as_bytes!(Vec3, 12, LeStruct);
 
 impl Vec3 {
     pub const DEFAULT: Self = Self {
@@ -83,6 +85,7 @@ impl Vec3 {
     AnyBitPattern,
     Struct,
 )]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[dotnet(val_struct)]
 #[repr(C)]
 pub struct Color {
@@ -90,7 +93,7 @@ pub struct Color {
     pub g: f32,
     pub b: f32,
 }
-impl_as_bytes!(Color, 12);
+impl_as_bytes!(Color, 12, LeStruct);
 
 impl Color {
     pub const BLACK: Self = Self {
@@ -123,6 +126,7 @@ impl Color {
     Struct,
     Default,
 )]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[dotnet(val_struct)]
 #[repr(C)]
 pub struct Quaternion {
@@ -131,7 +135,7 @@ pub struct Quaternion {
     pub z: f32,
     pub w: f32,
 }
-impl_as_bytes!(Quaternion, 16);
+impl_as_bytes!(Quaternion, 16, LeStruct);
 
 impl Quaternion {
     pub const DEFAULT: Self = Self {
@@ -155,6 +159,7 @@ impl Quaternion {
     Struct,
     Default,
 )]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[dotnet(val_struct)]
 #[repr(C)]
 pub struct Matrix {
@@ -3,6 +3,7 @@ use ::serde::{Deserialize, Serialize};
 use mech3ax_metadata_proc_macro::Struct;
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct MessageEntry {
     pub key: String,
     pub id: u32,
@@ -10,6 +11,7 @@ pub struct MessageEntry {
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[dotnet(partial, namespace = "Mech3DotNet.Zbd")]
 pub struct Messages {
     pub language_id: u32,
@@ -4,6 +4,7 @@ use ::serde::{Deserialize, Serialize};
 use mech3ax_metadata_proc_macro::Struct;
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[dotnet(generic(Vec3 = "TVec3", Quaternion = "TQuaternion"))]
 pub struct MotionFrame {
     pub translation: Vec3,
@@ -11,12 +12,14 @@ pub struct MotionFrame {
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct MotionPart {
     pub name: String,
     pub frames: Vec<MotionFrame>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Motion {
     pub loop_time: f32,
     pub parts: Vec<MotionPart>,
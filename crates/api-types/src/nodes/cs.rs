@@ -1,10 +1,14 @@
 use super::pm::AreaPartitionPm;
-use super::{Area, BoundingBox, Display, PartitionNg, Transformation};
+use super::{
+    node_accessors, Area, AreaPartitioned, BoundingBox, Display, PartitionNg, Transformation,
+};
+use crate::serde::u32_one;
 use crate::Range;
 use ::serde::{Deserialize, Serialize};
 use mech3ax_metadata_proc_macro::{Struct, Union};
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Camera {
     pub name: String,
     pub focus_node_xy: i32,
@@ -12,15 +16,19 @@ pub struct Camera {
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Light {
     pub name: String,
     pub range: Range,
+    #[serde(skip_serializing_if = "u32_one", default = "super::_one")]
+    pub parent_count: u32,
     pub parent_ptr: u32,
     pub data_ptr: u32,
     pub node_index: u32,
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Lod {
     pub name: String,
     pub level: bool,
@@ -42,6 +50,7 @@ pub struct Lod {
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Object3d {
     pub name: String,
     pub transformation: Option<Transformation>,
@@ -67,6 +76,7 @@ pub struct Object3d {
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Window {
     pub name: String,
     pub resolution_x: u32,
@@ -75,6 +85,7 @@ pub struct Window {
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct World {
     pub name: String,
     pub area: Area,
@@ -94,6 +105,7 @@ pub struct World {
 }
 
 #[derive(Debug, Serialize, Deserialize, Union)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum NodeCs {
     Camera(Camera),
     Display(Display),
@@ -103,3 +115,16 @@ pub enum NodeCs {
     Window(Window),
     World(World),
 }
+
+impl AreaPartitioned for NodeCs {
+    type AreaPartition = AreaPartitionPm;
+
+    fn area_partition(&self) -> Option<AreaPartitionPm> {
+        match self {
+            Self::Object3d(node) => node.area_partition,
+            _ => None,
+        }
+    }
+}
+
+node_accessors!(NodeCs);
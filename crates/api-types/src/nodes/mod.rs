@@ -11,6 +11,7 @@ use mech3ax_metadata_proc_macro::Struct;
 use mech3ax_types::impl_as_bytes;
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Camera {
     pub clip: Range,
     pub fov: Range,
@@ -19,6 +20,7 @@ pub struct Camera {
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Display {
     pub resolution_x: u32,
     pub resolution_y: u32,
@@ -27,6 +29,7 @@ pub struct Display {
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Window {
     pub resolution_x: u32,
     pub resolution_y: u32,
@@ -46,6 +49,7 @@ pub struct Window {
     Struct,
     Default,
 )]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[dotnet(val_struct)]
 #[repr(C)]
 pub struct AreaPartition {
@@ -60,6 +64,7 @@ impl AreaPartition {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[dotnet(val_struct)]
 pub struct Area {
     pub left: i32,
@@ -83,6 +88,7 @@ impl Area {
 #[derive(
     Debug, Clone, Copy, PartialEq, Serialize, Deserialize, NoUninit, AnyBitPattern, Struct, Default,
 )]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[repr(C)]
 pub struct BoundingBox {
     pub a: Vec3,
@@ -97,6 +103,7 @@ impl BoundingBox {
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Transformation {
     pub rotation: Vec3,
     pub translation: Vec3,
@@ -104,6 +111,7 @@ pub struct Transformation {
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct PartitionPg {
     pub x: i32,
     pub y: i32,
@@ -118,15 +126,17 @@ pub struct PartitionPg {
 #[derive(
     Debug, Clone, Copy, PartialEq, Serialize, Deserialize, NoUninit, AnyBitPattern, Struct,
 )]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[repr(C)]
 pub struct PartitionValue {
     pub index: u32,
     pub z_min: f32,
     pub z_max: f32,
 }
-impl_as_bytes!(PartitionValue, 12);
+impl_as_bytes!(PartitionValue, 12, LeStruct);
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct PartitionNg {
     pub x: i32,
     pub y: i32,
@@ -136,12 +146,90 @@ pub struct PartitionNg {
     pub ptr: u32,
 }
 
+/// A node that may be assigned to an area partition.
+pub trait AreaPartitioned {
+    type AreaPartition: Copy + PartialEq;
+
+    fn area_partition(&self) -> Option<Self::AreaPartition>;
+}
+
+/// All nodes assigned to the area partition.
+pub fn nodes_in_partition<N>(nodes: &[N], area_partition: N::AreaPartition) -> Vec<&N>
+where
+    N: AreaPartitioned,
+{
+    nodes
+        .iter()
+        .filter(|node| node.area_partition() == Some(area_partition))
+        .collect()
+}
+
+/// The distinct area partitions nodes are assigned to, in order of first
+/// appearance.
+pub fn area_partitions<N>(nodes: &[N]) -> Vec<N::AreaPartition>
+where
+    N: AreaPartitioned,
+{
+    let mut area_partitions = Vec::new();
+    for area_partition in nodes.iter().filter_map(N::area_partition) {
+        if !area_partitions.contains(&area_partition) {
+            area_partitions.push(area_partition);
+        }
+    }
+    area_partitions
+}
+
+/// Typed accessors over a list of nodes, to avoid matching on the node
+/// variants manually.
+macro_rules! node_accessors {
+    ($node:ident) => {
+        /// All camera nodes.
+        pub fn cameras(nodes: &[$node]) -> impl Iterator<Item = &Camera> {
+            nodes.iter().filter_map(|node| match node {
+                $node::Camera(camera) => Some(camera),
+                _ => None,
+            })
+        }
+
+        /// All light nodes.
+        pub fn lights(nodes: &[$node]) -> impl Iterator<Item = &Light> {
+            nodes.iter().filter_map(|node| match node {
+                $node::Light(light) => Some(light),
+                _ => None,
+            })
+        }
+
+        /// All object 3D nodes.
+        pub fn objects3d(nodes: &[$node]) -> impl Iterator<Item = &Object3d> {
+            nodes.iter().filter_map(|node| match node {
+                $node::Object3d(object3d) => Some(object3d),
+                _ => None,
+            })
+        }
+
+        /// The world node, if any.
+        pub fn world(nodes: &[$node]) -> Option<&World> {
+            nodes.iter().find_map(|node| match node {
+                $node::World(world) => Some(world),
+                _ => None,
+            })
+        }
+    };
+}
+use node_accessors;
+
 #[inline]
 fn _true() -> bool {
     true
 }
 
+#[inline]
+fn _one() -> u32 {
+    1
+}
+
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct NodeFlags {
     #[serde(skip_serializing_if = "bool_true", default = "_true")]
     pub active: bool,
@@ -174,3 +262,6 @@ pub struct NodeFlags {
     #[serde(skip_serializing_if = "bool_false", default)]
     pub unk28: bool,
 }
+
+#[cfg(test)]
+mod tests;
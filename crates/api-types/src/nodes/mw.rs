@@ -1,12 +1,14 @@
 use super::{
-    Area, AreaPartition, BoundingBox, Camera, Display, NodeFlags, PartitionPg, Transformation,
-    Window,
+    node_accessors, Area, AreaPartition, AreaPartitioned, BoundingBox, Camera, Display, NodeFlags,
+    PartitionPg, Transformation, Window,
 };
+use crate::serde::u32_one;
 use crate::{Color, Range, Vec3};
 use ::serde::{Deserialize, Serialize};
 use mech3ax_metadata_proc_macro::{Struct, Union};
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Empty {
     pub name: String,
     pub flags: NodeFlags,
@@ -19,6 +21,7 @@ pub struct Empty {
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Light {
     pub name: String,
     pub direction: Vec3,
@@ -26,11 +29,14 @@ pub struct Light {
     pub ambient: f32,
     pub color: Color,
     pub range: Range,
+    #[serde(skip_serializing_if = "u32_one", default = "super::_one")]
+    pub parent_count: u32,
     pub parent_ptr: u32,
     pub data_ptr: u32,
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Lod {
     pub name: String,
 
@@ -51,6 +57,7 @@ pub struct Lod {
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Object3d {
     pub name: String,
     pub transformation: Option<Transformation>,
@@ -72,6 +79,7 @@ pub struct Object3d {
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct World {
     pub name: String,
     pub area: Area,
@@ -91,6 +99,7 @@ pub struct World {
 }
 
 #[derive(Debug, Serialize, Deserialize, Union)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum NodeMw {
     Camera(Camera),
     Display(Display),
@@ -101,3 +110,17 @@ pub enum NodeMw {
     Window(Window),
     World(World),
 }
+
+impl AreaPartitioned for NodeMw {
+    type AreaPartition = AreaPartition;
+
+    fn area_partition(&self) -> Option<AreaPartition> {
+        match self {
+            Self::Lod(node) => node.area_partition,
+            Self::Object3d(node) => node.area_partition,
+            _ => None,
+        }
+    }
+}
+
+node_accessors!(NodeMw);
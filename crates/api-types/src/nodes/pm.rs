@@ -1,4 +1,8 @@
-use super::{Area, BoundingBox, Camera, Display, NodeFlags, PartitionNg, Transformation, Window};
+use super::{
+    node_accessors, Area, AreaPartitioned, BoundingBox, Camera, Display, NodeFlags, PartitionNg,
+    Transformation, Window,
+};
+use crate::serde::u32_one;
 use crate::Range;
 use ::serde::{Deserialize, Serialize};
 use bytemuck::{AnyBitPattern, NoUninit};
@@ -8,6 +12,7 @@ use mech3ax_types::impl_as_bytes;
 #[derive(
     Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, NoUninit, AnyBitPattern, Struct,
 )]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[repr(C)]
 pub struct AreaPartitionPm {
     pub x: i16,
@@ -33,18 +38,22 @@ impl AreaPartitionPm {
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Light {
     pub name: String,
     pub unk004: f32,
     pub unk156: f32,
     pub unk160: f32,
     pub range: Range,
+    #[serde(skip_serializing_if = "u32_one", default = "super::_one")]
+    pub parent_count: u32,
     pub parent_ptr: u32,
     pub data_ptr: u32,
     pub node_index: u32,
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Lod {
     pub name: String,
     pub level: bool,
@@ -62,6 +71,7 @@ pub struct Lod {
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Object3d {
     pub name: String,
     pub transformation: Option<Transformation>,
@@ -85,6 +95,7 @@ pub struct Object3d {
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct World {
     pub name: String,
     pub area: Area,
@@ -102,6 +113,7 @@ pub struct World {
 }
 
 #[derive(Debug, Serialize, Deserialize, Union)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum NodePm {
     Camera(Camera),
     Display(Display),
@@ -111,3 +123,16 @@ pub enum NodePm {
     Window(Window),
     World(World),
 }
+
+impl AreaPartitioned for NodePm {
+    type AreaPartition = AreaPartitionPm;
+
+    fn area_partition(&self) -> Option<AreaPartitionPm> {
+        match self {
+            Self::Object3d(node) => node.area_partition,
+            _ => None,
+        }
+    }
+}
+
+node_accessors!(NodePm);
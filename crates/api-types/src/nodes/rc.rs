@@ -1,21 +1,28 @@
-use super::{Area, AreaPartition, BoundingBox, Camera, Display, NodeFlags, PartitionPg, Window};
+use super::{
+    node_accessors, Area, AreaPartition, AreaPartitioned, BoundingBox, Camera, Display, NodeFlags,
+    PartitionPg, Window,
+};
+use crate::serde::u32_one;
 use crate::{Color, Matrix, Range, Vec3};
 use ::serde::{Deserialize, Serialize};
 use mech3ax_metadata_proc_macro::{Struct, Union};
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct RotationTranslation {
     pub rotation: Vec3,
     pub translation: Vec3,
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct TranslationOnly {
     pub translation: Vec3,
     pub matrix: Option<Matrix>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Union)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum Transformation {
     None,
     ScaleOnly(Vec3),
@@ -24,6 +31,7 @@ pub enum Transformation {
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Empty {
     pub name: String,
     pub flags: NodeFlags,
@@ -36,6 +44,7 @@ pub struct Empty {
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Light {
     pub name: String,
     pub unk008: f32,
@@ -45,11 +54,14 @@ pub struct Light {
     // pub ambient: f32,
     pub color: Color,
     pub range: Range,
+    #[serde(skip_serializing_if = "u32_one", default = "super::_one")]
+    pub parent_count: u32,
     pub parent_ptr: u32,
     pub data_ptr: u32,
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Lod {
     pub name: String,
     pub level: bool,
@@ -67,6 +79,7 @@ pub struct Lod {
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Object3d {
     pub name: String,
     pub transformation: Transformation,
@@ -89,6 +102,7 @@ pub struct Object3d {
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct World {
     pub name: String,
     pub area: Area,
@@ -110,6 +124,7 @@ pub struct World {
 }
 
 #[derive(Debug, Serialize, Deserialize, Union)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum NodeRc {
     Camera(Camera),
     Display(Display),
@@ -120,3 +135,16 @@ pub enum NodeRc {
     Window(Window),
     World(World),
 }
+
+impl AreaPartitioned for NodeRc {
+    type AreaPartition = AreaPartition;
+
+    fn area_partition(&self) -> Option<AreaPartition> {
+        match self {
+            Self::Object3d(node) => node.area_partition,
+            _ => None,
+        }
+    }
+}
+
+node_accessors!(NodeRc);
@@ -0,0 +1,177 @@
+use super::mw::{cameras, lights, objects3d, world, Light, Lod, NodeMw, Object3d, World};
+use super::{
+    area_partitions, nodes_in_partition, Area, AreaPartition, BoundingBox, Camera, NodeFlags,
+};
+use crate::{Color, Range, Vec3};
+
+fn flags() -> NodeFlags {
+    serde_json::from_str("{}").unwrap()
+}
+
+fn object3d(name: &str, area_partition: Option<AreaPartition>) -> NodeMw {
+    NodeMw::Object3d(Object3d {
+        name: name.to_string(),
+        transformation: None,
+        matrix_signs: 0,
+        flags: flags(),
+        zone_id: 0,
+        area_partition,
+        mesh_index: -1,
+        parent: None,
+        children: Vec::new(),
+        data_ptr: 0,
+        parent_array_ptr: 0,
+        children_array_ptr: 0,
+        unk116: BoundingBox::EMPTY,
+        unk140: BoundingBox::EMPTY,
+        unk164: BoundingBox::EMPTY,
+    })
+}
+
+fn lod(name: &str, area_partition: Option<AreaPartition>) -> NodeMw {
+    NodeMw::Lod(Lod {
+        name: name.to_string(),
+        level: false,
+        range: Range::DEFAULT,
+        unk60: 0.0,
+        unk76: None,
+        flags: flags(),
+        zone_id: 0,
+        area_partition,
+        parent: 0,
+        children: Vec::new(),
+        data_ptr: 0,
+        parent_array_ptr: 0,
+        children_array_ptr: 0,
+        unk116: BoundingBox::EMPTY,
+    })
+}
+
+fn camera() -> NodeMw {
+    NodeMw::Camera(Camera {
+        clip: Range::DEFAULT,
+        fov: Range::DEFAULT,
+        focus_node_xy: 0,
+        data_ptr: 0,
+    })
+}
+
+fn light(name: &str) -> NodeMw {
+    NodeMw::Light(Light {
+        name: name.to_string(),
+        direction: Vec3::DEFAULT,
+        diffuse: 0.0,
+        ambient: 0.0,
+        color: Color::BLACK,
+        range: Range::DEFAULT,
+        parent_count: 1,
+        parent_ptr: 0,
+        data_ptr: 0,
+    })
+}
+
+fn world_node(name: &str) -> NodeMw {
+    NodeMw::World(World {
+        name: name.to_string(),
+        area: Area {
+            left: 0,
+            top: 0,
+            right: 0,
+            bottom: 0,
+        },
+        partitions: Vec::new(),
+        area_partition_x_count: 0,
+        area_partition_y_count: 0,
+        fudge_count: false,
+        area_partition_ptr: 0,
+        virt_partition_ptr: 0,
+        world_children_ptr: 0,
+        world_child_value: 0,
+        world_lights_ptr: 0,
+        children: Vec::new(),
+        data_ptr: 0,
+        children_array_ptr: 0,
+    })
+}
+
+fn name(node: &NodeMw) -> &str {
+    match node {
+        NodeMw::Object3d(object3d) => &object3d.name,
+        NodeMw::Lod(lod) => &lod.name,
+        _ => panic!("unexpected node"),
+    }
+}
+
+const AP1: AreaPartition = AreaPartition { x: 1, y: 2 };
+const AP2: AreaPartition = AreaPartition { x: 3, y: 4 };
+
+fn nodes() -> Vec<NodeMw> {
+    vec![
+        camera(),
+        object3d("a", Some(AP1)),
+        lod("b", Some(AP2)),
+        object3d("c", None),
+        object3d("d", Some(AP2)),
+        lod("e", None),
+        object3d("f", Some(AP1)),
+    ]
+}
+
+#[test]
+fn in_partition() {
+    let nodes = nodes();
+    let names: Vec<_> = nodes_in_partition(&nodes, AP1)
+        .into_iter()
+        .map(name)
+        .collect();
+    assert_eq!(names, ["a", "f"]);
+    let names: Vec<_> = nodes_in_partition(&nodes, AP2)
+        .into_iter()
+        .map(name)
+        .collect();
+    assert_eq!(names, ["b", "d"]);
+    assert!(nodes_in_partition(&nodes, AreaPartition::DEFAULT).is_empty());
+}
+
+#[test]
+fn distinct_partitions() {
+    let nodes = nodes();
+    assert_eq!(area_partitions(&nodes), [AP1, AP2]);
+    assert!(area_partitions(&[camera(), lod("a", None)]).is_empty());
+}
+
+#[test]
+fn typed_accessors() {
+    let mut nodes = nodes();
+    nodes.push(light("sunlight"));
+    assert!(world(&nodes).is_none());
+    nodes.insert(0, world_node("world1"));
+
+    assert_eq!(cameras(&nodes).count(), 1);
+    let names: Vec<_> = lights(&nodes).map(|light| light.name.as_str()).collect();
+    assert_eq!(names, ["sunlight"]);
+    let names: Vec<_> = objects3d(&nodes)
+        .map(|object3d| object3d.name.as_str())
+        .collect();
+    assert_eq!(names, ["a", "c", "d", "f"]);
+    assert_eq!(
+        world(&nodes).map(|world| world.name.as_str()),
+        Some("world1")
+    );
+}
+
+#[test]
+fn light_parent_count_is_omitted_when_one() {
+    let NodeMw::Light(mut light) = light("sunlight") else {
+        unreachable!()
+    };
+    let json = serde_json::to_string(&light).unwrap();
+    assert!(!json.contains("parent_count"), "{}", json);
+    let read: Light = serde_json::from_str(&json).unwrap();
+    assert_eq!(read.parent_count, 1);
+
+    light.parent_count = 2;
+    let json = serde_json::to_string(&light).unwrap();
+    let read: Light = serde_json::from_str(&json).unwrap();
+    assert_eq!(read.parent_count, 2);
+}
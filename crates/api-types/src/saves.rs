@@ -5,6 +5,7 @@ use std::num::NonZeroU32;
 
 primitive_enum! {
     #[derive(Serialize, Deserialize)]
+    #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
     pub enum ActivationStatus: u8 {
         Unk1 = 1,
         // Unk2 = 2,
@@ -16,6 +17,7 @@ primitive_enum! {
 }
 
 #[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum ActivationType {
     One,
     Two(Option<Bytes>),
@@ -23,6 +25,7 @@ pub enum ActivationType {
 }
 
 #[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct AnimActivation {
     pub name: String,
     pub node_index: Option<i32>,
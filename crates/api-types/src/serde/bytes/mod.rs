@@ -94,5 +94,21 @@ impl<'de> serde::de::Deserialize<'de> for Bytes {
     }
 }
 
+/// Bytes are only serialized as a base64 string to JSON.
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for Bytes {
+    fn is_referenceable() -> bool {
+        false
+    }
+
+    fn schema_name() -> String {
+        "Bytes".to_owned()
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        String::json_schema(gen)
+    }
+}
+
 #[cfg(test)]
 mod tests;
@@ -14,3 +14,13 @@ pub fn bool_true(value: &bool) -> bool {
 pub fn pointer_zero(pointer: &u32) -> bool {
     *pointer == 0
 }
+
+#[inline]
+pub fn u32_zero(value: &u32) -> bool {
+    *value == 0
+}
+
+#[inline]
+pub fn u32_one(value: &u32) -> bool {
+    *value == 1
+}
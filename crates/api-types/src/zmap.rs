@@ -7,6 +7,7 @@ use mech3ax_types::impl_as_bytes;
 #[derive(
     Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, NoUninit, AnyBitPattern, Struct,
 )]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[dotnet(val_struct)]
 #[repr(C)]
 pub struct MapColor {
@@ -14,11 +15,12 @@ pub struct MapColor {
     pub g: u8,
     pub b: u8,
 }
-impl_as_bytes!(MapColor, 3);
+impl_as_bytes!(MapColor, 3, LeStruct);
 
 #[derive(
     Debug, Clone, Copy, PartialEq, Serialize, Deserialize, NoUninit, AnyBitPattern, Struct,
 )]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[dotnet(val_struct)]
 #[repr(C)]
 pub struct MapVertex {
@@ -26,9 +28,10 @@ pub struct MapVertex {
     pub z: f32,
     pub y: f32,
 }
-impl_as_bytes!(MapVertex, 12);
+impl_as_bytes!(MapVertex, 12, LeStruct);
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct MapFeature {
     pub color: MapColor,
     pub vertices: Vec<MapVertex>,
@@ -36,6 +39,7 @@ pub struct MapFeature {
 }
 
 #[derive(Debug, Serialize, Deserialize, Struct)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[dotnet(partial, namespace = "Mech3DotNet.Zbd")]
 pub struct Zmap {
     pub unk04: u32,
@@ -5,7 +5,7 @@ mod write;
 
 use bytemuck::{AnyBitPattern, NoUninit};
 use mech3ax_types::{impl_as_bytes, Ascii, Hex};
-pub use read::read_archive;
+pub use read::{read_archive, read_archive_with_progress};
 use std::fmt;
 pub use write::write_archive;
 
@@ -14,6 +14,8 @@ pub enum Mode {
     Reader,
     Sounds,
     Motion,
+    /// Mechlib archives are framed like sounds archives, without a checksum.
+    Mechlib,
     ReaderBypass,
 }
 
@@ -23,6 +25,19 @@ pub enum Version {
     Two(Mode),
 }
 
+/// Progress while reading an archive, reported after each entry.
+#[derive(Debug, Clone, Copy)]
+pub struct ArchiveProgress<'a> {
+    /// The index of the entry that was read.
+    pub index: usize,
+    /// The total number of entries.
+    pub total: usize,
+    /// The (possibly renamed) name of the entry that was read.
+    pub name: &'a str,
+    /// The number of entry data bytes read so far, including this entry.
+    pub bytes_done: usize,
+}
+
 const VERSION_ONE: u32 = 1;
 const VERSION_TWO: u32 = 2;
 
@@ -32,7 +47,7 @@ struct HeaderOneC {
     version: u32, // 0
     count: u32,   // 4
 }
-impl_as_bytes!(HeaderOneC, 8);
+impl_as_bytes!(HeaderOneC, 8, LeStruct);
 
 #[derive(Debug, Clone, Copy, NoUninit, AnyBitPattern)]
 #[repr(C)]
@@ -41,7 +56,7 @@ struct HeaderTwoC {
     count: u32,         // 4
     checksum: Hex<u32>, // 8
 }
-impl_as_bytes!(HeaderTwoC, 12);
+impl_as_bytes!(HeaderTwoC, 12, LeStruct);
 
 /// This exist, because using a single u64 would be unaligned.
 #[derive(Clone, Copy, NoUninit, AnyBitPattern, PartialEq)]
@@ -84,7 +99,7 @@ struct TableEntryC {
     comment: Ascii<64>,  // 76
     filetime: FiletimeC, // 140
 }
-impl_as_bytes!(TableEntryC, 148);
+impl_as_bytes!(TableEntryC, 148, LeStruct);
 
 #[cfg(test)]
 mod tests;
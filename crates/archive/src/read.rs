@@ -1,4 +1,6 @@
-use super::{HeaderOneC, HeaderTwoC, Mode, TableEntryC, Version, VERSION_ONE, VERSION_TWO};
+use super::{
+    ArchiveProgress, HeaderOneC, HeaderTwoC, Mode, TableEntryC, Version, VERSION_ONE, VERSION_TWO,
+};
 use log::{debug, trace};
 use mech3ax_api_types::archive::{
     ArchiveEntry, ArchiveEntryInfo, ArchiveEntryInfoInvalid, ArchiveEntryInfoValid,
@@ -25,9 +27,25 @@ struct TableEntry {
 }
 
 pub fn read_archive<R, F, E>(
+    read: &mut CountingReader<R>,
+    save_file: F,
+    version: Version,
+) -> std::result::Result<Vec<ArchiveEntry>, E>
+where
+    R: Read + Seek,
+    F: FnMut(&str, Vec<u8>, usize) -> std::result::Result<(), E>,
+    E: From<std::io::Error> + From<Error>,
+{
+    read_archive_with_progress(read, save_file, version, &mut |_progress| {})
+}
+
+/// Read an archive like [`read_archive`], and report progress after each
+/// entry is saved.
+pub fn read_archive_with_progress<R, F, E>(
     read: &mut CountingReader<R>,
     mut save_file: F,
     version: Version,
+    progress: &mut dyn FnMut(ArchiveProgress<'_>),
 ) -> std::result::Result<Vec<ArchiveEntry>, E>
 where
     R: Read + Seek,
@@ -39,7 +57,10 @@ where
     read.seek(SeekFrom::Start(0))?;
 
     let mut crc = CRC32_INIT;
-    let mut seen = Rename::new();
+    // entries are usually extracted to files
+    let mut seen = Rename::file_names();
+    let total = entries.len();
+    let mut bytes_done = 0;
 
     let entries = entries
         .into_iter()
@@ -64,8 +85,7 @@ where
             );
             assert_that!("entry offset", read.offset == start, index).map_err(Error::Assert)?;
 
-            let mut buffer = vec![0; len];
-            read.read_exact(&mut buffer)?;
+            let buffer = read.read_bytes(len)?;
             crc = crc32_update(crc, &buffer);
 
             let rename = seen.insert(&name);
@@ -77,6 +97,14 @@ where
             debug!("Saving entry {}: `{}`", index, filename);
             save_file(filename, buffer, read.prev)?;
 
+            bytes_done += len;
+            progress(ArchiveProgress {
+                index,
+                total,
+                name: filename,
+                bytes_done,
+            });
+
             Ok(ArchiveEntry {
                 name,
                 rename,
@@ -103,7 +131,7 @@ fn read_table(
             let header: HeaderOneC = read.read_struct()?;
             assert_that!(
                 "archive version",
-                header.version == VERSION_ONE,
+                version header.version == VERSION_ONE,
                 read.prev + 0
             )?;
             (header.count, HEADER_ONE_SIZE, 0)
@@ -113,12 +141,12 @@ fn read_table(
             let header: HeaderTwoC = read.read_struct()?;
             assert_that!(
                 "archive version",
-                header.version == VERSION_TWO,
+                version header.version == VERSION_TWO,
                 read.prev + 0
             )?;
 
             match mode {
-                Mode::Motion | Mode::Sounds => {
+                Mode::Motion | Mode::Sounds | Mode::Mechlib => {
                     assert_that!("archive checksum", header.checksum.0 == 0, read.prev + 8)?;
                 }
                 Mode::Reader | Mode::ReaderBypass => (),
@@ -1,4 +1,13 @@
-use super::FiletimeC;
+use super::{
+    read_archive, read_archive_with_progress, write_archive, FiletimeC, HeaderTwoC, Mode,
+    TableEntryC, Version,
+};
+use mech3ax_api_types::archive::{ArchiveEntry, ArchiveEntryInfo, ArchiveEntryInfoInvalid};
+use mech3ax_common::io_ext::{CountingReader, CountingWriter};
+use mech3ax_common::test_utils::assert_truncated_fails;
+use mech3ax_common::Error;
+use mech3ax_types::AsBytes as _;
+use std::io::Cursor;
 
 #[test]
 fn filetime_u64_lo() {
@@ -23,3 +32,267 @@ fn filetime_u64_hi() {
     let actual = FiletimeC::from_u64(filetime);
     assert_eq!(actual, expected, "filetime");
 }
+
+fn entries(names: &[&str]) -> Vec<ArchiveEntry> {
+    names
+        .iter()
+        .map(|name| ArchiveEntry {
+            name: name.to_string(),
+            rename: None,
+            flags: 0,
+            info: ArchiveEntryInfo::Invalid(ArchiveEntryInfoInvalid {
+                comment: vec![0; 64],
+                filetime: 0,
+            }),
+        })
+        .collect()
+}
+
+#[test]
+fn read_progress_once_per_entry() {
+    let entries = entries(&["alpha", "bravo", "charlie"]);
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    write_archive::<_, _, Error>(
+        &mut write,
+        &entries,
+        |name, _offset| Ok(name.as_bytes().to_vec()),
+        Version::One,
+    )
+    .unwrap();
+
+    let mut read = CountingReader::new(Cursor::new(write.into_inner()));
+    let mut progress = Vec::new();
+    let read_entries = read_archive_with_progress::<_, _, Error>(
+        &mut read,
+        |_name, _data, _offset| Ok(()),
+        Version::One,
+        &mut |p| progress.push((p.index, p.total, p.name.to_string(), p.bytes_done)),
+    )
+    .unwrap();
+
+    assert_eq!(read_entries.len(), 3);
+    assert_eq!(
+        progress,
+        vec![
+            (0, 3, "alpha".to_string(), 5),
+            (1, 3, "bravo".to_string(), 10),
+            (2, 3, "charlie".to_string(), 17),
+        ]
+    );
+}
+
+#[test]
+fn read_truncated_fails() {
+    let entries = entries(&["alpha", "bravo", "charlie"]);
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    write_archive::<_, _, Error>(
+        &mut write,
+        &entries,
+        |name, _offset| Ok(name.as_bytes().to_vec()),
+        Version::One,
+    )
+    .unwrap();
+    let data = write.into_inner();
+
+    assert_truncated_fails(&data, |data| {
+        let mut read = CountingReader::new(Cursor::new(data));
+        read_archive::<_, _, Error>(&mut read, |_name, _data, _offset| Ok(()), Version::One)
+    });
+}
+
+fn read_table_entries(data: &[u8], count: usize) -> Vec<(u32, u32)> {
+    let header_size = HeaderTwoC::SIZE as usize;
+    let entry_size = TableEntryC::SIZE as usize;
+    let table_start = data.len() - header_size - count * entry_size;
+    let mut read = CountingReader::new(Cursor::new(&data[table_start..]));
+    (0..count)
+        .map(|_| {
+            let entry: TableEntryC = read.read_struct().unwrap();
+            (entry.start, entry.length)
+        })
+        .collect()
+}
+
+#[test]
+fn write_table_uses_loaded_entry_lengths() {
+    // the manifest has no lengths or checksum, so editing an entry's content
+    // must be reflected in the table and the checksum
+    let entries = entries(&["alpha", "bravo", "charlie"]);
+    let version = Version::Two(Mode::Reader);
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    write_archive::<_, _, Error>(
+        &mut write,
+        &entries,
+        |name, _offset| match name {
+            "bravo" => Ok(b"bravo, but edited to be longer".to_vec()),
+            other => Ok(other.as_bytes().to_vec()),
+        },
+        version,
+    )
+    .unwrap();
+    let data = write.into_inner();
+
+    assert_eq!(read_table_entries(&data, 3), vec![(0, 5), (5, 30), (35, 7)]);
+
+    // reading asserts the checksum for reader archives
+    let mut read = CountingReader::new(Cursor::new(data));
+    let mut contents = Vec::new();
+    read_archive::<_, _, Error>(
+        &mut read,
+        |name, data, _offset| {
+            contents.push((name.to_string(), data));
+            Ok(())
+        },
+        version,
+    )
+    .unwrap();
+    assert_eq!(contents[1].1, b"bravo, but edited to be longer");
+}
+
+#[test]
+fn write_long_entry_name_fails() {
+    let name = "a".repeat(64);
+    let entries = entries(&[&name]);
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    let err = write_archive::<_, _, Error>(
+        &mut write,
+        &entries,
+        |_name, _offset| Ok(b"data".to_vec()),
+        Version::One,
+    )
+    .unwrap_err();
+    match err {
+        Error::Assert(e) => assert_eq!(
+            e.to_string(),
+            "Expected `entry name` length <= 63, but was 64 (at 4)"
+        ),
+        other => panic!("expected assertion error, got {:?}", other),
+    }
+}
+
+#[test]
+fn mechlib_is_framed_like_sounds() {
+    let entries = entries(&["format", "version", "materials"]);
+    let write = |mode| {
+        let mut write = CountingWriter::new(Vec::new(), 0);
+        write_archive::<_, _, Error>(
+            &mut write,
+            &entries,
+            |name, _offset| Ok(name.as_bytes().to_vec()),
+            Version::Two(mode),
+        )
+        .unwrap();
+        write.into_inner()
+    };
+    let mechlib = write(Mode::Mechlib);
+    assert_eq!(mechlib, write(Mode::Sounds));
+
+    let mut read = CountingReader::new(Cursor::new(mechlib));
+    let manifest = read_archive::<_, _, Error>(
+        &mut read,
+        |_name, _data, _offset| Ok(()),
+        Version::Two(Mode::Mechlib),
+    )
+    .unwrap();
+    assert_eq!(manifest.len(), 3);
+
+    // a mechlib archive doesn't have a checksum
+    let mut read = CountingReader::new(Cursor::new(write(Mode::Reader)));
+    let result = read_archive::<_, _, Error>(
+        &mut read,
+        |_name, _data, _offset| Ok(()),
+        Version::Two(Mode::Mechlib),
+    );
+    assert!(result.is_err());
+}
+
+fn read_names(data: Vec<u8>) -> (Vec<String>, Vec<ArchiveEntry>) {
+    let mut read = CountingReader::new(Cursor::new(data));
+    let mut names = Vec::new();
+    let manifest = read_archive::<_, _, Error>(
+        &mut read,
+        |name, _data, _offset| {
+            names.push(name.to_string());
+            Ok(())
+        },
+        Version::One,
+    )
+    .unwrap();
+    (names, manifest)
+}
+
+#[test]
+fn read_renames_names_colliding_by_case() {
+    let entries = entries(&["alpha.wav", "ALPHA.wav", "alpha-1.wav"]);
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    write_archive::<_, _, Error>(
+        &mut write,
+        &entries,
+        |name, _offset| Ok(name.as_bytes().to_vec()),
+        Version::One,
+    )
+    .unwrap();
+
+    let (names, manifest) = read_names(write.into_inner());
+    assert_eq!(names, ["alpha.wav", "ALPHA-1.wav", "alpha-1-1.wav"]);
+    let renames = manifest
+        .iter()
+        .map(|entry| entry.rename.as_deref())
+        .collect::<Vec<_>>();
+    assert_eq!(renames, [None, Some("ALPHA-1.wav"), Some("alpha-1-1.wav")]);
+
+    // the original names are restored from the manifest
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    write_archive::<_, _, Error>(
+        &mut write,
+        &manifest,
+        |filename, _offset| {
+            let index = names.iter().position(|name| name == filename).unwrap();
+            Ok(entries[index].name.as_bytes().to_vec())
+        },
+        Version::One,
+    )
+    .unwrap();
+    let (_, restored) = read_names(write.into_inner());
+    let restored = restored
+        .iter()
+        .map(|entry| entry.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(restored, ["alpha.wav", "ALPHA.wav", "alpha-1.wav"]);
+}
+
+#[test]
+fn read_renames_illegal_file_names() {
+    let entries = entries(&["c1:a?.wav", "trailing. "]);
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    write_archive::<_, _, Error>(
+        &mut write,
+        &entries,
+        |name, _offset| Ok(name.as_bytes().to_vec()),
+        Version::One,
+    )
+    .unwrap();
+
+    let (names, manifest) = read_names(write.into_inner());
+    assert_eq!(names, ["c1_a_.wav", "trailing__"]);
+    assert_eq!(manifest[0].name, "c1:a?.wav");
+    assert_eq!(manifest[0].rename.as_deref(), Some("c1_a_.wav"));
+}
+
+#[test]
+fn write_duplicate_file_names_fails() {
+    let mut entries = entries(&["alpha", "bravo"]);
+    entries[1].rename = Some("alpha".to_string());
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    let err = write_archive::<_, _, Error>(
+        &mut write,
+        &entries,
+        |name, _offset| Ok(name.as_bytes().to_vec()),
+        Version::One,
+    )
+    .unwrap_err();
+    assert_eq!(
+        format!("{:#?}", err),
+        "Assert(\n    Expected archive entry 1 file name `alpha` to be unique, but entry 0 has the same file name,\n)"
+    );
+}
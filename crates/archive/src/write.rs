@@ -4,11 +4,13 @@ use log::{debug, trace};
 use mech3ax_api_types::archive::{
     ArchiveEntry, ArchiveEntryInfo, ArchiveEntryInfoInvalid, ArchiveEntryInfoValid,
 };
+use mech3ax_common::assert::assert_utf8;
 use mech3ax_common::io_ext::CountingWriter;
 use mech3ax_common::{assert_len, assert_with_msg, Error, Result};
 use mech3ax_crc32::{crc32_update, CRC32_INIT};
 use mech3ax_timestamp::nt::to_filetime;
 use mech3ax_types::{Ascii, Bytes, Hex};
+use std::collections::HashMap;
 use std::io::Write;
 
 pub fn write_archive<W, F, E>(
@@ -22,6 +24,21 @@ where
     F: FnMut(&str, usize) -> std::result::Result<Vec<u8>, E>,
     E: From<std::io::Error> + From<Error>,
 {
+    // each entry is loaded from its own file, so the original names can only
+    // be restored if the file names are unique
+    let mut filenames = HashMap::new();
+    for (index, entry) in entries.iter().enumerate() {
+        let filename = entry.rename.as_deref().unwrap_or(&entry.name);
+        if let Some(other) = filenames.insert(filename, index) {
+            return Err(E::from(assert_with_msg!(
+                "Expected archive entry {} file name `{}` to be unique, but entry {} has the same file name",
+                index,
+                filename,
+                other
+            )));
+        }
+    }
+
     let mut crc = CRC32_INIT;
     let mut offset = 0;
 
@@ -57,11 +74,17 @@ where
                 _ => len,
             };
 
-            let name = Ascii::from_str_padded(&entry.name);
+            let name = assert_utf8("entry name", write.offset, || {
+                Ascii::from_str_padded(&entry.name)
+            })
+            .map_err(Error::Assert)?;
 
             let (comment, filetime) = match &entry.info {
                 ArchiveEntryInfo::Valid(ArchiveEntryInfoValid { comment, datetime }) => {
-                    let comment = Ascii::from_str_padded(comment);
+                    let comment = assert_utf8("entry comment", write.offset, || {
+                        Ascii::from_str_padded(comment)
+                    })
+                    .map_err(Error::Assert)?;
                     let filetime = to_filetime(datetime);
                     (comment, filetime)
                 }
@@ -125,7 +148,7 @@ fn write_table(
             };
             write.write_struct(&header)?;
         }
-        Version::Two(Mode::Motion) | Version::Two(Mode::Sounds) => {
+        Version::Two(Mode::Motion) | Version::Two(Mode::Sounds) | Version::Two(Mode::Mechlib) => {
             let header = HeaderTwoC {
                 version: VERSION_TWO,
                 count,
@@ -3,18 +3,102 @@ use mech3ax_types::{Bitflags, Bool, ConversionError, Maybe, PrimitiveEnum, Primi
 use std::cmp::{PartialEq, PartialOrd};
 use std::fmt;
 
+/// What kind of assertion failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssertionKind {
+    /// A value was not what was expected.
+    Value,
+    /// The data's version is not supported (the actual version).
+    UnsupportedVersion(u32),
+    /// A string could not be converted.
+    Utf8,
+}
+
+/// An assertion failure while reading (or writing) data.
+///
+/// Assertions on a named field also record the field's name and the offset
+/// of the value, so callers can react without parsing the message. String
+/// conversion failures also record the raw bytes that failed to convert.
 #[derive(Clone)]
-pub struct AssertionError(pub String);
+pub struct AssertionError {
+    msg: String,
+    kind: AssertionKind,
+    name: Option<String>,
+    pos: Option<usize>,
+    raw: Option<Vec<u8>>,
+}
+
+impl AssertionError {
+    /// An assertion failure without a field or an offset.
+    pub fn new(msg: String) -> Self {
+        Self {
+            msg,
+            kind: AssertionKind::Value,
+            name: None,
+            pos: None,
+            raw: None,
+        }
+    }
+
+    /// An assertion failure for the named field at the offset.
+    pub fn at(name: &str, pos: usize, msg: String) -> Self {
+        Self {
+            msg,
+            kind: AssertionKind::Value,
+            name: Some(name.to_string()),
+            pos: Some(pos),
+            raw: None,
+        }
+    }
+
+    /// Append context to the message, e.g. the item of an array that failed.
+    pub fn with_context(mut self, context: &str) -> Self {
+        self.msg = format!("{} ({})", self.msg, context);
+        self
+    }
+
+    /// Set what kind of assertion failed.
+    pub fn with_kind(mut self, kind: AssertionKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// Record the raw bytes the assertion failed on.
+    pub fn with_raw(mut self, raw: Vec<u8>) -> Self {
+        self.raw = Some(raw);
+        self
+    }
+
+    pub fn message(&self) -> &str {
+        &self.msg
+    }
+
+    pub fn kind(&self) -> AssertionKind {
+        self.kind
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn pos(&self) -> Option<usize> {
+        self.pos
+    }
+
+    pub fn raw(&self) -> Option<&[u8]> {
+        self.raw.as_deref()
+    }
+}
 
 impl fmt::Debug for AssertionError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.0)
+        write!(f, "{}", self.msg)
     }
 }
 
 impl fmt::Display for AssertionError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.0)
+        write!(f, "{}", self.msg)
     }
 }
 
@@ -34,7 +118,40 @@ where
             "Expected `{}` == {:#?}, but was {:#?} (at {})",
             name, expected, actual, pos
         );
-        Err(AssertionError(msg))
+        Err(AssertionError::at(name, pos, msg))
+    }
+}
+
+/// Like [`is_equal_to`], but a mismatch is an unsupported version.
+#[inline]
+pub fn is_version(name: &str, expected: u32, actual: u32, pos: usize) -> Result<()> {
+    is_equal_to(name, expected, actual, pos)
+        .map_err(|e| e.with_kind(AssertionKind::UnsupportedVersion(actual)))
+}
+
+/// The default relative tolerance for approximate float comparisons, a few
+/// ULPs. Derived values such as reciprocals may differ in the last bit
+/// depending on how the original compiler evaluated them.
+pub const APPROX_EPSILON: f32 = 4.0 * f32::EPSILON;
+
+#[inline]
+pub fn is_approx_equal_to(
+    name: &str,
+    expected: f32,
+    actual: f32,
+    epsilon: f32,
+    pos: usize,
+) -> Result<()> {
+    // exact equality also handles zero and infinities
+    let diff = (actual - expected).abs();
+    if actual == expected || diff <= epsilon * actual.abs().max(expected.abs()) {
+        Ok(())
+    } else {
+        let msg = format!(
+            "Expected `{}` ~= {:#?}, but was {:#?} (at {})",
+            name, expected, actual, pos
+        );
+        Err(AssertionError::at(name, pos, msg))
     }
 }
 
@@ -47,7 +164,7 @@ where
         Ok(())
     } else {
         let msg = format!("Expected `{}` != {:#?}, but was (at {})", name, actual, pos);
-        Err(AssertionError(msg))
+        Err(AssertionError::at(name, pos, msg))
     }
 }
 
@@ -63,7 +180,7 @@ where
             "Expected `{}` < {:#?}, but was {:#?} (at {})",
             name, expected, actual, pos
         );
-        Err(AssertionError(msg))
+        Err(AssertionError::at(name, pos, msg))
     }
 }
 
@@ -79,7 +196,7 @@ where
             "Expected `{}` <= {:#?}, but was {:#?} (at {})",
             name, expected, actual, pos
         );
-        Err(AssertionError(msg))
+        Err(AssertionError::at(name, pos, msg))
     }
 }
 
@@ -95,7 +212,7 @@ where
             "Expected `{}` > {:#?}, but was {:#?} (at {})",
             name, expected, actual, pos
         );
-        Err(AssertionError(msg))
+        Err(AssertionError::at(name, pos, msg))
     }
 }
 
@@ -111,7 +228,7 @@ where
             "Expected `{}` >= {:#?}, but was {:#?} (at {})",
             name, expected, actual, pos
         );
-        Err(AssertionError(msg))
+        Err(AssertionError::at(name, pos, msg))
     }
 }
 
@@ -133,7 +250,7 @@ where
             "Expected {:#?} <= `{}` <= {:#?}, but was {:#?} (at {})",
             expected_min, name, expected_max, actual, pos
         );
-        Err(AssertionError(msg))
+        Err(AssertionError::at(name, pos, msg))
     }
 }
 
@@ -149,7 +266,23 @@ where
             "Expected `{}` to be in {:#?}, but was {:#?} (at {})",
             name, haystack, needle, pos
         );
-        Err(AssertionError(msg))
+        Err(AssertionError::at(name, pos, msg))
+    }
+}
+
+#[inline]
+pub fn is_one_of<T>(name: &str, allowed: &[T], actual: &T, pos: usize) -> Result<()>
+where
+    T: PartialEq + fmt::Debug,
+{
+    if allowed.contains(actual) {
+        Ok(())
+    } else {
+        let msg = format!(
+            "Expected `{}` to be one of {:?}, but was {:?} (at {})",
+            name, allowed, actual, pos
+        );
+        Err(AssertionError::at(name, pos, msg))
     }
 }
 
@@ -165,29 +298,66 @@ where
             "Expected `{}` to be in {}..{}, but was {} (at {})",
             name, start, end, needle, pos
         );
-        Err(AssertionError(msg))
+        Err(AssertionError::at(name, pos, msg))
     }
 }
 
+/// Format bytes as upper-case hex, separated by spaces.
+pub fn format_hex(raw: &[u8]) -> String {
+    raw.iter()
+        .map(|b| format!("{:02X}", b))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 #[inline]
 pub fn format_conversion_err(name: &str, pos: usize, e: ConversionError) -> AssertionError {
-    let msg = match e {
-        ConversionError::PaddingError(padding) => format!(
-            "Expected `{}` to padded with {} (at {})",
-            name, padding, pos
+    let (msg, pos, raw) = match e {
+        ConversionError::PaddingError(padding) => (
+            format!(
+                "Expected `{}` to padded with {} (at {})",
+                name, padding, pos
+            ),
+            pos,
+            None,
         ),
-        ConversionError::NonAscii(index) => {
+        ConversionError::NonAscii(index, raw) => (
             format!(
-                "Expected `{}` to be a valid string (at {})",
+                "Expected `{}` to be a valid string, but was {} (at {})",
                 name,
+                format_hex(&raw),
                 pos + index
-            )
-        }
-        ConversionError::Unterminated => {
-            format!("Expected `{}` to be zero-terminated (at {})", name, pos)
-        }
+            ),
+            pos + index,
+            Some(raw),
+        ),
+        ConversionError::Unterminated => (
+            format!("Expected `{}` to be zero-terminated (at {})", name, pos),
+            pos,
+            None,
+        ),
+        ConversionError::TooLong(len, max) => (
+            format!(
+                "Expected `{}` length <= {}, but was {} (at {})",
+                name, max, len, pos
+            ),
+            pos,
+            None,
+        ),
+        ConversionError::NotWindows1252(c) => (
+            format!(
+                "Expected `{}` to be Windows-1252, but contained {:?} (at {})",
+                name, c, pos
+            ),
+            pos,
+            None,
+        ),
     };
-    AssertionError(msg)
+    let err = AssertionError::at(name, pos, msg).with_kind(AssertionKind::Utf8);
+    match raw {
+        Some(raw) => err.with_raw(raw),
+        None => err,
+    }
 }
 
 #[inline]
@@ -210,7 +380,27 @@ pub fn is_all_zero(name: &str, buf: &[u8], pos: usize) -> Result<()> {
             value,
             pos + index
         );
-        Err(AssertionError(msg))
+        Err(AssertionError::at(name, pos + index, msg))
+    } else {
+        Ok(())
+    }
+}
+
+/// Like [`is_all_zero`], but for a block filled with any repeated byte.
+#[inline]
+pub fn is_all_eq(name: &str, buf: &[u8], expected: u8, pos: usize) -> Result<()> {
+    let mut iter = buf.iter().copied();
+    if let Some(index) = iter.position(|v| v != expected) {
+        let value = buf[index];
+        let msg = format!(
+            "Expected `{}` to be filled with {:02X}, but byte {} was {:02X} (at {})",
+            name,
+            expected,
+            index,
+            value,
+            pos + index
+        );
+        Err(AssertionError::at(name, pos + index, msg))
     } else {
         Ok(())
     }
@@ -227,7 +417,7 @@ where
             "Expected `{}` to be 0 or 1, but was {} (at {})",
             name, v, pos
         );
-        AssertionError(msg)
+        AssertionError::at(name, pos, msg)
     })
 }
 
@@ -242,7 +432,7 @@ where
             "Expected `{}` to have valid flags, but was {} (at {})",
             name, v, pos
         );
-        AssertionError(msg)
+        AssertionError::at(name, pos, msg)
     })
 }
 
@@ -258,12 +448,27 @@ where
             "Expected `{}` to be {}, but was {} (at {})",
             name, discriminants, v, pos
         );
-        AssertionError(msg)
+        AssertionError::at(name, pos, msg)
     })
 }
 
 #[macro_export]
 macro_rules! assert_that {
+    ($name:expr, approx $($actual:tt).+ == $expected:tt (epsilon $epsilon:expr), $pos:expr) => {
+        $crate::assert::is_approx_equal_to($name, $expected, $($actual).+, $epsilon, $pos)
+    };
+    ($name:expr, approx $($actual:tt).+ == $expected:expr, $pos:expr) => {
+        $crate::assert::is_approx_equal_to(
+            $name,
+            $expected,
+            $($actual).+,
+            $crate::assert::APPROX_EPSILON,
+            $pos,
+        )
+    };
+    ($name:expr, version $($actual:tt).+ == $expected:expr, $pos:expr) => {
+        $crate::assert::is_version($name, $expected, $($actual).+, $pos)
+    };
     ($name:expr, $expected_min:tt <= $($actual:tt).+ <= $expected_max:expr, $pos:expr) => {
         $crate::assert::is_between($name, &$expected_min, &$expected_max, &$($actual).+, $pos)
     };
@@ -297,6 +502,9 @@ macro_rules! assert_that {
     ($name:expr, $($actual:tt).+ in ($start:literal..$end:literal), $pos:expr) => {
         $crate::assert::is_in_range($name, $start, $end, $($actual).+, $pos)
     };
+    ($name:expr, $($actual:tt).+ in [$($allowed:expr),+ $(,)?], $pos:expr) => {
+        $crate::assert::is_one_of($name, &[$($allowed),+], &$($actual).+, $pos)
+    };
     ($name:expr, $($actual:tt).+ in $haystack:expr, $pos:expr) => {
         $crate::assert::is_in_slice($name, &$haystack, &$($actual).+, $pos)
     };
@@ -309,6 +517,9 @@ macro_rules! assert_that {
     ($name:expr, zero $actual:expr, $pos:expr) => {
         $crate::assert::is_all_zero($name, &$actual, $pos)
     };
+    ($name:expr, all $expected:literal in $actual:expr, $pos:expr) => {
+        $crate::assert::is_all_eq($name, &$actual, $expected, $pos)
+    };
     ($name:expr, flags $actual:expr, $pos:expr) => {
         $crate::assert::is_bitflags($name, $actual, $pos)
     };
@@ -321,7 +532,7 @@ macro_rules! assert_with_msg {
     };
     ($msg:expr) => {
         $crate::Error::Assert(
-            $crate::assert::AssertionError($msg)
+            $crate::assert::AssertionError::new($msg)
         )
     };
     ($($arg:tt)*) => {
@@ -334,7 +545,7 @@ macro_rules! assert_len {
     ($ty:ty, $value:expr, $name:literal) => {{
         let value: usize = $value;
         <$ty>::try_from(value).map_err(|_e| {
-            $crate::Error::Assert($crate::assert::AssertionError(format!(
+            $crate::Error::Assert($crate::assert::AssertionError::new(format!(
                 "`{}` must be <= {max}, but was {value}",
                 $name,
                 max = <$ty>::MAX,
@@ -345,7 +556,7 @@ macro_rules! assert_len {
     ($ty:ty, $value:expr, $name:literal, $(arg:expr),+ $(,)?) => {{
         let value: usize = $value;
         <$ty>::try_from(value).map_err(|_e| {
-            $crate::Error::Assert($crate::assert::AssertionError(format!(
+            $crate::Error::Assert($crate::assert::AssertionError::new(format!(
                 concat!("`", $name, "` must be <= {max}, but was {value}"),
                 $name,
                 $($arg,)+
@@ -9,6 +9,39 @@ fn is_equal_to() {
     );
 }
 
+#[test]
+fn is_approx_equal_to() {
+    let expected = 1.0f32 / 3.0;
+    // off by one ULP, e.g. from a different rounding of the reciprocal
+    let ident = f32::from_bits(expected.to_bits() + 1);
+    assert!(assert_that!("foo", ident == expected, 0).is_err());
+    assert_that!("foo", approx ident == expected, 0).unwrap();
+
+    let ident = 0.0f32;
+    assert_that!("foo", approx ident == 0.0, 0).unwrap();
+    let ident = 0.5f32;
+    let err = assert_that!("foo", approx ident == expected, 0).unwrap_err();
+    assert_eq!(
+        format!("{:#?}", err),
+        "Expected `foo` ~= 0.33333334, but was 0.5 (at 0)"
+    );
+}
+
+#[test]
+fn is_approx_equal_to_epsilon() {
+    let ident = 1.0f32;
+    assert_that!("foo", approx ident == 1.0 (epsilon 0.0), 0).unwrap();
+    let ident = 1.005f32;
+    assert_that!("foo", approx ident == 1.0 (epsilon 0.01), 0).unwrap();
+    let err = assert_that!("foo", approx ident == 1.0 (epsilon 0.001), 0).unwrap_err();
+    assert_eq!(
+        format!("{:#?}", err),
+        "Expected `foo` ~= 1.0, but was 1.005 (at 0)"
+    );
+    assert_eq!(err.name(), Some("foo"));
+    assert_eq!(err.pos(), Some(0));
+}
+
 #[test]
 fn is_not_equal_to() {
     let ident = 1;
@@ -75,6 +108,33 @@ fn is_between() {
     );
 }
 
+#[test]
+fn is_one_of() {
+    let ident = 3;
+    assert_that!("foo", ident in [0, 1, 3], 0).unwrap();
+    assert_that!("foo", ident in [3], 0).unwrap();
+    let err = assert_that!("foo", ident in [0, 1, 2], 42).unwrap_err();
+    assert_eq!(
+        format!("{:#?}", err),
+        "Expected `foo` to be one of [0, 1, 2], but was 3 (at 42)"
+    );
+    assert_eq!(err.name(), Some("foo"));
+    assert_eq!(err.pos(), Some(42));
+}
+
+#[test]
+fn is_one_of_field() {
+    struct Info {
+        state: u32,
+    }
+    let info = Info { state: 4 };
+    let err = assert_that!("field 28", info.state in [0u32, 1, 2, 3,], 28).unwrap_err();
+    assert_eq!(
+        format!("{:#?}", err),
+        "Expected `field 28` to be one of [0, 1, 2, 3], but was 4 (at 28)"
+    );
+}
+
 #[test]
 fn all_zero_index() {
     let err = assert_that!("foo", zero[3], 42).unwrap_err();
@@ -95,3 +155,100 @@ fn all_zero_index() {
         "Expected `foo` to be zero, but byte 1 was FF (at 43)"
     );
 }
+
+#[test]
+fn all_eq_uniform() {
+    let fill = [0xFFu8; 16];
+    assert_that!("foo", all 0xFF in fill, 42).unwrap();
+    assert_that!("foo", all 0xFF in [], 42).unwrap();
+}
+
+#[test]
+fn all_eq_index() {
+    let mut fill = [0xFFu8; 16];
+    fill[5] = 0xFE;
+    let err = assert_that!("foo", all 0xFF in fill, 42).unwrap_err();
+    assert_eq!(err.name(), Some("foo"));
+    assert_eq!(err.pos(), Some(47));
+    assert_eq!(
+        err.message(),
+        "Expected `foo` to be filled with FF, but byte 5 was FE (at 47)"
+    );
+}
+
+#[test]
+fn field_and_pos() {
+    let ident = 1;
+    let err = assert_that!("foo", ident == 2, 42).unwrap_err();
+    assert_eq!(err.name(), Some("foo"));
+    assert_eq!(err.pos(), Some(42));
+    assert_eq!(err.message(), "Expected `foo` == 2, but was 1 (at 42)");
+
+    let err = assert_that!("foo", zero [0, 3], 42).unwrap_err();
+    assert_eq!(err.name(), Some("foo"));
+    assert_eq!(err.pos(), Some(43));
+}
+
+#[test]
+fn error_kinds() {
+    use super::AssertionKind;
+    use crate::{Error, ErrorKind};
+
+    let ident = 1;
+    let err = assert_that!("foo", ident == 2, 42).unwrap_err();
+    assert_eq!(err.kind(), AssertionKind::Value);
+    assert_eq!(Error::from(err).kind(), ErrorKind::Assert);
+
+    let version = 3u32;
+    assert_that!("version", version version == 3, 0).unwrap();
+    let err = assert_that!("version", version version == 4, 0).unwrap_err();
+    // the message is the same as for an equality assertion
+    assert_eq!(err.message(), "Expected `version` == 4, but was 3 (at 0)");
+    assert_eq!(err.kind(), AssertionKind::UnsupportedVersion(3));
+    assert_eq!(Error::from(err).kind(), ErrorKind::UnsupportedVersion(3));
+
+    let err = super::assert_utf8("foo", 0, || {
+        Err::<(), _>(mech3ax_types::ConversionError::Unterminated)
+    })
+    .unwrap_err();
+    assert_eq!(err.kind(), AssertionKind::Utf8);
+    assert_eq!(Error::from(err).kind(), ErrorKind::Utf8);
+
+    let err = Error::from(std::io::Error::from(std::io::ErrorKind::UnexpectedEof));
+    assert_eq!(err.kind(), ErrorKind::Io);
+}
+
+#[test]
+fn conversion_err_pos() {
+    use mech3ax_types::ConversionError;
+
+    let err = super::format_conversion_err("foo", 42, ConversionError::NonAscii(3, Vec::new()));
+    assert_eq!(err.name(), Some("foo"));
+    assert_eq!(err.pos(), Some(45));
+}
+
+#[test]
+fn utf8_err_has_raw_bytes() {
+    use mech3ax_types::Ascii;
+
+    let name: Ascii<8> = Ascii::new(b"ab\xFFd\0\0\0\0");
+    let err = super::assert_utf8("node name", 100, || name.to_str_padded()).unwrap_err();
+    assert_eq!(err.name(), Some("node name"));
+    assert_eq!(err.pos(), Some(102));
+    assert_eq!(err.raw(), Some(&b"ab\xFFd"[..]));
+    assert_eq!(
+        err.message(),
+        "Expected `node name` to be a valid string, but was 61 62 FF 64 (at 102)"
+    );
+}
+
+#[test]
+fn msg_has_no_field_or_pos() {
+    let err = match assert_with_msg!("bar {}", 1) {
+        crate::Error::Assert(err) => err,
+        other => panic!("unexpected error {:?}", other),
+    };
+    assert_eq!(err.name(), None);
+    assert_eq!(err.pos(), None);
+    assert_eq!(format!("{}", err), "bar 1");
+}
@@ -0,0 +1,123 @@
+//! The game files `unzbd all` extracts, and `rezbd all` repacks.
+use crate::GameType;
+use std::io::Result;
+use std::path::{Path, PathBuf};
+
+/// The kinds of game files, in the order they are repacked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Asset {
+    Sounds,
+    Interp,
+    Reader,
+    Messages,
+    Textures,
+    Motion,
+    Mechlib,
+    Gamez,
+    Anim,
+    Zmap,
+}
+
+impl Asset {
+    /// Classify a file in the game directory by name, if the game has this
+    /// kind of file.
+    pub fn from_game_file(file_name: &str, game: GameType) -> Option<Self> {
+        Self::from_file_name(file_name, game, Self::game_extension)
+    }
+
+    /// Classify an extracted file by name, if the game has this kind of
+    /// file.
+    ///
+    /// Extracted files keep the name of the game file, but archives are ZIP
+    /// files and the rest are JSON files.
+    pub fn from_extracted_file(file_name: &str, game: GameType) -> Option<Self> {
+        Self::from_file_name(file_name, game, Self::extracted_extension)
+    }
+
+    /// The extension of the file in the game directory.
+    pub fn game_extension(self) -> &'static str {
+        match self {
+            Self::Messages => "dll",
+            Self::Zmap => "zmap",
+            _ => "zbd",
+        }
+    }
+
+    /// The extension of the extracted file.
+    pub fn extracted_extension(self) -> &'static str {
+        match self {
+            Self::Interp | Self::Messages | Self::Zmap => "json",
+            _ => "zip",
+        }
+    }
+
+    fn from_file_name(
+        file_name: &str,
+        game: GameType,
+        extension: fn(Self) -> &'static str,
+    ) -> Option<Self> {
+        let name = file_name.to_ascii_lowercase();
+        let (stem, ext) = name.rsplit_once('.')?;
+        let asset = Self::from_stem(stem)?;
+        (ext == extension(asset) && asset.is_supported(game)).then_some(asset)
+    }
+
+    fn from_stem(stem: &str) -> Option<Self> {
+        let asset = match stem {
+            "interp" => Self::Interp,
+            "mech3msg" | "messages" | "strings" => Self::Messages,
+            "motion" => Self::Motion,
+            "mechlib" => Self::Mechlib,
+            // CS also has the planes, which is read like a gamez file
+            "gamez" | "planes" => Self::Gamez,
+            "anim" => Self::Anim,
+            "zrdr" => Self::Reader,
+            "rimage" => Self::Textures,
+            _ if stem.starts_with("sounds") => Self::Sounds,
+            _ if stem.starts_with("reader") => Self::Reader,
+            _ if stem.starts_with("rmechtex")
+                || stem.starts_with("rtexture")
+                || stem.starts_with("texture") =>
+            {
+                Self::Textures
+            }
+            // zmaps are named after the mission, e.g. `m1.zmap`
+            _ if stem
+                .strip_prefix('m')
+                .is_some_and(|num| !num.is_empty() && num.bytes().all(|b| b.is_ascii_digit())) =>
+            {
+                Self::Zmap
+            }
+            _ => return None,
+        };
+        Some(asset)
+    }
+
+    fn is_supported(self, game: GameType) -> bool {
+        match self {
+            Self::Motion | Self::Mechlib => matches!(game, GameType::MW | GameType::PM),
+            Self::Anim => matches!(game, GameType::MW),
+            Self::Zmap => matches!(game, GameType::RC),
+            _ => true,
+        }
+    }
+}
+
+/// Recursively find all files in the directory, sorted by path.
+pub fn discover(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    let mut paths = std::fs::read_dir(dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>>>()?;
+    paths.sort();
+    for path in paths {
+        if path.is_dir() {
+            discover(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,84 @@
+use super::Asset;
+use crate::GameType;
+
+#[test]
+fn from_game_file_known_files() {
+    let classify = |name| Asset::from_game_file(name, GameType::MW);
+    assert_eq!(classify("gamez.zbd"), Some(Asset::Gamez));
+    assert_eq!(classify("GAMEZ.ZBD"), Some(Asset::Gamez));
+    assert_eq!(classify("interp.zbd"), Some(Asset::Interp));
+    assert_eq!(classify("sounds.zbd"), Some(Asset::Sounds));
+    assert_eq!(classify("soundsL.zbd"), Some(Asset::Sounds));
+    assert_eq!(classify("reader.zbd"), Some(Asset::Reader));
+    assert_eq!(classify("zrdr.zbd"), Some(Asset::Reader));
+    assert_eq!(classify("rimage.zbd"), Some(Asset::Textures));
+    assert_eq!(classify("rmechtex16.zbd"), Some(Asset::Textures));
+    assert_eq!(classify("texture.zbd"), Some(Asset::Textures));
+    assert_eq!(classify("Mech3Msg.dll"), Some(Asset::Messages));
+    assert_eq!(classify("other.zbd"), None);
+    assert_eq!(classify("gamez.zip"), None);
+    assert_eq!(classify("readme.txt"), None);
+}
+
+#[test]
+fn from_extracted_file_known_files() {
+    let classify = |name| Asset::from_extracted_file(name, GameType::MW);
+    assert_eq!(classify("gamez.zip"), Some(Asset::Gamez));
+    assert_eq!(classify("GAMEZ.ZIP"), Some(Asset::Gamez));
+    assert_eq!(classify("soundsL.zip"), Some(Asset::Sounds));
+    assert_eq!(classify("zrdr.zip"), Some(Asset::Reader));
+    assert_eq!(classify("rmechtex16.zip"), Some(Asset::Textures));
+    assert_eq!(classify("interp.json"), Some(Asset::Interp));
+    assert_eq!(classify("Mech3Msg.json"), Some(Asset::Messages));
+    assert_eq!(classify("interp.zip"), None);
+    assert_eq!(classify("gamez.json"), None);
+    assert_eq!(classify("other.zip"), None);
+    assert_eq!(classify("readme.txt"), None);
+}
+
+#[test]
+fn classify_cs_files() {
+    let classify = |name| Asset::from_game_file(name, GameType::CS);
+    assert_eq!(classify("strings.dll"), Some(Asset::Messages));
+    assert_eq!(classify("planes.zbd"), Some(Asset::Gamez));
+    assert_eq!(classify("gamez.zbd"), Some(Asset::Gamez));
+    assert_eq!(
+        Asset::from_extracted_file("strings.json", GameType::CS),
+        Some(Asset::Messages)
+    );
+    assert_eq!(
+        Asset::from_extracted_file("planes.zip", GameType::CS),
+        Some(Asset::Gamez)
+    );
+}
+
+#[test]
+fn classify_skips_unsupported_files() {
+    assert_eq!(
+        Asset::from_game_file("anim.zbd", GameType::MW),
+        Some(Asset::Anim)
+    );
+    assert_eq!(Asset::from_game_file("anim.zbd", GameType::PM), None);
+    assert_eq!(Asset::from_game_file("motion.zbd", GameType::RC), None);
+    assert_eq!(Asset::from_extracted_file("anim.zip", GameType::PM), None);
+    assert_eq!(Asset::from_extracted_file("motion.zip", GameType::RC), None);
+}
+
+#[test]
+fn classify_zmaps_by_mission_name() {
+    assert_eq!(
+        Asset::from_game_file("m1.zmap", GameType::RC),
+        Some(Asset::Zmap)
+    );
+    assert_eq!(Asset::from_game_file("m1.zmap", GameType::MW), None);
+    assert_eq!(
+        Asset::from_extracted_file("M12.json", GameType::RC),
+        Some(Asset::Zmap)
+    );
+    assert_eq!(Asset::from_extracted_file("m1.json", GameType::MW), None);
+    // only the names `unzbd` writes zmaps to, not any JSON file
+    assert_eq!(Asset::from_extracted_file("m.json", GameType::RC), None);
+    assert_eq!(Asset::from_extracted_file("m1a.json", GameType::RC), None);
+    assert_eq!(Asset::from_extracted_file("notes.json", GameType::RC), None);
+    assert_eq!(Asset::from_extracted_file("m1.zmap", GameType::RC), None);
+}
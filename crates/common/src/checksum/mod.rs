@@ -0,0 +1,20 @@
+use sha2::{Digest as _, Sha256};
+use std::fs::File;
+use std::io::Result;
+use std::path::Path;
+
+/// The SHA-256 of the data, as lowercase hex.
+pub fn sha256_hex(data: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(data))
+}
+
+/// The SHA-256 of the file's contents, as lowercase hex.
+pub fn sha256_file(path: impl AsRef<Path>) -> Result<String> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,14 @@
+use super::{sha256_file, sha256_hex};
+
+#[test]
+fn sha256_matches_known_digest() {
+    // from FIPS 180-2
+    let expected = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+    assert_eq!(sha256_hex(b"abc"), expected);
+
+    let path = std::env::temp_dir().join(format!("mech3ax-sha256-{}", std::process::id()));
+    std::fs::write(&path, b"abc").unwrap();
+    let actual = sha256_file(&path);
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(actual.unwrap(), expected);
+}
@@ -1,4 +1,4 @@
-use crate::assert::AssertionError;
+use crate::assert::{AssertionError, AssertionKind};
 use std::fmt;
 
 #[derive(Debug, Clone)]
@@ -15,6 +15,12 @@ pub enum PeError {
     },
     TryFrom(std::num::TryFromIntError),
     ReadOutOfBounds(usize),
+    SectionOutOfBounds {
+        section: String,
+        start: usize,
+        len: usize,
+        size: usize,
+    },
 }
 
 impl fmt::Display for PeError {
@@ -40,6 +46,15 @@ impl fmt::Display for PeError {
             Self::ReadOutOfBounds(offset) => {
                 write!(f, "Offset {offset} would cause out-of-bounds read")
             }
+            Self::SectionOutOfBounds {
+                section,
+                start,
+                len,
+                size,
+            } => write!(
+                f,
+                "section {section} data at {start} with length {len} exceeds file size {size}"
+            ),
         }
     }
 }
@@ -48,7 +63,10 @@ impl std::error::Error for PeError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
             Self::TryFrom(e) => Some(e),
-            Self::Underflow { .. } | Self::Overflow { .. } | Self::ReadOutOfBounds(_) => None,
+            Self::Underflow { .. }
+            | Self::Overflow { .. }
+            | Self::ReadOutOfBounds(_)
+            | Self::SectionOutOfBounds { .. } => None,
         }
     }
 }
@@ -60,6 +78,20 @@ impl From<std::num::TryFromIntError> for PeError {
     }
 }
 
+/// The category of an error, so callers can react to an error without
+/// parsing the message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    Io,
+    Assert,
+    /// The data's version is not supported (the actual version).
+    UnsupportedVersion(u32),
+    /// A string could not be converted.
+    Utf8,
+    Pe,
+    Image,
+}
+
 #[derive(Debug)]
 pub enum Error {
     IO(std::io::Error),
@@ -76,6 +108,38 @@ pub enum Error {
     },
 }
 
+impl Error {
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::IO(_) => ErrorKind::Io,
+            Self::Assert(e) => match e.kind() {
+                AssertionKind::Value => ErrorKind::Assert,
+                AssertionKind::UnsupportedVersion(version) => {
+                    ErrorKind::UnsupportedVersion(version)
+                }
+                AssertionKind::Utf8 => ErrorKind::Utf8,
+            },
+            Self::PeError(_) => ErrorKind::Pe,
+            Self::InvalidAlphaChannel { .. } | Self::InvalidImageFormat { .. } => ErrorKind::Image,
+        }
+    }
+
+    /// Add the index of the item that failed to the message, for errors
+    /// while reading an array of `count` items. Only assertion and I/O errors
+    /// have a message to add it to, other errors are returned as-is.
+    pub fn in_item(self, index: u32, count: u32) -> Self {
+        let context = format!("item {} of {}", index, count);
+        match self {
+            Self::Assert(e) => Self::Assert(e.with_context(&context)),
+            Self::IO(e) => Self::IO(std::io::Error::new(
+                e.kind(),
+                format!("{} ({})", e, context),
+            )),
+            other => other,
+        }
+    }
+}
+
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -1,8 +1,12 @@
+use crate::assert::format_hex;
 use crate::assert_with_msg;
 use log::trace;
-use mech3ax_types::{u32_to_usize, AsBytes};
+use mech3ax_types::{u32_to_usize, LeStruct};
 use std::io::{Read, Result, Seek, SeekFrom, Write};
 
+/// The maximum number of trailing bytes shown when `assert_end` fails.
+const ASSERT_END_DUMP_LEN: u64 = 64;
+
 #[cfg(not(target_endian = "little"))]
 compile_error!("only little-endian architectures are supported");
 
@@ -10,6 +14,8 @@ pub struct CountingReader<R: Read> {
     inner: R,
     pub offset: usize,
     pub prev: usize,
+    #[cfg(feature = "checkpoints")]
+    checkpoints: Vec<(usize, String)>,
 }
 
 impl<R: Read> CountingReader<R> {
@@ -19,9 +25,46 @@ impl<R: Read> CountingReader<R> {
             inner: read,
             offset: 0,
             prev: 0,
+            #[cfg(feature = "checkpoints")]
+            checkpoints: Vec::new(),
         }
     }
 
+    /// Record the current offset with a label, to find where the offsets
+    /// start to drift when reading fails.
+    ///
+    /// This does nothing unless the `checkpoints` feature is enabled.
+    #[inline]
+    pub fn checkpoint(&mut self, label: &str) {
+        #[cfg(feature = "checkpoints")]
+        self.checkpoints.push((self.offset, label.to_string()));
+        #[cfg(not(feature = "checkpoints"))]
+        let _ = label;
+    }
+
+    /// The recorded offsets and labels, in the order they were recorded.
+    #[cfg(feature = "checkpoints")]
+    pub fn checkpoints(&self) -> &[(usize, String)] {
+        &self.checkpoints
+    }
+
+    /// The recorded offsets and labels, which are always empty without the
+    /// `checkpoints` feature.
+    #[cfg(not(feature = "checkpoints"))]
+    pub fn checkpoints(&self) -> &[(usize, String)] {
+        &[]
+    }
+
+    /// The recorded checkpoints, one per line, or an empty string if there
+    /// are none.
+    pub fn dump_checkpoints(&self) -> String {
+        self.checkpoints()
+            .iter()
+            .map(|(offset, label)| format!("{} (at {})", label, offset))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
     pub fn get_mut(&mut self) -> &mut R {
         &mut self.inner
     }
@@ -75,7 +118,7 @@ impl<R: Read> CountingReader<R> {
     }
 
     #[inline]
-    pub fn read_struct<S: AsBytes>(&mut self) -> Result<S> {
+    pub fn read_struct<S: LeStruct>(&mut self) -> Result<S> {
         let mut s = S::zeroed();
         let buf = s.as_bytes_mut();
         let len = buf.len();
@@ -85,18 +128,65 @@ impl<R: Read> CountingReader<R> {
     }
 
     #[inline]
-    pub fn read_struct_no_log<S: AsBytes>(&mut self) -> Result<S> {
+    pub fn read_struct_no_log<S: LeStruct>(&mut self) -> Result<S> {
         let mut s = S::zeroed();
         let buf = s.as_bytes_mut();
         self.read_exact(buf)?;
         Ok(s)
     }
 
+    /// Read an optional trailing struct, which is either present in full, or
+    /// absent because the data ends.
+    ///
+    /// Returns `None` if there is no data left, but fails if the data ends
+    /// part way through the struct, so a truncated struct isn't mistaken for
+    /// an absent one.
+    pub fn read_struct_if_present<S: LeStruct>(&mut self) -> Result<Option<S>> {
+        let mut s = S::zeroed();
+        let buf = s.as_bytes_mut();
+        let len = buf.len();
+        let mut filled = 0;
+        while filled < len {
+            match self.inner.read(&mut buf[filled..]) {
+                Ok(0) => break,
+                Ok(n) => filled += n,
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        if filled == 0 && len > 0 {
+            trace!("no trailing struct (at {})", self.offset);
+            return Ok(None);
+        }
+        if filled < len {
+            return Err(std::io::ErrorKind::UnexpectedEof.into());
+        }
+        self.prev = self.offset;
+        self.offset += len;
+        trace!("{:#?} (len: {}, at {})", s, len, self.prev);
+        Ok(Some(s))
+    }
+
+    /// Read `len` bytes, where the length comes from the data.
+    ///
+    /// Unlike `read_exact` into a buffer of `len` bytes, the buffer only grows
+    /// as the data is read. So a corrupt length fails when the data runs out,
+    /// instead of allocating a huge buffer up front.
+    pub fn read_bytes(&mut self, len: usize) -> Result<Vec<u8>> {
+        let mut buf = Vec::new();
+        (&mut self.inner).take(len as u64).read_to_end(&mut buf)?;
+        if buf.len() < len {
+            return Err(std::io::ErrorKind::UnexpectedEof.into());
+        }
+        self.prev = self.offset;
+        self.offset += len;
+        Ok(buf)
+    }
+
     pub fn read_string(&mut self) -> crate::Result<String> {
         let offset = self.offset;
         let len = u32_to_usize(self.read_u32()?);
-        let mut buf = vec![0u8; len];
-        self.read_exact(&mut buf)?;
+        let buf = self.read_bytes(len)?;
         trace!("`{}` (len: {}, at {})", buf.escape_ascii(), len, offset);
         if !buf.is_ascii() {
             // is_ascii is optimised, only try and find the invalid character after it
@@ -120,10 +210,25 @@ impl<R: Read> CountingReader<R> {
         let mut buf = [0; 1];
         match self.inner.read(&mut buf)? {
             0 => Ok(()),
-            _ => Err(assert_with_msg!(
-                "Expected all data to be read (at {})",
-                self.offset
-            )),
+            _ => {
+                // include the first few remaining bytes, which is very useful
+                // when a parser stops early
+                let mut dump = buf.to_vec();
+                (&mut self.inner)
+                    .take(ASSERT_END_DUMP_LEN - 1)
+                    .read_to_end(&mut dump)?;
+                let more = std::io::copy(&mut self.inner, &mut std::io::sink())?;
+                let hex = format_hex(&dump);
+                let remaining = dump.len() as u64 + more;
+                let ellipsis = if more > 0 { " ..." } else { "" };
+                Err(assert_with_msg!(
+                    "Expected all data to be read (at {}), but {} bytes remain: {}{}",
+                    self.offset,
+                    remaining,
+                    hex,
+                    ellipsis
+                ))
+            }
         }
     }
 }
@@ -138,6 +243,66 @@ impl<R: Read + Seek> CountingReader<R> {
         self.offset = offset;
         Ok(offset)
     }
+
+    /// The number of bytes after the current position.
+    pub fn remaining(&mut self) -> crate::Result<usize> {
+        let current = self.inner.stream_position()?;
+        let end = self.inner.seek(SeekFrom::End(0))?;
+        self.inner.seek(SeekFrom::Start(current))?;
+        let remaining = end.saturating_sub(current);
+        remaining
+            .try_into()
+            .map_err(|_e| assert_with_msg!("File is bigger than 4 GIB"))
+    }
+
+    /// Assert that `count` items of `size` bytes each fit in the remaining
+    /// data, for a count read from the data at `pos`.
+    ///
+    /// Check this before allocating or reading the items, so a corrupt count
+    /// fails instead of allocating memory for data that doesn't exist.
+    pub fn assert_count(
+        &mut self,
+        name: &str,
+        count: u32,
+        size: usize,
+        pos: usize,
+    ) -> crate::Result<usize> {
+        let count = u32_to_usize(count);
+        let remaining = self.remaining()?;
+        let fits = count
+            .checked_mul(size)
+            .is_some_and(|total| total <= remaining);
+        if !fits {
+            return Err(assert_with_msg!(
+                "Expected `{}` of {} items of {} bytes to fit in the remaining {} bytes (at {})",
+                name,
+                count,
+                size,
+                remaining,
+                pos
+            ));
+        }
+        Ok(count)
+    }
+}
+
+/// Read `count` items, by calling the closure with the reader and the index
+/// of each item.
+///
+/// If an item fails to read, the error says which item (e.g. `item 2 of 5`,
+/// zero-based).
+pub fn read_array<R, T, F>(
+    read: &mut CountingReader<R>,
+    count: u32,
+    mut f: F,
+) -> crate::Result<Vec<T>>
+where
+    R: Read,
+    F: FnMut(&mut CountingReader<R>, u32) -> crate::Result<T>,
+{
+    (0..count)
+        .map(|index| f(read, index).map_err(|e| e.in_item(index, count)))
+        .collect()
 }
 
 pub struct CountingWriter<W: Write> {
@@ -196,14 +361,14 @@ impl<W: Write> CountingWriter<W> {
     }
 
     #[inline]
-    pub fn write_struct<S: AsBytes>(&mut self, value: &S) -> Result<()> {
+    pub fn write_struct<S: LeStruct>(&mut self, value: &S) -> Result<()> {
         let buf = value.as_bytes();
         trace!("{:#?} (len: {}, at {})", value, buf.len(), self.offset);
         self.write_all(buf)
     }
 
     #[inline]
-    pub fn write_struct_no_log<S: AsBytes>(&mut self, value: &S) -> Result<()> {
+    pub fn write_struct_no_log<S: LeStruct>(&mut self, value: &S) -> Result<()> {
         let buf = value.as_bytes();
         self.write_all(buf)
     }
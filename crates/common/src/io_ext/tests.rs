@@ -1,4 +1,5 @@
 use super::*;
+use crate::assert_that;
 use bytemuck::{AnyBitPattern, NoUninit};
 use mech3ax_types::Ascii;
 use std::io::Cursor;
@@ -88,12 +89,12 @@ struct TestStruct {
     name: Ascii<32>,
     int: u32,
 }
-mech3ax_types::impl_as_bytes!(TestStruct, 36);
+mech3ax_types::impl_as_bytes!(TestStruct, 36, LeStruct);
 
 #[test]
 fn struct_roundtrip() {
     let expected = TestStruct {
-        name: Ascii::from_str_padded("Hello World"),
+        name: Ascii::from_str_padded("Hello World").unwrap(),
         int: 3735928559,
     };
 
@@ -111,6 +112,46 @@ fn struct_roundtrip() {
     assert_eq!(expected, actual);
 }
 
+fn test_struct_bytes() -> (TestStruct, Vec<u8>) {
+    let expected = TestStruct {
+        name: Ascii::from_str_padded("Trailing").unwrap(),
+        int: 42,
+    };
+    let mut writer = CountingWriter::new(Vec::new(), 0);
+    writer.write_struct(&expected).unwrap();
+    (expected, writer.into_inner())
+}
+
+#[test]
+fn read_struct_if_present_present() {
+    let (expected, data) = test_struct_bytes();
+    let mut reader = CountingReader::new(Cursor::new(data));
+    let actual: Option<TestStruct> = reader.read_struct_if_present().unwrap();
+    assert_eq!(actual, Some(expected));
+    assert_eq!(reader.offset, 36);
+    reader.assert_end().unwrap();
+}
+
+#[test]
+fn read_struct_if_present_absent() {
+    let (_, data) = test_struct_bytes();
+    let mut reader = CountingReader::new(Cursor::new(data));
+    let _: TestStruct = reader.read_struct().unwrap();
+    let actual: Option<TestStruct> = reader.read_struct_if_present().unwrap();
+    assert_eq!(actual, None);
+    assert_eq!(reader.offset, 36);
+}
+
+#[test]
+fn read_struct_if_present_partial() {
+    let (_, mut data) = test_struct_bytes();
+    data.truncate(20);
+    let mut reader = CountingReader::new(Cursor::new(data));
+    let err = reader.read_struct_if_present::<TestStruct>().unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+    assert_eq!(reader.offset, 0);
+}
+
 #[test]
 fn string_roundtrip() {
     let expected = "Hello World".to_owned();
@@ -124,3 +165,149 @@ fn string_roundtrip() {
     assert_eq!(expected, actual);
     assert_eq!(reader.offset, expected.len() + 4);
 }
+
+#[test]
+fn read_string_huge_length_fails() {
+    // a corrupt length must fail when the data runs out, instead of trying
+    // to allocate 4 GiB first
+    let mut data = u32::MAX.to_le_bytes().to_vec();
+    data.extend_from_slice(b"Hello World");
+    let mut reader = CountingReader::new(Cursor::new(data));
+    let err = reader.read_string().unwrap_err();
+    let crate::Error::IO(e) = err else {
+        panic!("expected an I/O error, got {:?}", err);
+    };
+    assert_eq!(e.kind(), std::io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn assert_count_fits() {
+    let mut reader = CountingReader::new(Cursor::new(vec![0u8; 16]));
+    reader.read_u32().unwrap();
+    assert_eq!(reader.assert_count("items", 3, 4, 0).unwrap(), 3);
+    // the position isn't changed
+    assert_eq!(reader.read_u32().unwrap(), 0);
+    assert_eq!(reader.offset, 8);
+}
+
+#[test]
+fn assert_count_too_large() {
+    let mut reader = CountingReader::new(Cursor::new(vec![0u8; 16]));
+    reader.read_u32().unwrap();
+    let err = reader.assert_count("items", 4, 4, 0).unwrap_err();
+    assert_eq!(
+        format!("{:?}", err),
+        "Assert(Expected `items` of 4 items of 4 bytes to fit in the remaining 12 bytes (at 0))"
+    );
+    // and doesn't overflow
+    assert!(reader
+        .assert_count("items", u32::MAX, usize::MAX, 0)
+        .is_err());
+}
+
+#[test]
+fn assert_end_ok() {
+    let mut reader = CountingReader::new(Cursor::new(vec![1, 2, 3, 4]));
+    reader.read_u32().unwrap();
+    reader.assert_end().unwrap();
+}
+
+#[test]
+fn assert_end_dumps_remaining() {
+    let mut reader = CountingReader::new(Cursor::new(vec![1, 2, 3, 4, 0xAB, 0xCD]));
+    reader.read_u32().unwrap();
+    let err = reader.assert_end().unwrap_err();
+    assert_eq!(
+        format!("{:?}", err),
+        "Assert(Expected all data to be read (at 4), but 2 bytes remain: AB CD)"
+    );
+}
+
+#[test]
+fn assert_end_dumps_at_most_64_bytes() {
+    let mut reader = CountingReader::new(Cursor::new(vec![0xFF; 100]));
+    let err = reader.assert_end().unwrap_err();
+    let msg = format!("{:?}", err);
+    assert!(msg.contains("(at 0), but 100 bytes remain: FF FF"));
+    assert!(msg.ends_with("FF ...)"));
+    assert_eq!(msg.matches("FF").count(), 64);
+}
+
+#[cfg(feature = "checkpoints")]
+#[test]
+fn checkpoints_are_recorded_in_order() {
+    let mut reader = CountingReader::new(Cursor::new(vec![0; 8]));
+    reader.checkpoint("header");
+    reader.read_u32().unwrap();
+    reader.checkpoint("data");
+    reader.read_u16().unwrap();
+    reader.checkpoint("end");
+
+    let expected = [
+        (0, "header".to_string()),
+        (4, "data".to_string()),
+        (6, "end".to_string()),
+    ];
+    assert_eq!(reader.checkpoints(), &expected[..]);
+    assert_eq!(
+        reader.dump_checkpoints(),
+        "header (at 0)\ndata (at 4)\nend (at 6)"
+    );
+}
+
+#[cfg(not(feature = "checkpoints"))]
+#[test]
+fn checkpoints_are_not_recorded_without_feature() {
+    let mut reader = CountingReader::new(Cursor::new(vec![0; 4]));
+    reader.checkpoint("header");
+    reader.read_u32().unwrap();
+    assert!(reader.checkpoints().is_empty());
+    assert_eq!(reader.dump_checkpoints(), "");
+}
+
+fn read_u32s(data: &[u32]) -> CountingReader<Cursor<Vec<u8>>> {
+    let buf = data.iter().flat_map(|value| value.to_le_bytes()).collect();
+    CountingReader::new(Cursor::new(buf))
+}
+
+#[test]
+fn read_array_passes_index() {
+    let mut reader = read_u32s(&[10, 20, 30]);
+    let items = read_array(&mut reader, 3, |read, index| Ok((index, read.read_u32()?))).unwrap();
+    assert_eq!(items, [(0, 10), (1, 20), (2, 30)]);
+    assert_eq!(reader.offset, 12);
+}
+
+#[test]
+fn read_array_failing_middle_item() {
+    let mut reader = read_u32s(&[0, 1, 0]);
+    let err = read_array(&mut reader, 3, |read, _index| {
+        let value = read.read_u32()?;
+        assert_that!("value", value == 0, read.prev)?;
+        Ok(value)
+    })
+    .unwrap_err();
+    let crate::Error::Assert(e) = err else {
+        panic!("expected an assertion error, got {:?}", err);
+    };
+    // the name and offset are kept
+    assert_eq!(e.name(), Some("value"));
+    assert_eq!(e.pos(), Some(4));
+    assert_eq!(
+        e.message(),
+        "Expected `value` == 0, but was 1 (at 4) (item 1 of 3)"
+    );
+    // the items after the failing item aren't read
+    assert_eq!(reader.offset, 8);
+}
+
+#[test]
+fn read_array_truncated_item() {
+    let mut reader = read_u32s(&[0, 0]);
+    let err = read_array(&mut reader, 3, |read, _index| Ok(read.read_u32()?)).unwrap_err();
+    let crate::Error::IO(e) = err else {
+        panic!("expected an I/O error, got {:?}", err);
+    };
+    assert_eq!(e.kind(), std::io::ErrorKind::UnexpectedEof);
+    assert!(e.to_string().ends_with("(item 2 of 3)"), "{}", e);
+}
@@ -1,11 +1,17 @@
 #![warn(clippy::all, clippy::cargo)]
 pub mod assert;
+pub mod asset;
+pub mod checksum;
 mod errors;
 pub mod io_ext;
 pub mod light;
+mod options;
 mod rename;
+#[cfg(feature = "test-utils")]
+pub mod test_utils;
 
-pub use errors::{Error, PeError, Result};
+pub use errors::{Error, ErrorKind, PeError, Result};
+pub use options::{ReadContext, ReadOptions, Warning, Warnings};
 pub use rename::Rename;
 use std::fmt;
 
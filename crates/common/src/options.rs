@@ -0,0 +1,138 @@
+use crate::assert::AssertionError;
+use std::fmt;
+
+/// Options for reading data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReadOptions {
+    /// Fail on values that are unexpected, but don't prevent reading the
+    /// data (e.g. unknown flag bits). Otherwise, they are collected as
+    /// warnings. Values that do prevent reading the data always fail.
+    pub strict: bool,
+}
+
+impl ReadOptions {
+    pub const STRICT: Self = Self { strict: true };
+    pub const LENIENT: Self = Self { strict: false };
+}
+
+impl Default for ReadOptions {
+    #[inline]
+    fn default() -> Self {
+        Self::STRICT
+    }
+}
+
+/// An unexpected value that was read anyway.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Warning {
+    pub offset: Option<usize>,
+    pub field: Option<String>,
+    pub message: String,
+}
+
+impl From<AssertionError> for Warning {
+    fn from(e: AssertionError) -> Self {
+        Self {
+            offset: e.pos(),
+            field: e.name().map(str::to_string),
+            message: e.message().to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// The warnings collected while reading data, in the order they were found.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Warnings(Vec<Warning>);
+
+impl Warnings {
+    #[inline]
+    pub fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    #[inline]
+    pub fn push(&mut self, warning: Warning) {
+        self.0.push(warning);
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    #[inline]
+    pub fn iter(&self) -> std::slice::Iter<'_, Warning> {
+        self.0.iter()
+    }
+
+    #[inline]
+    pub fn into_vec(self) -> Vec<Warning> {
+        self.0
+    }
+}
+
+impl<'a> IntoIterator for &'a Warnings {
+    type Item = &'a Warning;
+    type IntoIter = std::slice::Iter<'a, Warning>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+/// The state threaded through a read: the options, and the warnings
+/// collected so far.
+#[derive(Debug, Clone, Default)]
+pub struct ReadContext {
+    options: ReadOptions,
+    warnings: Warnings,
+}
+
+impl ReadContext {
+    #[inline]
+    pub fn new(options: ReadOptions) -> Self {
+        Self {
+            options,
+            warnings: Warnings::new(),
+        }
+    }
+
+    #[inline]
+    pub fn options(&self) -> ReadOptions {
+        self.options
+    }
+
+    #[inline]
+    pub fn warnings(&self) -> &Warnings {
+        &self.warnings
+    }
+
+    /// Return the assertion failure of an unexpected value when strict,
+    /// otherwise collect it as a warning.
+    pub fn check(&mut self, result: Result<(), AssertionError>) -> Result<(), AssertionError> {
+        match result {
+            Err(e) if !self.options.strict => {
+                self.warnings.push(e.into());
+                Ok(())
+            }
+            result => result,
+        }
+    }
+
+    #[inline]
+    pub fn into_warnings(self) -> Warnings {
+        self.warnings
+    }
+}
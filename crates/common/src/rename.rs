@@ -1,32 +1,82 @@
 use std::collections::HashSet;
 
+/// Characters that aren't allowed in file names on some platforms.
+const ILLEGAL_CHARS: &[char] = &['<', '>', ':', '"', '/', '\\', '|', '?', '*'];
+
+/// Replace characters that aren't allowed in file names with `_`. Trailing
+/// dots and spaces are also replaced, since Windows strips them.
+fn sanitize_file_name(name: &str) -> String {
+    let mut sanitized: String = name
+        .chars()
+        .map(|c| {
+            if c.is_control() || ILLEGAL_CHARS.contains(&c) {
+                '_'
+            } else {
+                c
+            }
+        })
+        .collect();
+    let trimmed = sanitized.trim_end_matches(['.', ' ']).len();
+    let trailing = sanitized.len() - trimmed;
+    sanitized.truncate(trimmed);
+    sanitized.extend(std::iter::repeat('_').take(trailing));
+    sanitized
+}
+
 #[derive(Debug)]
-#[repr(transparent)]
 pub struct Rename {
     seen: HashSet<String>,
+    file_names: bool,
 }
 
 impl Rename {
+    /// Rename duplicate names.
     #[inline]
     #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
         Self {
             seen: HashSet::new(),
+            file_names: false,
+        }
+    }
+
+    /// Rename duplicate names, and names that can't be used as file names on
+    /// every platform. Illegal characters are replaced, and names that only
+    /// differ by case are treated as duplicates, for case-insensitive file
+    /// systems.
+    #[inline]
+    pub fn file_names() -> Self {
+        Self {
+            seen: HashSet::new(),
+            file_names: true,
+        }
+    }
+
+    fn key(&self, name: &str) -> String {
+        if self.file_names {
+            name.to_lowercase()
+        } else {
+            name.to_string()
         }
     }
 
     pub fn insert(&mut self, name: &str) -> Option<String> {
-        if self.seen.insert(name.to_string()) {
-            return None;
+        let sanitized = if self.file_names {
+            sanitize_file_name(name)
+        } else {
+            name.to_string()
+        };
+        if self.seen.insert(self.key(&sanitized)) {
+            return (sanitized != name).then_some(sanitized);
         }
 
-        let info = name.rsplit_once('.');
+        let info = sanitized.rsplit_once('.');
         for index in 1usize.. {
             let rename = match info {
                 Some((stem, suffix)) => format!("{}-{}.{}", stem, index, suffix),
-                None => format!("{}-{}", name, index),
+                None => format!("{}-{}", sanitized, index),
             };
-            if self.seen.insert(rename.clone()) {
+            if self.seen.insert(self.key(&rename)) {
                 return Some(rename);
             }
         }
@@ -0,0 +1,58 @@
+//! Helpers for the tests of other crates.
+use std::fmt::Debug;
+use std::path::{Path, PathBuf};
+
+/// Assert that the data reads successfully, but every truncated prefix of it
+/// fails to read (and doesn't panic).
+pub fn assert_truncated_fails<T, E, F>(data: &[u8], read: F)
+where
+    E: Debug,
+    F: FnMut(&[u8]) -> Result<T, E>,
+{
+    assert_truncated_fails_except(data, &[], read);
+}
+
+/// Like `assert_truncated_fails`, but prefixes of the lengths given may also
+/// read successfully, for formats that can end early.
+pub fn assert_truncated_fails_except<T, E, F>(data: &[u8], valid: &[usize], mut read: F)
+where
+    E: Debug,
+    F: FnMut(&[u8]) -> Result<T, E>,
+{
+    if let Err(e) = read(data) {
+        panic!("read all {} bytes: {:?}", data.len(), e);
+    }
+    for len in 0..data.len() {
+        if valid.contains(&len) {
+            continue;
+        }
+        let result = read(&data[..len]);
+        assert!(result.is_err(), "read {} of {} bytes", len, data.len());
+    }
+}
+
+/// A directory under the system's temporary directory, which is removed when
+/// dropped, even if the test fails.
+#[derive(Debug)]
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    /// Create a new, empty directory, unique to the name and this process.
+    pub fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("mech3ax-{}-{}", name, std::process::id()));
+        // left over from an aborted run
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(&path).unwrap();
+        Self(path)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
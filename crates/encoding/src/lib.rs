@@ -18,5 +18,25 @@ pub fn windows1252_decode(bytes: &[u8]) -> Cow<str> {
     }
 }
 
+/// Encode a string as Windows-1252, returning the first character that cannot
+/// be represented on failure.
+pub fn windows1252_encode(s: &str) -> Result<Cow<[u8]>, char> {
+    if s.is_ascii() {
+        Ok(Cow::Borrowed(s.as_bytes()))
+    } else {
+        s.chars()
+            .map(|c| {
+                WINDOWS1252
+                    .iter()
+                    .position(|&m| m == c)
+                    // Cast safety: the table has 256 entries
+                    .map(|index| index as u8)
+                    .ok_or(c)
+            })
+            .collect::<Result<Vec<u8>, char>>()
+            .map(Cow::Owned)
+    }
+}
+
 #[cfg(test)]
 mod tests;
@@ -1,4 +1,4 @@
-use crate::windows1252_decode;
+use crate::{windows1252_decode, windows1252_encode};
 use std::borrow::Cow;
 
 #[test]
@@ -28,3 +28,31 @@ fn all_bytes() {
     let actual = windows1252_decode(&bytes);
     assert_eq!(expected, actual);
 }
+
+#[test]
+fn encode_ascii_is_borrowed() {
+    let actual = windows1252_encode("Hello, world!").unwrap();
+    assert_eq!(b"Hello, world!", &actual[..]);
+    assert!(matches!(actual, Cow::Borrowed(_)));
+}
+
+#[test]
+fn encode_cp1252_is_owned() {
+    let actual = windows1252_encode("Hellö, wörld!").unwrap();
+    assert_eq!(b"Hell\xf6, w\xf6rld!", &actual[..]);
+    assert!(matches!(actual, Cow::Owned(_)));
+}
+
+#[test]
+fn encode_all_bytes_round_trip() {
+    let bytes: Vec<_> = (u8::MIN..=u8::MAX).collect();
+    let decoded = windows1252_decode(&bytes);
+    let actual = windows1252_encode(&decoded).unwrap();
+    assert_eq!(&bytes[..], &actual[..]);
+}
+
+#[test]
+fn encode_unmappable() {
+    let err = windows1252_encode("Hello, 世界!").unwrap_err();
+    assert_eq!(err, '世');
+}
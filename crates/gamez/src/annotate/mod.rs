@@ -0,0 +1,214 @@
+//! Annotate raw bytes with the fields of a known structure, for reverse
+//! engineering.
+use crate::gamez::{cs, mw, pm, rc};
+use crate::mesh::common::LightC;
+use mech3ax_common::{assert_with_msg, GameType, Result};
+use mech3ax_types::{u32_to_usize, AsBytes};
+use std::fmt::Write as _;
+use FieldKind::*;
+
+/// How to interpret the bytes of a field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldKind {
+    U16,
+    U32,
+    F32,
+    Hex16,
+    Hex32,
+    Color,
+}
+
+impl FieldKind {
+    pub const fn size(self) -> usize {
+        match self {
+            Self::U16 | Self::Hex16 => 2,
+            Self::U32 | Self::F32 | Self::Hex32 => 4,
+            Self::Color => 12,
+        }
+    }
+
+    fn value(self, bytes: &[u8]) -> String {
+        let u16_at = |i: usize| u16::from_le_bytes([bytes[i], bytes[i + 1]]);
+        let u32_at =
+            |i: usize| u32::from_le_bytes([bytes[i], bytes[i + 1], bytes[i + 2], bytes[i + 3]]);
+        match self {
+            Self::U16 => format!("{}", u16_at(0)),
+            Self::U32 => format!("{}", u32_at(0)),
+            Self::F32 => format!("{:?}", f32::from_bits(u32_at(0))),
+            Self::Hex16 => format!("0x{:04X}", u16_at(0)),
+            Self::Hex32 => format!("0x{:08X}", u32_at(0)),
+            Self::Color => format!(
+                "({:?}, {:?}, {:?})",
+                f32::from_bits(u32_at(0)),
+                f32::from_bits(u32_at(4)),
+                f32::from_bits(u32_at(8)),
+            ),
+        }
+    }
+}
+
+/// The offset, name, and kind of a field in a structure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Field {
+    pub offset: usize,
+    pub name: &'static str,
+    pub kind: FieldKind,
+}
+
+const fn field(offset: usize, name: &'static str, kind: FieldKind) -> Field {
+    Field { offset, name, kind }
+}
+
+/// A structure with known fields.
+pub trait Annotate: AsBytes {
+    const NAME: &'static str;
+    /// The fields, in order. They must cover the whole structure.
+    const FIELDS: &'static [Field];
+}
+
+/// Print each field's offset, name, raw bytes, and interpreted value, one
+/// field per line.
+///
+/// Only the start of the bytes is annotated, any remaining bytes are
+/// ignored.
+pub fn annotate_struct<T: Annotate>(bytes: &[u8]) -> Result<String> {
+    let size = u32_to_usize(T::SIZE);
+    if bytes.len() < size {
+        return Err(assert_with_msg!(
+            "Expected at least {} bytes for `{}`, but was {}",
+            size,
+            T::NAME,
+            bytes.len()
+        ));
+    }
+    let name_width = T::FIELDS
+        .iter()
+        .map(|field| field.name.len())
+        .max()
+        .unwrap_or(0);
+    let hex_width = T::FIELDS
+        .iter()
+        .map(|field| field.kind.size() * 3 - 1)
+        .max()
+        .unwrap_or(0);
+
+    let mut out = String::new();
+    for field in T::FIELDS {
+        let raw = &bytes[field.offset..field.offset + field.kind.size()];
+        let hex = raw
+            .iter()
+            .map(|b| format!("{:02X}", b))
+            .collect::<Vec<_>>()
+            .join(" ");
+        // writing to a string can't fail
+        let _ = writeln!(
+            out,
+            "{:02} {:name_width$} {:hex_width$} {}",
+            field.offset,
+            field.name,
+            hex,
+            field.kind.value(raw),
+        );
+    }
+    Ok(out)
+}
+
+/// The structures that can be annotated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnnotateType {
+    /// The GameZ header of the game.
+    Header,
+    Light,
+}
+
+/// Annotate the bytes with the fields of the structure type, for the game.
+pub fn annotate(ty: AnnotateType, game: GameType, bytes: &[u8]) -> Result<String> {
+    match ty {
+        AnnotateType::Header => match game {
+            GameType::MW => annotate_struct::<mw::HeaderMwC>(bytes),
+            GameType::PM => annotate_struct::<pm::HeaderPmC>(bytes),
+            GameType::RC => annotate_struct::<rc::HeaderRcC>(bytes),
+            GameType::CS => annotate_struct::<cs::HeaderCsC>(bytes),
+        },
+        AnnotateType::Light => annotate_struct::<LightC>(bytes),
+    }
+}
+
+impl Annotate for mw::HeaderMwC {
+    const NAME: &'static str = "HeaderMwC";
+    const FIELDS: &'static [Field] = &[
+        field(0, "signature", Hex32),
+        field(4, "version", U32),
+        field(8, "texture_count", U32),
+        field(12, "textures_offset", U32),
+        field(16, "materials_offset", U32),
+        field(20, "meshes_offset", U32),
+        field(24, "node_array_size", U32),
+        field(28, "node_count", U32),
+        field(32, "nodes_offset", U32),
+    ];
+}
+
+impl Annotate for pm::HeaderPmC {
+    const NAME: &'static str = "HeaderPmC";
+    const FIELDS: &'static [Field] = &[
+        field(0, "signature", Hex32),
+        field(4, "version", U32),
+        field(8, "timestamp", U32),
+        field(12, "texture_count", U32),
+        field(16, "textures_offset", U32),
+        field(20, "materials_offset", U32),
+        field(24, "meshes_offset", U32),
+        field(28, "node_array_size", U32),
+        field(32, "node_count", U32),
+        field(36, "nodes_offset", U32),
+    ];
+}
+
+impl Annotate for rc::HeaderRcC {
+    const NAME: &'static str = "HeaderRcC";
+    const FIELDS: &'static [Field] = mw::HeaderMwC::FIELDS;
+}
+
+impl Annotate for cs::HeaderCsC {
+    const NAME: &'static str = "HeaderCsC";
+    const FIELDS: &'static [Field] = &[
+        field(0, "signature", Hex32),
+        field(4, "version", U32),
+        field(8, "timestamp", U32),
+        field(12, "texture_count", U32),
+        field(16, "textures_offset", U32),
+        field(20, "materials_offset", U32),
+        field(24, "meshes_offset", U32),
+        field(28, "node_array_size", U32),
+        field(32, "light_index", U32),
+        field(36, "nodes_offset", U32),
+    ];
+}
+
+impl Annotate for LightC {
+    const NAME: &'static str = "LightC";
+    const FIELDS: &'static [Field] = &[
+        field(0, "unk00", U32),
+        field(4, "unk04", U32),
+        field(8, "unk08", F32),
+        field(12, "extra_count", U32),
+        field(16, "zero16", U32),
+        field(20, "zero20", U32),
+        field(24, "unk24", Hex32),
+        field(28, "color", Color),
+        field(40, "pad40", U16),
+        field(42, "flags", Hex16),
+        field(44, "ptr", Hex32),
+        field(48, "unk48", F32),
+        field(52, "unk52", F32),
+        field(56, "unk56", F32),
+        field(60, "unk60", U32),
+        field(64, "unk64", F32),
+        field(68, "unk68", F32),
+        field(72, "unk72", F32),
+    ];
+}
+
+#[cfg(test)]
+mod tests;
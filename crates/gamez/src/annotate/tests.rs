@@ -0,0 +1,73 @@
+use super::*;
+use mech3ax_common::Error;
+
+fn assert_fields_cover<T: Annotate>() {
+    let mut offset = 0;
+    for field in T::FIELDS {
+        assert_eq!(field.offset, offset, "{} field `{}`", T::NAME, field.name);
+        offset += field.kind.size();
+    }
+    assert_eq!(offset, u32_to_usize(T::SIZE), "{} size", T::NAME);
+}
+
+#[test]
+fn fields_cover_structs() {
+    assert_fields_cover::<mw::HeaderMwC>();
+    assert_fields_cover::<pm::HeaderPmC>();
+    assert_fields_cover::<rc::HeaderRcC>();
+    assert_fields_cover::<cs::HeaderCsC>();
+    assert_fields_cover::<LightC>();
+}
+
+#[test]
+fn annotate_header() {
+    let mut bytes = Vec::new();
+    for value in [0x02971222u32, 27, 3, 36, 100, 200, 16, 2, 300] {
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+    // trailing bytes are ignored
+    bytes.extend_from_slice(&[0xFF; 4]);
+
+    let text = annotate(AnnotateType::Header, GameType::MW, &bytes).unwrap();
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines.len(), 9);
+    assert_eq!(lines[0], "00 signature        22 12 97 02 0x02971222");
+    assert_eq!(lines[8], "32 nodes_offset     2C 01 00 00 300");
+}
+
+#[test]
+fn annotate_light() {
+    let mut bytes = vec![0u8; 76];
+    bytes[8..12].copy_from_slice(&1.5f32.to_le_bytes());
+    bytes[28..32].copy_from_slice(&1.0f32.to_le_bytes());
+    bytes[32..36].copy_from_slice(&0.5f32.to_le_bytes());
+    bytes[42..44].copy_from_slice(&0x00A1u16.to_le_bytes());
+
+    let text = annotate(AnnotateType::Light, GameType::RC, &bytes).unwrap();
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines.len(), 18);
+    assert_eq!(
+        lines[2],
+        "08 unk08       00 00 C0 3F                         1.5"
+    );
+    assert_eq!(
+        lines[7],
+        "28 color       00 00 80 3F 00 00 00 3F 00 00 00 00 (1.0, 0.5, 0.0)"
+    );
+    assert_eq!(
+        lines[9],
+        "42 flags       A1 00                               0x00A1"
+    );
+}
+
+#[test]
+fn annotate_too_short_fails() {
+    let bytes = [0u8; 75];
+    match annotate_struct::<LightC>(&bytes) {
+        Err(Error::Assert(e)) => assert_eq!(
+            e.to_string(),
+            "Expected at least 76 bytes for `LightC`, but was 75"
+        ),
+        other => panic!("expected assertion error, got {:?}", other),
+    }
+}
@@ -25,7 +25,7 @@ pub(crate) struct MeshesInfoC {
     pub(crate) count: i32,      // 04
     pub(crate) last_index: i32, // 08
 }
-impl_as_bytes!(MeshesInfoC, 12);
+impl_as_bytes!(MeshesInfoC, 12, LeStruct);
 pub(crate) const MESHES_INFO_C_SIZE: u32 = MeshesInfoC::SIZE;
 
 #[derive(Debug)]
@@ -7,7 +7,7 @@ use crate::mesh::ng::{
 use log::trace;
 use mech3ax_api_types::gamez::mesh::MeshNg;
 use mech3ax_common::io_ext::{CountingReader, CountingWriter};
-use mech3ax_common::{assert_len, assert_that, Result};
+use mech3ax_common::{assert_len, assert_that, ReadContext, Result};
 use mech3ax_types::{u32_to_usize, AsBytes as _};
 use std::io::{Read, Write};
 
@@ -16,6 +16,7 @@ pub(crate) fn read_meshes(
     end_offset: usize,
     material_count: u32,
     fixup: Fixup,
+    ctx: &mut ReadContext,
 ) -> Result<Vec<Option<MeshNg>>> {
     let meshes_info = read_meshes_info_nonseq(read)?;
 
@@ -76,7 +77,7 @@ pub(crate) fn read_meshes(
                     meshes_info.array_size
                 );
                 assert_that!("mesh offset", read.offset == mesh_offset, read.offset)?;
-                let mesh = read_mesh_data(read, wrapped_mesh, material_count)?;
+                let mesh = read_mesh_data(read, wrapped_mesh, material_count, ctx)?;
                 Ok(Some(mesh))
             }
             None => Ok(None),
@@ -5,6 +5,7 @@ mod nodes;
 use super::common::{
     NODE_INDEX_BOT_MASK, NODE_INDEX_TOP, NODE_INDEX_TOP_MASK, SIGNATURE, VERSION_CS,
 };
+use super::validate;
 use crate::gamez::cs::fixup::Fixup;
 use crate::materials;
 use crate::textures::ng as textures;
@@ -13,14 +14,16 @@ use log::debug;
 use mech3ax_api_types::gamez::{GameZDataCs, GameZMetadataCs, TextureName};
 use mech3ax_api_types::nodes::cs::NodeCs;
 use mech3ax_common::io_ext::{CountingReader, CountingWriter};
-use mech3ax_common::{assert_len, assert_that, assert_with_msg, Rename, Result};
+use mech3ax_common::{
+    assert_len, assert_that, assert_with_msg, ReadContext, ReadOptions, Rename, Result, Warnings,
+};
 use mech3ax_timestamp::unix::{from_timestamp, to_timestamp};
 use mech3ax_types::{impl_as_bytes, u32_to_usize, AsBytes as _};
 use std::io::{Read, Write};
 
 #[derive(Debug, Clone, Copy, PartialEq, NoUninit, AnyBitPattern)]
 #[repr(C)]
-struct HeaderCsC {
+pub(crate) struct HeaderCsC {
     signature: u32,        // 00
     version: u32,          // 04
     timestamp: u32,        // 08
@@ -32,7 +35,7 @@ struct HeaderCsC {
     light_index: u32,      // 32
     nodes_offset: u32,     // 36
 }
-impl_as_bytes!(HeaderCsC, 40);
+impl_as_bytes!(HeaderCsC, 40, LeStruct);
 
 fn dedupe_texture_names(original_textures: Vec<String>) -> (Vec<String>, Vec<TextureName>) {
     let mut seen = Rename::new();
@@ -70,11 +73,15 @@ fn redupe_texture_names(textures: &[TextureName]) -> (Vec<String>, Vec<String>)
         .unzip()
 }
 
-pub fn read_gamez(read: &mut CountingReader<impl Read>) -> Result<GameZDataCs> {
+pub fn read_gamez(
+    read: &mut CountingReader<impl Read>,
+    options: ReadOptions,
+) -> Result<(GameZDataCs, Warnings)> {
+    let mut ctx = ReadContext::new(options);
     let header: HeaderCsC = read.read_struct()?;
 
     assert_that!("signature", header.signature == SIGNATURE, read.prev + 0)?;
-    assert_that!("version", header.version == VERSION_CS, read.prev + 4)?;
+    assert_that!("version", version header.version == VERSION_CS, read.prev + 4)?;
 
     let fixup = fixup::Fixup::read(&header);
     let datetime = from_timestamp(header.timestamp);
@@ -100,15 +107,16 @@ pub fn read_gamez(read: &mut CountingReader<impl Read>) -> Result<GameZDataCs> {
     let meshes_offset = u32_to_usize(header.meshes_offset);
     let nodes_offset = u32_to_usize(header.nodes_offset);
 
+    read.checkpoint("textures");
     assert_that!(
         "textures offset",
         read.offset == textures_offset,
         read.offset
     )?;
-    let (original_textures, texture_ptrs) =
-        textures::read_texture_infos(read, header.texture_count)?;
-    let (renamed_textures, textures) = dedupe_texture_names(original_textures);
+    let texture_infos = textures::read_texture_infos(read, header.texture_count, &mut ctx)?;
+    let (renamed_textures, textures) = dedupe_texture_names(texture_infos.names);
 
+    read.checkpoint("materials");
     assert_that!(
         "materials offset",
         read.offset == materials_offset,
@@ -116,8 +124,10 @@ pub fn read_gamez(read: &mut CountingReader<impl Read>) -> Result<GameZDataCs> {
     )?;
     let (materials, material_count) =
         materials::read_materials(read, &renamed_textures, materials::MatType::Ng)?;
+    read.checkpoint("meshes");
     assert_that!("meshes offset", read.offset == meshes_offset, read.offset)?;
-    let meshes = meshes::read_meshes(read, nodes_offset, material_count, fixup)?;
+    let meshes = meshes::read_meshes(read, nodes_offset, material_count, fixup, &mut ctx)?;
+    read.checkpoint("nodes");
     assert_that!("nodes offset", read.offset == nodes_offset, read.offset)?;
     let is_gamez = fixup != Fixup::Planes;
     let nodes = nodes::read_nodes(
@@ -131,18 +141,37 @@ pub fn read_gamez(read: &mut CountingReader<impl Read>) -> Result<GameZDataCs> {
 
     let metadata = GameZMetadataCs {
         datetime,
-        texture_ptrs,
+        texture_ptrs: texture_infos.ptrs,
+        texture_infos: texture_infos.infos,
     };
-    Ok(GameZDataCs {
+    let gamez = GameZDataCs {
         textures,
         materials,
         meshes,
         nodes,
         metadata,
-    })
+    };
+    Ok((gamez, ctx.into_warnings()))
+}
+
+/// Validate that material textures and mesh material indices resolve.
+///
+/// This is also called by `write_gamez`.
+pub fn validate_gamez(gamez: &GameZDataCs) -> Result<()> {
+    let (_, renamed_textures) = redupe_texture_names(&gamez.textures);
+    let material_count =
+        validate::validate_materials(&renamed_textures, &gamez.materials, materials::MatType::Ng)?;
+    for (index, mesh) in gamez.meshes.iter().enumerate() {
+        if let Some(mesh) = mesh {
+            validate::validate_mesh_ng(index, mesh, material_count)?;
+        }
+    }
+    Ok(())
 }
 
 pub fn write_gamez(write: &mut CountingWriter<impl Write>, gamez: &GameZDataCs) -> Result<()> {
+    validate_gamez(gamez)?;
+
     let texture_count = assert_len!(u32, gamez.textures.len(), "GameZ textures")?;
     let node_array_size = assert_len!(u32, gamez.nodes.len(), "GameZ nodes")?;
 
@@ -185,7 +214,12 @@ pub fn write_gamez(write: &mut CountingWriter<impl Write>, gamez: &GameZDataCs)
 
     let (original_textures, renamed_textures) = redupe_texture_names(&gamez.textures);
 
-    textures::write_texture_infos(write, &original_textures, &gamez.metadata.texture_ptrs)?;
+    textures::write_texture_infos(
+        write,
+        &original_textures,
+        &gamez.metadata.texture_ptrs,
+        gamez.metadata.texture_infos.as_deref(),
+    )?;
     materials::write_materials(
         write,
         &renamed_textures,
@@ -3,3 +3,7 @@ pub mod cs;
 pub mod mw;
 pub mod pm;
 pub mod rc;
+mod validate;
+
+#[cfg(test)]
+mod tests;
@@ -2,18 +2,21 @@ mod meshes;
 mod nodes;
 
 use super::common::{NODE_INDEX_INVALID, SIGNATURE, VERSION_MW};
+use super::validate;
 use crate::materials;
 use crate::textures::mw as textures;
 use bytemuck::{AnyBitPattern, NoUninit};
 use mech3ax_api_types::gamez::{GameZDataMw, GameZMetadataMw};
 use mech3ax_common::io_ext::{CountingReader, CountingWriter};
-use mech3ax_common::{assert_len, assert_that, Result};
+use mech3ax_common::{
+    assert_len, assert_that, assert_with_msg, ReadContext, ReadOptions, Result, Warnings,
+};
 use mech3ax_types::{impl_as_bytes, u32_to_usize, AsBytes as _};
 use std::io::{Read, Seek, Write};
 
 #[derive(Debug, Clone, Copy, NoUninit, AnyBitPattern)]
 #[repr(C)]
-struct HeaderMwC {
+pub(crate) struct HeaderMwC {
     signature: u32,        // 00
     version: u32,          // 04
     texture_count: u32,    // 08
@@ -24,13 +27,17 @@ struct HeaderMwC {
     node_count: u32,       // 28
     nodes_offset: u32,     // 32
 }
-impl_as_bytes!(HeaderMwC, 36);
+impl_as_bytes!(HeaderMwC, 36, LeStruct);
 
-pub fn read_gamez(read: &mut CountingReader<impl Read + Seek>) -> Result<GameZDataMw> {
+pub fn read_gamez(
+    read: &mut CountingReader<impl Read + Seek>,
+    options: ReadOptions,
+) -> Result<(GameZDataMw, Warnings)> {
+    let mut ctx = ReadContext::new(options);
     let header: HeaderMwC = read.read_struct()?;
 
     assert_that!("signature", header.signature == SIGNATURE, read.prev + 0)?;
-    assert_that!("version", header.version == VERSION_MW, read.prev + 4)?;
+    assert_that!("version", version header.version == VERSION_MW, read.prev + 4)?;
     assert_that!("texture count", header.texture_count < 4096, read.prev + 8)?;
     assert_that!(
         "texture offset",
@@ -61,12 +68,15 @@ pub fn read_gamez(read: &mut CountingReader<impl Read + Seek>) -> Result<GameZDa
         read.prev + 28
     )?;
 
+    read.checkpoint("textures");
     assert_that!(
         "textures offset",
         read.offset == textures_offset,
         read.offset
     )?;
-    let textures = textures::read_texture_infos(read, header.texture_count)?;
+    let (textures, texture_infos) =
+        textures::read_texture_infos(read, header.texture_count, &mut ctx)?;
+    read.checkpoint("materials");
     assert_that!(
         "materials offset",
         read.offset == materials_offset,
@@ -74,9 +84,11 @@ pub fn read_gamez(read: &mut CountingReader<impl Read + Seek>) -> Result<GameZDa
     )?;
     let (materials, material_count) =
         materials::read_materials(read, &textures, materials::MatType::Ng)?;
+    read.checkpoint("meshes");
     assert_that!("meshes offset", read.offset == meshes_offset, read.offset)?;
     let (meshes, meshes_count, mesh_array_size) =
-        meshes::read_meshes(read, nodes_offset, material_count)?;
+        meshes::read_meshes(read, nodes_offset, material_count, &mut ctx)?;
+    read.checkpoint("nodes");
     assert_that!("nodes offset", read.offset == nodes_offset, read.offset)?;
     let nodes = nodes::read_nodes(read, header.node_array_size, meshes_count)?;
     // `read_nodes` calls `assert_end`
@@ -85,17 +97,62 @@ pub fn read_gamez(read: &mut CountingReader<impl Read + Seek>) -> Result<GameZDa
         meshes_array_size: mesh_array_size,
         node_array_size: header.node_array_size,
         node_data_count: header.node_count,
+        texture_infos,
     };
-    Ok(GameZDataMw {
+    let gamez = GameZDataMw {
         textures,
         materials,
         meshes,
         nodes,
         metadata,
-    })
+    };
+    Ok((gamez, ctx.into_warnings()))
+}
+
+/// Validate that material textures and mesh material indices resolve, and
+/// that the metadata array sizes fit the meshes and nodes.
+///
+/// This is also called by `write_gamez`.
+pub fn validate_gamez(gamez: &GameZDataMw) -> Result<()> {
+    let material_count =
+        validate::validate_materials(&gamez.textures, &gamez.materials, materials::MatType::Ng)?;
+    for (index, mesh) in gamez.meshes.iter().enumerate() {
+        validate::validate_mesh_mw(index, mesh, material_count)?;
+    }
+    validate::validate_array_size(
+        "meshes_array_size",
+        gamez.metadata.meshes_array_size.into(),
+        gamez.meshes.len(),
+    )?;
+    validate::validate_array_size(
+        "node_array_size",
+        gamez.metadata.node_array_size.into(),
+        gamez.nodes.len(),
+    )?;
+    if let Some(texture_infos) = &gamez.metadata.texture_infos {
+        if texture_infos.len() != gamez.textures.len() {
+            return Err(assert_with_msg!(
+                "Expected metadata `texture_infos` length == {}, but was {}",
+                gamez.textures.len(),
+                texture_infos.len()
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Grow the metadata array sizes to fit the meshes and nodes.
+///
+/// By default, `write_gamez` fails if an array size is too small, e.g. after
+/// meshes or nodes were added. Call this first to recompute them instead.
+pub fn fit_metadata(gamez: &mut GameZDataMw) {
+    validate::fit_array_size(&mut gamez.metadata.meshes_array_size, gamez.meshes.len());
+    validate::fit_array_size(&mut gamez.metadata.node_array_size, gamez.nodes.len());
 }
 
 pub fn write_gamez(write: &mut CountingWriter<impl Write>, gamez: &GameZDataMw) -> Result<()> {
+    validate_gamez(gamez)?;
+
     let texture_count = assert_len!(u32, gamez.textures.len(), "GameZ textures")?;
 
     let node_array_size = gamez.metadata.node_array_size;
@@ -121,7 +178,11 @@ pub fn write_gamez(write: &mut CountingWriter<impl Write>, gamez: &GameZDataMw)
     };
     write.write_struct(&header)?;
 
-    textures::write_texture_infos(write, &gamez.textures)?;
+    textures::write_texture_infos(
+        write,
+        &gamez.textures,
+        gamez.metadata.texture_infos.as_deref(),
+    )?;
     materials::write_materials(
         write,
         &gamez.textures,
@@ -132,3 +193,6 @@ pub fn write_gamez(write: &mut CountingWriter<impl Write>, gamez: &GameZDataMw)
     nodes::write_nodes(write, &gamez.nodes, node_array_size, nodes_offset)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests;
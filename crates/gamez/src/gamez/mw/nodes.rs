@@ -16,7 +16,9 @@ pub(crate) fn read_nodes(
     array_size: u32,
     meshes_count: i32,
 ) -> Result<Vec<NodeMw>> {
-    let end_offset = read.offset + (u32_to_usize(NodeMwC::SIZE) + 4) * u32_to_usize(array_size);
+    let node_size = u32_to_usize(NodeMwC::SIZE) + 4;
+    let array_len = read.assert_count("node array size", array_size, node_size, read.offset)?;
+    let end_offset = read.offset + node_size * array_len;
 
     let mut variants = Vec::new();
     // the node_count is wildly inaccurate for some files, and there are more nodes to
@@ -0,0 +1,158 @@
+use super::{meshes, nodes, write_gamez, HeaderMwC};
+use crate::materials::{write_materials, MatType};
+use crate::mesh::mw::write_mesh_data;
+use crate::test_utils::{self, colored, empty, textured};
+use crate::textures::mw as textures;
+use mech3ax_api_types::gamez::mesh::{MeshLight, MeshMw, PolygonMw, UvCoord};
+use mech3ax_api_types::gamez::{GameZDataMw, GameZMetadataMw};
+use mech3ax_api_types::{Color, Vec3};
+use mech3ax_common::io_ext::CountingWriter;
+use mech3ax_common::Result;
+use mech3ax_types::{u32_to_usize, AsBytes as _};
+
+fn polygon(material_index: u32) -> PolygonMw {
+    PolygonMw {
+        vertex_colors: vec![Color::BLACK; 3],
+        normal_indices: Some(vec![0, 1, 2]),
+        uv_coords: Some(vec![UvCoord { u: 0.0, v: 1.0 }; 3]),
+        vertices_ptr: 1,
+        normals_ptr: 1,
+        uvs_ptr: 1,
+        colors_ptr: 1,
+        ..test_utils::polygon(material_index)
+    }
+}
+
+fn light() -> MeshLight {
+    MeshLight {
+        unk00: 0,
+        unk04: 0,
+        unk08: 0.0,
+        extra: vec![Vec3::DEFAULT; 2],
+        unk24: 0,
+        color: Color::BLACK,
+        flags: 0,
+        ptr: 1,
+        unk48: 0.0,
+        unk52: 0.0,
+        unk56: 0.0,
+        unk60: 0,
+        unk64: 0.0,
+        unk68: 0.0,
+        unk72: 0.0,
+    }
+}
+
+fn mesh(polygons: Vec<PolygonMw>, lights: Vec<MeshLight>) -> MeshMw {
+    MeshMw {
+        vertices: vec![Vec3::DEFAULT; 3],
+        normals: vec![Vec3::DEFAULT; 3],
+        morphs: vec![Vec3::DEFAULT; 1],
+        lights,
+        polygons_ptr: 1,
+        vertices_ptr: 1,
+        normals_ptr: 1,
+        lights_ptr: 1,
+        morphs_ptr: 1,
+        ..test_utils::mesh(polygons)
+    }
+}
+
+fn gamez() -> GameZDataMw {
+    GameZDataMw {
+        textures: vec!["sky".to_string(), "ground".to_string()],
+        materials: vec![textured("ground"), colored()],
+        meshes: vec![
+            mesh(vec![polygon(0), polygon(1)], vec![light()]),
+            mesh(vec![polygon(1)], Vec::new()),
+        ],
+        nodes: vec![empty("a"), empty("b")],
+        metadata: GameZMetadataMw {
+            meshes_array_size: 4,
+            node_array_size: 4,
+            node_data_count: 2,
+            texture_infos: None,
+        },
+    }
+}
+
+/// The number of bytes the function writes.
+fn written_len(
+    offset: u32,
+    write_fn: impl FnOnce(&mut CountingWriter<Vec<u8>>) -> Result<()>,
+) -> u32 {
+    let mut write = CountingWriter::new(Vec::new(), u32_to_usize(offset));
+    write_fn(&mut write).unwrap();
+    write.into_inner().len().try_into().unwrap()
+}
+
+fn header_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+}
+
+#[test]
+fn header_offsets_match_written_sections() {
+    let gamez = gamez();
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    write_gamez(&mut write, &gamez).unwrap();
+    let data = write.into_inner();
+
+    let textures_offset = header_u32(&data, 12);
+    let materials_offset = header_u32(&data, 16);
+    let meshes_offset = header_u32(&data, 20);
+    let nodes_offset = header_u32(&data, 32);
+
+    // each section must start where the previous one ends, so the sizes
+    // used for the offsets must match what the section writers write
+    assert_eq!(textures_offset, HeaderMwC::SIZE);
+    let textures_len = written_len(textures_offset, |write| {
+        textures::write_texture_infos(write, &gamez.textures, None)
+    });
+    assert_eq!(textures_offset + textures_len, materials_offset);
+
+    let materials_len = written_len(materials_offset, |write| {
+        write_materials(write, &gamez.textures, &gamez.materials, MatType::Ng)
+    });
+    assert_eq!(materials_offset + materials_len, meshes_offset);
+
+    let array_size = gamez.metadata.meshes_array_size;
+    let (sized_nodes_offset, mesh_offsets) =
+        meshes::size_meshes(meshes_offset, array_size, &gamez.meshes);
+    assert_eq!(sized_nodes_offset, nodes_offset);
+    let meshes_len = written_len(meshes_offset, |write| {
+        meshes::write_meshes(write, &gamez.meshes, &mesh_offsets, array_size)
+    });
+    assert_eq!(meshes_offset + meshes_len, nodes_offset);
+
+    // each mesh's data must start at its offset
+    let mesh_ends = mesh_offsets.iter().skip(1).chain(Some(&nodes_offset));
+    for ((mesh, start), end) in gamez.meshes.iter().zip(&mesh_offsets).zip(mesh_ends) {
+        let mesh_len = written_len(*start, |write| write_mesh_data(write, mesh));
+        assert_eq!(start + mesh_len, *end);
+    }
+
+    let nodes_len = written_len(nodes_offset, |write| {
+        nodes::write_nodes(
+            write,
+            &gamez.nodes,
+            gamez.metadata.node_array_size,
+            nodes_offset,
+        )
+    });
+    assert_eq!(
+        u32_to_usize(nodes_offset + nodes_len),
+        data.len(),
+        "file length"
+    );
+}
+
+#[test]
+fn read_nodes_huge_array_size_fails() {
+    let mut read = mech3ax_common::io_ext::CountingReader::new(std::io::Cursor::new(vec![0; 16]));
+    let err = nodes::read_nodes(&mut read, u32::MAX, 0).unwrap_err();
+    assert!(
+        format!("{:?}", err).contains("`node array size`"),
+        "{:?}",
+        err
+    );
+}
@@ -8,7 +8,7 @@ use crate::mesh::ng::{
 use log::trace;
 use mech3ax_api_types::gamez::mesh::MeshNg;
 use mech3ax_common::io_ext::{CountingReader, CountingWriter};
-use mech3ax_common::{assert_len, assert_that, Result};
+use mech3ax_common::{assert_len, assert_that, ReadContext, Result};
 use mech3ax_types::u32_to_usize;
 use std::io::{Read, Write};
 
@@ -16,6 +16,7 @@ pub(crate) fn read_meshes(
     read: &mut CountingReader<impl Read>,
     end_offset: usize,
     material_count: u32,
+    ctx: &mut ReadContext,
 ) -> Result<(Vec<MeshNg>, i32, i32)> {
     let mesh_indices = read_meshes_info_sequential(read)?;
 
@@ -53,7 +54,7 @@ pub(crate) fn read_meshes(
         .map(|(wrapped_mesh, mesh_offset, mesh_index)| {
             trace!("Reading mesh data {}/{}", mesh_index, mesh_indices.count);
             assert_that!("mesh offset", read.offset == mesh_offset, read.offset)?;
-            let mesh = read_mesh_data(read, wrapped_mesh, material_count)?;
+            let mesh = read_mesh_data(read, wrapped_mesh, material_count, ctx)?;
             Ok(mesh)
         })
         .collect::<Result<Vec<_>>>()?;
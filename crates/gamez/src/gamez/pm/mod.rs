@@ -4,19 +4,20 @@ mod nodes;
 use super::common::{
     NODE_INDEX_BOT_MASK, NODE_INDEX_TOP, NODE_INDEX_TOP_MASK, SIGNATURE, VERSION_PM,
 };
+use super::validate;
 use crate::materials;
 use crate::textures::ng as textures;
 use bytemuck::{AnyBitPattern, NoUninit};
 use mech3ax_api_types::gamez::{GameZDataPm, GameZMetadataPm};
 use mech3ax_common::io_ext::{CountingReader, CountingWriter};
-use mech3ax_common::{assert_len, assert_that, Result};
+use mech3ax_common::{assert_len, assert_that, ReadContext, ReadOptions, Result, Warnings};
 use mech3ax_timestamp::unix::{from_timestamp, to_timestamp};
 use mech3ax_types::{impl_as_bytes, u32_to_usize, AsBytes as _};
 use std::io::{Read, Write};
 
 #[derive(Debug, Clone, Copy, NoUninit, AnyBitPattern)]
 #[repr(C)]
-struct HeaderPmC {
+pub(crate) struct HeaderPmC {
     signature: u32,        // 00
     version: u32,          // 04
     timestamp: u32,        // 08
@@ -28,13 +29,17 @@ struct HeaderPmC {
     node_count: u32,       // 32
     nodes_offset: u32,     // 36
 }
-impl_as_bytes!(HeaderPmC, 40);
+impl_as_bytes!(HeaderPmC, 40, LeStruct);
 
-pub fn read_gamez(read: &mut CountingReader<impl Read>) -> Result<GameZDataPm> {
+pub fn read_gamez(
+    read: &mut CountingReader<impl Read>,
+    options: ReadOptions,
+) -> Result<(GameZDataPm, Warnings)> {
+    let mut ctx = ReadContext::new(options);
     let header: HeaderPmC = read.read_struct()?;
 
     assert_that!("signature", header.signature == SIGNATURE, read.prev + 0)?;
-    assert_that!("version", header.version == VERSION_PM, read.prev + 4)?;
+    assert_that!("version", version header.version == VERSION_PM, read.prev + 4)?;
     let datetime = from_timestamp(header.timestamp);
     // hardcoded limit in engine
     assert_that!("texture count", header.texture_count < 4096, read.prev + 12)?;
@@ -67,12 +72,15 @@ pub fn read_gamez(read: &mut CountingReader<impl Read>) -> Result<GameZDataPm> {
         read.prev + 32
     )?;
 
+    read.checkpoint("textures");
     assert_that!(
         "textures offset",
         read.offset == textures_offset,
         read.offset
     )?;
-    let (textures, texture_ptrs) = textures::read_texture_infos(read, header.texture_count)?;
+    let texture_infos = textures::read_texture_infos(read, header.texture_count, &mut ctx)?;
+    let textures = texture_infos.names;
+    read.checkpoint("materials");
     assert_that!(
         "materials offset",
         read.offset == materials_offset,
@@ -80,9 +88,11 @@ pub fn read_gamez(read: &mut CountingReader<impl Read>) -> Result<GameZDataPm> {
     )?;
     let (materials, material_count) =
         materials::read_materials(read, &textures, materials::MatType::Ng)?;
+    read.checkpoint("meshes");
     assert_that!("meshes offset", read.offset == meshes_offset, read.offset)?;
     let (meshes, meshes_count, mesh_array_size) =
-        meshes::read_meshes(read, nodes_offset, material_count)?;
+        meshes::read_meshes(read, nodes_offset, material_count, &mut ctx)?;
+    read.checkpoint("nodes");
     assert_that!("nodes offset", read.offset == nodes_offset, read.offset)?;
     let nodes = nodes::read_nodes(read, header.node_array_size, meshes_count)?;
     // `read_nodes` calls `assert_end`
@@ -91,18 +101,48 @@ pub fn read_gamez(read: &mut CountingReader<impl Read>) -> Result<GameZDataPm> {
         datetime,
         meshes_array_size: mesh_array_size,
         node_data_count: header.node_count,
-        texture_ptrs,
+        texture_ptrs: texture_infos.ptrs,
+        texture_infos: texture_infos.infos,
     };
-    Ok(GameZDataPm {
+    let gamez = GameZDataPm {
         textures,
         materials,
         meshes,
         nodes,
         metadata,
-    })
+    };
+    Ok((gamez, ctx.into_warnings()))
+}
+
+/// Validate that material textures and mesh material indices resolve, and
+/// that the metadata array sizes fit the meshes.
+///
+/// This is also called by `write_gamez`.
+pub fn validate_gamez(gamez: &GameZDataPm) -> Result<()> {
+    let material_count =
+        validate::validate_materials(&gamez.textures, &gamez.materials, materials::MatType::Ng)?;
+    for (index, mesh) in gamez.meshes.iter().enumerate() {
+        validate::validate_mesh_ng(index, mesh, material_count)?;
+    }
+    validate::validate_array_size(
+        "meshes_array_size",
+        gamez.metadata.meshes_array_size.into(),
+        gamez.meshes.len(),
+    )?;
+    Ok(())
+}
+
+/// Grow the metadata array sizes to fit the meshes.
+///
+/// By default, `write_gamez` fails if an array size is too small, e.g. after
+/// meshes were added. Call this first to recompute them instead.
+pub fn fit_metadata(gamez: &mut GameZDataPm) {
+    validate::fit_array_size(&mut gamez.metadata.meshes_array_size, gamez.meshes.len());
 }
 
 pub fn write_gamez(write: &mut CountingWriter<impl Write>, gamez: &GameZDataPm) -> Result<()> {
+    validate_gamez(gamez)?;
+
     let texture_count = assert_len!(u32, gamez.textures.len(), "GameZ textures")?;
 
     let node_array_size = assert_len!(u32, gamez.nodes.len(), "GameZ nodes")?;
@@ -131,7 +171,12 @@ pub fn write_gamez(write: &mut CountingWriter<impl Write>, gamez: &GameZDataPm)
     };
     write.write_struct(&header)?;
 
-    textures::write_texture_infos(write, &gamez.textures, &gamez.metadata.texture_ptrs)?;
+    textures::write_texture_infos(
+        write,
+        &gamez.textures,
+        &gamez.metadata.texture_ptrs,
+        gamez.metadata.texture_infos.as_deref(),
+    )?;
     materials::write_materials(
         write,
         &gamez.textures,
@@ -17,7 +17,16 @@ pub(crate) fn read_nodes(
     array_size: u32,
     meshes_count: i32,
 ) -> Result<Vec<NodePm>> {
-    let end_offset = read.offset + (u32_to_usize(NodeMwC::SIZE) + 4) * u32_to_usize(array_size);
+    let end_offset = (u32_to_usize(NodeMwC::SIZE) + 4)
+        .checked_mul(u32_to_usize(array_size))
+        .and_then(|array_len| array_len.checked_add(read.offset))
+        .ok_or_else(|| {
+            assert_with_msg!(
+                "Expected node array size to fit, but was {} (at {})",
+                array_size,
+                read.offset
+            )
+        })?;
 
     let mut light_node: Option<u32> = None;
     let variants = (0..array_size)
@@ -3,18 +3,19 @@ mod meshes;
 mod nodes;
 
 use super::common::{NODE_INDEX_INVALID, SIGNATURE, VERSION_RC};
+use super::validate;
 use crate::materials;
 use crate::textures::rc as textures;
 use bytemuck::{AnyBitPattern, NoUninit};
 use mech3ax_api_types::gamez::GameZDataRc;
 use mech3ax_common::io_ext::{CountingReader, CountingWriter};
-use mech3ax_common::{assert_len, assert_that, Result};
+use mech3ax_common::{assert_len, assert_that, ReadContext, ReadOptions, Result, Warnings};
 use mech3ax_types::{impl_as_bytes, u32_to_usize, AsBytes as _};
 use std::io::{Read, Write};
 
 #[derive(Debug, Clone, Copy, PartialEq, NoUninit, AnyBitPattern)]
 #[repr(C)]
-struct HeaderRcC {
+pub(crate) struct HeaderRcC {
     signature: u32,        // 00
     version: u32,          // 04
     texture_count: u32,    // 08
@@ -25,16 +26,20 @@ struct HeaderRcC {
     node_count: u32,       // 28
     nodes_offset: u32,     // 32
 }
-impl_as_bytes!(HeaderRcC, 36);
+impl_as_bytes!(HeaderRcC, 36, LeStruct);
 
 pub(crate) const NODE_ARRAY_SIZE: u32 = 16000;
 
-pub fn read_gamez(read: &mut CountingReader<impl Read>) -> Result<GameZDataRc> {
+pub fn read_gamez(
+    read: &mut CountingReader<impl Read>,
+    options: ReadOptions,
+) -> Result<(GameZDataRc, Warnings)> {
+    let mut ctx = ReadContext::new(options);
     let mut header: HeaderRcC = read.read_struct()?;
     fixup::read(&mut header);
 
     assert_that!("signature", header.signature == SIGNATURE, read.prev + 0)?;
-    assert_that!("version", header.version == VERSION_RC, read.prev + 4)?;
+    assert_that!("version", version header.version == VERSION_RC, read.prev + 4)?;
     assert_that!("texture count", header.texture_count < 4096, read.prev + 8)?;
     assert_that!(
         "texture offset",
@@ -70,12 +75,15 @@ pub fn read_gamez(read: &mut CountingReader<impl Read>) -> Result<GameZDataRc> {
         read.prev + 28
     )?;
 
+    read.checkpoint("textures");
     assert_that!(
         "textures offset",
         read.offset == textures_offset,
         read.offset
     )?;
-    let textures = textures::read_texture_infos(read, header.texture_count)?;
+    let (textures, texture_infos) =
+        textures::read_texture_infos(read, header.texture_count, &mut ctx)?;
+    read.checkpoint("materials");
     assert_that!(
         "materials offset",
         read.offset == materials_offset,
@@ -83,21 +91,39 @@ pub fn read_gamez(read: &mut CountingReader<impl Read>) -> Result<GameZDataRc> {
     )?;
     let (materials, material_count) =
         materials::read_materials(read, &textures, materials::MatType::Rc)?;
+    read.checkpoint("meshes");
     assert_that!("meshes offset", read.offset == meshes_offset, read.offset)?;
-    let (meshes, meshes_count) = meshes::read_meshes(read, nodes_offset, material_count)?;
+    let (meshes, meshes_count) = meshes::read_meshes(read, nodes_offset, material_count, &mut ctx)?;
+    read.checkpoint("nodes");
     assert_that!("nodes offset", read.offset == nodes_offset, read.offset)?;
     let nodes = nodes::read_nodes(read, header.node_count, meshes_count)?;
     // `read_nodes` calls `assert_end`
 
-    Ok(GameZDataRc {
+    let gamez = GameZDataRc {
         textures,
         materials,
         meshes,
         nodes,
-    })
+        texture_infos,
+    };
+    Ok((gamez, ctx.into_warnings()))
+}
+
+/// Validate that material textures and mesh material indices resolve.
+///
+/// This is also called by `write_gamez`.
+pub fn validate_gamez(gamez: &GameZDataRc) -> Result<()> {
+    let material_count =
+        validate::validate_materials(&gamez.textures, &gamez.materials, materials::MatType::Rc)?;
+    for (index, mesh) in gamez.meshes.iter().enumerate() {
+        validate::validate_mesh_rc(index, mesh, material_count)?;
+    }
+    Ok(())
 }
 
 pub fn write_gamez(write: &mut CountingWriter<impl Write>, gamez: &GameZDataRc) -> Result<()> {
+    validate_gamez(gamez)?;
+
     let texture_count = assert_len!(u32, gamez.textures.len(), "GameZ textures")?;
     let node_count = assert_len!(u32, gamez.nodes.len(), "GameZ nodes")?;
 
@@ -121,7 +147,7 @@ pub fn write_gamez(write: &mut CountingWriter<impl Write>, gamez: &GameZDataRc)
     fixup::write(&mut header);
     write.write_struct(&header)?;
 
-    textures::write_texture_infos(write, &gamez.textures)?;
+    textures::write_texture_infos(write, &gamez.textures, gamez.texture_infos.as_deref())?;
     materials::write_materials(
         write,
         &gamez.textures,
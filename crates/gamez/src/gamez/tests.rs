@@ -0,0 +1,459 @@
+use super::mw::{fit_metadata, validate_gamez};
+use crate::materials::{read_materials, write_materials, MatType};
+use crate::mesh::mw::{read_mesh_data, read_mesh_info, write_mesh_data, write_mesh_info};
+use crate::mesh::rc;
+use crate::test_utils::{self, colored, empty, polygon, textured};
+use mech3ax_api_types::gamez::materials::{
+    material_texture_name, ColoredMaterial, CycleData, Material, Soil,
+};
+use mech3ax_api_types::gamez::mesh::{MeshMw, MeshRc, PolygonRc, UvCoord};
+use mech3ax_api_types::gamez::{GameZDataMw, GameZMetadataMw, TextureInfoMw};
+use mech3ax_api_types::{Color, Vec3};
+use mech3ax_common::io_ext::{CountingReader, CountingWriter};
+use mech3ax_common::test_utils::assert_truncated_fails;
+use mech3ax_common::{Error, ReadContext, ReadOptions, Warning, Warnings};
+use std::io::Cursor;
+
+fn mesh(material_indices: &[u32]) -> MeshMw {
+    test_utils::mesh(material_indices.iter().copied().map(polygon).collect())
+}
+
+fn gamez(materials: Vec<Material>, meshes: Vec<MeshMw>) -> GameZDataMw {
+    GameZDataMw {
+        textures: vec!["sky".to_string(), "ground".to_string()],
+        materials,
+        meshes,
+        nodes: Vec::new(),
+        metadata: GameZMetadataMw {
+            meshes_array_size: 16,
+            node_array_size: 16,
+            node_data_count: 0,
+            texture_infos: None,
+        },
+    }
+}
+
+fn assert_message(result: mech3ax_common::Result<()>, expected: &str) {
+    match result {
+        Err(Error::Assert(e)) => assert_eq!(e.to_string(), expected),
+        other => panic!("expected assertion error, got {:?}", other),
+    }
+}
+
+#[test]
+fn validate_ok() {
+    let gamez = gamez(
+        vec![textured("sky"), colored(), textured("ground")],
+        vec![mesh(&[0, 1]), mesh(&[2])],
+    );
+    validate_gamez(&gamez).unwrap();
+}
+
+#[test]
+fn validate_texture_missing() {
+    let gamez = gamez(vec![textured("sky"), textured("water")], Vec::new());
+    assert_message(
+        validate_gamez(&gamez),
+        "Material 1 texture `water` not found in textures list",
+    );
+}
+
+#[test]
+fn validate_cycle_texture_missing() {
+    let mut material = textured("sky");
+    if let Material::Textured(textured) = &mut material {
+        textured.cycle = Some(CycleData {
+            textures: vec!["sky".to_string(), "lava".to_string()],
+            unk00: false,
+            unk04: 0,
+            unk12: 0.0,
+            info_ptr: 0,
+            data_ptr: 0,
+        });
+    }
+    let gamez = gamez(vec![material], Vec::new());
+    assert_message(
+        validate_gamez(&gamez),
+        "Material 0 cycle texture 1 `lava` not found in textures list",
+    );
+}
+
+#[test]
+fn validate_mesh_material_index_out_of_range() {
+    let gamez = gamez(vec![colored(), colored()], vec![mesh(&[0]), mesh(&[1, 2])]);
+    assert_message(
+        validate_gamez(&gamez),
+        "Expected mesh 1 polygon 1 material index < 2, but was 2",
+    );
+}
+
+#[test]
+fn validate_material_array_size() {
+    let materials = (0..1001).map(|_| colored()).collect();
+    let gamez = gamez(materials, Vec::new());
+    assert_message(
+        validate_gamez(&gamez),
+        "Expected material count <= 1000 (material array size), but was 1001",
+    );
+}
+
+#[test]
+fn validate_meshes_array_size() {
+    let mut gamez = gamez(vec![colored()], vec![mesh(&[0]), mesh(&[0])]);
+    gamez.metadata.meshes_array_size = 1;
+    assert_message(
+        validate_gamez(&gamez),
+        "Expected metadata `meshes_array_size` >= 2, but was 1",
+    );
+}
+
+#[test]
+fn validate_node_array_size() {
+    let mut gamez = gamez(Vec::new(), Vec::new());
+    gamez.nodes = vec![empty("a"), empty("b"), empty("c")];
+    gamez.metadata.node_array_size = 2;
+    assert_message(
+        validate_gamez(&gamez),
+        "Expected metadata `node_array_size` >= 3, but was 2",
+    );
+}
+
+#[test]
+fn fit_metadata_grows_array_sizes() {
+    let mut gamez = gamez(vec![colored()], vec![mesh(&[0]), mesh(&[0])]);
+    gamez.nodes = vec![empty("a"), empty("b"), empty("c")];
+    gamez.metadata.meshes_array_size = -1;
+    gamez.metadata.node_array_size = 2;
+
+    fit_metadata(&mut gamez);
+    assert_eq!(gamez.metadata.meshes_array_size, 2);
+    assert_eq!(gamez.metadata.node_array_size, 3);
+    validate_gamez(&gamez).unwrap();
+}
+
+#[test]
+fn fit_metadata_keeps_larger_array_sizes() {
+    let mut gamez = gamez(vec![colored()], vec![mesh(&[0])]);
+    fit_metadata(&mut gamez);
+    assert_eq!(gamez.metadata.meshes_array_size, 16);
+    assert_eq!(gamez.metadata.node_array_size, 16);
+}
+
+#[test]
+fn validate_texture_infos_length() {
+    let mut gamez = gamez(Vec::new(), Vec::new());
+    let info = TextureInfoMw {
+        state: 3,
+        index: 0,
+        unk36: -1,
+    };
+    gamez.metadata.texture_infos = Some(vec![info, info]);
+    validate_gamez(&gamez).unwrap();
+    gamez.metadata.texture_infos = Some(vec![info]);
+    assert_message(
+        validate_gamez(&gamez),
+        "Expected metadata `texture_infos` length == 2, but was 1",
+    );
+}
+
+#[test]
+fn mesh_read_truncated_fails() {
+    let mut polygon = polygon(0);
+    polygon.vertex_colors = vec![Color::WHITE_FULL; 3];
+    polygon.normal_indices = Some(vec![0, 1, 2]);
+    polygon.uv_coords = Some(vec![UvCoord { u: 0.0, v: 1.0 }; 3]);
+    polygon.vtx_bit = true;
+    polygon.vertices_ptr = 0x1000;
+    polygon.normals_ptr = 0x2000;
+    polygon.uvs_ptr = 0x3000;
+    polygon.colors_ptr = 0x4000;
+    polygon.unk_ptr = 0x5000;
+
+    let mut mesh = mesh(&[]);
+    mesh.vertices = vec![Vec3::DEFAULT; 3];
+    mesh.normals = vec![Vec3::DEFAULT; 3];
+    mesh.polygons = vec![polygon];
+    mesh.polygons_ptr = 0x1000;
+    mesh.vertices_ptr = 0x2000;
+    mesh.normals_ptr = 0x3000;
+    mesh.parent_count = 1;
+
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    write_mesh_info(&mut write, &mesh).unwrap();
+    write_mesh_data(&mut write, &mesh).unwrap();
+    let data = write.into_inner();
+
+    let read_mesh = |data: &[u8]| {
+        let mut read = CountingReader::new(Cursor::new(data));
+        let wrapped = read_mesh_info(&mut read)?;
+        read_mesh_data(&mut read, wrapped, 1, &mut ReadContext::default())
+    };
+    assert_truncated_fails(&data, read_mesh);
+}
+
+/// A mesh with one polygon per material index, with pointers set so that it
+/// can be read back.
+fn readable_mesh(material_indices: &[u32]) -> MeshMw {
+    let mut mesh = mesh(material_indices);
+    for polygon in &mut mesh.polygons {
+        polygon.vertex_colors = vec![Color::WHITE_FULL; 3];
+        polygon.vertices_ptr = 0x1000;
+        polygon.colors_ptr = 0x2000;
+        polygon.unk_ptr = 0x3000;
+    }
+    mesh.vertices = vec![Vec3::DEFAULT; 3];
+    mesh.polygons_ptr = 0x1000;
+    mesh.vertices_ptr = 0x2000;
+    mesh.parent_count = 1;
+    mesh
+}
+
+fn write_mesh(mesh: &MeshMw) -> Vec<u8> {
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    write_mesh_info(&mut write, mesh).unwrap();
+    write_mesh_data(&mut write, mesh).unwrap();
+    write.into_inner()
+}
+
+fn read_mesh(data: &[u8], material_count: u32) -> mech3ax_common::Result<MeshMw> {
+    let mut read = CountingReader::new(Cursor::new(data));
+    let wrapped = read_mesh_info(&mut read)?;
+    let mesh = read_mesh_data(
+        &mut read,
+        wrapped,
+        material_count,
+        &mut ReadContext::default(),
+    )?;
+    read.assert_end()?;
+    Ok(mesh)
+}
+
+#[test]
+fn mesh_morphs_round_trip() {
+    let mut mesh = readable_mesh(&[0]);
+    mesh.morphs = (0..3)
+        .map(|i| Vec3 {
+            x: i as f32,
+            y: 1.0,
+            z: -2.0,
+        })
+        .collect();
+    mesh.morphs_ptr = 0x3000;
+
+    let data = write_mesh(&mesh);
+    let read_mesh = read_mesh(&data, 1).unwrap();
+    assert_eq!(read_mesh.morphs, mesh.morphs);
+    assert_eq!(read_mesh.morphs_ptr, 0x3000);
+    assert_eq!(write_mesh(&read_mesh), data);
+}
+
+#[test]
+fn mesh_polygon_material_indices_read() {
+    let data = write_mesh(&readable_mesh(&[0, 1]));
+
+    let mesh = read_mesh(&data, 2).unwrap();
+    let material_indices = mesh
+        .polygons
+        .iter()
+        .map(|polygon| polygon.material_index)
+        .collect::<Vec<_>>();
+    assert_eq!(material_indices, vec![0, 1]);
+
+    // the second polygon's material doesn't exist
+    let err = read_mesh(&data, 1).unwrap_err();
+    let Error::Assert(e) = err else {
+        panic!("expected an assertion error, got {:?}", err);
+    };
+    assert_eq!(e.name(), Some("material index"));
+}
+
+fn rc_mesh_with_polygon_flags(flags: u8) -> Vec<u8> {
+    let polygon = PolygonRc {
+        vertex_indices: vec![0, 1, 2],
+        normal_indices: None,
+        uv_coords: None,
+        material_index: 0,
+        unk0_flag: false,
+        unknown_flags: 0,
+        unk04: 0,
+        unk24: 0,
+        vertices_ptr: 0x1000,
+        normals_ptr: 0,
+        uvs_ptr: 0,
+    };
+    let mesh = MeshRc {
+        vertices: vec![Vec3::DEFAULT; 3],
+        normals: Vec::new(),
+        morphs: Vec::new(),
+        lights: Vec::new(),
+        polygons: vec![polygon],
+        polygons_ptr: 0x1000,
+        vertices_ptr: 0x2000,
+        normals_ptr: 0,
+        lights_ptr: 0,
+        morphs_ptr: 0,
+        file_ptr: false,
+        unk04: 0,
+        parent_count: 1,
+        unk68: 0.0,
+        unk72: 0.0,
+        unk76: 0.0,
+        unk80: 0.0,
+    };
+
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    rc::write_mesh_info(&mut write, &mesh).unwrap();
+    rc::write_mesh_data(&mut write, &mesh).unwrap();
+    let mut data = write.into_inner();
+    // the polygon flags are the second byte of the polygon info, which
+    // follows the mesh info and the vertices
+    data[84 + 3 * 12 + 1] = flags;
+    data
+}
+
+fn read_rc_mesh(data: &[u8], options: ReadOptions) -> mech3ax_common::Result<(MeshRc, Warnings)> {
+    let mut read = CountingReader::new(Cursor::new(data));
+    let mut ctx = ReadContext::new(options);
+    let wrapped = rc::read_mesh_info(&mut read)?;
+    let mesh = rc::read_mesh_data(&mut read, wrapped, 1, &mut ctx)?;
+    read.assert_end()?;
+    Ok((mesh, ctx.into_warnings()))
+}
+
+#[test]
+fn mesh_unknown_polygon_flags_fail_strict() {
+    let data = rc_mesh_with_polygon_flags(0x04);
+    let result = read_rc_mesh(&data, ReadOptions::STRICT).map(|_| ());
+    assert_message(
+        result,
+        "Expected valid polygon flags, but was 0x04 (at 121)",
+    );
+}
+
+#[test]
+fn mesh_unknown_polygon_flags_read_lenient() {
+    let data = rc_mesh_with_polygon_flags(0x05);
+    let (mesh, _) = read_rc_mesh(&data, ReadOptions::LENIENT).unwrap();
+    // the known flags are still read
+    assert!(mesh.polygons[0].unk0_flag);
+    assert_eq!(mesh.polygons[0].unknown_flags, 0x04);
+    assert_eq!(mesh.polygons[0].vertex_indices, vec![0, 1, 2]);
+
+    // the unknown flags are written back
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    rc::write_mesh_info(&mut write, &mesh).unwrap();
+    rc::write_mesh_data(&mut write, &mesh).unwrap();
+    assert_eq!(write.into_inner(), data);
+
+    let data = rc_mesh_with_polygon_flags(0x01);
+    read_rc_mesh(&data, ReadOptions::STRICT).unwrap();
+}
+
+#[test]
+fn mesh_unknown_polygon_flags_overlapping_fail_write() {
+    let data = rc_mesh_with_polygon_flags(0x01);
+    let (mut mesh, _) = read_rc_mesh(&data, ReadOptions::STRICT).unwrap();
+    // overlaps the normals flag
+    mesh.polygons[0].unknown_flags = 0x02;
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    rc::write_mesh_info(&mut write, &mesh).unwrap();
+    let result = rc::write_mesh_data(&mut write, &mesh);
+    assert_message(
+        result,
+        "Expected polygon 0 unknown flags to not overlap, but was 0x02",
+    );
+}
+
+#[test]
+fn mesh_unknown_polygon_flags_collect_warnings() {
+    let data = rc_mesh_with_polygon_flags(0x05);
+    let (_, warnings) = read_rc_mesh(&data, ReadOptions::LENIENT).unwrap();
+    let expected = Warning {
+        offset: Some(121),
+        field: Some("polygon flags".to_string()),
+        message: "Expected valid polygon flags, but was 0x05 (at 121)".to_string(),
+    };
+    assert_eq!(warnings.into_vec(), vec![expected]);
+
+    let data = rc_mesh_with_polygon_flags(0x01);
+    let (_, warnings) = read_rc_mesh(&data, ReadOptions::LENIENT).unwrap();
+    assert!(warnings.is_empty());
+}
+
+fn write_test_materials(textures: &[String], materials: &[Material]) -> Vec<u8> {
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    write_materials(&mut write, textures, materials, MatType::Ng).unwrap();
+    write.into_inner()
+}
+
+#[test]
+fn materials_texture_index_valid() {
+    let textures = vec!["sky".to_string(), "ground".to_string()];
+    let data = write_test_materials(&textures, &[textured("ground"), colored()]);
+
+    let mut read = CountingReader::new(Cursor::new(data));
+    let (materials, count) = read_materials(&mut read, &textures, MatType::Ng).unwrap();
+    read.assert_end().unwrap();
+    assert_eq!(count, 2);
+    assert_eq!(
+        material_texture_name(&materials[0], &textures),
+        Some("ground")
+    );
+    assert_eq!(material_texture_name(&materials[1], &textures), None);
+    assert_eq!(material_texture_name(&textured("water"), &textures), None);
+}
+
+#[test]
+fn materials_texture_index_out_of_range() {
+    let textures = vec!["sky".to_string(), "ground".to_string()];
+    let data = write_test_materials(&textures, &[colored(), textured("ground")]);
+
+    // the second texture is missing, so its index is out of range
+    let mut read = CountingReader::new(Cursor::new(data));
+    let err = read_materials(&mut read, &textures[..1], MatType::Ng).unwrap_err();
+    assert_message(
+        Err(err),
+        "Expected material 1 texture index < 1, but was 1 (at 76)",
+    );
+}
+
+fn assert_materials_round_trip(textures: &[String], materials: &[Material], ty: MatType) {
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    write_materials(&mut write, textures, materials, ty).unwrap();
+    let expected = write.into_inner();
+
+    let mut read = CountingReader::new(Cursor::new(expected.clone()));
+    let (actual, _count) = read_materials(&mut read, textures, ty).unwrap();
+    read.assert_end().unwrap();
+    assert_eq!(format!("{:?}", actual), format!("{:?}", materials));
+
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    write_materials(&mut write, textures, &actual, ty).unwrap();
+    assert_eq!(write.into_inner(), expected);
+}
+
+#[test]
+fn materials_round_trip() {
+    let textures = vec!["sky".to_string(), "ground".to_string()];
+    let mut soiled = textured("ground");
+    if let Material::Textured(textured) = &mut soiled {
+        textured.soil = Soil::Mud;
+        textured.flag = true;
+    }
+    let materials = [
+        textured("sky"),
+        Material::Colored(ColoredMaterial {
+            color: Color {
+                r: 12.5,
+                g: 255.0,
+                b: 0.25,
+            },
+            alpha: 0x7F,
+            soil: Soil::Water,
+        }),
+        soiled,
+        colored(),
+    ];
+    assert_materials_round_trip(&textures, &materials, MatType::Ng);
+    assert_materials_round_trip(&textures, &materials, MatType::Rc);
+}
@@ -0,0 +1,168 @@
+//! Validation of references between GameZ textures, materials, and meshes,
+//! and of the metadata array sizes.
+//!
+//! Writing the materials or meshes would fail (or worse, succeed) only after
+//! part of the file has already been written, so these checks are run up
+//! front to report the first offending item instead.
+use crate::materials::MatType;
+use mech3ax_api_types::gamez::materials::Material;
+use mech3ax_api_types::gamez::mesh::{MeshMw, MeshNg, MeshRc};
+use mech3ax_common::{assert_with_msg, Result};
+use std::collections::HashSet;
+
+/// Validate that the materials fit into the material array, and that every
+/// texture (including cycled textures) is in the texture list.
+///
+/// Returns the material count.
+pub(crate) fn validate_materials(
+    textures: &[String],
+    materials: &[Material],
+    ty: MatType,
+) -> Result<u32> {
+    let array_size = ty.size_u32();
+    let material_count = u32::try_from(materials.len())
+        .ok()
+        .filter(|count| *count <= array_size)
+        .ok_or_else(|| {
+            assert_with_msg!(
+                "Expected material count <= {} (material array size), but was {}",
+                array_size,
+                materials.len()
+            )
+        })?;
+
+    let texture_names: HashSet<&str> = textures.iter().map(String::as_str).collect();
+    for (index, material) in materials.iter().enumerate() {
+        let Material::Textured(textured) = material else {
+            continue;
+        };
+        if !texture_names.contains(textured.texture.as_str()) {
+            return Err(assert_with_msg!(
+                "Material {} texture `{}` not found in textures list",
+                index,
+                textured.texture
+            ));
+        }
+        let cycle_textures = textured.cycle.iter().flat_map(|cycle| &cycle.textures);
+        for (cycle_index, texture_name) in cycle_textures.enumerate() {
+            if !texture_names.contains(texture_name.as_str()) {
+                return Err(assert_with_msg!(
+                    "Material {} cycle texture {} `{}` not found in textures list",
+                    index,
+                    cycle_index,
+                    texture_name
+                ));
+            }
+        }
+    }
+    Ok(material_count)
+}
+
+fn validate_material_index(
+    mesh_index: usize,
+    name: &str,
+    index: usize,
+    material_index: u32,
+    material_count: u32,
+) -> Result<()> {
+    if material_index < material_count {
+        Ok(())
+    } else {
+        Err(assert_with_msg!(
+            "Expected mesh {} {} {} material index < {}, but was {}",
+            mesh_index,
+            name,
+            index,
+            material_count,
+            material_index
+        ))
+    }
+}
+
+pub(crate) fn validate_mesh_mw(
+    mesh_index: usize,
+    mesh: &MeshMw,
+    material_count: u32,
+) -> Result<()> {
+    for (index, polygon) in mesh.polygons.iter().enumerate() {
+        validate_material_index(
+            mesh_index,
+            "polygon",
+            index,
+            polygon.material_index,
+            material_count,
+        )?;
+    }
+    Ok(())
+}
+
+pub(crate) fn validate_mesh_rc(
+    mesh_index: usize,
+    mesh: &MeshRc,
+    material_count: u32,
+) -> Result<()> {
+    for (index, polygon) in mesh.polygons.iter().enumerate() {
+        validate_material_index(
+            mesh_index,
+            "polygon",
+            index,
+            polygon.material_index,
+            material_count,
+        )?;
+    }
+    Ok(())
+}
+
+pub(crate) fn validate_mesh_ng(
+    mesh_index: usize,
+    mesh: &MeshNg,
+    material_count: u32,
+) -> Result<()> {
+    for (index, polygon) in mesh.polygons.iter().enumerate() {
+        for material in &polygon.materials {
+            validate_material_index(
+                mesh_index,
+                "polygon",
+                index,
+                material.material_index,
+                material_count,
+            )?;
+        }
+    }
+    for (index, info) in mesh.material_infos.iter().enumerate() {
+        validate_material_index(
+            mesh_index,
+            "material info",
+            index,
+            info.material_index,
+            material_count,
+        )?;
+    }
+    Ok(())
+}
+
+/// Validate that an array size from the metadata is large enough for all
+/// items, since the offsets of everything after the array depend on it.
+pub(crate) fn validate_array_size(name: &str, array_size: i64, count: usize) -> Result<()> {
+    match i64::try_from(count) {
+        Ok(count) if count <= array_size => Ok(()),
+        _ => Err(assert_with_msg!(
+            "Expected metadata `{}` >= {}, but was {}",
+            name,
+            count,
+            array_size
+        )),
+    }
+}
+
+/// Grow an array size from the metadata to fit all items. If the count
+/// doesn't fit the type, the array size is left as is, and validation will
+/// fail.
+pub(crate) fn fit_array_size<T>(array_size: &mut T, count: usize)
+where
+    T: TryFrom<usize> + Ord + Copy,
+{
+    if let Ok(count) = T::try_from(count) {
+        *array_size = (*array_size).max(count);
+    }
+}
@@ -1,7 +1,10 @@
 #![warn(clippy::all, clippy::cargo)]
 #![allow(clippy::identity_op)]
+pub mod annotate;
 pub mod gamez;
 mod materials;
 pub mod mechlib;
 mod mesh;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils;
 mod textures;
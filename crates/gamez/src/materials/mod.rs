@@ -52,7 +52,7 @@ struct MaterialInfoC {
     index_max: i32,
     index_last: i32,
 }
-impl_as_bytes!(MaterialInfoC, 16);
+impl_as_bytes!(MaterialInfoC, 16, LeStruct);
 
 bitflags! {
     struct MaterialFlags: u8 {
@@ -81,7 +81,7 @@ struct MaterialC {
     soil: MSoil,    // 32
     cycle_ptr: u32, // 36
 }
-impl_as_bytes!(MaterialC, 40);
+impl_as_bytes!(MaterialC, 40, LeStruct);
 
 #[derive(Debug, Clone, Copy, NoUninit, AnyBitPattern)]
 #[repr(C)]
@@ -94,7 +94,7 @@ struct CycleInfoC {
     count2: u32,
     data_ptr: u32,
 }
-impl_as_bytes!(CycleInfoC, 28);
+impl_as_bytes!(CycleInfoC, 28, LeStruct);
 
 #[derive(Debug)]
 pub(crate) struct RawTexturedMaterial {
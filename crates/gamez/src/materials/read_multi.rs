@@ -4,7 +4,7 @@ use crate::materials::RawMaterial;
 use log::trace;
 use mech3ax_api_types::gamez::materials::{Material, TexturedMaterial};
 use mech3ax_common::io_ext::CountingReader;
-use mech3ax_common::{assert_that, Result};
+use mech3ax_common::{assert_that, assert_with_msg, Result};
 use mech3ax_types::u32_to_usize;
 use std::io::Read;
 
@@ -26,8 +26,15 @@ pub(crate) fn read_materials(
             let material = match material {
                 RawMaterial::Textured(mat) => {
                     let texture_index = u32_to_usize(mat.pointer);
-                    assert_that!("texture index", texture_index < textures.len(), read.offset)?;
-                    let texture = textures[texture_index].clone();
+                    let texture = textures.get(texture_index).cloned().ok_or_else(|| {
+                        assert_with_msg!(
+                            "Expected material {} texture index < {}, but was {} (at {})",
+                            index,
+                            textures.len(),
+                            texture_index,
+                            read.prev + 16
+                        )
+                    })?;
                     trace!("{} -> `{}`", texture, texture_index);
 
                     Material::Textured(TexturedMaterial {
@@ -0,0 +1,114 @@
+//! Bounding boxes of mechlib models.
+use mech3ax_api_types::gamez::mechlib::{ModelBounds, ModelMw, ModelPm};
+use mech3ax_api_types::nodes::mw::NodeMw;
+use mech3ax_api_types::nodes::pm::NodePm;
+use mech3ax_api_types::Vec3;
+use mech3ax_nodes::transform::{multiply, Matrix4, IDENTITY};
+use mech3ax_nodes::{mw, pm};
+use std::collections::HashSet;
+
+/// The mesh index and children of an object 3D node.
+type ObjectNode<'a> = (i32, &'a [u32]);
+
+fn transform_point(m: &Matrix4, p: &Vec3) -> Vec3 {
+    Vec3 {
+        x: m[0][0] * p.x + m[0][1] * p.y + m[0][2] * p.z + m[0][3],
+        y: m[1][0] * p.x + m[1][1] * p.y + m[1][2] * p.z + m[1][3],
+        z: m[2][0] * p.x + m[2][1] * p.y + m[2][2] * p.z + m[2][3],
+    }
+}
+
+/// Walk the node tree from the root, and grow the box by the vertices of
+/// every mesh in world space.
+///
+/// Mechlib models don't store the actual parent indices, so the tree is
+/// walked down using the children instead.
+fn model_bounds<'a, N>(
+    nodes: &'a [N],
+    local: fn(&N) -> Matrix4,
+    object3d: fn(&'a N) -> Option<ObjectNode<'a>>,
+    vertices: impl Fn(usize) -> Option<&'a [Vec3]>,
+) -> ModelBounds {
+    let mut min = Vec3 {
+        x: f32::INFINITY,
+        y: f32::INFINITY,
+        z: f32::INFINITY,
+    };
+    let mut max = Vec3 {
+        x: f32::NEG_INFINITY,
+        y: f32::NEG_INFINITY,
+        z: f32::NEG_INFINITY,
+    };
+    let mut empty = true;
+
+    let mut visited = HashSet::new();
+    let mut stack = vec![(0u32, IDENTITY)];
+    while let Some((index, parent_world)) = stack.pop() {
+        // a repeated node means a cycle or shared child, which is only
+        // counted once
+        if !visited.insert(index) {
+            continue;
+        }
+        let Some(node) = usize::try_from(index).ok().and_then(|i| nodes.get(i)) else {
+            continue;
+        };
+        let Some((mesh_index, children)) = object3d(node) else {
+            continue;
+        };
+        let world = multiply(&parent_world, &local(node));
+
+        let mesh_vertices = usize::try_from(mesh_index).ok().and_then(&vertices);
+        for vertex in mesh_vertices.unwrap_or_default() {
+            let p = transform_point(&world, vertex);
+            min.x = min.x.min(p.x);
+            min.y = min.y.min(p.y);
+            min.z = min.z.min(p.z);
+            max.x = max.x.max(p.x);
+            max.y = max.y.max(p.y);
+            max.z = max.z.max(p.z);
+            empty = false;
+        }
+        stack.extend(children.iter().map(|&child| (child, world)));
+    }
+
+    if empty {
+        ModelBounds {
+            min: Vec3::DEFAULT,
+            max: Vec3::DEFAULT,
+            empty,
+        }
+    } else {
+        ModelBounds { min, max, empty }
+    }
+}
+
+/// The bounding box of a MechWarrior 3 model, with the node transforms
+/// applied. Empty models have a zero box.
+pub fn model_bounds_mw(model: &ModelMw) -> ModelBounds {
+    model_bounds(
+        &model.nodes,
+        mw::node_local_matrix,
+        |node| match node {
+            NodeMw::Object3d(object3d) => Some((object3d.mesh_index, &object3d.children)),
+            _ => None,
+        },
+        |index| model.meshes.get(index).map(|mesh| mesh.vertices.as_slice()),
+    )
+}
+
+/// The bounding box of a Pirate's Moon model, with the node transforms
+/// applied. Empty models have a zero box.
+pub fn model_bounds_pm(model: &ModelPm) -> ModelBounds {
+    model_bounds(
+        &model.nodes,
+        pm::node_local_matrix,
+        |node| match node {
+            NodePm::Object3d(object3d) => Some((object3d.mesh_index, &object3d.children)),
+            _ => None,
+        },
+        |index| model.meshes.get(index).map(|mesh| mesh.vertices.as_slice()),
+    )
+}
+
+#[cfg(test)]
+mod tests;
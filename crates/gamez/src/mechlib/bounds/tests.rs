@@ -0,0 +1,95 @@
+use super::model_bounds_mw;
+use crate::test_utils::mesh as base_mesh;
+use mech3ax_api_types::gamez::mechlib::{ModelBounds, ModelMw};
+use mech3ax_api_types::gamez::mesh::MeshMw;
+use mech3ax_api_types::nodes::mw::{NodeMw, Object3d};
+use mech3ax_api_types::nodes::{NodeFlags, Transformation};
+use mech3ax_api_types::Vec3;
+use mech3ax_nodes::test_utils;
+
+const fn vec3(x: f32, y: f32, z: f32) -> Vec3 {
+    Vec3 { x, y, z }
+}
+
+fn object3d(translation: Vec3, mesh_index: i32, children: Vec<u32>) -> NodeMw {
+    let object3d = test_utils::object3d();
+    NodeMw::Object3d(Object3d {
+        transformation: Some(Transformation {
+            rotation: Vec3::DEFAULT,
+            translation,
+            matrix: None,
+        }),
+        flags: NodeFlags {
+            has_mesh: mesh_index >= 0,
+            ..object3d.flags
+        },
+        mesh_index,
+        children,
+        ..object3d
+    })
+}
+
+fn mesh(vertices: Vec<Vec3>) -> MeshMw {
+    MeshMw {
+        vertices,
+        ..base_mesh(Vec::new())
+    }
+}
+
+#[test]
+fn bounds_apply_node_transforms() {
+    // mechlib models don't have parent indices, only children
+    let model = ModelMw {
+        nodes: vec![
+            object3d(vec3(10.0, 20.0, 30.0), 0, vec![1]),
+            object3d(vec3(-5.0, 0.0, 1.0), 1, Vec::new()),
+        ],
+        meshes: vec![
+            mesh(vec![vec3(0.0, 0.0, 0.0), vec3(1.0, 2.0, 3.0)]),
+            mesh(vec![vec3(0.0, -4.0, 0.0)]),
+        ],
+        mesh_ptrs: vec![0x100, 0x200],
+    };
+    let expected = ModelBounds {
+        min: vec3(5.0, 16.0, 30.0),
+        max: vec3(11.0, 22.0, 33.0),
+        empty: false,
+    };
+    assert_eq!(model_bounds_mw(&model), expected);
+}
+
+#[test]
+fn bounds_apply_rotation() {
+    // 90 degrees around Z
+    let mut node = object3d(vec3(0.0, 0.0, 0.0), 0, Vec::new());
+    if let NodeMw::Object3d(object3d) = &mut node {
+        if let Some(transformation) = &mut object3d.transformation {
+            transformation.rotation = vec3(0.0, 0.0, std::f32::consts::FRAC_PI_2);
+        }
+    }
+    let model = ModelMw {
+        nodes: vec![node],
+        meshes: vec![mesh(vec![vec3(1.0, 0.0, 0.0)])],
+        mesh_ptrs: vec![0x100],
+    };
+    let bounds = model_bounds_mw(&model);
+    assert!(!bounds.empty);
+    assert!(bounds.min.x.abs() < 1e-6, "{:?}", bounds);
+    assert!((bounds.min.y.abs() - 1.0).abs() < 1e-6, "{:?}", bounds);
+    assert_eq!(bounds.min, bounds.max);
+}
+
+#[test]
+fn bounds_empty_model() {
+    let model = ModelMw {
+        nodes: vec![object3d(vec3(10.0, 20.0, 30.0), -1, Vec::new())],
+        meshes: Vec::new(),
+        mesh_ptrs: Vec::new(),
+    };
+    let expected = ModelBounds {
+        min: Vec3::DEFAULT,
+        max: Vec3::DEFAULT,
+        empty: true,
+    };
+    assert_eq!(model_bounds_mw(&model), expected);
+}
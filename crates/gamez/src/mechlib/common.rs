@@ -17,7 +17,7 @@ pub fn read_version(read: &mut CountingReader<impl Read>, game: GameType) -> Res
         GameType::RC => return Err(assert_with_msg!("Recoil has no mechlib")),
         GameType::CS => return Err(assert_with_msg!("Crimson Skies has no mechlib")),
     };
-    assert_that!("version", actual == expected, read.prev)?;
+    assert_that!("version", version actual == expected, read.prev)?;
     read.assert_end()
 }
 
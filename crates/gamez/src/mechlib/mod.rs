@@ -1,7 +1,9 @@
+mod bounds;
 mod common;
 pub mod mw;
 pub mod pm;
 
+pub use bounds::{model_bounds_mw, model_bounds_pm};
 pub use common::{
     read_format, read_materials, read_version, write_format, write_materials, write_version,
     FORMAT, VERSION_MW, VERSION_PM,
@@ -4,7 +4,7 @@ use mech3ax_api_types::gamez::mechlib::ModelPm;
 use mech3ax_api_types::gamez::mesh::MeshNg;
 use mech3ax_api_types::nodes::pm::{Lod, NodePm, Object3d};
 use mech3ax_common::io_ext::{CountingReader, CountingWriter};
-use mech3ax_common::Result;
+use mech3ax_common::{ReadContext, Result};
 use mech3ax_nodes::pm::{
     mechlib_only_err_pm, read_node_mechlib, write_node_data, write_node_info, WrappedNodePm,
     WrapperPm,
@@ -52,7 +52,12 @@ fn read_node_and_mesh_object3d(
         let wrapped_mesh = read_mesh_info(read)?;
         // TODO: we ought to base this on the materials in mechlib, but...
         let material_count = 4096;
-        let mesh = read_mesh_data(read, wrapped_mesh, material_count)?;
+        let mesh = read_mesh_data(
+            read,
+            wrapped_mesh,
+            material_count,
+            &mut ReadContext::default(),
+        )?;
         meshes.push(mesh);
     } else {
         object3d.mesh_index = -1;
@@ -99,7 +99,7 @@ pub(crate) struct LightC {
     pub(crate) unk68: f32,       // 68
     pub(crate) unk72: f32,       // 72
 }
-impl_as_bytes!(LightC, 76);
+impl_as_bytes!(LightC, 76, LeStruct);
 
 fn assert_light(light: &LightC, offset: usize) -> Result<LightFlags> {
     // RC: 0/3224, 1/48
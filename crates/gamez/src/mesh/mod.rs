@@ -1,4 +1,4 @@
-mod common;
+pub(crate) mod common;
 pub(crate) mod mw;
 pub(crate) mod ng;
 pub(crate) mod rc;
@@ -34,7 +34,7 @@ pub(crate) struct MeshMwC {
     unk84: f32,         // 84
     zero88: u32,        // 88
 }
-impl_as_bytes!(MeshMwC, 92);
+impl_as_bytes!(MeshMwC, 92, LeStruct);
 pub(crate) const MESH_C_SIZE: u32 = MeshMwC::SIZE;
 
 #[derive(Debug, Clone, Copy, NoUninit, AnyBitPattern)]
@@ -50,7 +50,7 @@ struct PolygonMwC {
     material_index: u32,   // 28
     texture_info: u32,     // 32
 }
-impl_as_bytes!(PolygonMwC, 36);
+impl_as_bytes!(PolygonMwC, 36, LeStruct);
 
 pub(crate) struct WrappedMeshMw {
     pub(crate) mesh: MeshMw,
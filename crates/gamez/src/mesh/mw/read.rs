@@ -3,7 +3,7 @@ use crate::mesh::common::*;
 use log::trace;
 use mech3ax_api_types::gamez::mesh::{MeshMw, PolygonMw};
 use mech3ax_common::io_ext::CountingReader;
-use mech3ax_common::{assert_that, Result};
+use mech3ax_common::{assert_that, ReadContext, Result};
 use mech3ax_types::{Bool32, Ptr};
 use std::io::Read;
 
@@ -94,10 +94,11 @@ fn assert_polygon(
     offset: usize,
     material_count: u32,
     poly_index: u32,
+    ctx: &mut ReadContext,
 ) -> Result<(u32, u32, bool, bool, PolygonMw)> {
     let vertex_info = poly.vertex_info.0;
     assert_that!("vertex info", vertex_info < 0x3FF, offset + 0)?;
-    assert_that!("field 04", 0 <= poly.unk04 <= 20, offset + 4)?;
+    ctx.check(assert_that!("field 04", 0 <= poly.unk04 <= 20, offset + 4))?;
 
     let unk_bit = (vertex_info & 0x100) != 0;
     let vtx_bit = (vertex_info & 0x200) != 0;
@@ -143,13 +144,14 @@ fn read_polygons(
     read: &mut CountingReader<impl Read>,
     count: u32,
     material_count: u32,
+    ctx: &mut ReadContext,
 ) -> Result<Vec<PolygonMw>> {
     let poly_infos = (0..count)
         .map(|index| {
             trace!("Reading polygon info {}/{}", index, count);
             let poly: PolygonMwC = read.read_struct()?;
 
-            let result = assert_polygon(poly, read.prev, material_count, index)?;
+            let result = assert_polygon(poly, read.prev, material_count, index, ctx)?;
             Ok(result)
         })
         .collect::<Result<Vec<_>>>()?;
@@ -194,6 +196,7 @@ pub(crate) fn read_mesh_data(
     read: &mut CountingReader<impl Read>,
     wrapped: WrappedMeshMw,
     material_count: u32,
+    ctx: &mut ReadContext,
 ) -> Result<MeshMw> {
     let mut mesh = wrapped.mesh;
 
@@ -225,7 +228,7 @@ pub(crate) fn read_mesh_data(
         mesh.lights = read_lights(read, wrapped.light_count)?;
     }
 
-    mesh.polygons = read_polygons(read, wrapped.polygon_count, material_count)?;
+    mesh.polygons = read_polygons(read, wrapped.polygon_count, material_count, ctx)?;
 
     Ok(mesh)
 }
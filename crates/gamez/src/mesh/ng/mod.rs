@@ -37,7 +37,7 @@ pub(crate) struct MeshNgC {
     material_count: u32,          // 92
     materials_ptr: Ptr,           // 96
 }
-impl_as_bytes!(MeshNgC, 100);
+impl_as_bytes!(MeshNgC, 100, LeStruct);
 pub(crate) const MESH_C_SIZE: u32 = MeshNgC::SIZE;
 
 #[derive(Debug, Clone, Copy, NoUninit, AnyBitPattern)]
@@ -54,7 +54,7 @@ struct PolygonNgC {
     unk32: Ptr,            // 32
     unk36: Hex<u32>,       // 36
 }
-impl_as_bytes!(PolygonNgC, 40);
+impl_as_bytes!(PolygonNgC, 40, LeStruct);
 
 bitflags! {
     struct PolygonBitFlags: u32 {
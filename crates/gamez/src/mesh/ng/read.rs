@@ -2,8 +2,9 @@ use super::{MeshNgC, PolygonBitFlags, PolygonNgC, WrappedMeshNg};
 use crate::mesh::common::*;
 use log::trace;
 use mech3ax_api_types::gamez::mesh::{MeshMaterialInfo, MeshNg, PolygonMaterialNg, PolygonNg};
+use mech3ax_common::assert::AssertionError;
 use mech3ax_common::io_ext::CountingReader;
-use mech3ax_common::{assert_that, assert_with_msg, Result};
+use mech3ax_common::{assert_that, ReadContext, Result};
 use mech3ax_types::{Bool32, Ptr};
 use std::io::Read;
 
@@ -103,6 +104,7 @@ fn assert_polygon(
     poly: PolygonNgC,
     offset: usize,
     poly_index: u32,
+    ctx: &mut ReadContext,
 ) -> Result<(u32, u32, bool, u32, PolygonNg)> {
     let vertex_info = poly.vertex_info.0;
     assert_that!("vertex info", vertex_info < 0xFFFF, offset + 0)?;
@@ -112,13 +114,17 @@ fn assert_polygon(
     // must have at least 3 vertices for a triangle
     assert_that!("verts in poly", 3 <= verts_in_poly <= 0x1FF, offset + 0)?;
 
-    let flags = PolygonBitFlags::from_bits(verts_bits).ok_or_else(|| {
-        assert_with_msg!(
+    let flags = PolygonBitFlags::from_bits_truncate(verts_bits);
+    let unknown_flags = verts_bits & !flags.bits();
+    if unknown_flags != 0 {
+        let msg = format!(
             "Expected valid polygon flags, but was 0x{:02X} (at {})",
             verts_bits,
             offset + 1,
-        )
-    })?;
+        );
+        // unknown bits are kept, so they can be written back
+        ctx.check(Err(AssertionError::at("polygon flags", offset + 1, msg)))?;
+    }
 
     let has_normals = flags.contains(PolygonBitFlags::NORMALS);
     let triangle_strip = flags.contains(PolygonBitFlags::TRI_STRIP);
@@ -131,7 +137,7 @@ fn assert_polygon(
         )?;
     }
 
-    assert_that!("field 04", -50 <= poly.unk04 <= 50, offset + 4)?;
+    ctx.check(assert_that!("field 04", -50 <= poly.unk04 <= 50, offset + 4))?;
     // must always have a vertices ptr
     assert_that!("vertices ptr", poly.vertices_ptr != Ptr::NULL, offset + 8)?;
     if has_normals {
@@ -156,6 +162,7 @@ fn assert_polygon(
         normal_indices: None,
         materials: vec![],
 
+        unknown_flags,
         unk04: poly.unk04,
         vertices_ptr: poly.vertices_ptr.0,
         normals_ptr: poly.normals_ptr.0,
@@ -179,13 +186,14 @@ fn read_polygons(
     read: &mut CountingReader<impl Read>,
     count: u32,
     material_count: u32,
+    ctx: &mut ReadContext,
 ) -> Result<Vec<PolygonNg>> {
     let poly_infos = (0..count)
         .map(|index| {
             trace!("Reading polygon info {}/{}", index, count);
             let poly: PolygonNgC = read.read_struct()?;
 
-            let result = assert_polygon(poly, read.prev, index)?;
+            let result = assert_polygon(poly, read.prev, index, ctx)?;
             Ok(result)
         })
         .collect::<Result<Vec<_>>>()?;
@@ -255,6 +263,7 @@ pub(crate) fn read_mesh_data(
     read: &mut CountingReader<impl Read>,
     wrapped: WrappedMeshNg,
     material_count: u32,
+    ctx: &mut ReadContext,
 ) -> Result<MeshNg> {
     let mut mesh = wrapped.mesh;
 
@@ -286,7 +295,7 @@ pub(crate) fn read_mesh_data(
         mesh.lights = read_lights(read, wrapped.light_count)?;
     }
 
-    mesh.polygons = read_polygons(read, wrapped.polygon_count, material_count)?;
+    mesh.polygons = read_polygons(read, wrapped.polygon_count, material_count, ctx)?;
 
     trace!(
         "Reading {} material infos at {}",
@@ -4,7 +4,7 @@ use log::trace;
 use mech3ax_api_types::gamez::mesh::{MeshMaterialInfo, MeshNg, PolygonNg, UvCoord};
 use mech3ax_api_types::{Color, Vec3};
 use mech3ax_common::io_ext::CountingWriter;
-use mech3ax_common::{assert_len, Result};
+use mech3ax_common::{assert_len, assert_with_msg, Result};
 use mech3ax_types::{AsBytes as _, Hex, Ptr};
 use std::io::Write;
 
@@ -58,7 +58,20 @@ fn write_polygons(write: &mut CountingWriter<impl Write>, polygons: &[PolygonNg]
         if polygon.normal_indices.is_some() {
             flags |= PolygonBitFlags::NORMALS;
         }
-        let vertex_info = Hex(vertex_indices_len | (flags.bits() << 8));
+        // unknown flags are only kept from a lenient read, so must not clobber
+        // the known flags or the vertex count
+        let unknown_flags = polygon.unknown_flags;
+        if unknown_flags & !0xFE != 0
+            || PolygonBitFlags::from_bits_truncate(unknown_flags) != PolygonBitFlags::empty()
+        {
+            return Err(assert_with_msg!(
+                "Expected polygon {} unknown flags to not overlap, but was 0x{:02X}",
+                index,
+                unknown_flags
+            ));
+        }
+        let verts_bits = flags.bits() | unknown_flags;
+        let vertex_info = Hex(vertex_indices_len | (verts_bits << 8));
         let poly = PolygonNgC {
             vertex_info,
             unk04: polygon.unk04,
@@ -32,7 +32,7 @@ pub(crate) struct MeshRcC {
     unk76: f32,         // 76
     unk80: f32,         // 80
 }
-impl_as_bytes!(MeshRcC, 84);
+impl_as_bytes!(MeshRcC, 84, LeStruct);
 pub(crate) const MESH_C_SIZE: u32 = MeshRcC::SIZE;
 
 #[derive(Debug, Clone, Copy, NoUninit, AnyBitPattern)]
@@ -46,7 +46,7 @@ struct PolygonRcC {
     material_index: u32,   // 28
     unk24: Hex<u32>,       // 24
 }
-impl_as_bytes!(PolygonRcC, 28);
+impl_as_bytes!(PolygonRcC, 28, LeStruct);
 
 bitflags! {
     struct PolygonBitFlags: u32 {
@@ -2,8 +2,9 @@ use super::{MeshRcC, PolygonBitFlags, PolygonRcC, WrappedMeshRc};
 use crate::mesh::common::*;
 use log::trace;
 use mech3ax_api_types::gamez::mesh::{MeshRc, PolygonRc};
+use mech3ax_common::assert::AssertionError;
 use mech3ax_common::io_ext::CountingReader;
-use mech3ax_common::{assert_that, assert_with_msg, Result};
+use mech3ax_common::{assert_that, ReadContext, Result};
 use mech3ax_types::{Bool32, Ptr};
 use std::io::Read;
 
@@ -92,6 +93,7 @@ fn assert_polygon(
     offset: usize,
     material_count: u32,
     poly_index: u32,
+    ctx: &mut ReadContext,
 ) -> Result<(u32, u32, bool, bool, PolygonRc)> {
     let vertex_info = poly.vertex_info.0;
     assert_that!("vertex info", vertex_info < 0xFFFF, offset + 0)?;
@@ -99,17 +101,21 @@ fn assert_polygon(
     assert_that!("verts in poly", verts_in_poly > 0, offset + 0)?;
 
     let verts_bits = (vertex_info & 0xFF00) >> 8;
-    let flags = PolygonBitFlags::from_bits(verts_bits).ok_or_else(|| {
-        assert_with_msg!(
+    let flags = PolygonBitFlags::from_bits_truncate(verts_bits);
+    let unknown_flags = verts_bits & !flags.bits();
+    if unknown_flags != 0 {
+        let msg = format!(
             "Expected valid polygon flags, but was 0x{:02X} (at {})",
             verts_bits,
             offset + 1,
-        )
-    })?;
+        );
+        // unknown bits are kept, so they can be written back
+        ctx.check(Err(AssertionError::at("polygon flags", offset + 1, msg)))?;
+    }
     let unk0_flag = flags.contains(PolygonBitFlags::UNK0);
     let has_normals = flags.contains(PolygonBitFlags::NORMALS);
 
-    assert_that!("field 04", 0 <= poly.unk04 <= 20, offset + 4)?;
+    ctx.check(assert_that!("field 04", 0 <= poly.unk04 <= 20, offset + 4))?;
     assert_that!("vertices ptr", poly.vertices_ptr != Ptr::NULL, offset + 8)?;
     if has_normals {
         assert_that!("normals ptr", poly.normals_ptr != Ptr::NULL, offset + 12)?;
@@ -130,6 +136,7 @@ fn assert_polygon(
         uv_coords: None,
         material_index: poly.material_index,
         unk0_flag,
+        unknown_flags,
         unk04: poly.unk04,
         unk24: poly.unk24.0,
         vertices_ptr: poly.vertices_ptr.0,
@@ -144,13 +151,14 @@ fn read_polygons(
     read: &mut CountingReader<impl Read>,
     count: u32,
     material_count: u32,
+    ctx: &mut ReadContext,
 ) -> Result<Vec<PolygonRc>> {
     let poly_infos = (0..count)
         .map(|index| {
             trace!("Reading polygon info {}/{}", index, count);
             let poly: PolygonRcC = read.read_struct()?;
 
-            let result = assert_polygon(poly, read.prev, material_count, index)?;
+            let result = assert_polygon(poly, read.prev, material_count, index, ctx)?;
             Ok(result)
         })
         .collect::<Result<Vec<_>>>()?;
@@ -194,6 +202,7 @@ pub(crate) fn read_mesh_data(
     read: &mut CountingReader<impl Read>,
     wrapped: WrappedMeshRc,
     material_count: u32,
+    ctx: &mut ReadContext,
 ) -> Result<MeshRc> {
     let mut mesh = wrapped.mesh;
 
@@ -225,7 +234,7 @@ pub(crate) fn read_mesh_data(
         mesh.lights = read_lights(read, wrapped.light_count)?;
     }
 
-    mesh.polygons = read_polygons(read, wrapped.polygon_count, material_count)?;
+    mesh.polygons = read_polygons(read, wrapped.polygon_count, material_count, ctx)?;
 
     Ok(mesh)
 }
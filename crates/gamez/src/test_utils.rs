@@ -0,0 +1,99 @@
+//! Fixtures shared by the tests of this crate and of other crates.
+use mech3ax_api_types::gamez::materials::{ColoredMaterial, Material, Soil, TexturedMaterial};
+use mech3ax_api_types::gamez::mesh::{MeshMw, PolygonMw};
+use mech3ax_api_types::nodes::mw::{Empty, NodeMw};
+use mech3ax_api_types::nodes::{BoundingBox, NodeFlags};
+use mech3ax_api_types::Color;
+
+pub fn textured(texture: &str) -> Material {
+    Material::Textured(TexturedMaterial {
+        texture: texture.to_string(),
+        pointer: 0,
+        cycle: None,
+        soil: Soil::Default,
+        flag: false,
+    })
+}
+
+pub fn colored() -> Material {
+    Material::Colored(ColoredMaterial {
+        color: Color::BLACK,
+        alpha: 255,
+        soil: Soil::Default,
+    })
+}
+
+/// A triangle without colors, normals, or UVs.
+pub fn polygon(material_index: u32) -> PolygonMw {
+    PolygonMw {
+        vertex_indices: vec![0, 1, 2],
+        vertex_colors: Vec::new(),
+        normal_indices: None,
+        uv_coords: None,
+        material_index,
+        texture_info: 0,
+        unk04: 0,
+        unk_bit: false,
+        vtx_bit: false,
+        vertices_ptr: 0,
+        normals_ptr: 0,
+        uvs_ptr: 0,
+        colors_ptr: 0,
+        unk_ptr: 0,
+    }
+}
+
+/// A mesh with only the polygons, and no pointers set.
+pub fn mesh(polygons: Vec<PolygonMw>) -> MeshMw {
+    MeshMw {
+        vertices: Vec::new(),
+        normals: Vec::new(),
+        morphs: Vec::new(),
+        lights: Vec::new(),
+        polygons,
+        polygons_ptr: 0,
+        vertices_ptr: 0,
+        normals_ptr: 0,
+        lights_ptr: 0,
+        morphs_ptr: 0,
+        file_ptr: false,
+        unk04: 0,
+        unk08: 0,
+        parent_count: 0,
+        unk40: 0.0,
+        unk44: 0.0,
+        unk72: 0.0,
+        unk76: 0.0,
+        unk80: 0.0,
+        unk84: 0.0,
+    }
+}
+
+pub fn empty(name: &str) -> NodeMw {
+    NodeMw::Empty(Empty {
+        name: name.to_string(),
+        flags: NodeFlags {
+            active: true,
+            altitude_surface: false,
+            intersect_surface: false,
+            intersect_bbox: false,
+            landmark: false,
+            unk08: false,
+            has_mesh: false,
+            unk10: false,
+            terrain: false,
+            can_modify: false,
+            clip_to: false,
+            tree_valid: true,
+            id_zone_check: true,
+            unk25: false,
+            unk28: false,
+        },
+        unk044: 0,
+        zone_id: 0,
+        unk116: BoundingBox::default(),
+        unk140: BoundingBox::default(),
+        unk164: BoundingBox::default(),
+        parent: 0,
+    })
+}
@@ -4,3 +4,6 @@ pub(crate) mod rc;
 
 const STATE_USED: u32 = 2;
 const STATE_UNUSED: u32 = 1;
+
+#[cfg(test)]
+mod tests;
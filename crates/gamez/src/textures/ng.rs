@@ -2,10 +2,11 @@
 use super::{STATE_UNUSED, STATE_USED};
 use bytemuck::{AnyBitPattern, NoUninit};
 use log::trace;
+use mech3ax_api_types::gamez::TextureInfoNg;
 use mech3ax_common::assert::assert_utf8;
-use mech3ax_common::io_ext::{CountingReader, CountingWriter};
-use mech3ax_common::{assert_that, Result};
-use mech3ax_types::{impl_as_bytes, u32_to_usize, AsBytes as _, Ascii, Ptr};
+use mech3ax_common::io_ext::{read_array, CountingReader, CountingWriter};
+use mech3ax_common::{assert_that, ReadContext, Result};
+use mech3ax_types::{impl_as_bytes, AsBytes as _, Ascii, Ptr};
 use std::io::{Read, Write};
 
 #[derive(Debug, Clone, Copy, NoUninit, AnyBitPattern)]
@@ -19,51 +20,98 @@ struct TextureInfoNgC {
     index: u32,         // 36
     unk40: i32,         // 40
 }
-impl_as_bytes!(TextureInfoNgC, 44);
+impl_as_bytes!(TextureInfoNgC, 44, LeStruct);
+
+fn texture_info_default(ptr: Option<u32>) -> TextureInfoNg {
+    let state = if ptr.is_some() {
+        STATE_UNUSED
+    } else {
+        STATE_USED
+    };
+    TextureInfoNg {
+        state,
+        index: 0,
+        unk40: -1,
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct TextureInfos {
+    pub(crate) names: Vec<String>,
+    pub(crate) ptrs: Vec<Option<u32>>,
+    /// Only present if any texture info isn't the usual one.
+    pub(crate) infos: Option<Vec<TextureInfoNg>>,
+}
 
 pub(crate) fn read_texture_infos(
     read: &mut CountingReader<impl Read>,
     count: u32,
-) -> Result<(Vec<String>, Vec<Option<u32>>)> {
-    let mut ptrs = Vec::with_capacity(u32_to_usize(count));
-    let names = (0..count)
-        .map(|index| {
-            trace!("Reading texture info {}/{}", index, count);
-            let info: TextureInfoNgC = read.read_struct()?;
+    ctx: &mut ReadContext,
+) -> Result<TextureInfos> {
+    let mut ptrs = Vec::new();
+    let mut infos = Vec::new();
+    let names = read_array(read, count, |read, index| {
+        trace!("Reading texture info {}/{}", index, count);
+        let info: TextureInfoNgC = read.read_struct()?;
 
-            // validate field 00 later, with used
-            assert_that!("field 04", info.zero04 == 0, read.prev + 4)?;
-            assert_that!("field 08", info.zero08 == 0, read.prev + 8)?;
-            let name = assert_utf8("texture", read.prev + 12, || info.texture.to_str_suffix())?;
-            // 2 if the texture is used, 0 if the texture is unused
-            // 1 or 3 if the texture is being processed (deallocated?)
-            assert_that!("field 32", info.state in [STATE_UNUSED, STATE_USED], read.prev + 32)?;
-            let ptr = if info.state == STATE_USED {
-                // somehow, this is now the rarer case
-                assert_that!("field 00", info.unk00 == Ptr::NULL, read.prev + 0)?;
-                None
-            } else {
-                // not sure what this is. a pointer to the previous texture in the global
-                // array? or a pointer to the texture?
-                assert_that!("field 00", info.unk00 != Ptr::NULL, read.prev + 0)?;
-                Some(info.unk00.0)
-            };
+        // validate field 00 later, with used
+        assert_that!("field 04", info.zero04 == 0, read.prev + 4)?;
+        assert_that!("field 08", info.zero08 == 0, read.prev + 8)?;
+        let name = assert_utf8("texture", read.prev + 12, || info.texture.to_str_suffix())?;
+        // 2 if the texture is used, 0 if the texture is unused
+        // 1 or 3 if the texture is being processed (deallocated?)
+        ctx.check(assert_that!(
+            "field 32",
+            info.state in [0, 1, STATE_USED, 3],
+            read.prev + 32
+        ))?;
+        let ptr = if info.state == STATE_USED {
+            // somehow, this is now the rarer case
+            assert_that!("field 00", info.unk00 == Ptr::NULL, read.prev + 0)?;
+            None
+        } else {
+            // not sure what this is. a pointer to the previous texture in the global
+            // array? or a pointer to the texture?
+            assert_that!("field 00", info.unk00 != Ptr::NULL, read.prev + 0)?;
+            Some(info.unk00.0)
+        };
 
-            assert_that!("field 36", info.index == 0, read.prev + 36)?;
-            assert_that!("field 40", info.unk40 == -1, read.prev + 40)?;
+        // these have always been 0 and -1, but are kept if read leniently
+        ctx.check(assert_that!("field 36", info.index == 0, read.prev + 36))?;
+        ctx.check(assert_that!("field 40", info.unk40 == -1, read.prev + 40))?;
 
-            ptrs.push(ptr);
-            Ok(name)
-        })
-        .collect::<Result<Vec<_>>>()?;
-    Ok((names, ptrs))
+        ptrs.push(ptr);
+        infos.push(TextureInfoNg {
+            state: info.state,
+            index: info.index,
+            unk40: info.unk40,
+        });
+        Ok(name)
+    })?;
+    let usual = infos
+        .iter()
+        .zip(&ptrs)
+        .all(|(info, ptr)| *info == texture_info_default(*ptr));
+    let infos = if usual { None } else { Some(infos) };
+    Ok(TextureInfos { names, ptrs, infos })
 }
 
 pub(crate) fn write_texture_infos(
     write: &mut CountingWriter<impl Write>,
     textures: &[String],
     ptrs: &[Option<u32>],
+    infos: Option<&[TextureInfoNg]>,
 ) -> Result<()> {
+    if let Some(infos) = infos {
+        // otherwise, extra infos would be dropped silently
+        let info_count = infos.len();
+        let texture_count = textures.len();
+        assert_that!(
+            "texture infos count",
+            info_count == texture_count,
+            write.offset
+        )?;
+    }
     let ptrs = ptrs
         .iter()
         .chain(std::iter::repeat(&Some(u32::MAX)))
@@ -72,10 +120,9 @@ pub(crate) fn write_texture_infos(
     for (index, (name, ptr)) in textures.iter().zip(ptrs).enumerate() {
         trace!("Writing texture info {}/{}", index, count);
         let texture = Ascii::from_str_suffix(name);
-        let state = if ptr.is_some() {
-            STATE_UNUSED
-        } else {
-            STATE_USED
+        let info = match infos {
+            Some(infos) => infos[index],
+            None => texture_info_default(ptr),
         };
         let unk00 = Ptr(ptr.unwrap_or(0));
         let info = TextureInfoNgC {
@@ -83,9 +130,9 @@ pub(crate) fn write_texture_infos(
             zero04: 0,
             zero08: 0,
             texture,
-            state,
-            index: 0,
-            unk40: -1,
+            state: info.state,
+            index: info.index,
+            unk40: info.unk40,
         };
         write.write_struct(&info)?;
     }
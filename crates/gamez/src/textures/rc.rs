@@ -1,9 +1,10 @@
 use super::STATE_USED;
 use bytemuck::{AnyBitPattern, NoUninit};
 use log::trace;
+use mech3ax_api_types::gamez::TextureInfoRc;
 use mech3ax_common::assert::assert_utf8;
-use mech3ax_common::io_ext::{CountingReader, CountingWriter};
-use mech3ax_common::{assert_that, Result};
+use mech3ax_common::io_ext::{read_array, CountingReader, CountingWriter};
+use mech3ax_common::{assert_that, ReadContext, Result};
 use mech3ax_types::{impl_as_bytes, AsBytes as _, Ascii};
 use std::io::{Read, Write};
 
@@ -16,41 +17,81 @@ struct TextureInfoRcC {
     state: u32,         // 28
     unk32: i32,         // 32
 }
-impl_as_bytes!(TextureInfoRcC, 36);
+impl_as_bytes!(TextureInfoRcC, 36, LeStruct);
 
+const TEXTURE_INFO_USED: TextureInfoRc = TextureInfoRc {
+    state: STATE_USED,
+    unk32: -1,
+};
+
+/// Read the texture names, and the texture infos if any texture info isn't
+/// the usual one.
 pub(crate) fn read_texture_infos(
     read: &mut CountingReader<impl Read>,
     count: u32,
-) -> Result<Vec<String>> {
-    (0..count)
-        .map(|index| {
-            trace!("Reading texture info {}/{}", index, count);
-            let info: TextureInfoRcC = read.read_struct()?;
+    ctx: &mut ReadContext,
+) -> Result<(Vec<String>, Option<Vec<TextureInfoRc>>)> {
+    let mut infos = Vec::new();
+    let names = read_array(read, count, |read, index| {
+        trace!("Reading texture info {}/{}", index, count);
+        let info: TextureInfoRcC = read.read_struct()?;
 
-            assert_that!("field 00", info.zero00 == 0, read.prev + 0)?;
-            assert_that!("field 04", info.zero04 == 0, read.prev + 4)?;
-            let texture = assert_utf8("texture", read.prev + 8, || info.texture.to_str_suffix())?;
-            assert_that!("field 28", info.state == STATE_USED, read.prev + 28)?;
-            assert_that!("field 32", info.unk32 == -1, read.prev + 32)?;
-            Ok(texture)
-        })
-        .collect::<Result<Vec<_>>>()
+        assert_that!("field 00", info.zero00 == 0, read.prev + 0)?;
+        assert_that!("field 04", info.zero04 == 0, read.prev + 4)?;
+        let texture = assert_utf8("texture", read.prev + 8, || info.texture.to_str_suffix())?;
+        // 2 if the texture is used, 0 if the texture is unused
+        // 1 or 3 if the texture is being processed (deallocated?)
+        ctx.check(assert_that!(
+            "field 28",
+            info.state in [0, 1, STATE_USED, 3],
+            read.prev + 28
+        ))?;
+        // this has always been -1, but is kept if read leniently
+        ctx.check(assert_that!("field 32", info.unk32 == -1, read.prev + 32))?;
+        infos.push(TextureInfoRc {
+            state: info.state,
+            unk32: info.unk32,
+        });
+        Ok(texture)
+    })?;
+    let infos = if infos.iter().all(|info| *info == TEXTURE_INFO_USED) {
+        None
+    } else {
+        Some(infos)
+    };
+    Ok((names, infos))
 }
 
 pub(crate) fn write_texture_infos(
     write: &mut CountingWriter<impl Write>,
     textures: &[String],
+    infos: Option<&[TextureInfoRc]>,
 ) -> Result<()> {
+    if let Some(infos) = infos {
+        // otherwise, extra infos would be dropped silently
+        let info_count = infos.len();
+        let texture_count = textures.len();
+        assert_that!(
+            "texture infos count",
+            info_count == texture_count,
+            write.offset
+        )?;
+    }
+    let infos = infos
+        .unwrap_or_default()
+        .iter()
+        .chain(std::iter::repeat(&TEXTURE_INFO_USED))
+        .copied();
     let count = textures.len();
-    for (index, name) in textures.iter().enumerate() {
+    for (index, (name, info)) in textures.iter().zip(infos).enumerate() {
         trace!("Writing texture info {}/{}", index, count);
         let texture = Ascii::from_str_suffix(name);
         let info = TextureInfoRcC {
             zero00: 0,
             zero04: 0,
             texture,
-            state: STATE_USED,
-            unk32: -1,
+            state: info.state,
+            unk32: info.unk32,
         };
         write.write_struct(&info)?;
     }
@@ -0,0 +1,232 @@
+use super::mw::{read_texture_infos, write_texture_infos};
+use mech3ax_api_types::gamez::{TextureInfoMw, TextureInfoNg, TextureInfoRc};
+use mech3ax_common::io_ext::{CountingReader, CountingWriter};
+use mech3ax_common::{ReadContext, ReadOptions};
+
+fn textures() -> Vec<String> {
+    vec!["sky".to_string(), "ground".to_string()]
+}
+
+fn round_trip(infos: Option<&[TextureInfoMw]>) -> (Vec<String>, Option<Vec<TextureInfoMw>>) {
+    let textures = textures();
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    write_texture_infos(&mut write, &textures, infos).unwrap();
+    let buf = write.into_inner();
+    assert_eq!(buf.len(), 80);
+
+    let mut read = CountingReader::new(&buf[..]);
+    let mut ctx = ReadContext::new(ReadOptions::LENIENT);
+    read_texture_infos(&mut read, 2, &mut ctx).unwrap()
+}
+
+#[test]
+fn mw_round_trip_default() {
+    let (names, infos) = round_trip(None);
+    assert_eq!(names, textures());
+    assert_eq!(infos, None);
+}
+
+#[test]
+fn mw_round_trip_non_default() {
+    let infos = [
+        TextureInfoMw {
+            state: 2,
+            index: 0,
+            unk36: -1,
+        },
+        TextureInfoMw {
+            state: 3,
+            index: 7,
+            unk36: 5,
+        },
+    ];
+    let (names, read_infos) = round_trip(Some(&infos));
+    assert_eq!(names, textures());
+    assert_eq!(read_infos.as_deref(), Some(&infos[..]));
+}
+
+#[test]
+fn mw_unexpected_field_fails_strict() {
+    let infos = [TextureInfoMw {
+        state: 2,
+        index: 0,
+        unk36: 5,
+    }];
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    write_texture_infos(&mut write, &textures()[..1], Some(&infos)).unwrap();
+    let buf = write.into_inner();
+
+    let mut read = CountingReader::new(&buf[..]);
+    let mut ctx = ReadContext::new(ReadOptions::STRICT);
+    let err = read_texture_infos(&mut read, 1, &mut ctx).unwrap_err();
+    assert_eq!(
+        format!("{:?}", err),
+        "Assert(Expected `field 36` == -1, but was 5 (at 36) (item 0 of 1))"
+    );
+}
+
+#[test]
+fn mw_write_extra_infos_fails() {
+    let infos = [TextureInfoMw {
+        state: 2,
+        index: 0,
+        unk36: -1,
+    }; 3];
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    let err = write_texture_infos(&mut write, &textures(), Some(&infos)).unwrap_err();
+    assert_eq!(
+        format!("{:?}", err),
+        "Assert(Expected `texture infos count` == 2, but was 3 (at 0))"
+    );
+    assert!(write.into_inner().is_empty());
+}
+
+#[test]
+fn rc_round_trip_non_default() {
+    let infos = [
+        TextureInfoRc {
+            state: 2,
+            unk32: -1,
+        },
+        TextureInfoRc { state: 3, unk32: 0 },
+    ];
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    super::rc::write_texture_infos(&mut write, &textures(), Some(&infos)).unwrap();
+    let buf = write.into_inner();
+
+    let mut read = CountingReader::new(&buf[..]);
+    let mut ctx = ReadContext::new(ReadOptions::LENIENT);
+    let (names, read_infos) = super::rc::read_texture_infos(&mut read, 2, &mut ctx).unwrap();
+    assert_eq!(names, textures());
+    assert_eq!(read_infos.as_deref(), Some(&infos[..]));
+    // only field 32, since state 3 is expected
+    assert_eq!(ctx.into_warnings().len(), 1);
+}
+
+#[test]
+fn rc_unexpected_field_fails_strict() {
+    let infos = [TextureInfoRc { state: 2, unk32: 0 }];
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    super::rc::write_texture_infos(&mut write, &textures()[..1], Some(&infos)).unwrap();
+    let buf = write.into_inner();
+
+    let mut read = CountingReader::new(&buf[..]);
+    let mut ctx = ReadContext::new(ReadOptions::STRICT);
+    let err = super::rc::read_texture_infos(&mut read, 1, &mut ctx).unwrap_err();
+    assert_eq!(
+        format!("{:?}", err),
+        "Assert(Expected `field 32` == -1, but was 0 (at 32) (item 0 of 1))"
+    );
+}
+
+#[test]
+fn ng_round_trip_non_default() {
+    let ptrs = [None, Some(0x1234)];
+    let infos = [
+        TextureInfoNg {
+            state: 2,
+            index: 0,
+            unk40: -1,
+        },
+        TextureInfoNg {
+            state: 1,
+            index: 7,
+            unk40: 0,
+        },
+    ];
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    super::ng::write_texture_infos(&mut write, &textures(), &ptrs, Some(&infos)).unwrap();
+    let buf = write.into_inner();
+
+    let mut read = CountingReader::new(&buf[..]);
+    let mut ctx = ReadContext::new(ReadOptions::LENIENT);
+    let read_infos = super::ng::read_texture_infos(&mut read, 2, &mut ctx).unwrap();
+    assert_eq!(read_infos.names, textures());
+    assert_eq!(read_infos.ptrs, ptrs);
+    assert_eq!(read_infos.infos.as_deref(), Some(&infos[..]));
+    // field 36 and field 40
+    assert_eq!(ctx.into_warnings().len(), 2);
+}
+
+#[test]
+fn ng_unexpected_field_fails_strict() {
+    let infos = [TextureInfoNg {
+        state: 2,
+        index: 7,
+        unk40: -1,
+    }];
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    super::ng::write_texture_infos(&mut write, &textures()[..1], &[None], Some(&infos)).unwrap();
+    let buf = write.into_inner();
+
+    let mut read = CountingReader::new(&buf[..]);
+    let mut ctx = ReadContext::new(ReadOptions::STRICT);
+    let err = super::ng::read_texture_infos(&mut read, 1, &mut ctx).unwrap_err();
+    assert_eq!(
+        format!("{:?}", err),
+        "Assert(Expected `field 36` == 0, but was 7 (at 36) (item 0 of 1))"
+    );
+}
+
+#[test]
+fn ng_round_trip_default() {
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    super::ng::write_texture_infos(&mut write, &textures(), &[None, None], None).unwrap();
+    let buf = write.into_inner();
+
+    let mut read = CountingReader::new(&buf[..]);
+    let mut ctx = ReadContext::new(ReadOptions::STRICT);
+    let read_infos = super::ng::read_texture_infos(&mut read, 2, &mut ctx).unwrap();
+    assert_eq!(read_infos.infos, None);
+}
+
+fn ng_texture_info_with_state(state: u32) -> Vec<u8> {
+    let infos = [TextureInfoNg {
+        state,
+        index: 0,
+        unk40: -1,
+    }];
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    super::ng::write_texture_infos(&mut write, &textures()[..1], &[Some(0x1234)], Some(&infos))
+        .unwrap();
+    write.into_inner()
+}
+
+#[test]
+fn ng_transitional_state_round_trip() {
+    let buf = ng_texture_info_with_state(3);
+    let mut read = CountingReader::new(&buf[..]);
+    let mut ctx = ReadContext::new(ReadOptions::STRICT);
+    let read_infos = super::ng::read_texture_infos(&mut read, 1, &mut ctx).unwrap();
+    assert_eq!(read_infos.ptrs, vec![Some(0x1234)]);
+    let infos = read_infos.infos.unwrap();
+    assert_eq!(infos[0].state, 3);
+
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    super::ng::write_texture_infos(
+        &mut write,
+        &read_infos.names,
+        &read_infos.ptrs,
+        Some(&infos),
+    )
+    .unwrap();
+    assert_eq!(write.into_inner(), buf);
+}
+
+#[test]
+fn ng_unexpected_state_fails_strict() {
+    let buf = ng_texture_info_with_state(5);
+    let mut read = CountingReader::new(&buf[..]);
+    let mut ctx = ReadContext::new(ReadOptions::STRICT);
+    let err = super::ng::read_texture_infos(&mut read, 1, &mut ctx).unwrap_err();
+    assert_eq!(
+        format!("{:?}", err),
+        "Assert(Expected `field 32` to be one of [0, 1, 2, 3], but was 5 (at 32) (item 0 of 1))"
+    );
+
+    let mut read = CountingReader::new(&buf[..]);
+    let mut ctx = ReadContext::new(ReadOptions::LENIENT);
+    let read_infos = super::ng::read_texture_infos(&mut read, 1, &mut ctx).unwrap();
+    assert_eq!(read_infos.infos.unwrap()[0].state, 5);
+    assert_eq!(ctx.into_warnings().len(), 1);
+}
@@ -0,0 +1,132 @@
+use image::DynamicImage;
+use mech3ax_api_types::image::{TextureAlpha, TextureInfo, TexturePalette};
+use mech3ax_common::{assert_with_msg, Result};
+use std::io::{self, Cursor};
+
+/// The keyword of the PNG text chunk describing the original texture format.
+pub const FORMAT_KEYWORD: &str = "mech3ax:format";
+
+fn palette_kind(palette: &TexturePalette) -> &'static str {
+    match palette {
+        TexturePalette::None => "none",
+        TexturePalette::Local(_) => "local",
+        TexturePalette::Global(_) => "global",
+    }
+}
+
+fn alpha_kind(alpha: TextureAlpha) -> &'static str {
+    match alpha {
+        TextureAlpha::None => "none",
+        TextureAlpha::Simple => "simple",
+        TextureAlpha::Full => "full",
+    }
+}
+
+/// Describe the original format of a texture, e.g.
+/// `palette=local alpha=full width=64 height=32`.
+pub fn texture_format(info: &TextureInfo) -> String {
+    format!(
+        "palette={} alpha={} width={} height={}",
+        palette_kind(&info.palette),
+        alpha_kind(info.alpha),
+        info.width,
+        info.height,
+    )
+}
+
+/// Apply a texture format description to the texture info, so the texture
+/// is written the same way it was read.
+///
+/// The palette data can't be stored in the description, so a texture can
+/// only be switched from a palette to full color, and not the other way. The
+/// dimensions are informational, since they are taken from the image. Unknown
+/// keys are ignored.
+pub fn apply_texture_format(info: &mut TextureInfo, format: &str) -> Result<()> {
+    for pair in format.split_whitespace() {
+        let (key, value) = pair.split_once('=').ok_or_else(|| {
+            assert_with_msg!(
+                "Expected `key=value` in format of texture `{}`, but was `{}`",
+                info.name,
+                pair
+            )
+        })?;
+        match key {
+            "palette" => match value {
+                "none" => info.palette = TexturePalette::None,
+                "local" | "global" => {
+                    let current = palette_kind(&info.palette);
+                    if current != value {
+                        return Err(assert_with_msg!(
+                            "Expected texture `{}` to have a {} palette in the manifest, but was {}",
+                            info.name,
+                            value,
+                            current
+                        ));
+                    }
+                }
+                _ => {
+                    return Err(assert_with_msg!(
+                    "Expected palette of texture `{}` to be none, local, or global, but was `{}`",
+                    info.name,
+                    value
+                ))
+                }
+            },
+            "alpha" => {
+                info.alpha = match value {
+                    "none" => TextureAlpha::None,
+                    "simple" => TextureAlpha::Simple,
+                    "full" => TextureAlpha::Full,
+                    _ => {
+                        return Err(assert_with_msg!(
+                        "Expected alpha of texture `{}` to be none, simple, or full, but was `{}`",
+                        info.name,
+                        value
+                    ))
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Encode a texture as a PNG, with its original format in a text chunk.
+pub fn encode_png(image: &DynamicImage, info: &TextureInfo) -> Result<Vec<u8>> {
+    let rgba;
+    let (color, bytes) = match image {
+        DynamicImage::ImageRgb8(rgb) => (png::ColorType::Rgb, rgb.as_raw().as_slice()),
+        DynamicImage::ImageRgba8(rgba) => (png::ColorType::Rgba, rgba.as_raw().as_slice()),
+        _ => {
+            rgba = image.to_rgba8();
+            (png::ColorType::Rgba, rgba.as_raw().as_slice())
+        }
+    };
+
+    let mut data = Vec::new();
+    let mut encoder = png::Encoder::new(&mut data, image.width(), image.height());
+    encoder.set_color(color);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .add_text_chunk(FORMAT_KEYWORD.to_string(), texture_format(info))
+        .map_err(io::Error::from)?;
+    let mut writer = encoder.write_header().map_err(io::Error::from)?;
+    writer.write_image_data(bytes).map_err(io::Error::from)?;
+    writer.finish().map_err(io::Error::from)?;
+    Ok(data)
+}
+
+/// Read the original texture format from a PNG's text chunk, if present.
+pub fn decode_png_format(data: &[u8]) -> Result<Option<String>> {
+    let reader = png::Decoder::new(Cursor::new(data))
+        .read_info()
+        .map_err(io::Error::from)?;
+    let format = reader
+        .info()
+        .uncompressed_latin1_text
+        .iter()
+        .find(|chunk| chunk.keyword == FORMAT_KEYWORD)
+        .map(|chunk| chunk.text.clone());
+    Ok(format)
+}
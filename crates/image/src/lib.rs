@@ -1,10 +1,14 @@
 #![warn(clippy::all, clippy::cargo)]
 #![allow(clippy::identity_op)]
+mod format;
 mod read;
 mod write;
 
-pub use read::read_textures;
-pub use write::write_textures;
+pub use format::{
+    apply_texture_format, decode_png_format, encode_png, texture_format, FORMAT_KEYWORD,
+};
+pub use read::{read_texture_manifest, read_textures, read_textures_raw};
+pub use write::{sort_textures, write_textures};
 
 use bytemuck::{AnyBitPattern, NoUninit};
 use mech3ax_types::{bitflags, impl_as_bytes, Ascii, Maybe};
@@ -19,7 +23,7 @@ struct TexturesHeaderC {
     zero16: u32,               // 16
     zero20: u32,               // 20
 }
-impl_as_bytes!(TexturesHeaderC, 24);
+impl_as_bytes!(TexturesHeaderC, 24, LeStruct);
 
 macro_rules! global_palette_len {
     () => {
@@ -36,7 +40,7 @@ struct TextureEntryC {
     start_offset: u32,  // 32
     palette_index: i32, // 36
 }
-impl_as_bytes!(TextureEntryC, 40);
+impl_as_bytes!(TextureEntryC, 40, LeStruct);
 
 bitflags! {
     struct TexFlags: u32 {
@@ -67,4 +71,7 @@ struct TextureInfoC {
     palette_count: u16, // 12
     stretch: Stretch,   // 14
 }
-impl_as_bytes!(TextureInfoC, 16);
+impl_as_bytes!(TextureInfoC, 16, LeStruct);
+
+#[cfg(test)]
+mod tests;
@@ -9,32 +9,55 @@ use mech3ax_common::io_ext::CountingReader;
 use mech3ax_common::{assert_that, Error, Rename, Result};
 use mech3ax_pixel_ops::{pal8to888, pal8to888a, rgb565to888, rgb565to888a, simple_alpha};
 use mech3ax_types::{u16_to_usize, u32_to_usize};
-use std::io::Read;
+use std::io::{Read, Seek, SeekFrom};
 
 pub fn read_textures<F, E>(
     read: &mut CountingReader<impl Read>,
     mut save_texture: F,
 ) -> std::result::Result<TextureManifest, E>
 where
-    F: FnMut(&str, DynamicImage) -> std::result::Result<(), E>,
+    F: FnMut(&str, &TextureInfo, DynamicImage) -> std::result::Result<(), E>,
     E: From<Error> + From<std::io::Error> + From<mech3ax_common::assert::AssertionError>,
 {
-    let header: TexturesHeaderC = read.read_struct()?;
+    read_textures_with(read, |filename, info, data, global_palettes| {
+        let image = decode_texture(info, data, global_palettes);
+        save_texture(filename, info, image)
+    })
+}
 
-    assert_that!("field 00", header.zero00 == 0, read.prev + 0)?;
-    assert_that!("has entries", header.has_entries == 1, read.prev + 4)?;
-    assert_that!(
-        "global palette count",
-        header.global_palette_count >= 0,
-        read.prev + 8
-    )?;
-    assert_that!("texture count", header.texture_count > 0, read.prev + 12)?;
-    assert_that!("field 16", header.zero16 == 0, read.prev + 16)?;
-    assert_that!("field 20", header.zero20 == 0, read.prev + 20)?;
+/// Read the textures without decoding the image data.
+///
+/// The callback receives the texture data as it is in the file, so it can
+/// be passed through without a decode/encode round-trip. The data is the
+/// palette indices (one byte per pixel) if the texture has a palette, or
+/// the RGB565 colors (two bytes per pixel) otherwise. This is followed by
+/// the alpha values (one byte per pixel) if the alpha is
+/// [`TextureAlpha::Full`], and then the RGB565 palette if the palette is
+/// [`TexturePalette::Local`]. Global palettes are part of the manifest.
+///
+/// The manifest is the same as the one [`read_textures`] returns.
+pub fn read_textures_raw<F, E>(
+    read: &mut CountingReader<impl Read>,
+    mut save_texture: F,
+) -> std::result::Result<TextureManifest, E>
+where
+    F: FnMut(&str, &TextureInfo, &[u8]) -> std::result::Result<(), E>,
+    E: From<Error> + From<std::io::Error> + From<mech3ax_common::assert::AssertionError>,
+{
+    read_textures_with(read, |filename, info, data, _global_palettes| {
+        save_texture(filename, info, data)
+    })
+}
 
-    let texture_entries =
-        read_texture_entries(read, header.texture_count, header.global_palette_count)?;
-    let global_palettes = read_global_palettes(read, header.global_palette_count)?;
+fn read_textures_with<F, E>(
+    read: &mut CountingReader<impl Read>,
+    mut save_texture: F,
+) -> std::result::Result<TextureManifest, E>
+where
+    F: FnMut(&str, &TextureInfo, &[u8], &[PaletteData]) -> std::result::Result<(), E>,
+    E: From<Error> + From<std::io::Error> + From<mech3ax_common::assert::AssertionError>,
+{
+    let (texture_entries, global_palettes) = read_texture_table(read)?;
 
     // rename only required for rc...
     let mut seen = Rename::new();
@@ -51,9 +74,8 @@ where
             debug!("Reading texture {}/`{}`", index, name);
             assert_that!("texture offset", read.offset == start_offset, read.offset)?;
 
-            let global_palette = palette_index.map(|i| (i, &global_palettes[u32_to_usize(i)]));
-
-            let (mut info, image) = read_texture(read, name, global_palette)?;
+            let (mut info, data) = read_texture(read, name, palette_index)?;
+            info.index = Some(index);
             info.rename = seen.insert(&info.name);
 
             let filename = info
@@ -62,7 +84,7 @@ where
                 .inspect(|renamed| debug!("Renaming texture from `{}` to `{}`", info.name, renamed))
                 .unwrap_or(&info.name);
             debug!("Saving texture {}: `{}`", index, filename);
-            save_texture(filename, image)?;
+            save_texture(filename, &info, &data, &global_palettes)?;
             Ok(info)
         })
         .collect::<std::result::Result<Vec<_>, E>>()?;
@@ -72,9 +94,76 @@ where
     Ok(TextureManifest {
         texture_infos,
         global_palettes,
+        image_format: None,
     })
 }
 
+/// Read only the texture table and texture infos, without decoding any
+/// image data.
+///
+/// The image data is skipped by seeking past it, so this is much faster
+/// than [`read_textures`] when only e.g. the texture count or dimensions
+/// are needed. The manifest is the same as the one `read_textures` returns.
+pub fn read_texture_manifest(
+    read: &mut CountingReader<impl Read + Seek>,
+) -> Result<TextureManifest> {
+    let (texture_entries, global_palettes) = read_texture_table(read)?;
+
+    let mut seen = Rename::new();
+    let texture_infos = texture_entries
+        .into_iter()
+        .map(|entry| {
+            let TextureEntry {
+                index,
+                name,
+                start_offset,
+                palette_index,
+            } = entry;
+
+            debug!("Reading texture info {}/`{}`", index, name);
+            assert_that!("texture offset", read.offset == start_offset, read.offset)?;
+
+            let mut info = skip_texture(read, name, palette_index)?;
+            info.index = Some(index);
+            info.rename = seen.insert(&info.name);
+            Ok(info)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    // seeking past the end doesn't fail, so check the skipped data was there
+    let offset = read.offset;
+    let end = read.seek(SeekFrom::End(0))?;
+    assert_that!("texture data end", offset == end, offset)?;
+
+    Ok(TextureManifest {
+        texture_infos,
+        global_palettes,
+        image_format: None,
+    })
+}
+
+fn read_texture_table(
+    read: &mut CountingReader<impl Read>,
+) -> Result<(Vec<TextureEntry>, Vec<PaletteData>)> {
+    let header: TexturesHeaderC = read.read_struct()?;
+
+    assert_that!("field 00", header.zero00 == 0, read.prev + 0)?;
+    assert_that!("has entries", header.has_entries == 1, read.prev + 4)?;
+    assert_that!(
+        "global palette count",
+        header.global_palette_count >= 0,
+        read.prev + 8
+    )?;
+    assert_that!("texture count", header.texture_count > 0, read.prev + 12)?;
+    assert_that!("field 16", header.zero16 == 0, read.prev + 16)?;
+    assert_that!("field 20", header.zero20 == 0, read.prev + 20)?;
+
+    let texture_entries =
+        read_texture_entries(read, header.texture_count, header.global_palette_count)?;
+    let global_palettes = read_global_palettes(read, header.global_palette_count)?;
+    Ok((texture_entries, global_palettes))
+}
+
 #[derive(Debug)]
 struct TextureEntry {
     index: u32,
@@ -140,21 +229,97 @@ fn read_global_palettes(
         .collect()
 }
 
+/// The length of the image and alpha data, and of the local palette data.
+fn texture_data_len(
+    info: &TextureInfo,
+    palette_count: u16,
+    has_global_palette: bool,
+) -> (usize, usize) {
+    let size = u16_to_usize(info.width) * u16_to_usize(info.height);
+    let alpha_size = match info.alpha {
+        TextureAlpha::Full => size,
+        TextureAlpha::Simple | TextureAlpha::None => 0,
+    };
+    if palette_count == 0 {
+        (size * 2 + alpha_size, 0)
+    } else if has_global_palette {
+        (size + alpha_size, 0)
+    } else {
+        (size + alpha_size, u16_to_usize(palette_count) * 2)
+    }
+}
+
+fn texture_palette(
+    palette_count: u16,
+    palette_index: Option<u32>,
+    palette_data: &[u8],
+) -> TexturePalette {
+    if palette_count == 0 {
+        return TexturePalette::None;
+    }
+    match palette_index {
+        Some(index) => TexturePalette::Global(GlobalPalette {
+            index,
+            count: palette_count,
+        }),
+        None => TexturePalette::Local(PaletteData {
+            data: rgb565to888(palette_data),
+        }),
+    }
+}
+
+fn read_palette_data(read: &mut CountingReader<impl Read>, palette_len: usize) -> Result<Vec<u8>> {
+    if palette_len == 0 {
+        return Ok(Vec::new());
+    }
+    debug!("Reading palette data ({}) at {}", palette_len, read.offset);
+    Ok(read.read_bytes(palette_len)?)
+}
+
 fn read_texture(
     read: &mut CountingReader<impl Read>,
     name: String,
-    global_palette: Option<(u32, &PaletteData)>,
-) -> Result<(TextureInfo, DynamicImage)> {
+    palette_index: Option<u32>,
+) -> Result<(TextureInfo, Vec<u8>)> {
     let info_c: TextureInfoC = read.read_struct()?;
 
-    let has_global_palette = global_palette.is_some();
-    let (info, palette_count) = convert_info_from_c(name, info_c, has_global_palette, read.prev)?;
+    let has_global_palette = palette_index.is_some();
+    let (mut info, palette_count) =
+        convert_info_from_c(name, info_c, has_global_palette, read.prev)?;
+
+    let (data_len, palette_len) = texture_data_len(&info, palette_count, has_global_palette);
+    debug!("Reading image data ({} bytes) at {}", data_len, read.offset);
+    let mut data = read.read_bytes(data_len)?;
+    let palette_data = read_palette_data(read, palette_len)?;
+    info.palette = texture_palette(palette_count, palette_index, &palette_data);
+    data.extend_from_slice(&palette_data);
+    Ok((info, data))
+}
 
-    if palette_count == 0 {
-        read_img_full_color(read, info)
-    } else {
-        read_img_palette(read, info, palette_count, global_palette)
-    }
+fn skip_texture(
+    read: &mut CountingReader<impl Read + Seek>,
+    name: String,
+    palette_index: Option<u32>,
+) -> Result<TextureInfo> {
+    let info_c: TextureInfoC = read.read_struct()?;
+
+    let has_global_palette = palette_index.is_some();
+    let (mut info, palette_count) =
+        convert_info_from_c(name, info_c, has_global_palette, read.prev)?;
+
+    let (data_len, palette_len) = texture_data_len(&info, palette_count, has_global_palette);
+    skip_data(read, data_len)?;
+    let palette_data = read_palette_data(read, palette_len)?;
+    info.palette = texture_palette(palette_count, palette_index, &palette_data);
+    Ok(info)
+}
+
+fn skip_data(read: &mut CountingReader<impl Read + Seek>, len: usize) -> Result<()> {
+    debug!("Skipping image data ({} bytes) at {}", len, read.offset);
+    let end = read.offset + len;
+    // Cast safety: usize <= u64 on supported platforms
+    read.seek(SeekFrom::Start(end as u64))?;
+    Ok(())
 }
 
 fn convert_info_from_c(
@@ -200,6 +365,7 @@ fn convert_info_from_c(
 
     let info = TextureInfo {
         name,
+        index: None, // set this later
         rename: None,
         alpha,
         width: info_c.width,
@@ -213,102 +379,60 @@ fn convert_info_from_c(
     Ok((info, palette_count))
 }
 
-fn read_img_full_color(
-    read: &mut CountingReader<impl Read>,
-    info: TextureInfo,
-) -> Result<(TextureInfo, DynamicImage)> {
+/// Decode the texture data as read by [`read_texture`].
+fn decode_texture(
+    info: &TextureInfo,
+    data: &[u8],
+    global_palettes: &[PaletteData],
+) -> DynamicImage {
     let width: u32 = info.width.into();
     let height: u32 = info.height.into();
-
     let size = u32_to_usize(width) * u32_to_usize(height);
 
-    debug!(
-        "Reading full color data ({} bytes) at {}",
-        size * 2,
-        read.offset
-    );
-    let mut image_data = vec![0u8; size * 2];
-    read.read_exact(&mut image_data)?;
-
-    let alpha_data = match info.alpha {
-        TextureAlpha::Simple => Some(simple_alpha(&image_data)),
-        TextureAlpha::Full => {
-            debug!("Reading alpha data ({} bytes) at {}", size, read.offset);
-            let mut buf = vec![0; size];
-            read.read_exact(&mut buf)?;
-            Some(buf)
+    let palette = match &info.palette {
+        TexturePalette::None => return decode_full_color(info, data, width, height, size),
+        TexturePalette::Local(local) => &local.data[..],
+        TexturePalette::Global(global) => {
+            let len = u16_to_usize(global.count) * 3;
+            &global_palettes[u32_to_usize(global.index)].data[0..len]
         }
-        TextureAlpha::None => None,
     };
 
-    let img = if let Some(alpha) = alpha_data {
-        let image_data = rgb565to888a(&image_data, &alpha);
-        DynamicImage::ImageRgba8(RgbaImage::from_raw(width, height, image_data).unwrap())
-    } else {
-        let image_data = rgb565to888(&image_data);
-        DynamicImage::ImageRgb8(RgbImage::from_raw(width, height, image_data).unwrap())
-    };
-
-    Ok((info, img))
-}
-
-fn read_img_palette(
-    read: &mut CountingReader<impl Read>,
-    mut info: TextureInfo,
-    palette_count: u16,
-    global_palette: Option<(u32, &PaletteData)>,
-) -> Result<(TextureInfo, DynamicImage)> {
-    let width: u32 = info.width.into();
-    let height: u32 = info.height.into();
-
-    let size = u32_to_usize(width) * u32_to_usize(height);
-
-    debug!("Reading palette indices ({}) at {}", size, read.offset);
-    let mut index_data = vec![0u8; size];
-    read.read_exact(&mut index_data)?;
-
-    let alpha_data = match &info.alpha {
+    let index_data = &data[..size];
+    match info.alpha {
         TextureAlpha::Full => {
-            debug!("Reading alpha data ({}) at {}", size, read.offset);
-            let mut buf = vec![0; size];
-            read.read_exact(&mut buf)?;
-            Some(buf)
+            let alpha = &data[size..size * 2];
+            let image_data = pal8to888a(index_data, palette, alpha);
+            DynamicImage::ImageRgba8(RgbaImage::from_raw(width, height, image_data).unwrap())
         }
         // TODO: skipping this for now, how would you know which pixel was
         // transparent? the first? the last? some color?
-        TextureAlpha::Simple => None,
-        TextureAlpha::None => None,
-    };
-
-    let convert_image = |palette| {
-        if let Some(alpha) = alpha_data {
-            let image_data = pal8to888a(&index_data, palette, &alpha);
-            DynamicImage::ImageRgba8(RgbaImage::from_raw(width, height, image_data).unwrap())
-        } else {
-            let image_data = pal8to888(&index_data, palette);
+        TextureAlpha::Simple | TextureAlpha::None => {
+            let image_data = pal8to888(index_data, palette);
             DynamicImage::ImageRgb8(RgbImage::from_raw(width, height, image_data).unwrap())
         }
-    };
+    }
+}
 
-    let img = if let Some((index, palette)) = global_palette {
-        let global = GlobalPalette {
-            index,
-            count: palette_count,
-        };
-        info.palette = TexturePalette::Global(global);
-        let len = u16_to_usize(palette_count) * 3;
-        convert_image(&palette.data[0..len])
-    } else {
-        let palette_len = u16_to_usize(palette_count) * 2;
-        debug!("Reading palette data ({}) at {}", palette_len, read.offset);
-        let mut palette_data = vec![0u8; palette_len];
-        read.read_exact(&mut palette_data)?;
-        let palette_data = rgb565to888(&palette_data);
-        let image = convert_image(&palette_data);
-        let local = PaletteData { data: palette_data };
-        info.palette = TexturePalette::Local(local);
-        image
+fn decode_full_color(
+    info: &TextureInfo,
+    data: &[u8],
+    width: u32,
+    height: u32,
+    size: usize,
+) -> DynamicImage {
+    let image_data = &data[..size * 2];
+    let alpha_data = match info.alpha {
+        TextureAlpha::Simple => Some(simple_alpha(image_data)),
+        TextureAlpha::Full => Some(data[size * 2..size * 3].to_vec()),
+        TextureAlpha::None => None,
     };
 
-    Ok((info, img))
+    if let Some(alpha) = alpha_data {
+        let image_data = rgb565to888a(image_data, &alpha);
+        DynamicImage::ImageRgba8(RgbaImage::from_raw(width, height, image_data).unwrap())
+    } else {
+        let image_data = rgb565to888(image_data);
+        DynamicImage::ImageRgb8(RgbImage::from_raw(width, height, image_data).unwrap())
+    }
 }
@@ -0,0 +1,293 @@
+use super::{
+    apply_texture_format, decode_png_format, encode_png, read_texture_manifest, read_textures,
+    read_textures_raw, sort_textures, write_textures,
+};
+use image::{DynamicImage, RgbImage, RgbaImage};
+use mech3ax_api_types::image::{
+    PaletteData, TextureAlpha, TextureInfo, TextureManifest, TexturePalette, TextureStretch,
+};
+use mech3ax_common::io_ext::{CountingReader, CountingWriter};
+use mech3ax_common::test_utils::assert_truncated_fails;
+use mech3ax_common::Error;
+use std::collections::HashMap;
+use std::io::Cursor;
+
+fn info(name: &str, alpha: TextureAlpha, width: u16, height: u16) -> TextureInfo {
+    TextureInfo {
+        name: name.to_string(),
+        index: None,
+        rename: None,
+        alpha,
+        width,
+        height,
+        stretch: TextureStretch::None,
+        image_loaded: false,
+        alpha_loaded: false,
+        palette_loaded: false,
+        palette: TexturePalette::None,
+    }
+}
+
+fn textures() -> Vec<u8> {
+    let mut palette = info("palette", TextureAlpha::None, 2, 2);
+    palette.palette = TexturePalette::Local(PaletteData {
+        data: vec![0, 0, 0, 248, 252, 248],
+    });
+    let manifest = TextureManifest {
+        texture_infos: vec![
+            info("color", TextureAlpha::None, 4, 2),
+            palette,
+            info("alpha", TextureAlpha::Full, 3, 5),
+        ],
+        global_palettes: Vec::new(),
+        image_format: None,
+    };
+
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    write_textures::<_, Error>(&mut write, &manifest, |name| {
+        let image = match name {
+            "color" => DynamicImage::ImageRgb8(RgbImage::new(4, 2)),
+            "alpha" => DynamicImage::ImageRgba8(RgbaImage::new(3, 5)),
+            _ => DynamicImage::ImageRgb8(RgbImage::new(2, 2)),
+        };
+        Ok(image)
+    })
+    .unwrap();
+    write.into_inner()
+}
+
+#[test]
+fn manifest_only_matches_full_read() {
+    let data = textures();
+
+    let mut read = CountingReader::new(Cursor::new(data.clone()));
+    let manifest = read_texture_manifest(&mut read).unwrap();
+
+    let dimensions = manifest
+        .texture_infos
+        .iter()
+        .map(|info| (info.name.as_str(), info.width, info.height))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        dimensions,
+        vec![("color", 4, 2), ("palette", 2, 2), ("alpha", 3, 5)]
+    );
+
+    let mut read = CountingReader::new(Cursor::new(data));
+    let mut decoded = 0;
+    let expected = read_textures::<_, Error>(&mut read, |_name, _info, _image| {
+        decoded += 1;
+        Ok(())
+    })
+    .unwrap();
+    assert_eq!(decoded, 3);
+    assert_eq!(format!("{:?}", manifest), format!("{:?}", expected));
+}
+
+#[test]
+fn manifest_only_truncated() {
+    let mut data = textures();
+    data.pop();
+
+    let mut read = CountingReader::new(Cursor::new(data));
+    let err = read_texture_manifest(&mut read).unwrap_err();
+    assert!(
+        format!("{:?}", err).contains("texture data end"),
+        "{:?}",
+        err
+    );
+}
+
+#[test]
+fn read_truncated_fails() {
+    let data = textures();
+    assert_truncated_fails(&data, |data| {
+        let mut read = CountingReader::new(Cursor::new(data));
+        read_textures::<_, Error>(&mut read, |_name, _info, _image| Ok(()))
+    });
+    assert_truncated_fails(&data, |data| {
+        read_texture_manifest(&mut CountingReader::new(Cursor::new(data)))
+    });
+}
+
+#[test]
+fn png_format_round_trip() {
+    let data = textures();
+
+    let mut pngs = HashMap::new();
+    let mut read = CountingReader::new(Cursor::new(data.clone()));
+    let mut manifest = read_textures::<_, Error>(&mut read, |name, info, image| {
+        pngs.insert(name.to_string(), encode_png(&image, info)?);
+        Ok(())
+    })
+    .unwrap();
+
+    let formats = pngs
+        .iter()
+        .map(|(name, png)| (name.as_str(), decode_png_format(png).unwrap().unwrap()))
+        .collect::<HashMap<_, _>>();
+    assert_eq!(formats["color"], "palette=none alpha=none width=4 height=2");
+    assert_eq!(
+        formats["palette"],
+        "palette=local alpha=none width=2 height=2"
+    );
+    assert_eq!(formats["alpha"], "palette=none alpha=full width=3 height=5");
+
+    // the embedded format takes precedence over the manifest
+    for info in &mut manifest.texture_infos {
+        info.alpha = TextureAlpha::Simple;
+        apply_texture_format(info, &formats[info.name.as_str()]).unwrap();
+    }
+
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    write_textures::<_, Error>(&mut write, &manifest, |name| {
+        let image =
+            image::load_from_memory_with_format(&pngs[name], image::ImageFormat::Png).unwrap();
+        Ok(image)
+    })
+    .unwrap();
+    assert_eq!(write.into_inner(), data);
+}
+
+#[test]
+fn png_format_absent() {
+    let image = DynamicImage::ImageRgb8(RgbImage::new(2, 2));
+    let mut png = Cursor::new(Vec::new());
+    image.write_to(&mut png, image::ImageFormat::Png).unwrap();
+    assert_eq!(decode_png_format(png.get_ref()).unwrap(), None);
+
+    let png = encode_png(&image, &info("color", TextureAlpha::None, 2, 2)).unwrap();
+    let decoded = image::load_from_memory(&png).unwrap();
+    assert_eq!(decoded.to_rgb8(), image.to_rgb8());
+    assert!(decode_png_format(&png).unwrap().is_some());
+}
+
+#[test]
+fn png_format_cannot_add_palette() {
+    let mut info = info("color", TextureAlpha::None, 2, 2);
+    let err = apply_texture_format(&mut info, "palette=local alpha=full").unwrap_err();
+    assert!(format!("{:?}", err).contains("local palette"), "{:?}", err);
+
+    apply_texture_format(&mut info, "palette=none alpha=full unknown=1").unwrap();
+    assert_eq!(info.alpha, TextureAlpha::Full);
+    assert!(apply_texture_format(&mut info, "alpha").is_err());
+    assert!(apply_texture_format(&mut info, "alpha=some").is_err());
+}
+
+fn read_with_images(data: &[u8]) -> (TextureManifest, HashMap<String, DynamicImage>) {
+    let mut images = HashMap::new();
+    let mut read = CountingReader::new(Cursor::new(data));
+    let manifest = read_textures::<_, Error>(&mut read, |name, _info, image| {
+        images.insert(name.to_string(), image);
+        Ok(())
+    })
+    .unwrap();
+    (manifest, images)
+}
+
+fn write_with_images(
+    manifest: &TextureManifest,
+    images: &HashMap<String, DynamicImage>,
+) -> Result<Vec<u8>, Error> {
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    write_textures::<_, Error>(&mut write, manifest, |name| Ok(images[name].clone()))?;
+    Ok(write.into_inner())
+}
+
+#[test]
+fn shuffled_manifest_is_sorted() {
+    let data = textures();
+    let (mut manifest, images) = read_with_images(&data);
+    let indices = manifest
+        .texture_infos
+        .iter()
+        .map(|info| info.index)
+        .collect::<Vec<_>>();
+    assert_eq!(indices, vec![Some(0), Some(1), Some(2)]);
+
+    manifest.texture_infos.swap(0, 2);
+    let err = write_with_images(&manifest, &images).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Expected texture 0 `alpha` to have index 0, but was 2 (sort the textures first)"
+    );
+
+    sort_textures(&mut manifest).unwrap();
+    assert_eq!(write_with_images(&manifest, &images).unwrap(), data);
+}
+
+#[test]
+fn manifest_without_indices_keeps_order() {
+    let data = textures();
+    let (mut manifest, images) = read_with_images(&data);
+    for info in &mut manifest.texture_infos {
+        info.index = None;
+    }
+    sort_textures(&mut manifest).unwrap();
+    assert_eq!(write_with_images(&manifest, &images).unwrap(), data);
+}
+
+#[test]
+fn manifest_bad_indices_fail() {
+    let data = textures();
+    let (manifest, _images) = read_with_images(&data);
+
+    let mut duplicate = read_with_images(&data).0;
+    duplicate.texture_infos[2].index = Some(0);
+    let err = sort_textures(&mut duplicate).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Texture index 0 is duplicated (`color` and `alpha`)"
+    );
+
+    let mut missing = read_with_images(&data).0;
+    missing.texture_infos[1].index = Some(3);
+    let err = sort_textures(&mut missing).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Expected texture `palette` index to be less than 3, but was 3"
+    );
+
+    let mut partial = manifest;
+    partial.texture_infos[1].index = None;
+    let err = sort_textures(&mut partial).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Expected all or no textures to have an index, but 2 of 3 do"
+    );
+}
+
+#[test]
+fn raw_data_matches_file() {
+    let data = textures();
+
+    let mut read = CountingReader::new(Cursor::new(data.clone()));
+    let mut raw = Vec::new();
+    let manifest = read_textures_raw::<_, Error>(&mut read, |name, _info, texels| {
+        raw.push((name.to_string(), texels.to_vec()));
+        Ok(())
+    })
+    .unwrap();
+
+    let mut read = CountingReader::new(Cursor::new(data.clone()));
+    let expected = read_textures::<_, Error>(&mut read, |_name, _info, _image| Ok(())).unwrap();
+    assert_eq!(format!("{:?}", manifest), format!("{:?}", expected));
+
+    // color: 4x2 RGB565, palette: 2x2 indices + 2 RGB565 colors,
+    // alpha: 3x5 RGB565 + 3x5 alpha
+    let lens = raw
+        .iter()
+        .map(|(name, texels)| (name.as_str(), texels.len()))
+        .collect::<Vec<_>>();
+    assert_eq!(lens, vec![("color", 16), ("palette", 8), ("alpha", 45)]);
+
+    for (index, (name, texels)) in raw.iter().enumerate() {
+        // the start offset in the texture entry, followed by the texture info
+        let entry = 24 + index * 40 + 32;
+        let start = u32::from_le_bytes(data[entry..entry + 4].try_into().unwrap()) as usize;
+        let start = start + 16;
+        assert_eq!(&data[start..start + texels.len()], &texels[..], "{}", name);
+    }
+    let (_, last) = raw.last().unwrap();
+    assert!(data.ends_with(last));
+}
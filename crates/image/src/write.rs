@@ -4,12 +4,74 @@ use log::debug;
 use mech3ax_api_types::image::{
     PaletteData, TextureAlpha, TextureInfo, TextureManifest, TexturePalette,
 };
+use mech3ax_common::assert::assert_utf8;
 use mech3ax_common::io_ext::CountingWriter;
 use mech3ax_common::{assert_len, assert_that, assert_with_msg, Error, Result};
 use mech3ax_pixel_ops::{rgb888ato565, rgb888atopal8, rgb888to565, rgb888topal8};
 use mech3ax_types::{u16_to_usize, u32_to_usize, AsBytes as _, Ascii};
 use std::io::Write;
 
+/// Check the textures either all have an index, or none do. If they do, the
+/// indices must be unique and cover every position. Returns if they do.
+fn check_indices(texture_infos: &[TextureInfo]) -> Result<bool> {
+    let count = texture_infos.len();
+    let with_index = texture_infos
+        .iter()
+        .filter(|info| info.index.is_some())
+        .count();
+    if with_index == 0 {
+        return Ok(false);
+    }
+    if with_index != count {
+        return Err(assert_with_msg!(
+            "Expected all or no textures to have an index, but {} of {} do",
+            with_index,
+            count
+        ));
+    }
+
+    let mut seen: Vec<Option<&str>> = vec![None; count];
+    for info in texture_infos {
+        // checked above
+        let index = info.index.unwrap_or_default();
+        let slot = seen.get_mut(u32_to_usize(index)).ok_or_else(|| {
+            assert_with_msg!(
+                "Expected texture `{}` index to be less than {}, but was {}",
+                info.name,
+                count,
+                index
+            )
+        })?;
+        if let Some(other) = slot.replace(&info.name) {
+            return Err(assert_with_msg!(
+                "Texture index {} is duplicated (`{}` and `{}`)",
+                index,
+                other,
+                info.name
+            ));
+        }
+    }
+    // with no duplicates and all indices in range, none are missing
+    Ok(true)
+}
+
+/// Sort the textures by their original index, so they are written in the
+/// original order even if the manifest was reordered.
+///
+/// Textures without an index (e.g. from an older manifest) are left in the
+/// manifest order. Fails if only some textures have an index, or if an index
+/// is duplicated or missing.
+pub fn sort_textures(manifest: &mut TextureManifest) -> Result<()> {
+    if check_indices(&manifest.texture_infos)? {
+        manifest.texture_infos.sort_by_key(|info| info.index);
+    }
+    Ok(())
+}
+
+/// Write the textures in the manifest order.
+///
+/// If the textures have an index, the manifest order must match it, see
+/// [`sort_textures`].
 pub fn write_textures<F, E>(
     write: &mut CountingWriter<impl Write>,
     manifest: &TextureManifest,
@@ -22,8 +84,26 @@ where
     let TextureManifest {
         texture_infos,
         global_palettes,
+        image_format: _,
     } = manifest;
 
+    if check_indices(texture_infos)? {
+        for (position, info) in texture_infos.iter().enumerate() {
+            // checked above
+            let index = u32_to_usize(info.index.unwrap_or_default());
+            if index != position {
+                return Err(assert_with_msg!(
+                    "Expected texture {} `{}` to have index {}, but was {} (sort the textures first)",
+                    position,
+                    info.name,
+                    position,
+                    index
+                )
+                .into());
+            }
+        }
+    }
+
     let texture_count = assert_len!(u32, texture_infos.len(), "texture count")?;
     let global_palette_count = assert_len!(i32, global_palettes.len(), "global palette count")?;
     let global_palette_len = global_palette_count as u32;
@@ -74,7 +154,7 @@ fn write_texture_entries(
         .map(|(index, info)| {
             debug!("Writing texture entry {}", index);
 
-            let name = Ascii::from_str_padded(&info.name);
+            let name = assert_utf8("name", write.offset, || Ascii::from_str_padded(&info.name))?;
             let start_offset = offset;
 
             let palette_index = match &info.palette {
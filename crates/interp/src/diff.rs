@@ -0,0 +1,121 @@
+use mech3ax_api_types::interp::Script;
+use std::collections::HashMap;
+
+/// A command that differs between two versions of a script.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LineChange<'a> {
+    /// A command only in the first script, at the index in the first script.
+    Removed { index: usize, line: &'a str },
+    /// A command only in the second script, at the index in the second script.
+    Added { index: usize, line: &'a str },
+}
+
+/// A script in both lists, but with different commands.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangedScript<'a> {
+    pub name: &'a str,
+    pub lines: Vec<LineChange<'a>>,
+}
+
+/// The differences between two lists of scripts, matched by name.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ScriptDiff<'a> {
+    /// Scripts only in the second list, in the order of the second list.
+    pub added: Vec<&'a str>,
+    /// Scripts only in the first list, in the order of the first list.
+    pub removed: Vec<&'a str>,
+    /// Scripts in both lists, in the order of the first list.
+    pub changed: Vec<ChangedScript<'a>>,
+}
+
+impl ScriptDiff<'_> {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Diff the commands of two scripts, using the longest common subsequence.
+fn diff_lines<'a>(a: &'a [String], b: &'a [String]) -> Vec<LineChange<'a>> {
+    // lcs[i][j] is the length of the longest common subsequence of a[i..]
+    // and b[j..]
+    let mut lcs = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for (i, line_a) in a.iter().enumerate().rev() {
+        for (j, line_b) in b.iter().enumerate().rev() {
+            lcs[i][j] = if line_a == line_b {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut changes = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            changes.push(LineChange::Removed {
+                index: i,
+                line: &a[i],
+            });
+            i += 1;
+        } else {
+            changes.push(LineChange::Added {
+                index: j,
+                line: &b[j],
+            });
+            j += 1;
+        }
+    }
+    changes.extend(
+        a.iter()
+            .enumerate()
+            .skip(i)
+            .map(|(index, line)| LineChange::Removed { index, line }),
+    );
+    changes.extend(
+        b.iter()
+            .enumerate()
+            .skip(j)
+            .map(|(index, line)| LineChange::Added { index, line }),
+    );
+    changes
+}
+
+/// Compare two lists of scripts, e.g. from two versions of an interpreter
+/// file.
+///
+/// Scripts are matched by name. The last modified time is ignored, since it
+/// changes whenever a script is recompiled.
+pub fn diff_interp<'a>(a: &'a [Script], b: &'a [Script]) -> ScriptDiff<'a> {
+    let lookup_a: HashMap<&str, &Script> = a
+        .iter()
+        .map(|script| (script.name.as_str(), script))
+        .collect();
+    let lookup_b: HashMap<&str, &Script> = b
+        .iter()
+        .map(|script| (script.name.as_str(), script))
+        .collect();
+
+    let mut diff = ScriptDiff::default();
+    for script_a in a {
+        let name = script_a.name.as_str();
+        match lookup_b.get(name) {
+            Some(script_b) => {
+                let lines = diff_lines(&script_a.lines, &script_b.lines);
+                if !lines.is_empty() {
+                    diff.changed.push(ChangedScript { name, lines });
+                }
+            }
+            None => diff.removed.push(name),
+        }
+    }
+    diff.added = b
+        .iter()
+        .map(|script| script.name.as_str())
+        .filter(|name| !lookup_a.contains_key(name))
+        .collect();
+    diff
+}
@@ -1,9 +1,13 @@
 #![warn(clippy::all, clippy::cargo)]
 #![allow(clippy::identity_op)]
+mod diff;
 mod read;
+mod validate;
 mod write;
 
+pub use diff::{diff_interp, ChangedScript, LineChange, ScriptDiff};
 pub use read::read_interp;
+pub use validate::validate_interp;
 pub use write::write_interp;
 
 use bytemuck::{AnyBitPattern, NoUninit};
@@ -19,7 +23,7 @@ struct InterpHeaderC {
     version: u32,
     count: u32,
 }
-impl_as_bytes!(InterpHeaderC, 12);
+impl_as_bytes!(InterpHeaderC, 12, LeStruct);
 
 #[derive(Debug, Clone, Copy, NoUninit, AnyBitPattern)]
 #[repr(C)]
@@ -28,4 +32,7 @@ struct InterpEntryC {
     last_modified: u32,
     start: u32,
 }
-impl_as_bytes!(InterpEntryC, 128);
+impl_as_bytes!(InterpEntryC, 128, LeStruct);
+
+#[cfg(test)]
+mod tests;
@@ -15,7 +15,7 @@ pub fn read_interp(read: &mut CountingReader<impl Read>) -> Result<Vec<Script>>
         header.signature == SIGNATURE,
         read.prev + 0
     )?;
-    assert_that!("interp version", header.version == VERSION, read.prev + 4)?;
+    assert_that!("interp version", version header.version == VERSION, read.prev + 4)?;
 
     let script_info = (0..header.count)
         .map(|index| {
@@ -72,8 +72,7 @@ fn read_line(read: &mut CountingReader<impl Read>, size: u32, index: usize) -> R
         read.prev - 4,
     );
 
-    let mut buf = vec![0u8; len];
-    read.read_exact(&mut buf)?;
+    let mut buf = read.read_bytes(len)?;
     trace!(
         "`{}` (len: {}, at {})",
         buf.escape_ascii(),
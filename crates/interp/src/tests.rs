@@ -0,0 +1,181 @@
+use crate::{diff_interp, read_interp, validate_interp, write_interp, LineChange};
+use mech3ax_api_types::interp::Script;
+use mech3ax_common::io_ext::{CountingReader, CountingWriter};
+use mech3ax_common::test_utils::assert_truncated_fails;
+use mech3ax_common::ErrorKind;
+use mech3ax_timestamp::unix::from_timestamp;
+
+fn script(name: &str, lines: &[&str]) -> Script {
+    Script {
+        name: name.to_string(),
+        last_modified: from_timestamp(0),
+        lines: lines.iter().map(|line| line.to_string()).collect(),
+    }
+}
+
+#[test]
+fn validate_valid() {
+    let scripts = vec![script("c1", &["load c1.zbd", "fog 1 2"]), script("c2", &[])];
+    validate_interp(&scripts).unwrap();
+}
+
+#[test]
+fn validate_empty_name() {
+    let scripts = vec![script("c1", &[]), script("", &[])];
+    let err = validate_interp(&scripts).unwrap_err();
+    assert_eq!(
+        format!("{:#?}", err),
+        "Assert(\n    Expected script 1 name to be 1..119 characters, but was `` (0 characters),\n)"
+    );
+}
+
+#[test]
+fn validate_long_name() {
+    let name = "c".repeat(120);
+    let scripts = vec![script(&name, &[])];
+    let err = validate_interp(&scripts).unwrap_err();
+    assert!(format!("{:#?}", err).contains("(120 characters)"));
+}
+
+#[test]
+fn validate_duplicate_name() {
+    let scripts = vec![script("c1", &[]), script("c2", &[]), script("c1", &[])];
+    let err = validate_interp(&scripts).unwrap_err();
+    assert_eq!(
+        format!("{:#?}", err),
+        "Assert(\n    Script 2 name `c1` is duplicated,\n)"
+    );
+}
+
+#[test]
+fn validate_non_ascii_line() {
+    let scripts = vec![script("c1", &["load c1.zbd", "print ö"])];
+    let err = validate_interp(&scripts).unwrap_err();
+    assert_eq!(
+        format!("{:#?}", err),
+        "Assert(\n    Expected script `c1` line 1 to be ASCII, but was `print ö`,\n)"
+    );
+}
+
+fn write(scripts: &[Script]) -> Vec<u8> {
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    write_interp(&mut write, scripts).unwrap();
+    write.into_inner()
+}
+
+fn names(scripts: &[Script]) -> Vec<&str> {
+    scripts.iter().map(|script| script.name.as_str()).collect()
+}
+
+#[test]
+fn round_trip_order() {
+    let scripts = vec![
+        script("c1", &["load c1.zbd"]),
+        script("c2", &["load c2.zbd", "fog 1 2"]),
+        script("c3", &[]),
+    ];
+    let original = write(&scripts);
+    let read = read_interp(&mut CountingReader::new(&original[..])).unwrap();
+    assert_eq!(names(&read), ["c1", "c2", "c3"]);
+    assert_eq!(write(&read), original);
+
+    let reordered = vec![
+        script("c2", &["load c2.zbd", "fog 1 2"]),
+        script("c1", &["load c1.zbd"]),
+        script("c3", &[]),
+    ];
+    let written = write(&reordered);
+    assert_ne!(written, original);
+    let read = read_interp(&mut CountingReader::new(&written[..])).unwrap();
+    assert_eq!(names(&read), ["c2", "c1", "c3"]);
+    assert_eq!(write(&read), written);
+}
+
+#[test]
+fn write_duplicate_name() {
+    let scripts = vec![script("c1", &[]), script("c2", &[]), script("c1", &[])];
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    let err = write_interp(&mut write, &scripts).unwrap_err();
+    assert_eq!(
+        format!("{:#?}", err),
+        "Assert(\n    Script 2 name `c1` is duplicated,\n)"
+    );
+    assert!(write.into_inner().is_empty());
+}
+
+#[test]
+fn diff_same() {
+    let a = vec![script("c1", &["load c1.zbd", "fog 1 2"]), script("c2", &[])];
+    let mut b = vec![script("c1", &["load c1.zbd", "fog 1 2"]), script("c2", &[])];
+    // the timestamp is ignored
+    b[1].last_modified = from_timestamp(1);
+    assert!(diff_interp(&a, &b).is_empty());
+}
+
+#[test]
+fn diff_one_command() {
+    let a = vec![
+        script("c1", &["load c1.zbd", "fog 1 2", "sky 3"]),
+        script("c2", &["load c2.zbd"]),
+    ];
+    let b = vec![
+        script("c1", &["load c1.zbd", "fog 1 5", "sky 3"]),
+        script("c2", &["load c2.zbd"]),
+    ];
+    let diff = diff_interp(&a, &b);
+    assert!(diff.added.is_empty());
+    assert!(diff.removed.is_empty());
+    assert_eq!(diff.changed.len(), 1);
+    assert_eq!(diff.changed[0].name, "c1");
+    assert_eq!(
+        diff.changed[0].lines,
+        vec![
+            LineChange::Removed {
+                index: 1,
+                line: "fog 1 2"
+            },
+            LineChange::Added {
+                index: 1,
+                line: "fog 1 5"
+            },
+        ]
+    );
+}
+
+#[test]
+fn diff_added_and_removed() {
+    let a = vec![script("c1", &[]), script("c2", &["load c2.zbd"])];
+    let b = vec![script("c3", &[]), script("c2", &["load c2.zbd", "fog 1 2"])];
+    let diff = diff_interp(&a, &b);
+    assert_eq!(diff.added, vec!["c3"]);
+    assert_eq!(diff.removed, vec!["c1"]);
+    assert_eq!(diff.changed.len(), 1);
+    assert_eq!(
+        diff.changed[0].lines,
+        vec![LineChange::Added {
+            index: 1,
+            line: "fog 1 2"
+        }]
+    );
+}
+
+#[test]
+fn read_truncated_fails() {
+    let data = write(&[
+        script("c1", &["load c1.zbd"]),
+        script("c2", &["load c2.zbd", "fog 1 2"]),
+    ]);
+    assert_truncated_fails(&data, |data| read_interp(&mut CountingReader::new(data)));
+}
+
+#[test]
+fn read_unsupported_version() {
+    let mut data = write(&[script("c1", &["load c1.zbd"])]);
+    data[4..8].copy_from_slice(&8u32.to_le_bytes());
+    let err = read_interp(&mut CountingReader::new(&data[..])).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnsupportedVersion(8));
+    assert_eq!(
+        err.to_string(),
+        "Expected `interp version` == 7, but was 8 (at 4)"
+    );
+}
@@ -0,0 +1,55 @@
+use mech3ax_api_types::interp::Script;
+use mech3ax_common::{assert_with_msg, Result};
+use std::collections::HashSet;
+
+/// The script name field is 120 bytes, including the zero terminator.
+const MAX_NAME_LEN: usize = 119;
+
+/// Validate scripts that were not read from an interpreter file (e.g. edited
+/// by hand), so they can be written without being truncated or mangled.
+///
+/// The format has no known command that calls another script by name, so
+/// there are no cross-references to resolve; only the names and lines are
+/// validated. Script names must also be unique.
+///
+/// This is also called by `write_interp`.
+pub fn validate_interp(scripts: &[Script]) -> Result<()> {
+    let mut seen = HashSet::new();
+    for (index, script) in scripts.iter().enumerate() {
+        let name = &script.name;
+        if name.is_empty() || name.len() > MAX_NAME_LEN {
+            return Err(assert_with_msg!(
+                "Expected script {} name to be 1..{} characters, but was `{}` ({} characters)",
+                index,
+                MAX_NAME_LEN,
+                name,
+                name.len()
+            ));
+        }
+        if !seen.insert(name.as_str()) {
+            return Err(assert_with_msg!(
+                "Script {} name `{}` is duplicated",
+                index,
+                name
+            ));
+        }
+        if !name.is_ascii() {
+            return Err(assert_with_msg!(
+                "Expected script {} name to be ASCII, but was `{}`",
+                index,
+                name
+            ));
+        }
+        for (line_index, line) in script.lines.iter().enumerate() {
+            if !line.is_ascii() {
+                return Err(assert_with_msg!(
+                    "Expected script `{}` line {} to be ASCII, but was `{}`",
+                    name,
+                    line_index,
+                    line
+                ));
+            }
+        }
+    }
+    Ok(())
+}
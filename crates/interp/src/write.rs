@@ -1,13 +1,21 @@
-use super::{InterpEntryC, InterpHeaderC, SIGNATURE, VERSION};
+use super::{validate_interp, InterpEntryC, InterpHeaderC, SIGNATURE, VERSION};
 use log::trace;
 use mech3ax_api_types::interp::Script;
+use mech3ax_common::assert::assert_utf8;
 use mech3ax_common::io_ext::CountingWriter;
 use mech3ax_common::{assert_len, Result};
 use mech3ax_timestamp::unix::to_timestamp;
 use mech3ax_types::{AsBytes as _, Ascii};
 use std::io::Write;
 
+/// Write the scripts in the given order.
+///
+/// The script table and script data are both written in order, so the output
+/// is only identical to the original file if the order is unchanged. The
+/// scripts are validated first, see `validate_interp`.
 pub fn write_interp(write: &mut CountingWriter<impl Write>, scripts: &[Script]) -> Result<()> {
+    validate_interp(scripts)?;
+
     let count = assert_len!(u32, scripts.len(), "scripts")?;
     let header = InterpHeaderC {
         signature: SIGNATURE,
@@ -19,7 +27,9 @@ pub fn write_interp(write: &mut CountingWriter<impl Write>, scripts: &[Script])
     let mut offset = 12 + count * InterpEntryC::SIZE;
     for (index, script) in scripts.iter().enumerate() {
         trace!("Writing interp entry {}", index);
-        let name = Ascii::from_str_padded(&script.name);
+        let name = assert_utf8("name", write.offset, || {
+            Ascii::from_str_padded(&script.name)
+        })?;
         // Cast safety: truncation simply leads to incorrect timestamp
         let last_modified = to_timestamp(&script.last_modified);
         let entry = InterpEntryC {
@@ -1,4 +1,5 @@
 use eyre::{eyre, Error, Result};
+use std::any::Any;
 use std::cell::RefCell;
 use std::panic::{catch_unwind, UnwindSafe};
 
@@ -16,6 +17,17 @@ pub(crate) fn take_last_error() -> Option<Box<Error>> {
     LAST_ERROR.with(|prev| prev.borrow_mut().take())
 }
 
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    // `panic!` with a literal produces a `&str`, with formatting a `String`
+    if let Some(msg) = payload.downcast_ref::<&str>() {
+        msg
+    } else if let Some(msg) = payload.downcast_ref::<String>() {
+        msg
+    } else {
+        "unknown panic payload"
+    }
+}
+
 pub(crate) fn err_to_c<F>(func: F) -> i32
 where
     F: FnOnce() -> Result<()> + UnwindSafe,
@@ -32,8 +44,9 @@ where
     });
     match result {
         Ok(ret) => ret,
-        Err(_) => {
-            crate::error::set_last_error(Some(eyre!("Panicked!")));
+        Err(payload) => {
+            let msg = panic_message(&*payload);
+            crate::error::set_last_error(Some(eyre!("Panicked: {}", msg)));
             -2
         }
     }
@@ -66,3 +79,6 @@ pub unsafe extern "C" fn last_error_message(ptr: *mut u8, len: usize) -> usize {
     std::ptr::copy_nonoverlapping(message.as_ptr(), ptr, count);
     count.saturating_sub(1)
 }
+
+#[cfg(test)]
+mod tests;
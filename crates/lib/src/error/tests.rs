@@ -0,0 +1,40 @@
+use super::{err_to_c, last_error_length, last_error_message};
+use eyre::bail;
+
+fn last_error() -> String {
+    let len = last_error_length();
+    assert!(len > 0);
+    let mut buf = vec![0u8; len];
+    let count = unsafe { last_error_message(buf.as_mut_ptr(), buf.len()) };
+    assert_eq!(count, len - 1);
+    buf.truncate(count);
+    String::from_utf8(buf).unwrap()
+}
+
+#[test]
+fn ok_clears_error() {
+    assert_eq!(err_to_c(|| bail!("error")), -1);
+    assert_eq!(err_to_c(|| Ok(())), 0);
+    assert_eq!(last_error_length(), 0);
+}
+
+#[test]
+fn error_is_retrievable() {
+    assert_eq!(err_to_c(|| bail!("something went wrong")), -1);
+    assert_eq!(last_error(), "something went wrong");
+    // the error is taken
+    assert_eq!(last_error_length(), 0);
+}
+
+#[test]
+fn panic_str_is_retrievable() {
+    assert_eq!(err_to_c(|| panic!("oh no")), -2);
+    assert_eq!(last_error(), "Panicked: oh no");
+}
+
+#[test]
+fn panic_string_is_retrievable() {
+    let value = 42;
+    assert_eq!(err_to_c(|| panic!("oh no: {}", value)), -2);
+    assert_eq!(last_error(), "Panicked: oh no: 42");
+}
@@ -3,10 +3,9 @@ use crate::error::err_to_c;
 use crate::wave::WaveFile;
 use crate::{filename_to_string, i32_to_game};
 use eyre::{bail, Context as _, Result};
-use image::ImageFormat;
 use mech3ax_archive::{Mode, Version};
 use mech3ax_common::io_ext::CountingReader;
-use mech3ax_common::GameType;
+use mech3ax_common::{GameType, ReadOptions};
 use std::fs::File;
 use std::io::{BufReader, Cursor};
 use std::os::raw::c_char;
@@ -242,7 +241,7 @@ pub extern "C" fn read_mechlib(
         let game = i32_to_game(game_type_id)?;
         let version = match game {
             GameType::MW => Version::One,
-            GameType::PM => Version::Two(Mode::Sounds),
+            GameType::PM => Version::Two(Mode::Mechlib),
             GameType::RC => bail!("Recoil does not have mechlib"),
             GameType::CS => bail!("Crimson Skies does not have mechlib"),
         };
@@ -265,13 +264,11 @@ pub extern "C" fn read_textures(
     err_to_c(|| {
         let input = buf_reader(filename)?;
         let mut read = CountingReader::new(input);
-        let manifest = mech3ax_image::read_textures(&mut read, |name, image| {
-            let mut data = Cursor::new(Vec::new());
-            image
-                .write_to(&mut data, ImageFormat::Png)
+        let manifest = mech3ax_image::read_textures(&mut read, |name, info, image| {
+            let data = mech3ax_image::encode_png(&image, info)
                 .with_context(|| format!("Failed to write image `{}`", name))?;
 
-            buffer_callback(callback, name, data.get_ref())
+            buffer_callback(callback, name, &data)
         })?;
 
         let data = mech3ax_exchange::to_vec(&manifest)?;
@@ -288,29 +285,29 @@ pub extern "C" fn read_gamez(filename: *const c_char, game_type_id: i32, callbac
         let mut read = CountingReader::new(input);
         let data = match game {
             GameType::MW => {
-                let gamez = {
-                    mech3ax_gamez::gamez::mw::read_gamez(&mut read)
+                let (gamez, _) = {
+                    mech3ax_gamez::gamez::mw::read_gamez(&mut read, ReadOptions::default())
                         .context("Failed to read gamez data")
                 }?;
                 mech3ax_exchange::to_vec(&gamez)?
             }
             GameType::PM => {
-                let gamez = {
-                    mech3ax_gamez::gamez::pm::read_gamez(&mut read)
+                let (gamez, _) = {
+                    mech3ax_gamez::gamez::pm::read_gamez(&mut read, ReadOptions::default())
                         .context("Failed to read gamez data")
                 }?;
                 mech3ax_exchange::to_vec(&gamez)?
             }
             GameType::RC => {
-                let gamez = {
-                    mech3ax_gamez::gamez::rc::read_gamez(&mut read)
+                let (gamez, _) = {
+                    mech3ax_gamez::gamez::rc::read_gamez(&mut read, ReadOptions::default())
                         .context("Failed to read gamez data")
                 }?;
                 mech3ax_exchange::to_vec(&gamez)?
             }
             GameType::CS => {
-                let gamez = {
-                    mech3ax_gamez::gamez::cs::read_gamez(&mut read)
+                let (gamez, _) = {
+                    mech3ax_gamez::gamez::cs::read_gamez(&mut read, ReadOptions::default())
                         .context("Failed to read gamez data")
                 }?;
                 mech3ax_exchange::to_vec(&gamez)?
@@ -168,3 +168,6 @@ impl WaveFile {
         read_wav_file(read)
     }
 }
+
+#[cfg(test)]
+mod tests;
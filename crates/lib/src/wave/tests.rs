@@ -0,0 +1,58 @@
+use super::*;
+use std::io::Cursor;
+
+/// Wrap PCM data in a RIFF/WAVE header, like the sound archive entries.
+fn wav(channels: u16, frequency: u32, bits_per_sample: u16, pcm: &[u8]) -> Vec<u8> {
+    let block_align = channels * (bits_per_sample / 8);
+    let mut buf = Vec::new();
+    buf.extend_from_slice(RIFF_CHUNK_ID);
+    buf.extend_from_slice(&(36 + pcm.len() as u32).to_le_bytes());
+    buf.extend_from_slice(FORM_TYPE_WAVE);
+    buf.extend_from_slice(FMT_CHUNK_ID);
+    buf.extend_from_slice(&16u32.to_le_bytes());
+    buf.extend_from_slice(&WAVE_FORMAT_PCM.to_le_bytes());
+    buf.extend_from_slice(&channels.to_le_bytes());
+    buf.extend_from_slice(&frequency.to_le_bytes());
+    buf.extend_from_slice(&(frequency * u32::from(block_align)).to_le_bytes());
+    buf.extend_from_slice(&block_align.to_le_bytes());
+    buf.extend_from_slice(&bits_per_sample.to_le_bytes());
+    buf.extend_from_slice(DATA_CHUNK_ID);
+    buf.extend_from_slice(&(pcm.len() as u32).to_le_bytes());
+    buf.extend_from_slice(pcm);
+    buf
+}
+
+fn read(data: Vec<u8>) -> Result<WaveFile> {
+    WaveFile::new(&mut CountingReader::new(Cursor::new(data)))
+}
+
+#[test]
+fn read_wrapped_pcm_16bit() {
+    let samples: [i16; 4] = [0, i16::MIN, i16::MAX, -16384];
+    let pcm = samples
+        .iter()
+        .flat_map(|sample| sample.to_le_bytes())
+        .collect::<Vec<_>>();
+    let wave = read(wav(2, 44100, 16, &pcm)).unwrap();
+    assert_eq!(wave.channels, 2);
+    assert_eq!(wave.frequency, 44100);
+    assert_eq!(wave.samples, [0.0, -1.0, 32767.0 / 32768.0, -0.5]);
+}
+
+#[test]
+fn read_raw_pcm_fails() {
+    // without the header, the data can't be read
+    let Err(err) = read(vec![0x80; 16]) else {
+        panic!("read raw PCM");
+    };
+    assert!(err.to_string().contains("RIFF chunk ID"), "{}", err);
+}
+
+#[test]
+fn read_format_from_header_22050hz_mono() {
+    // 8-bit samples are unsigned, with the midpoint at 128
+    let wave = read(wav(1, 22050, 8, &[128, 0, 255, 64])).unwrap();
+    assert_eq!(wave.channels, 1);
+    assert_eq!(wave.frequency, 22050);
+    assert_eq!(wave.samples, [0.0, -1.0, 127.0 / 128.0, -0.5]);
+}
@@ -276,7 +276,7 @@ pub extern "C" fn write_mechlib(
         let game = i32_to_game(game_type_id)?;
         let version = match game {
             GameType::MW => Version::One,
-            GameType::PM => Version::Two(Mode::Sounds),
+            GameType::PM => Version::Two(Mode::Mechlib),
             GameType::RC => bail!("Recoil does not have mechlib"),
             GameType::CS => bail!("Crimson Skies does not have mechlib"),
         };
@@ -313,7 +313,8 @@ pub extern "C" fn write_textures(
     callback: NameBufferCb,
 ) -> i32 {
     err_to_c(|| {
-        let manifest = parse_manifest(manifest_ptr, manifest_len)?;
+        let mut manifest = parse_manifest(manifest_ptr, manifest_len)?;
+        mech3ax_image::sort_textures(&mut manifest)?;
         let mut write = buf_writer(filename)?;
         mech3ax_image::write_textures(
             &mut write,
@@ -8,6 +8,11 @@ mod pe;
 mod read;
 mod resources;
 mod string_table;
+mod write;
 mod zloc;
 
 pub use read::read_messages;
+pub use write::write_messages;
+
+#[cfg(test)]
+mod tests;
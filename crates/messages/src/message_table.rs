@@ -1,9 +1,9 @@
 use crate::size::u16_to_usize;
 use mech3ax_common::io_ext::CountingReader;
 use mech3ax_common::{assert_that, assert_with_msg, Result};
-use mech3ax_encoding::windows1252_decode;
+use mech3ax_encoding::{windows1252_decode, windows1252_encode};
 use mech3ax_types::u32_to_usize;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::io::Cursor;
 
 fn remove_trailing(buf: &mut Vec<u8>) -> Result<()> {
@@ -75,3 +75,60 @@ pub fn read_message_table(data: &[u8]) -> Result<HashMap<u32, String>> {
 
     Ok(entries)
 }
+
+fn to_u32(value: usize) -> Result<u32> {
+    value
+        .try_into()
+        .map_err(|_| assert_with_msg!("Message table: value {} too large", value))
+}
+
+pub fn write_message_table(messages: &BTreeMap<u32, String>) -> Result<Vec<u8>> {
+    // group consecutive IDs into blocks
+    let mut blocks: Vec<(u32, u32, Vec<&str>)> = Vec::new();
+    for (&entry_id, message) in messages {
+        match blocks.last_mut() {
+            Some((_, high_id, block)) if high_id.checked_add(1) == Some(entry_id) => {
+                *high_id = entry_id;
+                block.push(message);
+            }
+            _ => blocks.push((entry_id, entry_id, vec![message])),
+        }
+    }
+
+    let mut table = Vec::new();
+    let mut data = Vec::new();
+    let data_offset = 4 + 12 * blocks.len();
+    for (low_id, high_id, block) in &blocks {
+        table.extend_from_slice(&low_id.to_le_bytes());
+        table.extend_from_slice(&high_id.to_le_bytes());
+        table.extend_from_slice(&to_u32(data_offset + data.len())?.to_le_bytes());
+
+        for message in block {
+            let mut buf = windows1252_encode(message)
+                .map_err(|c| {
+                    assert_with_msg!(
+                        "Message table: character `{}` cannot be encoded in `{}`",
+                        c,
+                        message
+                    )
+                })?
+                .into_owned();
+            // the trailing \r\n and at least one \0, padded to 4 bytes
+            buf.extend_from_slice(b"\r\n\0");
+            buf.resize(buf.len().next_multiple_of(4), 0);
+
+            let length: u16 = (buf.len() + 4)
+                .try_into()
+                .map_err(|_| assert_with_msg!("Message table: `{}` is too long", message))?;
+            data.extend_from_slice(&length.to_le_bytes());
+            data.extend_from_slice(&0u16.to_le_bytes());
+            data.extend_from_slice(&buf);
+        }
+    }
+
+    let mut out = Vec::with_capacity(data_offset + data.len());
+    out.extend_from_slice(&to_u32(blocks.len())?.to_le_bytes());
+    out.extend_from_slice(&table);
+    out.extend_from_slice(&data);
+    Ok(out)
+}
@@ -1,5 +1,6 @@
 mod constants;
 mod structures;
+mod write;
 
 use super::bin::StructAt as _;
 use super::size::{u16_to_usize, FromBytes as _};
@@ -8,19 +9,42 @@ use log::trace;
 use mech3ax_common::{assert_that, Error, PeError, Result};
 use structures::*;
 
+pub use write::write_resource_section;
+
 pub type ImageSection = IMAGE_SECTION_HEADER;
 
 pub struct SectionsAndDirectories {
     pub image_base: u32,
-    #[allow(dead_code)]
     pub file_alignment: u32,
+    pub section_alignment: u32,
+    nt_header_offset: usize,
     data_directory: ImageDataDirectories,
     sections: Vec<IMAGE_SECTION_HEADER>,
 }
 
+/// Find the first section with the given name.
+pub fn find_section_by_name<'a>(
+    sections: &'a [ImageSection],
+    name: &str,
+) -> Option<&'a ImageSection> {
+    sections.iter().find(|section| section.name() == name)
+}
+
+/// Find the first section whose virtual range contains the RVA.
+///
+/// The virtual range is half-open, so for adjacent sections, an RVA at the
+/// boundary is in the second section.
+pub fn section_for_rva(sections: &[ImageSection], rva: u32) -> Option<&ImageSection> {
+    sections.iter().find(|section| section.contains_rva(rva))
+}
+
 impl SectionsAndDirectories {
     pub fn lookup(&self, name: &str) -> Option<&ImageSection> {
-        self.sections.iter().find(|&section| section.name() == name)
+        find_section_by_name(&self.sections, name)
+    }
+
+    pub fn lookup_rva(&self, rva: u32) -> Option<&ImageSection> {
+        section_for_rva(&self.sections, rva)
     }
 
     pub fn resource_dir(&self) -> &IMAGE_DATA_DIRECTORY {
@@ -119,8 +143,21 @@ pub fn read_pe_headers(buf: &[u8]) -> Result<SectionsAndDirectories> {
         optional_header_offset
     )?;
 
+    // the alignments are used to lay out the resource section when writing
+    assert_that!(
+        "Optional header section alignment",
+        optional_header.section_alignment > 0,
+        optional_header_offset
+    )?;
+    assert_that!(
+        "Optional header file alignment",
+        optional_header.file_alignment > 0,
+        optional_header_offset
+    )?;
+
     let image_base = optional_header.image_base;
     let file_alignment = optional_header.file_alignment;
+    let section_alignment = optional_header.section_alignment;
     let data_directory = optional_header.data_directory;
 
     // --- read sections
@@ -139,6 +176,8 @@ pub fn read_pe_headers(buf: &[u8]) -> Result<SectionsAndDirectories> {
     Ok(SectionsAndDirectories {
         image_base,
         file_alignment,
+        section_alignment,
+        nt_header_offset,
         data_directory,
         sections,
     })
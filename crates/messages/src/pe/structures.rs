@@ -151,6 +151,18 @@ impl IMAGE_SECTION_HEADER {
         }
     }
 
+    /// Whether the RVA is in the section's virtual range. If the virtual
+    /// size is zero, the size of raw data is used instead.
+    pub fn contains_rva(&self, rva: u32) -> bool {
+        let size = if self.virtual_size == 0 {
+            self.size_of_raw_data
+        } else {
+            self.virtual_size
+        };
+        rva.checked_sub(self.virtual_address)
+            .is_some_and(|delta| delta < size)
+    }
+
     pub fn virt_to_real(&self, virt: u32) -> Result<Option<usize>> {
         // prevent out-of-section read (underflow)
         let delta = virt
@@ -205,9 +217,16 @@ impl IMAGE_SECTION_HEADER {
         }
     }
 
-    pub fn get_section_bytes<'a>(&self, buf: &'a [u8]) -> &'a [u8] {
+    pub fn get_section_bytes<'a>(&self, buf: &'a [u8]) -> Result<&'a [u8]> {
         let start = u32_to_usize(self.pointer_to_raw_data);
         let len = u32_to_usize(self.size_of_raw_data);
-        &buf[start..][..len]
+        buf.get(start..)
+            .and_then(|section| section.get(..len))
+            .ok_or_else(|| Error::SectionOutOfBounds {
+                section: self.name().to_owned(),
+                start,
+                len,
+                size: buf.len(),
+            })
     }
 }
@@ -0,0 +1,169 @@
+use super::structures::*;
+use super::{find_section_by_name, SectionsAndDirectories, IMAGE_DIRECTORY_ENTRY_RESOURCE};
+use crate::bin::StructAt as _;
+use crate::size::{u32_to_usize, FromBytes as _};
+use log::trace;
+use mech3ax_common::{assert_with_msg, Error, PeError, Result};
+
+fn align(value: u32, alignment: u32) -> Result<u32> {
+    value
+        .div_ceil(alignment)
+        .checked_mul(alignment)
+        .ok_or_else(|| assert_with_msg!("Value {} overflows alignment {}", value, alignment))
+}
+
+fn add(value: u32, offset: u32) -> Result<u32> {
+    value
+        .checked_add(offset)
+        .ok_or_else(|| assert_with_msg!("Value {} overflows adding {}", value, offset))
+}
+
+fn adjust(value: u32, old: u32, new: u32) -> Result<u32> {
+    value
+        .checked_sub(old)
+        .and_then(|value| value.checked_add(new))
+        .ok_or_else(|| {
+            assert_with_msg!("Value {} cannot be adjusted from {} to {}", value, old, new)
+        })
+}
+
+fn put_struct<S: bytemuck::NoUninit>(buf: &mut [u8], offset: usize, s: &S) -> Result<()> {
+    let bytes = bytemuck::bytes_of(s);
+    let size = buf.len();
+    let dest = offset
+        .checked_add(bytes.len())
+        .and_then(|end| buf.get_mut(offset..end))
+        .ok_or_else(|| {
+            assert_with_msg!(
+                "Expected struct at {} with length {} to be within file size {}",
+                offset,
+                bytes.len(),
+                size
+            )
+        })?;
+    dest.copy_from_slice(bytes);
+    Ok(())
+}
+
+/// The PE checksum, as computed by `CheckSumMappedFile`. The checksum field
+/// itself must be zeroed.
+fn pe_checksum(buf: &[u8]) -> Result<u32> {
+    let mut sum: u32 = 0;
+    for chunk in buf.chunks(2) {
+        let word = match chunk {
+            [lo, hi] => u16::from_le_bytes([*lo, *hi]),
+            [lo] => u16::from(*lo),
+            _ => unreachable!(),
+        };
+        sum += u32::from(word);
+        sum = (sum & 0xFFFF) + (sum >> 16);
+    }
+    sum = (sum & 0xFFFF) + (sum >> 16);
+    let len: u32 = buf
+        .len()
+        .try_into()
+        .map_err(|e| Error::PeError(PeError::TryFrom(e)))?;
+    Ok(sum.wrapping_add(len))
+}
+
+/// Replace the resource section's data with `rsrc`, and fix up the section
+/// headers, the resource data directory, the image size, and the checksum.
+///
+/// The resource section keeps its virtual address, so it can only grow if
+/// no section is mapped after it.
+pub fn write_resource_section(
+    buf: &[u8],
+    sections: &SectionsAndDirectories,
+    rsrc: &[u8],
+) -> Result<Vec<u8>> {
+    let old = find_section_by_name(&sections.sections, ".rsrc")
+        .ok_or_else(|| assert_with_msg!("Expected DLL to contain a resource section"))?;
+
+    let virtual_size: u32 = rsrc
+        .len()
+        .try_into()
+        .map_err(|e| Error::PeError(PeError::TryFrom(e)))?;
+    let raw_size = align(virtual_size, sections.file_alignment)?;
+    trace!(
+        "Resource section size: {} -> {}, raw: {} -> {}",
+        old.virtual_size,
+        virtual_size,
+        old.size_of_raw_data,
+        raw_size
+    );
+
+    let mapped_end = add(
+        old.virtual_address,
+        align(virtual_size, sections.section_alignment)?,
+    )?;
+    if let Some(next) = sections
+        .sections
+        .iter()
+        .filter(|section| section.virtual_address > old.virtual_address)
+        .min_by_key(|section| section.virtual_address)
+    {
+        if mapped_end > next.virtual_address {
+            return Err(assert_with_msg!(
+                "Expected resource section to end before section {} at {}, but ends at {}",
+                next.name(),
+                next.virtual_address,
+                mapped_end
+            ));
+        }
+    }
+
+    // splice the new section data into the file
+    let raw_start = u32_to_usize(old.pointer_to_raw_data);
+    let raw_end = u32_to_usize(add(old.pointer_to_raw_data, old.size_of_raw_data)?);
+    let tail = buf
+        .get(raw_end..)
+        .ok_or(Error::PeError(PeError::ReadOutOfBounds(raw_end)))?;
+    let mut out = Vec::with_capacity(raw_start + u32_to_usize(raw_size) + tail.len());
+    out.extend_from_slice(&buf[..raw_start]);
+    out.extend_from_slice(rsrc);
+    out.resize(raw_start + u32_to_usize(raw_size), 0);
+    out.extend_from_slice(tail);
+
+    // fix up the section headers
+    let mut section_headers = sections.sections.clone();
+    for (section, original) in section_headers.iter_mut().zip(&sections.sections) {
+        if std::ptr::eq(original, old) {
+            section.virtual_size = virtual_size;
+            section.size_of_raw_data = raw_size;
+        } else if section.pointer_to_raw_data > old.pointer_to_raw_data {
+            section.pointer_to_raw_data =
+                adjust(section.pointer_to_raw_data, old.size_of_raw_data, raw_size)?;
+        }
+    }
+
+    let mut image_end = 0;
+    for section in section_headers.iter() {
+        image_end = image_end.max(add(section.virtual_address, section.virtual_size)?);
+    }
+
+    // fix up the optional header
+    let mut nt_header: IMAGE_NT_HEADERS = buf.struct_at(sections.nt_header_offset)?;
+    let optional_header = &mut nt_header.optional_header;
+    optional_header.size_of_initialized_data = adjust(
+        optional_header.size_of_initialized_data,
+        old.size_of_raw_data,
+        raw_size,
+    )?;
+    optional_header.size_of_image = align(image_end, sections.section_alignment)?;
+    optional_header.data_directory[IMAGE_DIRECTORY_ENTRY_RESOURCE].size = virtual_size;
+    optional_header.check_sum = 0;
+
+    let section_base_offset = sections.nt_header_offset + IMAGE_NT_HEADERS::SIZE;
+    for (section_index, section) in section_headers.iter().enumerate() {
+        let section_offset = section_base_offset + IMAGE_SECTION_HEADER::SIZE * section_index;
+        put_struct(&mut out, section_offset, section)?;
+    }
+    put_struct(&mut out, sections.nt_header_offset, &nt_header)?;
+
+    // the checksum must be calculated with the checksum field zeroed
+    nt_header.optional_header.check_sum = pe_checksum(&out)?;
+    trace!("PE checksum: {}", nt_header.optional_header.check_sum);
+    put_struct(&mut out, sections.nt_header_offset, &nt_header)?;
+
+    Ok(out)
+}
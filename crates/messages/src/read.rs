@@ -9,7 +9,7 @@ use mech3ax_common::{assert_that, assert_with_msg, GameType, Result};
 use std::collections::HashMap;
 use std::io::Read;
 
-fn parse_data_section(
+pub fn parse_data_section(
     buf: &[u8],
     sections: &pe::SectionsAndDirectories,
     skip_data: Option<usize>,
@@ -36,7 +36,7 @@ fn parse_data_section(
 
     trace!("Data section VA: {}, len: {}", mem_start, mem_end);
 
-    let data_section_bytes = data_section.get_section_bytes(buf);
+    let data_section_bytes = data_section.get_section_bytes(buf)?;
 
     read_zlocids(
         data_section_bytes,
@@ -47,7 +47,7 @@ fn parse_data_section(
     )
 }
 
-fn get_resource_section(sections: &pe::SectionsAndDirectories) -> Result<&pe::ImageSection> {
+pub fn get_resource_section(sections: &pe::SectionsAndDirectories) -> Result<&pe::ImageSection> {
     let resource_section = sections
         .lookup(".rsrc")
         .ok_or_else(|| assert_with_msg!("Expected DLL to contain a resource section"))?;
@@ -63,11 +63,19 @@ fn get_resource_section(sections: &pe::SectionsAndDirectories) -> Result<&pe::Im
         resource_section.virtual_size
     );
 
-    // Technically, you're supposed to look up which section a data directory
-    // is in. It would be odd for the resource directory not to be in the
-    // resource section.
+    // It would be odd for the resource directory not to be in the resource
+    // section, so check the section the directory is in is the same.
     let resource_dir = sections.resource_dir();
     assert_that!("Resource dir size", resource_dir.size > 0, 0usize)?;
+    let resource_dir_section = sections
+        .lookup_rva(resource_dir.virtual_address)
+        .map(pe::ImageSection::name)
+        .unwrap_or("<none>");
+    assert_that!(
+        "Resource dir section",
+        resource_dir_section == ".rsrc",
+        0usize
+    )?;
     assert_that!(
         "Resource dir virtual address",
         resource_dir.virtual_address == resource_section.virtual_address,
@@ -86,7 +94,7 @@ fn parse_resource_section_mt(
     resource_section: &pe::ImageSection,
 ) -> Result<(u32, HashMap<u32, String>)> {
     let resource_section_offset = u32_to_usize(resource_section.pointer_to_raw_data);
-    let resource_section_bytes = resource_section.get_section_bytes(buf);
+    let resource_section_bytes = resource_section.get_section_bytes(buf)?;
 
     let (language_id, data_offset, data_size) =
         read_resource_directory_mt(resource_section_bytes, resource_section_offset)?;
@@ -116,7 +124,7 @@ fn parse_resource_section_st(
     resource_section: &pe::ImageSection,
 ) -> Result<(u32, HashMap<u32, String>)> {
     let resource_section_offset = u32_to_usize(resource_section.pointer_to_raw_data);
-    let resource_section_bytes = resource_section.get_section_bytes(buf);
+    let resource_section_bytes = resource_section.get_section_bytes(buf)?;
 
     let (language_id, blocks) =
         read_resource_directory_st(resource_section_bytes, resource_section_offset)?;
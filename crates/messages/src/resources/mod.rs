@@ -1,4 +1,5 @@
 mod structures;
+mod write;
 
 use super::bin::StructAt as _;
 use super::size::FromBytes as _;
@@ -6,11 +7,13 @@ use log::trace;
 use mech3ax_common::{assert_that, assert_with_msg, Result};
 use structures::*;
 
-const RT_STRING: u32 = 6;
-const RT_MESSAGETABLE: u32 = 11;
+pub use write::{read_resource_tree, write_resource_tree, ResourceDir, ResourceEntry};
+
+pub const RT_STRING: u32 = 6;
+pub const RT_MESSAGETABLE: u32 = 11;
 const ENTRY_OFFSET: usize = IMAGE_RESOURCE_DIRECTORY::SIZE + IMAGE_RESOURCE_DIRECTORY_ENTRY::SIZE;
-const MT_CODE_PAGE: u32 = 0;
-const ST_CODE_PAGE: u32 = 1252;
+pub const MT_CODE_PAGE: u32 = 0;
+pub const ST_CODE_PAGE: u32 = 1252;
 
 #[derive(Debug)]
 pub struct StringBlock {
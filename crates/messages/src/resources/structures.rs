@@ -23,7 +23,7 @@ pub struct IMAGE_RESOURCE_DIRECTORY_ENTRY {
 impl_from_bytes!(IMAGE_RESOURCE_DIRECTORY_ENTRY, 8);
 
 const IMAGE_RESOURCE_NAME_IS_STRING: u32 = 0x80000000;
-const IMAGE_RESOURCE_DATA_IS_DIRECTORY: u32 = 0x80000000;
+pub const IMAGE_RESOURCE_DATA_IS_DIRECTORY: u32 = 0x80000000;
 
 impl IMAGE_RESOURCE_DIRECTORY_ENTRY {
     pub fn id(&self) -> Option<u32> {
@@ -0,0 +1,243 @@
+use super::structures::*;
+use crate::bin::StructAt as _;
+use crate::pe::ImageSection;
+use crate::size::{u32_to_usize, FromBytes as _};
+use log::trace;
+use mech3ax_common::{assert_that, assert_with_msg, Error, PeError, Result};
+use std::collections::VecDeque;
+
+// type, name, and language
+const MAX_DEPTH: usize = 3;
+
+#[derive(Debug, Clone)]
+pub struct ResourceDir {
+    pub characteristics: u32,
+    pub time_date_stamp: u32,
+    pub major_version: u16,
+    pub minor_version: u16,
+    pub entries: Vec<(u32, ResourceEntry)>,
+}
+
+#[derive(Debug, Clone)]
+pub enum ResourceEntry {
+    Dir(ResourceDir),
+    Data { code_page: u32, data: Vec<u8> },
+}
+
+impl ResourceDir {
+    pub fn new(entries: Vec<(u32, ResourceEntry)>) -> Self {
+        Self {
+            characteristics: 0,
+            time_date_stamp: 0,
+            major_version: 0,
+            minor_version: 0,
+            entries,
+        }
+    }
+
+    pub fn get_mut(&mut self, id: u32) -> Option<&mut ResourceEntry> {
+        self.entries
+            .iter_mut()
+            .find(|(entry_id, _)| *entry_id == id)
+            .map(|(_, entry)| entry)
+    }
+}
+
+fn read_dir(
+    data: &[u8],
+    section: &ImageSection,
+    offset: usize,
+    depth: usize,
+) -> Result<ResourceDir> {
+    let abs_offset = u32_to_usize(section.pointer_to_raw_data) + offset;
+    assert_that!("resource dir depth", depth < MAX_DEPTH, abs_offset)?;
+
+    let res_dir: IMAGE_RESOURCE_DIRECTORY = data.struct_at(offset)?;
+    assert_that!(
+        "resource dir named entries",
+        res_dir.number_of_named_entries == 0,
+        abs_offset
+    )?;
+    trace!(
+        "Resource dir at {} ({}), depth {}, ID entries: {}",
+        abs_offset,
+        offset,
+        depth,
+        res_dir.number_of_id_entries
+    );
+
+    let entries = (0..usize::from(res_dir.number_of_id_entries))
+        .map(|index| {
+            let entry_offset = offset
+                + IMAGE_RESOURCE_DIRECTORY::SIZE
+                + IMAGE_RESOURCE_DIRECTORY_ENTRY::SIZE * index;
+            let res_entry: IMAGE_RESOURCE_DIRECTORY_ENTRY = data.struct_at(entry_offset)?;
+            let id = res_entry.id().ok_or_else(|| {
+                assert_with_msg!(
+                    "Expected resource entry name to be an ID (at {})",
+                    abs_offset
+                )
+            })?;
+            let (is_dir, child_offset) = res_entry.is_dir_and_offset();
+            let entry = if is_dir {
+                ResourceEntry::Dir(read_dir(data, section, child_offset, depth + 1)?)
+            } else {
+                read_data(data, section, child_offset)?
+            };
+            Ok((id, entry))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(ResourceDir {
+        characteristics: res_dir.characteristics,
+        time_date_stamp: res_dir.time_date_stamp,
+        major_version: res_dir.major_version,
+        minor_version: res_dir.minor_version,
+        entries,
+    })
+}
+
+fn read_data(data: &[u8], section: &ImageSection, offset: usize) -> Result<ResourceEntry> {
+    let res_data: IMAGE_RESOURCE_DATA_ENTRY = data.struct_at(offset)?;
+    let start = res_data
+        .offset_to_data
+        .checked_sub(section.virtual_address)
+        .ok_or_else(|| PeError::Underflow {
+            section: section.name().to_owned(),
+            value: res_data.offset_to_data,
+            bound: section.virtual_address,
+        })
+        .map_err(Error::PeError)?;
+    let start = u32_to_usize(start);
+    let end = start
+        .checked_add(u32_to_usize(res_data.size))
+        .ok_or_else(|| PeError::Overflow {
+            section: section.name().to_owned(),
+            value: res_data.offset_to_data,
+            bound: res_data.size,
+        })
+        .map_err(Error::PeError)?;
+    let bytes = data
+        .get(start..end)
+        .ok_or(Error::PeError(PeError::ReadOutOfBounds(end)))?;
+    Ok(ResourceEntry::Data {
+        code_page: res_data.code_page,
+        data: bytes.to_vec(),
+    })
+}
+
+/// Read the entire resource directory tree, including the resource data.
+pub fn read_resource_tree(data: &[u8], section: &ImageSection) -> Result<ResourceDir> {
+    read_dir(data, section, 0, 0)
+}
+
+fn to_u32(value: usize) -> Result<u32> {
+    value
+        .try_into()
+        .map_err(|e| Error::PeError(PeError::TryFrom(e)))
+}
+
+fn add_rva(virtual_address: u32, offset: u32) -> Result<u32> {
+    virtual_address
+        .checked_add(offset)
+        .ok_or_else(|| PeError::Overflow {
+            section: ".rsrc".to_owned(),
+            value: virtual_address,
+            bound: offset,
+        })
+        .map_err(Error::PeError)
+}
+
+/// Write the resource directory tree for a section at the given virtual
+/// address.
+///
+/// Like the resource compiler, all directories are written first (breadth
+/// first), then the data entries, then the data.
+pub fn write_resource_tree(root: &ResourceDir, virtual_address: u32) -> Result<Vec<u8>> {
+    let mut dirs = Vec::new();
+    let mut leaves = Vec::new();
+    let mut queue = VecDeque::from([root]);
+    while let Some(dir) = queue.pop_front() {
+        dirs.push(dir);
+        for (_, entry) in &dir.entries {
+            match entry {
+                ResourceEntry::Dir(child) => queue.push_back(child),
+                ResourceEntry::Data { code_page, data } => leaves.push((*code_page, data)),
+            }
+        }
+    }
+
+    let dir_offsets = dirs
+        .iter()
+        .scan(0, |offset, dir| {
+            let current = *offset;
+            *offset += IMAGE_RESOURCE_DIRECTORY::SIZE
+                + IMAGE_RESOURCE_DIRECTORY_ENTRY::SIZE * dir.entries.len();
+            Some(current)
+        })
+        .collect::<Vec<_>>();
+    let leaves_offset = dirs
+        .iter()
+        .map(|dir| {
+            IMAGE_RESOURCE_DIRECTORY::SIZE
+                + IMAGE_RESOURCE_DIRECTORY_ENTRY::SIZE * dir.entries.len()
+        })
+        .sum::<usize>();
+    let data_offset = leaves_offset + IMAGE_RESOURCE_DATA_ENTRY::SIZE * leaves.len();
+
+    let mut buf = Vec::new();
+    // the children of each directory are numbered in the same order they
+    // were queued
+    let mut next_dir = 1;
+    let mut next_leaf = 0;
+    for dir in &dirs {
+        let res_dir = IMAGE_RESOURCE_DIRECTORY {
+            characteristics: dir.characteristics,
+            time_date_stamp: dir.time_date_stamp,
+            major_version: dir.major_version,
+            minor_version: dir.minor_version,
+            number_of_named_entries: 0,
+            number_of_id_entries: dir
+                .entries
+                .len()
+                .try_into()
+                .map_err(|e| Error::PeError(PeError::TryFrom(e)))?,
+        };
+        buf.extend_from_slice(bytemuck::bytes_of(&res_dir));
+        for (id, entry) in &dir.entries {
+            let offset = match entry {
+                ResourceEntry::Dir(_) => {
+                    let offset = to_u32(dir_offsets[next_dir])? | IMAGE_RESOURCE_DATA_IS_DIRECTORY;
+                    next_dir += 1;
+                    offset
+                }
+                ResourceEntry::Data { .. } => {
+                    let offset =
+                        to_u32(leaves_offset + IMAGE_RESOURCE_DATA_ENTRY::SIZE * next_leaf)?;
+                    next_leaf += 1;
+                    offset
+                }
+            };
+            let res_entry = IMAGE_RESOURCE_DIRECTORY_ENTRY { name: *id, offset };
+            buf.extend_from_slice(bytemuck::bytes_of(&res_entry));
+        }
+    }
+
+    let mut offset = data_offset;
+    for (code_page, data) in &leaves {
+        let res_data = IMAGE_RESOURCE_DATA_ENTRY {
+            offset_to_data: add_rva(virtual_address, to_u32(offset)?)?,
+            size: to_u32(data.len())?,
+            code_page: *code_page,
+            reserved: 0,
+        };
+        buf.extend_from_slice(bytemuck::bytes_of(&res_data));
+        offset += data.len().next_multiple_of(8);
+    }
+
+    for (_, data) in &leaves {
+        buf.extend_from_slice(data);
+        buf.resize(buf.len().next_multiple_of(8), 0);
+    }
+    Ok(buf)
+}
@@ -2,7 +2,7 @@ use crate::size::u16_to_usize;
 use log::trace;
 use mech3ax_common::io_ext::CountingReader;
 use mech3ax_common::{assert_with_msg, Result};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::io::Read;
 
 fn utf16_decode(iter: Vec<u16>) -> Result<String> {
@@ -47,3 +47,41 @@ pub fn read_string_block(
     }
     Ok(())
 }
+
+/// Write the string blocks for the messages. Each block contains 16
+/// strings, so the block ID is derived from the string ID.
+pub fn write_string_blocks(messages: &BTreeMap<u32, String>) -> Result<Vec<(u32, Vec<u8>)>> {
+    let mut blocks: BTreeMap<u32, [Option<&str>; 16]> = BTreeMap::new();
+    for (&string_id, message) in messages {
+        let block_id = string_id / 16 + 1;
+        // Cast safety: the remainder is always less than 16
+        let index = (string_id % 16) as usize;
+        blocks.entry(block_id).or_insert([None; 16])[index] = Some(message);
+    }
+
+    blocks
+        .into_iter()
+        .map(|(block_id, strings)| {
+            let mut data = Vec::new();
+            for message in strings {
+                // empty strings are indistinguishable from missing strings
+                if message == Some("") {
+                    return Err(assert_with_msg!(
+                        "String table: empty strings cannot be written (block {})",
+                        block_id
+                    ));
+                }
+                let chars: Vec<u16> = message.unwrap_or_default().encode_utf16().collect();
+                let len: u16 = chars
+                    .len()
+                    .try_into()
+                    .map_err(|_| assert_with_msg!("String table: `{:?}` is too long", message))?;
+                data.extend_from_slice(&len.to_le_bytes());
+                for c in chars {
+                    data.extend_from_slice(&c.to_le_bytes());
+                }
+            }
+            Ok((block_id, data))
+        })
+        .collect()
+}
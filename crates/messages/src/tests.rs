@@ -0,0 +1,382 @@
+use crate::pe::{find_section_by_name, section_for_rva, ImageSection};
+use crate::resources::{write_resource_tree, ResourceDir, ResourceEntry, RT_STRING, ST_CODE_PAGE};
+use crate::string_table::write_string_blocks;
+use crate::{read_messages, write_messages};
+use mech3ax_api_types::messages::Messages;
+use mech3ax_common::test_utils::assert_truncated_fails;
+use mech3ax_common::GameType;
+use mech3ax_types::Ascii;
+use std::collections::BTreeMap;
+use std::io::Cursor;
+
+const IMAGE_BASE: u32 = 0x10000000;
+const DATA_RVA: u32 = 0x1000;
+const RSRC_RVA: u32 = 0x2000;
+const NT_HEADER: usize = 0x40;
+const OPTIONAL_HEADER: usize = NT_HEADER + 4 + 20;
+const SECTIONS: usize = OPTIONAL_HEADER + 224;
+
+fn put_u16(buf: &mut [u8], offset: usize, value: u16) {
+    buf[offset..][..2].copy_from_slice(&value.to_le_bytes());
+}
+
+fn put_u32(buf: &mut [u8], offset: usize, value: u32) {
+    buf[offset..][..4].copy_from_slice(&value.to_le_bytes());
+}
+
+fn put_section(buf: &mut [u8], index: usize, name: &[u8], rva: u32, raw: u32, size: u32) {
+    let offset = SECTIONS + 40 * index;
+    buf[offset..][..name.len()].copy_from_slice(name);
+    put_u32(buf, offset + 8, size);
+    put_u32(buf, offset + 12, rva);
+    put_u32(buf, offset + 16, size.next_multiple_of(0x200));
+    put_u32(buf, offset + 20, raw);
+}
+
+/// Build the data section, containing the CRT initialization section, the
+/// message table IDs, and the message keys.
+fn build_data(messages: &[(&str, u32, &str)], crt_size: usize) -> Vec<u8> {
+    let mut data = vec![0u8; crt_size];
+    let strings_offset = crt_size + 8 * messages.len();
+    let mut strings = Vec::new();
+    let mut table = Vec::new();
+    for (key, id, _) in messages {
+        let address = IMAGE_BASE + DATA_RVA + (strings_offset + strings.len()) as u32;
+        table.push((address, *id));
+        strings.extend_from_slice(key.as_bytes());
+        strings.push(0);
+    }
+    // the table is written backwards, highest address first
+    for (address, id) in table.into_iter().rev() {
+        data.extend_from_slice(&address.to_le_bytes());
+        data.extend_from_slice(&id.to_le_bytes());
+    }
+    data.extend_from_slice(&strings);
+    data
+}
+
+/// Build a minimal DLL with a data section and a resource section.
+fn build_pe(data: &[u8], rsrc: &[u8]) -> Vec<u8> {
+    let data_raw = 0x200;
+    let rsrc_raw = data_raw + data.len().next_multiple_of(0x200);
+    let mut buf = vec![0u8; rsrc_raw + rsrc.len().next_multiple_of(0x200)];
+    buf[0..2].copy_from_slice(b"MZ");
+    put_u32(&mut buf, 0x3C, NT_HEADER as u32);
+    buf[NT_HEADER..][..4].copy_from_slice(b"PE\0\0");
+    put_u16(&mut buf, NT_HEADER + 4, 0x014c);
+    put_u16(&mut buf, NT_HEADER + 6, 2);
+    put_u16(&mut buf, NT_HEADER + 20, 224);
+    put_u16(&mut buf, NT_HEADER + 22, 0x2102);
+    put_u16(&mut buf, OPTIONAL_HEADER, 0x010b);
+    let initialized_data = (buf.len() - data_raw) as u32;
+    put_u32(&mut buf, OPTIONAL_HEADER + 8, initialized_data);
+    put_u32(&mut buf, OPTIONAL_HEADER + 28, IMAGE_BASE);
+    put_u32(&mut buf, OPTIONAL_HEADER + 32, 0x1000);
+    put_u32(&mut buf, OPTIONAL_HEADER + 36, 0x200);
+    put_u32(&mut buf, OPTIONAL_HEADER + 56, 0x3000);
+    put_u32(&mut buf, OPTIONAL_HEADER + 60, 0x200);
+    put_u16(&mut buf, OPTIONAL_HEADER + 68, 2);
+    put_u32(&mut buf, OPTIONAL_HEADER + 92, 16);
+    put_u32(&mut buf, OPTIONAL_HEADER + 96 + 16, RSRC_RVA);
+    put_u32(&mut buf, OPTIONAL_HEADER + 96 + 20, rsrc.len() as u32);
+    put_section(
+        &mut buf,
+        0,
+        b".data",
+        DATA_RVA,
+        data_raw as u32,
+        data.len() as u32,
+    );
+    put_section(
+        &mut buf,
+        1,
+        b".rsrc",
+        RSRC_RVA,
+        rsrc_raw as u32,
+        rsrc.len() as u32,
+    );
+
+    buf[data_raw..][..data.len()].copy_from_slice(data);
+    buf[rsrc_raw..][..rsrc.len()].copy_from_slice(rsrc);
+    buf
+}
+
+/// Build a minimal message table DLL, with a data section containing the
+/// message keys, and a resource section containing the message table.
+fn build_dll(messages: &[(&str, u32, &str)]) -> Vec<u8> {
+    let data = build_data(messages, 16);
+
+    // --- message table (one block per message)
+    let mut mt = Vec::new();
+    mt.extend_from_slice(&(messages.len() as u32).to_le_bytes());
+    let mut entries = Vec::new();
+    for (_, id, value) in messages {
+        let offset = 4 + 12 * messages.len() + entries.len();
+        mt.extend_from_slice(&id.to_le_bytes());
+        mt.extend_from_slice(&id.to_le_bytes());
+        mt.extend_from_slice(&(offset as u32).to_le_bytes());
+        let mut text = value.as_bytes().to_vec();
+        text.extend_from_slice(b"\r\n\0");
+        text.resize(text.len().next_multiple_of(4), 0);
+        entries.extend_from_slice(&(text.len() as u16 + 4).to_le_bytes());
+        entries.extend_from_slice(&0u16.to_le_bytes());
+        entries.extend_from_slice(&text);
+    }
+    mt.extend_from_slice(&entries);
+
+    // --- resource section (root -> type -> name -> lang -> data)
+    let mut rsrc = vec![0u8; 88];
+    put_u16(&mut rsrc, 14, 1);
+    put_u32(&mut rsrc, 16, 11);
+    put_u32(&mut rsrc, 20, 0x80000000 | 24);
+    put_u16(&mut rsrc, 24 + 14, 1);
+    put_u32(&mut rsrc, 24 + 16, 1);
+    put_u32(&mut rsrc, 24 + 20, 0x80000000 | 48);
+    put_u16(&mut rsrc, 48 + 14, 1);
+    put_u32(&mut rsrc, 48 + 16, 0x409);
+    put_u32(&mut rsrc, 48 + 20, 72);
+    put_u32(&mut rsrc, 72, RSRC_RVA + 88);
+    put_u32(&mut rsrc, 76, mt.len() as u32);
+    rsrc.extend_from_slice(&mt);
+
+    build_pe(&data, &rsrc)
+}
+
+/// Build a minimal string table DLL, as used by CS. The resource section
+/// contains the string table first, followed by two other resource types.
+fn build_cs_dll(messages: &[(&str, u32, &str)]) -> Vec<u8> {
+    let data = build_data(messages, 48);
+
+    let values = messages
+        .iter()
+        .map(|(_, id, value)| (*id, value.to_string()))
+        .collect();
+    let blocks = write_string_blocks(&values).unwrap();
+    let lang_dir = |code_page, data| {
+        ResourceEntry::Dir(ResourceDir::new(vec![(
+            0x409,
+            ResourceEntry::Data { code_page, data },
+        )]))
+    };
+    let string_dir = ResourceDir::new(
+        blocks
+            .into_iter()
+            .map(|(block_id, data)| (block_id, lang_dir(ST_CODE_PAGE, data)))
+            .collect(),
+    );
+    let other_dir = || ResourceEntry::Dir(ResourceDir::new(vec![(1, lang_dir(0, vec![0; 8]))]));
+    let root = ResourceDir::new(vec![
+        (RT_STRING, ResourceEntry::Dir(string_dir)),
+        (16, other_dir()),
+        (24, other_dir()),
+    ]);
+    let rsrc = write_resource_tree(&root, RSRC_RVA).unwrap();
+
+    build_pe(&data, &rsrc)
+}
+
+fn to_map(messages: &Messages) -> BTreeMap<String, (u32, String)> {
+    messages
+        .entries
+        .iter()
+        .map(|entry| (entry.key.clone(), (entry.id, entry.value.clone())))
+        .collect()
+}
+
+fn round_trip(dll: &[u8], messages: &Messages, game: GameType) -> (Vec<u8>, Messages) {
+    let mut written = Vec::new();
+    write_messages(&mut written, &mut Cursor::new(dll), messages, game).unwrap();
+    let reread = read_messages(&mut Cursor::new(&written), game).unwrap();
+    (written, reread)
+}
+
+#[test]
+fn round_trip_unchanged() {
+    let dll = build_dll(&[("MSG_ALPHA", 1, "Hello"), ("MSG_BRAVO", 2, "World")]);
+    let messages = read_messages(&mut Cursor::new(&dll), GameType::MW).unwrap();
+    assert_eq!(messages.language_id, 0x409);
+    assert_eq!(messages.entries.len(), 2);
+
+    let (_, reread) = round_trip(&dll, &messages, GameType::MW);
+    assert_eq!(reread.language_id, messages.language_id);
+    assert_eq!(to_map(&reread), to_map(&messages));
+}
+
+#[test]
+fn read_truncated_fails() {
+    let dll = build_dll(&[("MSG_ALPHA", 1, "Hello"), ("MSG_BRAVO", 2, "World")]);
+    assert_truncated_fails(&dll, |data| {
+        read_messages(&mut Cursor::new(data), GameType::MW)
+    });
+}
+
+#[test]
+fn round_trip_changed() {
+    let dll = build_dll(&[("MSG_ALPHA", 1, "Hello"), ("MSG_BRAVO", 3, "World")]);
+    let mut messages = read_messages(&mut Cursor::new(&dll), GameType::MW).unwrap();
+    messages.entries[0].value = "Hellö, wörld! ".repeat(100);
+    messages.entries[1].value = "Changed".to_string();
+
+    let (written, reread) = round_trip(&dll, &messages, GameType::MW);
+    assert_eq!(to_map(&reread), to_map(&messages));
+    // the resource section grew past the file alignment
+    assert!(written.len() > dll.len());
+
+    let size_of_image =
+        u32::from_le_bytes(written[OPTIONAL_HEADER + 56..][..4].try_into().unwrap());
+    assert_eq!(size_of_image, 0x3000);
+    let check_sum = u32::from_le_bytes(written[OPTIONAL_HEADER + 64..][..4].try_into().unwrap());
+    // the known checksum of the rewritten fixture
+    assert_eq!(check_sum, 29343);
+}
+
+#[test]
+fn round_trip_string_table() {
+    let dll = build_cs_dll(&[("MSG_ALPHA", 1, "Hello"), ("MSG_BRAVO", 18, "World")]);
+    let mut messages = read_messages(&mut Cursor::new(&dll), GameType::CS).unwrap();
+    assert_eq!(messages.language_id, 0x409);
+    assert_eq!(messages.entries.len(), 2);
+
+    messages.entries[0].value = "Hellö, wörld! ".repeat(100);
+    messages.entries[1].value = "Changed".to_string();
+    let (written, reread) = round_trip(&dll, &messages, GameType::CS);
+    assert_eq!(reread.language_id, messages.language_id);
+    assert_eq!(to_map(&reread), to_map(&messages));
+    assert!(written.len() > dll.len());
+}
+
+#[test]
+fn read_zero_file_alignment_fails() {
+    let mut dll = build_dll(&[("MSG_ALPHA", 1, "Hello")]);
+    put_u32(&mut dll, OPTIONAL_HEADER + 36, 0);
+    let err = read_messages(&mut Cursor::new(&dll), GameType::MW).unwrap_err();
+    assert!(
+        format!("{:?}", err).contains("Optional header file alignment"),
+        "{:?}",
+        err
+    );
+}
+
+#[test]
+fn write_unknown_key_fails() {
+    let dll = build_dll(&[("MSG_ALPHA", 1, "Hello"), ("MSG_BRAVO", 2, "World")]);
+    let mut messages = read_messages(&mut Cursor::new(&dll), GameType::MW).unwrap();
+    messages.entries[0].key = "MSG_CHARLIE".to_string();
+
+    let mut written = Vec::new();
+    let err = write_messages(
+        &mut written,
+        &mut Cursor::new(&dll),
+        &messages,
+        GameType::MW,
+    )
+    .unwrap_err();
+    assert!(format!("{:?}", err).contains("MSG_CHARLIE"));
+}
+
+#[test]
+fn write_resource_tree_rva_overflow_fails() {
+    let root = ResourceDir::new(vec![(
+        RT_STRING,
+        ResourceEntry::Data {
+            code_page: 0,
+            data: vec![0; 8],
+        },
+    )]);
+    let err = write_resource_tree(&root, u32::MAX - 8).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "address 4294967287 overflows bound 40 in section .rsrc"
+    );
+}
+
+fn section(name: &[u8; 8], virtual_address: u32, virtual_size: u32) -> ImageSection {
+    let mut section: ImageSection = bytemuck::Zeroable::zeroed();
+    section.name = Ascii::new(name);
+    section.virtual_address = virtual_address;
+    section.virtual_size = virtual_size;
+    section
+}
+
+#[test]
+fn section_bytes_out_of_bounds() {
+    let mut section = section(b".rsrc\0\0\0", 0, 0);
+    section.pointer_to_raw_data = 0x100;
+    section.size_of_raw_data = 0x80;
+
+    let buf = vec![0u8; 0x180];
+    assert_eq!(section.get_section_bytes(&buf).unwrap().len(), 0x80);
+
+    let buf = vec![0u8; 0x17F];
+    let err = section.get_section_bytes(&buf).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "section .rsrc data at 256 with length 128 exceeds file size 383"
+    );
+
+    section.pointer_to_raw_data = u32::MAX;
+    assert!(section.get_section_bytes(&buf).is_err());
+}
+
+#[test]
+fn read_section_past_end_fails() {
+    let mut dll = build_dll(&[("MSG_ALPHA", 1, "Hello")]);
+    // point the resource section past the end of the file
+    let size = dll.len() as u32;
+    put_u32(&mut dll, SECTIONS + 40 + 20, size);
+    let err = read_messages(&mut Cursor::new(&dll), GameType::MW).unwrap_err();
+    assert!(
+        format!("{:?}", err).contains("SectionOutOfBounds"),
+        "{:?}",
+        err
+    );
+}
+
+#[test]
+fn find_section_by_name_first_match() {
+    let sections = [
+        section(b".text\0\0\0", 0x1000, 0x100),
+        section(b".data\0\0\0", 0x2000, 0x100),
+        section(b".data\0\0\0", 0x3000, 0x100),
+    ];
+    let found = find_section_by_name(&sections, ".data").unwrap();
+    assert_eq!(found.virtual_address, 0x2000);
+    assert!(find_section_by_name(&sections, ".rsrc").is_none());
+}
+
+#[test]
+fn section_for_rva_adjacent() {
+    let sections = [
+        section(b".text\0\0\0", 0x1000, 0x1000),
+        section(b".data\0\0\0", 0x2000, 0x800),
+    ];
+    let name = |rva| section_for_rva(&sections, rva).map(ImageSection::name);
+    assert_eq!(name(0x0FFF), None);
+    assert_eq!(name(0x1000), Some(".text"));
+    assert_eq!(name(0x1FFF), Some(".text"));
+    // the boundary belongs to the second section
+    assert_eq!(name(0x2000), Some(".data"));
+    assert_eq!(name(0x27FF), Some(".data"));
+    assert_eq!(name(0x2800), None);
+}
+
+#[test]
+fn section_for_rva_overlapping() {
+    let sections = [
+        section(b".text\0\0\0", 0x1000, 0x1800),
+        section(b".data\0\0\0", 0x2000, 0x1000),
+    ];
+    let name = |rva| section_for_rva(&sections, rva).map(ImageSection::name);
+    // the first matching section wins
+    assert_eq!(name(0x2400), Some(".text"));
+    assert_eq!(name(0x2800), Some(".data"));
+}
+
+#[test]
+fn section_for_rva_zero_virtual_size() {
+    let mut bss = section(b".bss\0\0\0\0", 0x1000, 0);
+    bss.size_of_raw_data = 0x200;
+    let sections = [bss];
+    assert!(section_for_rva(&sections, 0x11FF).is_some());
+    assert!(section_for_rva(&sections, 0x1200).is_none());
+}
@@ -0,0 +1,148 @@
+use crate::read::{get_resource_section, parse_data_section};
+use crate::resources::{
+    read_resource_tree, write_resource_tree, ResourceDir, ResourceEntry, MT_CODE_PAGE,
+    RT_MESSAGETABLE, RT_STRING, ST_CODE_PAGE,
+};
+use crate::{message_table, pe, string_table};
+use mech3ax_api_types::messages::{MessageEntry, Messages};
+use mech3ax_common::{assert_with_msg, GameType, Result};
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+
+fn match_entries(
+    message_ids: Vec<(u32, String)>,
+    entries: &[MessageEntry],
+) -> Result<BTreeMap<u32, String>> {
+    let mut lookup = BTreeMap::new();
+    for entry in entries {
+        if lookup.insert(entry.id, entry).is_some() {
+            return Err(assert_with_msg!(
+                "Message `{}` ({}) is duplicated",
+                entry.key,
+                entry.id
+            ));
+        }
+    }
+
+    // the keys are stored in the data section, which isn't rewritten. so
+    // messages can be changed, but not added or removed.
+    let values = message_ids
+        .into_iter()
+        .map(|(entry_id, key)| {
+            let entry = lookup
+                .remove(&entry_id)
+                .ok_or_else(|| assert_with_msg!("Message `{}` ({}) not found", &key, entry_id))?;
+            if entry.key != key {
+                return Err(assert_with_msg!(
+                    "Expected message {} to be `{}`, but was `{}`",
+                    entry_id,
+                    key,
+                    entry.key
+                ));
+            }
+            Ok((entry_id, entry.value.clone()))
+        })
+        .collect::<Result<BTreeMap<_, _>>>()?;
+
+    if let Some(entry) = lookup.into_values().next() {
+        return Err(assert_with_msg!(
+            "Message `{}` ({}) not found in DLL",
+            entry.key,
+            entry.id
+        ));
+    }
+    Ok(values)
+}
+
+fn type_dir(root: &mut ResourceDir, type_id: u32) -> Result<&mut ResourceDir> {
+    match root.get_mut(type_id) {
+        Some(ResourceEntry::Dir(dir)) => Ok(dir),
+        _ => Err(assert_with_msg!(
+            "Expected resource type {} directory",
+            type_id
+        )),
+    }
+}
+
+fn replace_message_table(
+    root: &mut ResourceDir,
+    language_id: u32,
+    values: &BTreeMap<u32, String>,
+) -> Result<()> {
+    let data = message_table::write_message_table(values)?;
+    let name_dir = match type_dir(root, RT_MESSAGETABLE)?.get_mut(1) {
+        Some(ResourceEntry::Dir(dir)) => dir,
+        _ => return Err(assert_with_msg!("Expected message table name directory")),
+    };
+    name_dir.entries = vec![(
+        language_id,
+        ResourceEntry::Data {
+            code_page: MT_CODE_PAGE,
+            data,
+        },
+    )];
+    Ok(())
+}
+
+fn replace_string_table(
+    root: &mut ResourceDir,
+    language_id: u32,
+    values: &BTreeMap<u32, String>,
+) -> Result<()> {
+    let blocks = string_table::write_string_blocks(values)?;
+    let type_dir = type_dir(root, RT_STRING)?;
+    let time_date_stamp = type_dir.time_date_stamp;
+    type_dir.entries = blocks
+        .into_iter()
+        .map(|(block_id, data)| {
+            let mut name_dir = ResourceDir::new(vec![(
+                language_id,
+                ResourceEntry::Data {
+                    code_page: ST_CODE_PAGE,
+                    data,
+                },
+            )]);
+            name_dir.time_date_stamp = time_date_stamp;
+            (block_id, ResourceEntry::Dir(name_dir))
+        })
+        .collect();
+    Ok(())
+}
+
+/// Write the messages into a copy of the original DLL.
+///
+/// All other sections are preserved, so the message keys and IDs must match
+/// the original DLL; only the message values can be changed.
+pub fn write_messages(
+    write: &mut impl Write,
+    read: &mut impl Read,
+    messages: &Messages,
+    game: GameType,
+) -> Result<()> {
+    let mut mem = Vec::new();
+    read.read_to_end(&mut mem)?;
+    let buf = &mem[..];
+
+    let skip_data = match game {
+        GameType::MW | GameType::PM => None,
+        GameType::RC | GameType::CS => Some(48),
+    };
+
+    let sections = pe::read_pe_headers(buf)?;
+    let message_ids = parse_data_section(buf, &sections, skip_data)?;
+    let values = match_entries(message_ids, &messages.entries)?;
+
+    let resource_section = get_resource_section(&sections)?;
+    let mut root = read_resource_tree(resource_section.get_section_bytes(buf)?, resource_section)?;
+    match game {
+        GameType::MW | GameType::PM | GameType::RC => {
+            replace_message_table(&mut root, messages.language_id, &values)?
+        }
+        GameType::CS => replace_string_table(&mut root, messages.language_id, &values)?,
+    }
+    let rsrc = write_resource_tree(&root, resource_section.virtual_address)?;
+
+    let out = pe::write_resource_section(buf, &sections, &rsrc)?;
+    write.write_all(&out)?;
+    Ok(())
+}
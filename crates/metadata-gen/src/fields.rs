@@ -108,6 +108,8 @@ impl Field {
             DefaultHandling::BoolFalse => Some("false".to_string()),
             DefaultHandling::BoolTrue => Some("true".to_string()),
             DefaultHandling::PointerZero => Some("0".to_string()),
+            DefaultHandling::U32Zero => Some("0".to_string()),
+            DefaultHandling::U32One => Some("1".to_string()),
             DefaultHandling::SoilIsDefault => {
                 Some("Mech3DotNet.Types.Gamez.Materials.Soil.Default".to_string())
             }
@@ -51,6 +51,7 @@ fn main() {
     resolver.push::<api::image::GlobalPalette>();
     resolver.push::<api::image::TexturePalette>();
     resolver.push::<api::image::TextureInfo>();
+    resolver.push::<api::image::TextureFileFormat>();
     resolver.push::<api::image::TextureManifest>();
 
     // --- GameZ
@@ -135,8 +136,10 @@ fn main() {
     // --- gamez/mechlib.rs
     resolver.push::<api::gamez::mechlib::ModelMw>();
     resolver.push::<api::gamez::mechlib::ModelPm>();
+    resolver.push::<api::gamez::mechlib::ModelBounds>();
 
     // --- gamez/mod.rs
+    resolver.push::<api::gamez::TextureInfoMw>();
     resolver.push::<api::gamez::GameZMetadataMw>();
     resolver.push::<api::gamez::GameZDataMw>();
     resolver.push::<api::gamez::GameZMetadataPm>();
@@ -16,6 +16,8 @@ fn parse_serde_skip_serializing_if(lit: LitStr) -> Result<DefaultHandling> {
         "bool_false" => Ok(DefaultHandling::BoolFalse),
         "bool_true" => Ok(DefaultHandling::BoolTrue),
         "pointer_zero" => Ok(DefaultHandling::PointerZero),
+        "u32_zero" => Ok(DefaultHandling::U32Zero),
+        "u32_one" => Ok(DefaultHandling::U32One),
         "Soil::is_default" => Ok(DefaultHandling::SoilIsDefault),
         _ => Err(Error::new_spanned(lit, format!("unknown skip `{}`", value))),
     }
@@ -135,6 +135,12 @@ fn generate_struct_field_default(default: DefaultHandling) -> Path {
         DefaultHandling::PointerZero => parse_quote! {
             ::mech3ax_metadata_types::DefaultHandling::PointerZero
         },
+        DefaultHandling::U32Zero => parse_quote! {
+            ::mech3ax_metadata_types::DefaultHandling::U32Zero
+        },
+        DefaultHandling::U32One => parse_quote! {
+            ::mech3ax_metadata_types::DefaultHandling::U32One
+        },
         DefaultHandling::SoilIsDefault => parse_quote! {
             ::mech3ax_metadata_types::DefaultHandling::SoilIsDefault
         },
@@ -71,6 +71,10 @@ pub struct TypeInfoUnion {
 ///   and implied during deserialization.
 /// * `PointerZero` indicates `0` values can be omitted from serialization,
 ///   and implied during deserialization.
+/// * `U32Zero` indicates `0` values can be omitted from serialization,
+///   and implied during deserialization.
+/// * `U32One` indicates `1` values can be omitted from serialization,
+///   and implied during deserialization.
 /// * `SoilIsDefault` indicated `Soil::Default` values can be omitted from
 ///   serialization, and implied during deserialization.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -80,6 +84,8 @@ pub enum DefaultHandling {
     BoolFalse,
     BoolTrue,
     PointerZero,
+    U32Zero,
+    U32One,
     SoilIsDefault,
 }
 
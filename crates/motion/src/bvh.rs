@@ -0,0 +1,198 @@
+//! Export motions to the Biovision Hierarchy (BVH) format.
+//!
+//! The game is Z-up and left-handed, but BVH is Y-up and right-handed.
+//! Swapping the Y and Z axes converts between both, since it also mirrors
+//! the coordinate system. Positions are converted as `(x, y, z)` ->
+//! `(x, z, y)`. For rotations, the mirroring also reverses the direction of
+//! rotation, so quaternions are converted as `(x, y, z, w)` ->
+//! `(-x, -z, -y, w)`.
+use mech3ax_api_types::motion::Motion;
+use mech3ax_api_types::{Quaternion, Vec3};
+use mech3ax_common::{assert_that, assert_with_msg, Result};
+use mech3ax_types::u32_to_usize;
+use std::collections::HashMap;
+use std::io::Write;
+
+/// A joint of the skeleton a motion is applied to.
+#[derive(Debug, Clone)]
+pub struct BvhJoint {
+    pub name: String,
+    /// The index of the parent joint, or `None` for the root joint.
+    pub parent: Option<usize>,
+    /// The offset from the parent joint, in game coordinates.
+    pub offset: Vec3,
+}
+
+const CHANNELS: &str = "CHANNELS 6 Xposition Yposition Zposition Zrotation Xrotation Yrotation";
+
+fn convert_position(v: &Vec3) -> Vec3 {
+    Vec3 {
+        x: v.x,
+        y: v.z,
+        z: v.y,
+    }
+}
+
+fn convert_rotation(q: &Quaternion) -> Quaternion {
+    Quaternion {
+        x: -q.x,
+        y: -q.z,
+        z: -q.y,
+        w: q.w,
+    }
+}
+
+/// Convert a (BVH) quaternion to the Euler angles for the `ZXY` channel
+/// order, i.e. `R = Rz * Rx * Ry`, as `(z, x, y)` in degrees.
+fn to_euler_zxy(q: &Quaternion) -> (f32, f32, f32) {
+    let Quaternion { x, y, z, w } = *q;
+    let r01 = 2.0 * (x * y - z * w);
+    let r11 = 1.0 - 2.0 * (x * x + z * z);
+    let r20 = 2.0 * (x * z - y * w);
+    let r21 = 2.0 * (y * z + x * w);
+    let r22 = 1.0 - 2.0 * (x * x + y * y);
+
+    let rx = r21.clamp(-1.0, 1.0).asin();
+    let rz = (-r01).atan2(r11);
+    let ry = (-r20).atan2(r22);
+    (rz.to_degrees(), rx.to_degrees(), ry.to_degrees())
+}
+
+/// Order the joints depth-first from the root, and validate the hierarchy.
+fn joint_order(skeleton: &[BvhJoint]) -> Result<(Vec<usize>, Vec<Vec<usize>>)> {
+    let mut children = vec![Vec::new(); skeleton.len()];
+    let mut roots = Vec::new();
+    for (index, joint) in skeleton.iter().enumerate() {
+        match joint.parent {
+            None => roots.push(index),
+            Some(parent) if parent < skeleton.len() => children[parent].push(index),
+            Some(parent) => {
+                return Err(assert_with_msg!(
+                    "Expected joint `{}` parent to be < {}, but was {}",
+                    joint.name,
+                    skeleton.len(),
+                    parent
+                ))
+            }
+        }
+    }
+    let root = match roots[..] {
+        [root] => root,
+        _ => {
+            return Err(assert_with_msg!(
+                "Expected skeleton to have one root joint, but has {}",
+                roots.len()
+            ))
+        }
+    };
+
+    let mut order = Vec::with_capacity(skeleton.len());
+    let mut stack = vec![root];
+    while let Some(index) = stack.pop() {
+        order.push(index);
+        stack.extend(children[index].iter().rev());
+    }
+    // with a single root, any joint not reached is part of a cycle
+    if order.len() != skeleton.len() {
+        return Err(assert_with_msg!(
+            "Expected all joints to be reachable from root `{}`",
+            skeleton[root].name
+        ));
+    }
+    Ok((order, children))
+}
+
+fn write_joint(
+    write: &mut impl Write,
+    skeleton: &[BvhJoint],
+    children: &[Vec<usize>],
+    index: usize,
+    depth: usize,
+) -> Result<()> {
+    let joint = &skeleton[index];
+    let indent = "\t".repeat(depth);
+    let kind = if depth == 0 { "ROOT" } else { "JOINT" };
+    let offset = convert_position(&joint.offset);
+    writeln!(write, "{}{} {}", indent, kind, joint.name)?;
+    writeln!(write, "{}{{", indent)?;
+    writeln!(
+        write,
+        "{}\tOFFSET {:.6} {:.6} {:.6}",
+        indent, offset.x, offset.y, offset.z
+    )?;
+    writeln!(write, "{}\t{}", indent, CHANNELS)?;
+    if children[index].is_empty() {
+        writeln!(write, "{}\tEnd Site", indent)?;
+        writeln!(write, "{}\t{{", indent)?;
+        writeln!(write, "{}\t\tOFFSET 0.000000 0.000000 0.000000", indent)?;
+        writeln!(write, "{}\t}}", indent)?;
+    }
+    for &child in &children[index] {
+        write_joint(write, skeleton, children, child, depth + 1)?;
+    }
+    writeln!(write, "{}}}", indent)?;
+    Ok(())
+}
+
+/// Write a motion applied to a skeleton as BVH.
+///
+/// Every motion part must have a joint of the same name. Joints without a
+/// motion part keep their offset and aren't rotated.
+pub fn motion_to_bvh(motion: &Motion, skeleton: &[BvhJoint], write: &mut impl Write) -> Result<()> {
+    assert_that!("motion frame count", motion.frame_count > 0, 0)?;
+    let frame_count = u32_to_usize(motion.frame_count);
+    let (order, children) = joint_order(skeleton)?;
+
+    let lookup: HashMap<&str, usize> = skeleton
+        .iter()
+        .enumerate()
+        .map(|(index, joint)| (joint.name.as_str(), index))
+        .collect();
+    let mut parts = vec![None; skeleton.len()];
+    for part in &motion.parts {
+        let index = lookup
+            .get(part.name.as_str())
+            .ok_or_else(|| assert_with_msg!("Motion part `{}` not found in skeleton", part.name))?;
+        if part.frames.len() != frame_count {
+            return Err(assert_with_msg!(
+                "Expected motion part `{}` to have {} frames, but has {}",
+                part.name,
+                motion.frame_count,
+                part.frames.len()
+            ));
+        }
+        parts[*index] = Some(part);
+    }
+
+    writeln!(write, "HIERARCHY")?;
+    write_joint(write, skeleton, &children, order[0], 0)?;
+
+    writeln!(write, "MOTION")?;
+    writeln!(write, "Frames: {}", motion.frame_count)?;
+    let frame_time = motion.loop_time / motion.frame_count as f32;
+    writeln!(write, "Frame Time: {:.6}", frame_time)?;
+    for frame_index in 0..frame_count {
+        let values = order
+            .iter()
+            .map(|&index| {
+                let (position, (rz, rx, ry)) = match parts[index] {
+                    Some(part) => {
+                        let frame = &part.frames[frame_index];
+                        let rotation = convert_rotation(&frame.rotation);
+                        (
+                            convert_position(&frame.translation),
+                            to_euler_zxy(&rotation),
+                        )
+                    }
+                    None => (convert_position(&skeleton[index].offset), (0.0, 0.0, 0.0)),
+                };
+                format!(
+                    "{:.6} {:.6} {:.6} {:.6} {:.6} {:.6}",
+                    position.x, position.y, position.z, rz, rx, ry
+                )
+            })
+            .collect::<Vec<_>>();
+        writeln!(write, "{}", values.join(" "))?;
+    }
+    Ok(())
+}
@@ -1,8 +1,14 @@
 #![warn(clippy::all, clippy::cargo)]
+mod bvh;
 mod read;
+mod resample;
+mod validate;
 mod write;
 
+pub use bvh::{motion_to_bvh, BvhJoint};
 pub use read::read_motion;
+pub use resample::resample_motion;
+pub use validate::validate_motion;
 pub use write::write_motion;
 
 use bytemuck::{AnyBitPattern, NoUninit};
@@ -33,4 +39,7 @@ struct MotionHeaderC {
     unk16: f32,       // 16
     unk20: f32,       // 20
 }
-impl_as_bytes!(MotionHeaderC, 24);
+impl_as_bytes!(MotionHeaderC, 24, LeStruct);
+
+#[cfg(test)]
+mod tests;
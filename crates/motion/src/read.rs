@@ -9,7 +9,7 @@ use std::io::Read;
 pub fn read_motion(read: &mut CountingReader<impl Read>) -> Result<Motion> {
     let header: MotionHeaderC = read.read_struct()?;
 
-    assert_that!("motion version", header.version == VERSION, read.prev)?;
+    assert_that!("motion version", version header.version == VERSION, read.prev)?;
     assert_that!("motion loop time", header.loop_time > 0.0, read.prev + 4)?;
     assert_that!("motion frame count", header.frame_count > 0, read.prev + 8)?;
     assert_that!("motion field 16", header.unk16 == -1.0, read.prev + 16)?;
@@ -0,0 +1,123 @@
+use mech3ax_api_types::motion::{Motion, MotionFrame, MotionPart};
+use mech3ax_api_types::{Quaternion, Vec3};
+use mech3ax_common::{assert_that, assert_with_msg, Result};
+
+fn lerp(a: &Vec3, b: &Vec3, t: f32) -> Vec3 {
+    Vec3 {
+        x: a.x + (b.x - a.x) * t,
+        y: a.y + (b.y - a.y) * t,
+        z: a.z + (b.z - a.z) * t,
+    }
+}
+
+fn slerp(a: &Quaternion, b: &Quaternion, t: f32) -> Quaternion {
+    let mut dot = a.x * b.x + a.y * b.y + a.z * b.z + a.w * b.w;
+    // take the shorter path
+    let b = if dot < 0.0 {
+        dot = -dot;
+        Quaternion {
+            x: -b.x,
+            y: -b.y,
+            z: -b.z,
+            w: -b.w,
+        }
+    } else {
+        *b
+    };
+
+    let (s0, s1) = if dot > 0.9995 {
+        // the rotations are (almost) the same, so a linear interpolation is
+        // accurate enough, and avoids dividing by (almost) zero
+        (1.0 - t, t)
+    } else {
+        let theta0 = dot.acos();
+        let theta = theta0 * t;
+        let sin_theta0 = theta0.sin();
+        (
+            (theta0 - theta).sin() / sin_theta0,
+            theta.sin() / sin_theta0,
+        )
+    };
+
+    let q = Quaternion {
+        x: a.x * s0 + b.x * s1,
+        y: a.y * s0 + b.y * s1,
+        z: a.z * s0 + b.z * s1,
+        w: a.w * s0 + b.w * s1,
+    };
+    let norm = (q.x * q.x + q.y * q.y + q.z * q.z + q.w * q.w).sqrt();
+    if norm > 0.0 {
+        Quaternion {
+            x: q.x / norm,
+            y: q.y / norm,
+            z: q.z / norm,
+            w: q.w / norm,
+        }
+    } else {
+        q
+    }
+}
+
+fn resample_part(part: &MotionPart, frame_count: u32) -> Result<MotionPart> {
+    let count = part.frames.len();
+    if count == 0 {
+        return Err(assert_with_msg!(
+            "Expected motion part `{}` to have frames",
+            part.name
+        ));
+    }
+    let step = count as f64 / f64::from(frame_count);
+    let frames = (0..frame_count)
+        .map(|index| {
+            let time = f64::from(index) * step;
+            let current = time.floor();
+            let t = (time - current) as f32;
+            let current = current as usize % count;
+            // motions loop, so the last frame interpolates to the first
+            let next = (current + 1) % count;
+            let a = &part.frames[current];
+            let b = &part.frames[next];
+            MotionFrame {
+                translation: lerp(&a.translation, &b.translation, t),
+                rotation: slerp(&a.rotation, &b.rotation, t),
+            }
+        })
+        .collect();
+    Ok(MotionPart {
+        name: part.name.clone(),
+        frames,
+    })
+}
+
+/// Resample a motion to evenly spaced frames at the target frame rate.
+///
+/// The frames of a motion are evenly spaced over the loop time, and the last
+/// frame is followed by the first. Translations are interpolated linearly,
+/// and rotations spherically. The loop time is unchanged, so the number of
+/// frames is rounded to the nearest whole frame (but at least one).
+pub fn resample_motion(motion: &Motion, target_fps: f32) -> Result<Motion> {
+    assert_that!("motion target fps", target_fps > 0.0, 0)?;
+    assert_that!("motion loop time", motion.loop_time > 0.0, 0)?;
+
+    let frame_count = (motion.loop_time * target_fps).round().max(1.0);
+    let frame_count = if frame_count < u32::MAX as f32 {
+        frame_count as u32
+    } else {
+        return Err(assert_with_msg!(
+            "Too many frames when resampling at {} fps",
+            target_fps
+        ));
+    };
+
+    let parts = motion
+        .parts
+        .iter()
+        .map(|part| resample_part(part, frame_count))
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(Motion {
+        loop_time: motion.loop_time,
+        parts,
+        frame_count,
+    })
+}
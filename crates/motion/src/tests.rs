@@ -0,0 +1,229 @@
+use crate::{motion_to_bvh, read_motion, resample_motion, validate_motion, write_motion, BvhJoint};
+use mech3ax_api_types::motion::{Motion, MotionFrame, MotionPart};
+use mech3ax_api_types::{Quaternion, Vec3};
+use mech3ax_common::io_ext::{CountingReader, CountingWriter};
+use mech3ax_common::test_utils::assert_truncated_fails;
+
+const EPSILON: f32 = 1e-6;
+
+fn frame(x: f32, angle: f32) -> MotionFrame {
+    // rotation around the z axis
+    let half = angle / 2.0;
+    MotionFrame {
+        translation: Vec3 { x, y: 0.0, z: 0.0 },
+        rotation: Quaternion {
+            x: 0.0,
+            y: 0.0,
+            z: half.sin(),
+            w: half.cos(),
+        },
+    }
+}
+
+fn assert_frame(actual: &MotionFrame, expected: &MotionFrame) {
+    let a = &actual.translation;
+    let e = &expected.translation;
+    assert!(
+        (a.x - e.x).abs() < EPSILON && (a.y - e.y).abs() < EPSILON && (a.z - e.z).abs() < EPSILON,
+        "translation {:?} != {:?}",
+        a,
+        e
+    );
+    let a = &actual.rotation;
+    let e = &expected.rotation;
+    assert!(
+        (a.x - e.x).abs() < EPSILON
+            && (a.y - e.y).abs() < EPSILON
+            && (a.z - e.z).abs() < EPSILON
+            && (a.w - e.w).abs() < EPSILON,
+        "rotation {:?} != {:?}",
+        a,
+        e
+    );
+}
+
+fn two_frame_motion() -> Motion {
+    Motion {
+        loop_time: 1.0,
+        parts: vec![MotionPart {
+            name: "hip".to_string(),
+            frames: vec![frame(0.0, 0.0), frame(2.0, 1.0)],
+        }],
+        frame_count: 2,
+    }
+}
+
+#[test]
+fn read_truncated_fails() {
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    write_motion(&mut write, &two_frame_motion()).unwrap();
+    let data = write.into_inner();
+    assert_truncated_fails(&data, |data| read_motion(&mut CountingReader::new(data)));
+}
+
+fn node_names(names: &[&str]) -> Vec<String> {
+    names.iter().map(|name| name.to_string()).collect()
+}
+
+#[test]
+fn validate_valid() {
+    let motion = two_frame_motion();
+    validate_motion(&motion, &node_names(&["torso", "hip"])).unwrap();
+}
+
+#[test]
+fn validate_missing_node() {
+    let motion = two_frame_motion();
+    let err = validate_motion(&motion, &node_names(&["torso"])).unwrap_err();
+    assert_eq!(
+        format!("{:#?}", err),
+        "Assert(\n    Expected motion part `hip` to be a skeleton node,\n)"
+    );
+}
+
+#[test]
+fn validate_part_animated_twice() {
+    let mut motion = two_frame_motion();
+    motion.parts.push(MotionPart {
+        name: "hip".to_string(),
+        frames: vec![frame(0.0, 0.0), frame(1.0, 0.0)],
+    });
+    let err = validate_motion(&motion, &node_names(&["hip"])).unwrap_err();
+    assert_eq!(
+        format!("{:#?}", err),
+        "Assert(\n    Expected motion part `hip` to be animated once, but was parts 0 and 1,\n)"
+    );
+}
+
+#[test]
+fn write_ragged_frames_fails() {
+    let mut motion = two_frame_motion();
+    motion.parts.push(MotionPart {
+        name: "knee".to_string(),
+        frames: vec![frame(0.0, 0.0)],
+    });
+    let expected = "Assert(\n    Expected motion part `knee` to have 2 frames, but was 1,\n)";
+
+    let err = validate_motion(&motion, &node_names(&["hip", "knee"])).unwrap_err();
+    assert_eq!(format!("{:#?}", err), expected);
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    let err = write_motion(&mut write, &motion).unwrap_err();
+    assert_eq!(format!("{:#?}", err), expected);
+}
+
+#[test]
+fn resample_double() {
+    let motion = resample_motion(&two_frame_motion(), 4.0).unwrap();
+    assert_eq!(motion.loop_time, 1.0);
+    assert_eq!(motion.frame_count, 4);
+    let frames = &motion.parts[0].frames;
+    assert_eq!(frames.len(), 4);
+    assert_frame(&frames[0], &frame(0.0, 0.0));
+    assert_frame(&frames[1], &frame(1.0, 0.5));
+    assert_frame(&frames[2], &frame(2.0, 1.0));
+    // the motion loops back to the first frame
+    assert_frame(&frames[3], &frame(1.0, 0.5));
+}
+
+#[test]
+fn resample_same() {
+    let motion = resample_motion(&two_frame_motion(), 2.0).unwrap();
+    assert_eq!(motion.frame_count, 2);
+    let frames = &motion.parts[0].frames;
+    assert_frame(&frames[0], &frame(0.0, 0.0));
+    assert_frame(&frames[1], &frame(2.0, 1.0));
+}
+
+#[test]
+fn resample_invalid_fps() {
+    assert!(resample_motion(&two_frame_motion(), 0.0).is_err());
+}
+
+fn skeleton() -> Vec<BvhJoint> {
+    vec![BvhJoint {
+        name: "hip".to_string(),
+        parent: None,
+        offset: Vec3 {
+            x: 1.0,
+            y: 2.0,
+            z: 3.0,
+        },
+    }]
+}
+
+fn bvh(motion: &Motion, skeleton: &[BvhJoint]) -> String {
+    let mut buf = Vec::new();
+    motion_to_bvh(motion, skeleton, &mut buf).unwrap();
+    String::from_utf8(buf).unwrap()
+}
+
+#[test]
+fn bvh_single_joint() {
+    let motion = resample_motion(&two_frame_motion(), 4.0).unwrap();
+    let bvh = bvh(&motion, &skeleton());
+    let mut lines = bvh.lines();
+
+    let hierarchy = lines
+        .by_ref()
+        .take_while(|line| *line != "MOTION")
+        .collect::<Vec<_>>();
+    assert_eq!(hierarchy[0], "HIERARCHY");
+    assert_eq!(hierarchy[1], "ROOT hip");
+    // the Y and Z axes are swapped
+    assert_eq!(hierarchy[3], "\tOFFSET 1.000000 3.000000 2.000000");
+    assert_eq!(hierarchy.last(), Some(&"}"));
+
+    assert_eq!(lines.next(), Some("Frames: 4"));
+    assert_eq!(lines.next(), Some("Frame Time: 0.250000"));
+    let frames = lines
+        .map(|line| {
+            line.split(' ')
+                .map(|value| value.parse::<f32>().unwrap())
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(frames.len(), 4);
+    assert!(frames.iter().all(|values| values.len() == 6));
+    assert_eq!(frames[0], [0.0, 0.0, 0.0, 0.0, 0.0, 0.0]);
+    // a rotation around the Z (up) axis becomes a reversed rotation around
+    // the Y (up) axis
+    let values = &frames[2];
+    assert_eq!(&values[..5], &[2.0, 0.0, 0.0, 0.0, 0.0]);
+    assert!((values[5] + 1.0f32.to_degrees()).abs() < 1e-3);
+}
+
+#[test]
+fn bvh_joint_without_part() {
+    let mut skeleton = skeleton();
+    skeleton.push(BvhJoint {
+        name: "torso".to_string(),
+        parent: Some(0),
+        offset: Vec3 {
+            x: 0.0,
+            y: 0.0,
+            z: 1.0,
+        },
+    });
+    let bvh = bvh(&two_frame_motion(), &skeleton);
+    assert!(bvh.contains("\tJOINT torso\n"));
+    let last = bvh.lines().last().unwrap();
+    assert!(last.ends_with(" 0.000000 1.000000 0.000000 0.000000 0.000000 0.000000"));
+}
+
+#[test]
+fn bvh_part_not_in_skeleton() {
+    let mut skeleton = skeleton();
+    skeleton[0].name = "torso".to_string();
+    let err = motion_to_bvh(&two_frame_motion(), &skeleton, &mut Vec::new()).unwrap_err();
+    assert_eq!(
+        format!("{:#?}", err),
+        "Assert(\n    Motion part `hip` not found in skeleton,\n)"
+    );
+}
+
+#[test]
+fn bvh_multiple_roots() {
+    let mut skeleton = skeleton();
+    skeleton.extend(skeleton.clone());
+    assert!(motion_to_bvh(&two_frame_motion(), &skeleton, &mut Vec::new()).is_err());
+}
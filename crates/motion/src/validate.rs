@@ -0,0 +1,54 @@
+use mech3ax_api_types::motion::Motion;
+use mech3ax_common::{assert_with_msg, Result};
+use std::collections::HashMap;
+
+/// Validate that every part has one frame per motion frame.
+///
+/// This is also called by `write_motion`.
+pub(crate) fn validate_frames(motion: &Motion) -> Result<()> {
+    if motion.frame_count == 0 {
+        return Err(assert_with_msg!(
+            "Expected motion frame count > 0, but was 0"
+        ));
+    }
+    for part in &motion.parts {
+        let frame_count = part.frames.len();
+        if u32::try_from(frame_count).ok() != Some(motion.frame_count) {
+            return Err(assert_with_msg!(
+                "Expected motion part `{}` to have {} frames, but was {}",
+                part.name,
+                motion.frame_count,
+                frame_count
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Validate a motion against the nodes of the skeleton it animates (e.g. a
+/// mechlib model), so every part is a node that is animated once, and every
+/// part has the same number of frames.
+///
+/// The motion doesn't reference its skeleton, so `write_motion` only
+/// validates the frames.
+pub fn validate_motion(motion: &Motion, node_names: &[String]) -> Result<()> {
+    validate_frames(motion)?;
+    let mut seen = HashMap::new();
+    for (index, part) in motion.parts.iter().enumerate() {
+        if !node_names.contains(&part.name) {
+            return Err(assert_with_msg!(
+                "Expected motion part `{}` to be a skeleton node",
+                part.name
+            ));
+        }
+        if let Some(first) = seen.insert(part.name.as_str(), index) {
+            return Err(assert_with_msg!(
+                "Expected motion part `{}` to be animated once, but was parts {} and {}",
+                part.name,
+                first,
+                index
+            ));
+        }
+    }
+    Ok(())
+}
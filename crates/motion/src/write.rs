@@ -1,13 +1,14 @@
+use super::validate::validate_frames;
 use super::{MotionFlags, MotionHeaderC, VERSION};
 use log::trace;
 use mech3ax_api_types::motion::Motion;
 use mech3ax_common::io_ext::CountingWriter;
-use mech3ax_common::{assert_len, assert_that, Result};
+use mech3ax_common::{assert_len, Result};
 use std::io::Write;
 
 pub fn write_motion(write: &mut CountingWriter<impl Write>, motion: &Motion) -> Result<()> {
+    validate_frames(motion)?;
     let part_count = assert_len!(u32, motion.parts.len(), "motion parts")?;
-    assert_that!("motion frame count", motion.frame_count > 0, 0)?;
 
     let header = MotionHeaderC {
         version: VERSION,
@@ -24,13 +25,7 @@ pub fn write_motion(write: &mut CountingWriter<impl Write>, motion: &Motion) ->
         write.write_string(&part.name)?;
         write.write_u32(MotionFlags::DEFAULT.bits())?;
 
-        let frame_count = assert_len!(u32, part.frames.len(), "motion part frames")?;
-        assert_that!(
-            "motion part frame count",
-            frame_count == motion.frame_count,
-            write.offset
-        )?;
-
+        // the frame count is validated to be the same for all parts, and > 0
         let first = part.frames.first().unwrap();
 
         for frame in &part.frames {
@@ -43,7 +43,7 @@ struct LightCsC {
     parent_count: u32,   // 248
     parent_ptr: u32,     // 252
 }
-impl_as_bytes!(LightCsC, 256);
+impl_as_bytes!(LightCsC, 256, LeStruct);
 
 fn assert_light(light: &LightCsC, offset: usize) -> Result<()> {
     assert_that!("light field 000", light.unk000 == 0.0, offset + 0)?;
@@ -86,12 +86,12 @@ fn assert_light(light: &LightCsC, offset: usize) -> Result<()> {
     let expected = light.range.max * light.range.max;
     assert_that!(
         "light range far sq",
-        light.range_far_sq == expected,
+        approx light.range_far_sq == expected,
         offset + 240
     )?;
     let expected = 1.0 / (light.range.max - light.range.min);
-    assert_that!("light range inv", light.range_inv == expected, offset + 244)?;
-    assert_that!("light parent count", light.parent_count == 1, offset + 248)?;
+    assert_that!("light range inv", approx light.range_inv == expected, offset + 244)?;
+    assert_that!("light parent count", light.parent_count > 0, offset + 248)?;
     assert_that!("light parent ptr", light.parent_ptr != 0, offset + 252)?;
     Ok(())
 }
@@ -106,12 +106,15 @@ pub(crate) fn read(
     assert_light(&light, read.prev)?;
 
     // read as a result of parent_count, but is always 0 (= world node index)
-    let light_parent = read.read_u32()?;
-    assert_that!("light parent", light_parent == 0, read.prev)?;
+    for _ in 0..light.parent_count {
+        let light_parent = read.read_u32()?;
+        assert_that!("light parent", light_parent == 0, read.prev)?;
+    }
 
     Ok(Light {
         name: LIGHT_NAME.to_owned(),
         range: light.range,
+        parent_count: light.parent_count,
         parent_ptr: light.parent_ptr,
         data_ptr,
         node_index,
@@ -150,11 +153,13 @@ pub(crate) fn write(write: &mut CountingWriter<impl Write>, light: &Light) -> Re
         unk236: 1024.0,
         range_far_sq: light.range.max * light.range.max,
         range_inv: 1.0 / (light.range.max - light.range.min),
-        parent_count: 1,
+        parent_count: light.parent_count,
         parent_ptr: light.parent_ptr,
     };
     write.write_struct(&light)?;
     // written as a result of parent_count, but is always 0 (= world node index)
-    write.write_u32(0)?;
+    for _ in 0..light.parent_count {
+        write.write_u32(0)?;
+    }
     Ok(())
 }
@@ -7,6 +7,11 @@ mod object3d;
 mod window;
 mod world;
 
+use crate::transform::node_transforms;
+use mech3ax_api_types::nodes::cs::NodeCs;
+
 pub use node::{
     read_node_data, read_node_info, write_node_data, write_node_info, NodeVariantCs, NODE_CS_C_SIZE,
 };
+
+node_transforms!(NodeCs);
@@ -109,7 +109,7 @@ struct NodeCsC {
     zero200: u32,                    // 200
     zero204: u32,                    // 204
 }
-impl_as_bytes!(NodeCsC, 208);
+impl_as_bytes!(NodeCsC, 208, LeStruct);
 
 pub const NODE_CS_C_SIZE: u32 = NodeCsC::SIZE;
 
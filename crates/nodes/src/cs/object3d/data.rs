@@ -28,7 +28,7 @@ struct Object3dCsC {
     translation: Vec3,  // 084
     zero096: Zeros<48>, // 096
 }
-impl_as_bytes!(Object3dCsC, 144);
+impl_as_bytes!(Object3dCsC, 144, LeStruct);
 
 const SCALE_ONE: Vec3 = Vec3 {
     x: 1.0,
@@ -20,7 +20,7 @@ struct WindowCsC {
     zero240: u32,        // 240
     zero244: u32,        // 244
 }
-impl_as_bytes!(WindowCsC, 248);
+impl_as_bytes!(WindowCsC, 248, LeStruct);
 
 pub(crate) fn read(
     read: &mut CountingReader<impl Read>,
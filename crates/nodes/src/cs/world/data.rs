@@ -63,7 +63,7 @@ struct WorldCsC {
     zero196: u32,                         // 196
     zero200: u32,                         // 200
 }
-impl_as_bytes!(WorldCsC, 204);
+impl_as_bytes!(WorldCsC, 204, LeStruct);
 
 #[derive(Debug, Clone, Copy, NoUninit, AnyBitPattern)]
 #[repr(C)]
@@ -92,7 +92,7 @@ struct PartitionCsC {
     zero80: u32,   // 80
     zero84: u32,   // 84
 }
-impl_as_bytes!(PartitionCsC, 88);
+impl_as_bytes!(PartitionCsC, 88, LeStruct);
 
 const FOG_STATE_LINEAR: u32 = 1;
 
@@ -9,4 +9,7 @@ mod node_data;
 pub mod pm;
 mod range;
 pub mod rc;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils;
+pub mod transform;
 pub(crate) mod types;
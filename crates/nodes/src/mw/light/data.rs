@@ -32,7 +32,7 @@ struct LightMwC {
     parent_count: u32,   // 200
     parent_ptr: u32,     // 204
 }
-impl_as_bytes!(LightMwC, 208);
+impl_as_bytes!(LightMwC, 208, LeStruct);
 
 fn assert_light(light: &LightMwC, offset: usize) -> Result<()> {
     assert_that!(
@@ -69,19 +69,19 @@ fn assert_light(light: &LightMwC, offset: usize) -> Result<()> {
     let expected = light.range.min * light.range.min;
     assert_that!(
         "light range near sq",
-        light.range_near_sq == expected,
+        approx light.range_near_sq == expected,
         offset + 188
     )?;
     let expected = light.range.max * light.range.max;
     assert_that!(
         "light range far sq",
-        light.range_far_sq == expected,
+        approx light.range_far_sq == expected,
         offset + 192
     )?;
     let expected = 1.0 / (light.range.max - light.range.min);
-    assert_that!("light range inv", light.range_inv == expected, offset + 196)?;
+    assert_that!("light range inv", approx light.range_inv == expected, offset + 196)?;
 
-    assert_that!("light parent count", light.parent_count == 1, offset + 200)?;
+    assert_that!("light parent count", light.parent_count > 0, offset + 200)?;
     assert_that!("light parent ptr", light.parent_ptr != 0, offset + 204)?;
     Ok(())
 }
@@ -92,8 +92,10 @@ pub(crate) fn read(read: &mut CountingReader<impl Read>, data_ptr: u32) -> Resul
     assert_light(&light, read.prev)?;
 
     // read as a result of parent_count, but is always 0 (= world node index)
-    let light_parent = read.read_u32()?;
-    assert_that!("light parent", light_parent == 0, read.prev)?;
+    for _ in 0..light.parent_count {
+        let light_parent = read.read_u32()?;
+        assert_that!("light parent", light_parent == 0, read.prev)?;
+    }
 
     Ok(Light {
         name: LIGHT_NAME.to_owned(),
@@ -102,6 +104,7 @@ pub(crate) fn read(read: &mut CountingReader<impl Read>, data_ptr: u32) -> Resul
         ambient: light.ambient,
         color: light.color,
         range: light.range,
+        parent_count: light.parent_count,
         parent_ptr: light.parent_ptr,
         data_ptr,
     })
@@ -125,15 +128,17 @@ pub(crate) fn write(write: &mut CountingWriter<impl Write>, light: &Light) -> Re
         range_near_sq: light.range.min * light.range.min,
         range_far_sq: light.range.max * light.range.max,
         range_inv: 1.0 / (light.range.max - light.range.min),
-        parent_count: 1,
+        parent_count: light.parent_count,
         parent_ptr: light.parent_ptr,
     };
     write.write_struct(&light)?;
     // written as a result of parent_count, but is always 0 (= world node index)
-    write.write_u32(0)?;
+    for _ in 0..light.parent_count {
+        write.write_u32(0)?;
+    }
     Ok(())
 }
 
-pub(crate) fn size() -> u32 {
-    LightMwC::SIZE + 4
+pub(crate) fn size(light: &Light) -> u32 {
+    LightMwC::SIZE + 4 * light.parent_count
 }
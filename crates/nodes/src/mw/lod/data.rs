@@ -22,7 +22,7 @@ struct LodMwC {
     unk72: Bool32,      // 72
     unk76: u32,         // 76
 }
-impl_as_bytes!(LodMwC, 80);
+impl_as_bytes!(LodMwC, 80, LeStruct);
 
 fn assert_lod(lod: LodMwC, offset: usize) -> Result<(bool, Range, f32, Option<u32>)> {
     let level = assert_that!("lod level", bool lod.level, offset + 0)?;
@@ -9,8 +9,13 @@ mod window;
 mod world;
 mod wrappers;
 
+use crate::transform::node_transforms;
+use mech3ax_api_types::nodes::mw::NodeMw;
+
 pub use node::{
     assert_node_info_zero, mechlib_only_err_mw, read_node_data, read_node_info_gamez,
     read_node_mechlib, size_node, write_node_data, write_node_info, NodeMwC, NodeVariantMw,
 };
 pub use wrappers::{WrappedNodeMw, WrapperMw};
+
+node_transforms!(NodeMw);
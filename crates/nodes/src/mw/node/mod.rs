@@ -103,7 +103,7 @@ pub struct NodeMwC {
     zero200: u32,                  // 200
     zero204: u32,                  // 204
 }
-impl_as_bytes!(NodeMwC, 208);
+impl_as_bytes!(NodeMwC, 208, LeStruct);
 
 impl NodeMwC {
     #[inline]
@@ -369,7 +369,7 @@ pub fn size_node(node: &NodeMw) -> u32 {
         NodeMw::Camera(_) => camera::size(),
         NodeMw::Empty(_) => empty::size(),
         NodeMw::Display(_) => display::size(),
-        NodeMw::Light(_) => light::size(),
+        NodeMw::Light(light) => light::size(light),
         NodeMw::Lod(lod) => lod::size(lod),
         NodeMw::Object3d(object3d) => object3d::size(object3d),
         NodeMw::Window(_) => window::size(),
@@ -446,3 +446,6 @@ pub fn read_node_mechlib(read: &mut CountingReader<impl Read>) -> Result<Wrapped
     }?;
     read_node_data(read, variant)
 }
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,726 @@
+use super::{
+    read_node_data, read_node_info_gamez, size_node, write_node_data, write_node_info,
+    write_variant, NodeVariantMw, NodeVariantsMw,
+};
+use crate::flags::NodeBitFlags;
+use crate::mw::wrappers::WrappedNodeMw;
+use crate::test_utils;
+use crate::types::{NodeType, ZONE_DEFAULT};
+use mech3ax_api_types::nodes::mw::{Empty, Light, Lod, NodeMw, Object3d, World};
+use mech3ax_api_types::nodes::{
+    Area, AreaPartition, BoundingBox, Camera, Display, NodeFlags, PartitionPg, Transformation,
+    Window,
+};
+use mech3ax_api_types::{Color, Range, Vec3};
+use mech3ax_common::io_ext::{CountingReader, CountingWriter};
+use mech3ax_common::test_utils::assert_truncated_fails;
+use mech3ax_common::Error;
+use mech3ax_types::u32_to_usize;
+use std::io::Cursor;
+
+const FLAGS_OFFSET: usize = 36;
+
+const BBOX: BoundingBox = BoundingBox {
+    a: Vec3 {
+        x: 1.0,
+        y: 1.0,
+        z: -2.0,
+    },
+    b: Vec3 {
+        x: 2.0,
+        y: 2.0,
+        z: -1.0,
+    },
+};
+
+/// The flags a node type must have, and the flags it may have.
+struct Case {
+    node_type: NodeType,
+    name: &'static str,
+    always: NodeBitFlags,
+    variable: NodeBitFlags,
+    /// The name of the node type's flags assertion.
+    error_name: &'static str,
+}
+
+const CASES: &[Case] = &[
+    Case {
+        node_type: NodeType::Camera,
+        name: "camera1",
+        always: NodeBitFlags::DEFAULT,
+        variable: NodeBitFlags::empty(),
+        error_name: "camera flags",
+    },
+    Case {
+        node_type: NodeType::Display,
+        name: "display",
+        always: NodeBitFlags::DEFAULT,
+        variable: NodeBitFlags::empty(),
+        error_name: "display flags",
+    },
+    Case {
+        node_type: NodeType::Empty,
+        name: "empty",
+        always: NodeBitFlags::BASE,
+        variable: NodeBitFlags::from_bits_truncate(
+            NodeBitFlags::ALTITUDE_SURFACE.bits()
+                | NodeBitFlags::INTERSECT_SURFACE.bits()
+                | NodeBitFlags::INTERSECT_BBOX.bits()
+                | NodeBitFlags::UNK08.bits()
+                | NodeBitFlags::UNK10.bits()
+                | NodeBitFlags::UNK25.bits()
+                | NodeBitFlags::UNK28.bits(),
+        ),
+        error_name: "empty flags",
+    },
+    Case {
+        node_type: NodeType::Light,
+        name: "sunlight",
+        always: NodeBitFlags::from_bits_truncate(
+            NodeBitFlags::DEFAULT.bits() | NodeBitFlags::UNK08.bits(),
+        ),
+        variable: NodeBitFlags::empty(),
+        error_name: "light flags",
+    },
+    Case {
+        node_type: NodeType::LoD,
+        name: "lod",
+        always: NodeBitFlags::from_bits_truncate(
+            NodeBitFlags::ACTIVE.bits()
+                | NodeBitFlags::UNK08.bits()
+                | NodeBitFlags::UNK10.bits()
+                | NodeBitFlags::TREE_VALID.bits()
+                | NodeBitFlags::ID_ZONE_CHECK.bits(),
+        ),
+        variable: NodeBitFlags::from_bits_truncate(
+            NodeBitFlags::ALTITUDE_SURFACE.bits()
+                | NodeBitFlags::INTERSECT_SURFACE.bits()
+                | NodeBitFlags::INTERSECT_BBOX.bits()
+                | NodeBitFlags::TERRAIN.bits()
+                | NodeBitFlags::UNK25.bits(),
+        ),
+        error_name: "lod flags",
+    },
+    Case {
+        node_type: NodeType::Object3d,
+        name: "object",
+        always: NodeBitFlags::from_bits_truncate(
+            NodeBitFlags::ACTIVE.bits()
+                | NodeBitFlags::TREE_VALID.bits()
+                | NodeBitFlags::ID_ZONE_CHECK.bits()
+                | NodeBitFlags::UNK25.bits(),
+        ),
+        variable: NodeBitFlags::from_bits_truncate(
+            NodeBitFlags::ALTITUDE_SURFACE.bits()
+                | NodeBitFlags::INTERSECT_SURFACE.bits()
+                | NodeBitFlags::INTERSECT_BBOX.bits()
+                | NodeBitFlags::LANDMARK.bits()
+                | NodeBitFlags::UNK08.bits()
+                | NodeBitFlags::HAS_MESH.bits()
+                | NodeBitFlags::UNK10.bits()
+                | NodeBitFlags::TERRAIN.bits()
+                | NodeBitFlags::CAN_MODIFY.bits()
+                | NodeBitFlags::CLIP_TO.bits(),
+        ),
+        error_name: "object3d flags",
+    },
+    Case {
+        node_type: NodeType::Window,
+        name: "window1",
+        always: NodeBitFlags::DEFAULT,
+        variable: NodeBitFlags::empty(),
+        error_name: "window flags",
+    },
+    Case {
+        node_type: NodeType::World,
+        name: "world1",
+        always: NodeBitFlags::DEFAULT,
+        variable: NodeBitFlags::empty(),
+        error_name: "world flags",
+    },
+];
+
+/// Build node info fields that are valid for the node type, apart from the
+/// flags.
+fn variants(case: &Case, flags: NodeBitFlags) -> NodeVariantsMw {
+    let mut variants = NodeVariantsMw {
+        name: case.name.to_string(),
+        flags,
+        unk044: 0,
+        zone_id: ZONE_DEFAULT,
+        data_ptr: 0x1000,
+        mesh_index: -1,
+        area_partition: None,
+        has_parent: false,
+        parent_array_ptr: 0,
+        children_count: 0,
+        children_array_ptr: 0,
+        unk116: BoundingBox::EMPTY,
+        unk140: BoundingBox::EMPTY,
+        unk164: BoundingBox::EMPTY,
+        unk196: 0,
+    };
+    match case.node_type {
+        NodeType::Camera | NodeType::Display | NodeType::Window => {}
+        NodeType::Empty => {
+            variants.unk044 = 1;
+            variants.data_ptr = 0;
+            variants.unk196 = 160;
+        }
+        NodeType::Light => {
+            variants.unk116 = BBOX;
+        }
+        NodeType::LoD => {
+            variants.unk044 = 1;
+            variants.has_parent = true;
+            variants.parent_array_ptr = 0x2000;
+            variants.children_count = 1;
+            variants.children_array_ptr = 0x3000;
+            variants.unk116 = BBOX;
+            variants.unk164 = BBOX;
+            variants.unk196 = 160;
+        }
+        NodeType::Object3d => {
+            variants.unk044 = 1;
+            if flags.contains(NodeBitFlags::HAS_MESH) {
+                variants.mesh_index = 0;
+            }
+            variants.unk196 = 160;
+        }
+        NodeType::World => {
+            variants.children_count = 1;
+            variants.children_array_ptr = 0x3000;
+        }
+    }
+    variants
+}
+
+fn write(case: &Case, flags: NodeBitFlags) -> Vec<u8> {
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    write_variant(&mut write, case.node_type, variants(case, flags)).unwrap();
+    write.into_inner()
+}
+
+fn read(buf: Vec<u8>) -> mech3ax_common::Result<NodeVariantMw> {
+    let mut read = CountingReader::new(Cursor::new(buf));
+    read_node_info_gamez(&mut read).map(|variant| variant.unwrap())
+}
+
+/// The flags of the node as read, via the API flags where they are exposed.
+fn read_flags(case: &Case, variant: NodeVariantMw) -> NodeBitFlags {
+    match variant {
+        NodeVariantMw::Empty(empty) => NodeBitFlags::from(&empty.flags),
+        NodeVariantMw::Lod(lod) => NodeBitFlags::from(&NodeFlags::from(lod.flags)),
+        NodeVariantMw::Object3d(node) => NodeBitFlags::from(&NodeFlags::from(node.flags)),
+        // these flags are constant, and not part of the API
+        _ => case.always,
+    }
+}
+
+/// Every combination of the set bits.
+fn combinations(flags: NodeBitFlags) -> Vec<NodeBitFlags> {
+    let bits: Vec<u32> = (0..32)
+        .map(|bit| 1 << bit)
+        .filter(|bit| flags.bits() & bit != 0)
+        .collect();
+    (0..1u32 << bits.len())
+        .map(|combination| {
+            let value = bits
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| combination & (1 << index) != 0)
+                .fold(0, |value, (_, bit)| value | bit);
+            NodeBitFlags::from_bits_truncate(value)
+        })
+        .collect()
+}
+
+#[test]
+fn api_flags_round_trip() {
+    let all = NodeBitFlags::from_bits_truncate(u32::MAX);
+    for case in CASES {
+        for variable in combinations(case.variable) {
+            let flags = case.always | variable;
+            assert_eq!(NodeBitFlags::from(&NodeFlags::from(flags)), flags);
+        }
+    }
+    assert_eq!(NodeBitFlags::from(&NodeFlags::from(all)), all);
+}
+
+#[test]
+fn valid_flags_round_trip() {
+    for case in CASES {
+        for variable in combinations(case.variable) {
+            let flags = case.always | variable;
+            let buf = write(case, flags);
+
+            let variant = read(buf.clone()).unwrap_or_else(|e| {
+                panic!("{:?} {} failed to read: {:?}", case.node_type, flags, e)
+            });
+            let read_flags = read_flags(case, variant);
+            assert_eq!(read_flags, flags, "{:?}", case.node_type);
+            assert_eq!(write(case, read_flags), buf, "{:?}", case.node_type);
+        }
+    }
+}
+
+#[test]
+fn invalid_flags_are_rejected() {
+    let known = NodeBitFlags::from_bits_truncate(u32::MAX).bits();
+    for case in CASES {
+        let buf = write(case, case.always);
+        for bit in (0..32).map(|bit| 1u32 << bit) {
+            if case.variable.bits() & bit != 0 {
+                continue;
+            }
+            // toggle a bit that must be set, or mustn't be
+            let flags = case.always.bits() ^ bit;
+            let mut buf = buf.clone();
+            buf[FLAGS_OFFSET..FLAGS_OFFSET + 4].copy_from_slice(&flags.to_le_bytes());
+
+            let expected = if known & bit == 0 {
+                "node flags"
+            } else {
+                case.error_name
+            };
+            match read(buf) {
+                Err(Error::Assert(e)) => {
+                    assert_eq!(e.name(), Some(expected), "{:?} {:#x}", case.node_type, bit);
+                    assert_eq!(e.pos(), Some(FLAGS_OFFSET));
+                }
+                other => panic!(
+                    "{:?} {:#x}: expected an error, got {:?}",
+                    case.node_type, bit, other
+                ),
+            }
+        }
+    }
+}
+
+fn object3d() -> Object3d {
+    let flags = NodeBitFlags::from_bits_truncate(
+        NodeBitFlags::ACTIVE.bits()
+            | NodeBitFlags::HAS_MESH.bits()
+            | NodeBitFlags::TREE_VALID.bits()
+            | NodeBitFlags::ID_ZONE_CHECK.bits()
+            | NodeBitFlags::UNK25.bits(),
+    );
+    Object3d {
+        transformation: Some(Transformation {
+            rotation: Vec3 {
+                x: 0.5,
+                y: -0.25,
+                z: 0.0,
+            },
+            translation: Vec3 {
+                x: 10.0,
+                y: 20.0,
+                z: 30.0,
+            },
+            matrix: None,
+        }),
+        flags: NodeFlags::from(flags),
+        zone_id: 1,
+        area_partition: Some(AreaPartition { x: 2, y: 3 }),
+        mesh_index: 3,
+        parent: Some(0),
+        children: vec![4, 5],
+        data_ptr: 0x1000,
+        parent_array_ptr: 0x2000,
+        children_array_ptr: 0x3000,
+        unk116: BBOX,
+        unk140: BBOX,
+        unk164: BBOX,
+        ..test_utils::object3d()
+    }
+}
+
+fn write_node(node: &NodeMw) -> Vec<u8> {
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    write_node_info(&mut write, node).unwrap();
+    write_node_data(&mut write, node).unwrap();
+    write.into_inner()
+}
+
+#[test]
+fn object3d_with_children_round_trip() {
+    let node = NodeMw::Object3d(object3d());
+    let buf = write_node(&node);
+
+    let mut read = CountingReader::new(Cursor::new(buf.clone()));
+    let variant = read_node_info_gamez(&mut read).unwrap().unwrap();
+    let wrapped = read_node_data(&mut read, variant).unwrap();
+    let WrappedNodeMw::Object3d(wrapper) = wrapped else {
+        panic!("expected an object3d node");
+    };
+    // the parent and children arrays are read by the gamez nodes
+    assert!(wrapper.has_parent);
+    assert_eq!(wrapper.children_count, 2);
+
+    let mut object3d = wrapper.wrapped;
+    assert_eq!(object3d.mesh_index, 3);
+    assert!(object3d.flags.has_mesh);
+    assert_eq!(object3d.children_array_ptr, 0x3000);
+    assert!(object3d.transformation.is_some());
+    object3d.parent = Some(0);
+    object3d.children = vec![4, 5];
+    assert_eq!(write_node(&NodeMw::Object3d(object3d)), buf);
+}
+
+#[test]
+fn object3d_mesh_index_requires_has_mesh() {
+    let mut object3d = object3d();
+    object3d.flags.has_mesh = false;
+    let buf = write_node(&NodeMw::Object3d(object3d));
+
+    let mut read = CountingReader::new(Cursor::new(buf));
+    match read_node_info_gamez(&mut read) {
+        Err(Error::Assert(e)) => {
+            assert_eq!(e.name(), Some("object3d mesh index"));
+            assert_eq!(e.pos(), Some(60));
+        }
+        _ => panic!("expected an assertion error"),
+    }
+}
+
+fn read_node(buf: Vec<u8>) -> mech3ax_common::Result<WrappedNodeMw> {
+    let mut read = CountingReader::new(Cursor::new(buf));
+    let variant = read_node_info_gamez(&mut read)?.unwrap();
+    read_node_data(&mut read, variant)
+}
+
+fn camera() -> NodeMw {
+    NodeMw::Camera(Camera {
+        clip: Range {
+            min: 1.0,
+            max: 5000.0,
+        },
+        fov: Range {
+            min: 1.0,
+            max: 0.75,
+        },
+        focus_node_xy: -1,
+        data_ptr: 0x1000,
+    })
+}
+
+fn window() -> NodeMw {
+    NodeMw::Window(Window {
+        resolution_x: 320,
+        resolution_y: 200,
+        data_ptr: 0x1000,
+    })
+}
+
+#[test]
+fn camera_round_trip() {
+    let buf = write_node(&camera());
+    let WrappedNodeMw::Camera(camera) = read_node(buf.clone()).unwrap() else {
+        panic!("expected a camera node");
+    };
+    assert_eq!(camera.clip.max, 5000.0);
+    assert_eq!(camera.fov.max, 0.75);
+    assert_eq!(write_node(&NodeMw::Camera(camera)), buf);
+}
+
+#[test]
+fn window_round_trip() {
+    let buf = write_node(&window());
+    let WrappedNodeMw::Window(window) = read_node(buf.clone()).unwrap() else {
+        panic!("expected a window node");
+    };
+    assert_eq!(window.resolution_x, 320);
+    assert_eq!(window.resolution_y, 200);
+    assert_eq!(write_node(&NodeMw::Window(window)), buf);
+}
+
+fn assert_corrupt(node: NodeMw, offset: usize, expected: &str) {
+    let mut buf = write_node(&node);
+    buf[offset] ^= 1;
+    match read_node(buf) {
+        Err(Error::Assert(e)) => {
+            assert_eq!(e.name(), Some(expected));
+            assert_eq!(e.pos(), Some(offset));
+        }
+        _ => panic!("expected an assertion error"),
+    }
+}
+
+#[test]
+fn camera_corrupt_constant_field() {
+    // node info field 044
+    assert_corrupt(camera(), 44, "camera field 044");
+    // camera data window index, after the node info
+    assert_corrupt(camera(), 208 + 4, "camera window index");
+}
+
+#[test]
+fn window_corrupt_constant_field() {
+    // node info field 196
+    assert_corrupt(window(), 196, "window field 196");
+    // window data buffer ptr, after the node info
+    assert_corrupt(window(), 208 + 232, "window buffer ptr");
+}
+
+fn lod(level: bool, range: Range) -> Lod {
+    let flags = NodeBitFlags::from_bits_truncate(
+        NodeBitFlags::ACTIVE.bits()
+            | NodeBitFlags::UNK08.bits()
+            | NodeBitFlags::UNK10.bits()
+            | NodeBitFlags::TREE_VALID.bits()
+            | NodeBitFlags::ID_ZONE_CHECK.bits(),
+    );
+    Lod {
+        name: "lod".to_string(),
+        level,
+        range,
+        unk60: 2.0,
+        unk76: None,
+        flags: NodeFlags::from(flags),
+        zone_id: ZONE_DEFAULT,
+        area_partition: None,
+        parent: 0,
+        children: vec![2],
+        data_ptr: 0x1000,
+        parent_array_ptr: 0x2000,
+        children_array_ptr: 0x3000,
+        unk116: BBOX,
+    }
+}
+
+#[test]
+fn lod_levels_round_trip() {
+    let levels = [
+        lod(
+            true,
+            Range {
+                min: 0.0,
+                max: 100.0,
+            },
+        ),
+        lod(
+            false,
+            Range {
+                min: 100.0,
+                max: 500.0,
+            },
+        ),
+    ];
+    for level in levels {
+        let buf = write_node(&NodeMw::Lod(level));
+        let WrappedNodeMw::Lod(wrapper) = read_node(buf.clone()).unwrap() else {
+            panic!("expected a lod node");
+        };
+        assert_eq!(wrapper.children_count, 1);
+
+        let mut lod = wrapper.wrapped;
+        lod.parent = 0;
+        lod.children = vec![2];
+        assert_eq!(write_node(&NodeMw::Lod(lod)), buf);
+    }
+}
+
+#[test]
+fn lod_range_far_sq_must_match() {
+    let mut buf = write_node(&NodeMw::Lod(lod(
+        true,
+        Range {
+            min: 0.0,
+            max: 100.0,
+        },
+    )));
+    // range far sq, after the node info
+    buf[208 + 12..208 + 16].copy_from_slice(&9999.0f32.to_le_bytes());
+    match read_node(buf) {
+        Err(Error::Assert(e)) => {
+            assert_eq!(e.name(), Some("lod range far sq"));
+            assert_eq!(e.pos(), Some(208 + 12));
+        }
+        _ => panic!("expected an assertion error"),
+    }
+}
+
+/// Write the node data, followed by the parent and child indices like the
+/// gamez nodes are, and assert the number of bytes written is the expected
+/// size.
+fn assert_written_size(node: &NodeMw, expected: u32) {
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    write_node_data(&mut write, node).unwrap();
+    let (parent, children) = match node {
+        NodeMw::Lod(lod) => (Some(lod.parent), lod.children.as_slice()),
+        NodeMw::Object3d(object3d) => (object3d.parent, object3d.children.as_slice()),
+        NodeMw::World(world) => (None, world.children.as_slice()),
+        _ => (None, [].as_slice()),
+    };
+    for index in parent.iter().chain(children) {
+        write.write_u32(*index).unwrap();
+    }
+    assert_eq!(write.offset, u32_to_usize(expected), "{:?}", node);
+}
+
+fn world() -> World {
+    let partition = |x, nodes| PartitionPg {
+        x,
+        y: 256,
+        z_min: 0.0,
+        z_max: 10.0,
+        z_mid: None,
+        nodes,
+        ptr: 0x4000,
+    };
+    World {
+        name: "world1".to_string(),
+        // two partitions wide, and one high
+        area: Area {
+            left: 0,
+            top: 0,
+            right: 512,
+            bottom: 256,
+        },
+        partitions: vec![vec![partition(0, vec![1, 2]), partition(256, vec![2])]],
+        area_partition_x_count: 2,
+        area_partition_y_count: 1,
+        fudge_count: false,
+        area_partition_ptr: 0x1000,
+        virt_partition_ptr: 0x2000,
+        world_children_ptr: 0x3000,
+        world_child_value: 0,
+        world_lights_ptr: 0x7000,
+        children: vec![1],
+        data_ptr: 0x5000,
+        children_array_ptr: 0x6000,
+    }
+}
+
+fn light() -> NodeMw {
+    NodeMw::Light(Light {
+        name: "sunlight".to_string(),
+        direction: Vec3 {
+            x: 0.0,
+            y: -1.0,
+            z: 0.0,
+        },
+        diffuse: 0.5,
+        ambient: 0.5,
+        color: Color::WHITE_NORM,
+        range: Range {
+            min: 1.0,
+            max: 100.0,
+        },
+        parent_count: 1,
+        parent_ptr: 0x1000,
+        data_ptr: 0x2000,
+    })
+}
+
+#[test]
+fn light_round_trip() {
+    let buf = write_node(&light());
+    // the node info, the light data, and one parent value
+    assert_eq!(buf.len(), 208 + 208 + 4);
+    let WrappedNodeMw::Light(light) = read_node(buf.clone()).unwrap() else {
+        panic!("expected a light node");
+    };
+    assert_eq!(write_node(&NodeMw::Light(light)), buf);
+}
+
+#[test]
+fn light_with_two_parents_round_trip() {
+    let NodeMw::Light(mut light) = light() else {
+        unreachable!()
+    };
+    light.parent_count = 2;
+    let node = NodeMw::Light(light);
+    let buf = write_node(&node);
+    // the node info, the light data, and two parent values
+    assert_eq!(buf.len(), 208 + 208 + 2 * 4);
+    assert_eq!(size_node(&node), 208 + 2 * 4);
+    assert_written_size(&node, size_node(&node));
+    let WrappedNodeMw::Light(light) = read_node(buf.clone()).unwrap() else {
+        panic!("expected a light node");
+    };
+    assert_eq!(light.parent_count, 2);
+    assert_eq!(write_node(&NodeMw::Light(light)), buf);
+}
+
+#[test]
+fn light_without_parents_is_rejected() {
+    let mut buf = write_node(&light());
+    // light data parent count, after the node info
+    let offset = 208 + 200;
+    buf[offset..offset + 4].copy_from_slice(&0u32.to_le_bytes());
+    buf.truncate(buf.len() - 4);
+    match read_node(buf) {
+        Err(Error::Assert(e)) => {
+            assert_eq!(e.name(), Some("light parent count"));
+            assert_eq!(e.pos(), Some(offset));
+        }
+        _ => panic!("expected an assertion error"),
+    }
+}
+
+fn nodes() -> Vec<NodeMw> {
+    let mut orphan = object3d();
+    orphan.parent = None;
+    orphan.parent_array_ptr = 0;
+    orphan.children = Vec::new();
+    orphan.children_array_ptr = 0;
+    vec![
+        camera(),
+        NodeMw::Display(Display {
+            resolution_x: 640,
+            resolution_y: 400,
+            clear_color: Color::BLACK,
+            data_ptr: 0x1000,
+        }),
+        NodeMw::Empty(Empty {
+            name: "empty".to_string(),
+            flags: NodeFlags::from(NodeBitFlags::from_bits_truncate(
+                NodeBitFlags::ACTIVE.bits()
+                    | NodeBitFlags::TREE_VALID.bits()
+                    | NodeBitFlags::ID_ZONE_CHECK.bits(),
+            )),
+            unk044: 1,
+            zone_id: ZONE_DEFAULT,
+            unk116: BBOX,
+            unk140: BBOX,
+            unk164: BBOX,
+            parent: 0,
+        }),
+        light(),
+        NodeMw::Lod(lod(
+            true,
+            Range {
+                min: 0.0,
+                max: 100.0,
+            },
+        )),
+        NodeMw::Object3d(object3d()),
+        NodeMw::Object3d(orphan),
+        window(),
+        NodeMw::World(world()),
+    ]
+}
+
+#[test]
+fn written_size_matches_size_node() {
+    for node in &nodes() {
+        assert_written_size(node, size_node(node));
+    }
+}
+
+#[test]
+fn read_truncated_fails() {
+    let read = |data: &[u8]| {
+        let mut read = CountingReader::new(Cursor::new(data));
+        match read_node_info_gamez(&mut read)? {
+            Some(variant) => read_node_data(&mut read, variant).map(Some),
+            None => Ok(None),
+        }
+    };
+    for node in &nodes() {
+        let data = write_node(node);
+        assert!(read(&data).unwrap().is_some(), "{:?}", node);
+        assert_truncated_fails(&data, read);
+    }
+}
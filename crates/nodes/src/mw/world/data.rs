@@ -59,7 +59,7 @@ struct WorldMwC {
     zero180: u32,                         // 180
     zero184: u32,                         // 184
 }
-impl_as_bytes!(WorldMwC, 188);
+impl_as_bytes!(WorldMwC, 188, LeStruct);
 
 #[derive(Debug, Clone, Copy, NoUninit, AnyBitPattern)]
 #[repr(C)]
@@ -84,7 +84,7 @@ struct PartitionMwC {
     zero64: u32,   // 64
     zero68: u32,   // 68
 }
-impl_as_bytes!(PartitionMwC, 72);
+impl_as_bytes!(PartitionMwC, 72, LeStruct);
 
 const FOG_STATE_LINEAR: u32 = 1;
 
@@ -48,7 +48,7 @@ pub(crate) struct CameraC {
     pub(crate) zone_set: i32,          // 480
     pub(crate) unk484: i32,            // 484
 }
-impl_as_bytes!(CameraC, 488);
+impl_as_bytes!(CameraC, 488, LeStruct);
 
 fn assert_camera(camera: &CameraC, offset: usize) -> Result<()> {
     assert_that!("camera world index", camera.world_index == 0, offset + 0)?;
@@ -15,7 +15,7 @@ struct DisplayC {
     resolution_y: u32,
     clear_color: Color,
 }
-impl_as_bytes!(DisplayC, 28);
+impl_as_bytes!(DisplayC, 28, LeStruct);
 
 pub(crate) fn read(read: &mut CountingReader<impl Read>, data_ptr: u32) -> Result<Display> {
     let display: DisplayC = read.read_struct()?;
@@ -19,7 +19,7 @@ struct WindowC {
     zero240: u32,        // 240
     zero244: u32,        // 244
 }
-impl_as_bytes!(WindowC, 248);
+impl_as_bytes!(WindowC, 248, LeStruct);
 
 pub(crate) fn read(read: &mut CountingReader<impl Read>, data_ptr: u32) -> Result<Window> {
     let window: WindowC = read.read_struct()?;
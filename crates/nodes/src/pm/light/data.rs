@@ -43,7 +43,7 @@ struct LightPmC {
     parent_count: u32,   // 248
     parent_ptr: u32,     // 252
 }
-impl_as_bytes!(LightPmC, 256);
+impl_as_bytes!(LightPmC, 256, LeStruct);
 
 #[allow(clippy::approx_constant)]
 const LIGHT_UNK_000: f32 = -0.5235988;
@@ -124,12 +124,12 @@ fn assert_light(light: &LightPmC, offset: usize) -> Result<()> {
     let expected = light.range.max * light.range.max;
     assert_that!(
         "light range far sq",
-        light.range_far_sq == expected,
+        approx light.range_far_sq == expected,
         offset + 240
     )?;
     let expected = 1.0 / (light.range.max - light.range.min);
-    assert_that!("light range inv", light.range_inv == expected, offset + 244)?;
-    assert_that!("light parent count", light.parent_count == 1, offset + 248)?;
+    assert_that!("light range inv", approx light.range_inv == expected, offset + 244)?;
+    assert_that!("light parent count", light.parent_count > 0, offset + 248)?;
     assert_that!("light parent ptr", light.parent_ptr != 0, offset + 252)?;
     Ok(())
 }
@@ -140,8 +140,10 @@ pub(crate) fn read(read: &mut CountingReader<impl Read>, data_ptr: u32) -> Resul
     assert_light(&light, read.prev)?;
 
     // read as a result of parent_count, but is always 0 (= world node index)
-    let light_parent = read.read_u32()?;
-    assert_that!("light parent", light_parent == 0, read.prev)?;
+    for _ in 0..light.parent_count {
+        let light_parent = read.read_u32()?;
+        assert_that!("light parent", light_parent == 0, read.prev)?;
+    }
 
     Ok(Light {
         name: LIGHT_NAME.to_owned(),
@@ -149,6 +151,7 @@ pub(crate) fn read(read: &mut CountingReader<impl Read>, data_ptr: u32) -> Resul
         unk156: light.unk156,
         unk160: light.unk160,
         range: light.range,
+        parent_count: light.parent_count,
         parent_ptr: light.parent_ptr,
         data_ptr,
         node_index: 0, // to be filled in for gamez
@@ -189,11 +192,13 @@ pub(crate) fn write(write: &mut CountingWriter<impl Write>, light: &Light) -> Re
         unk236: 1024.0,
         range_far_sq: light.range.max * light.range.max,
         range_inv: 1.0 / (light.range.max - light.range.min),
-        parent_count: 1,
+        parent_count: light.parent_count,
         parent_ptr: light.parent_ptr,
     };
     write.write_struct(&light)?;
     // written as a result of parent_count, but is always 0 (= world node index)
-    write.write_u32(0)?;
+    for _ in 0..light.parent_count {
+        write.write_u32(0)?;
+    }
     Ok(())
 }
@@ -24,7 +24,7 @@ struct LodPmC {
     unk84: u32,         // 84
     unk88: u32,         // 88
 }
-impl_as_bytes!(LodPmC, 92);
+impl_as_bytes!(LodPmC, 92, LeStruct);
 
 fn assert_lod(lod: &LodPmC, offset: usize) -> Result<(bool, Range)> {
     let level = assert_that!("level", bool lod.level, offset + 0)?;
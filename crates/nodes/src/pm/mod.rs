@@ -8,8 +8,13 @@ mod window;
 mod world;
 mod wrappers;
 
+use crate::transform::node_transforms;
+use mech3ax_api_types::nodes::pm::NodePm;
+
 pub use node::{
     mechlib_only_err_pm, read_node_data, read_node_info_gamez, read_node_mechlib, write_node_data,
     write_node_info, NodeVariantPm,
 };
 pub use wrappers::{WrappedNodePm, WrapperPm};
+
+node_transforms!(NodePm);
@@ -102,7 +102,7 @@ struct NodePmC {
     zero200: u32,                    // 200
     zero204: u32,                    // 204
 }
-impl_as_bytes!(NodePmC, 208);
+impl_as_bytes!(NodePmC, 208, LeStruct);
 
 fn assert_node(node: NodePmC, offset: usize) -> Result<(NodeType, NodeVariantsPm)> {
     // invariants for every node type
@@ -63,7 +63,7 @@ struct WorldPmC {
     zero196: u32,                         // 196
     zero200: u32,                         // 200
 }
-impl_as_bytes!(WorldPmC, 204);
+impl_as_bytes!(WorldPmC, 204, LeStruct);
 
 #[derive(Debug, Clone, Copy, NoUninit, AnyBitPattern)]
 #[repr(C)]
@@ -92,7 +92,7 @@ struct PartitionPmC {
     zero80: u32,   // 80
     zero84: u32,   // 84
 }
-impl_as_bytes!(PartitionPmC, 88);
+impl_as_bytes!(PartitionPmC, 88, LeStruct);
 
 const FOG_STATE_LINEAR: u32 = 1;
 
@@ -34,7 +34,7 @@ struct LightRcC {
     parent_count: u32,   // 220
     parent_ptr: u32,     // 224
 }
-impl_as_bytes!(LightRcC, 228);
+impl_as_bytes!(LightRcC, 228, LeStruct);
 
 fn assert_light(light: &LightRcC, offset: usize) -> Result<()> {
     assert_that!("light field 000", light.unk000 == 1, offset + 0)?;
@@ -66,12 +66,12 @@ fn assert_light(light: &LightRcC, offset: usize) -> Result<()> {
     let expected = light.range.max * light.range.max;
     assert_that!(
         "light range far sq",
-        light.range_far_sq == expected,
+        approx light.range_far_sq == expected,
         offset + 212
     )?;
     let expected = 1.0 / (light.range.max - light.range.min);
-    assert_that!("light range inv", light.range_inv == expected, offset + 216)?;
-    assert_that!("light parent count", light.parent_count == 1, offset + 220)?;
+    assert_that!("light range inv", approx light.range_inv == expected, offset + 216)?;
+    assert_that!("light parent count", light.parent_count > 0, offset + 220)?;
     assert_that!("light parent ptr", light.parent_ptr != 0, offset + 224)?;
     Ok(())
 }
@@ -82,8 +82,10 @@ pub(crate) fn read(read: &mut CountingReader<impl Read>, data_ptr: u32) -> Resul
     assert_light(&light, read.prev)?;
 
     // read as a result of parent_count, but is always 0 (= world node index)
-    let light_parent = read.read_u32()?;
-    assert_that!("light parent", light_parent == 0, read.prev)?;
+    for _ in 0..light.parent_count {
+        let light_parent = read.read_u32()?;
+        assert_that!("light parent", light_parent == 0, read.prev)?;
+    }
 
     Ok(Light {
         name: LIGHT_NAME.to_owned(),
@@ -94,6 +96,7 @@ pub(crate) fn read(read: &mut CountingReader<impl Read>, data_ptr: u32) -> Resul
         // ambient: light.ambient,
         color: light.color,
         range: light.range,
+        parent_count: light.parent_count,
         parent_ptr: light.parent_ptr,
         data_ptr,
     })
@@ -122,15 +125,17 @@ pub(crate) fn write(write: &mut CountingWriter<impl Write>, light: &Light) -> Re
         range: light.range,
         range_far_sq: light.range.max * light.range.max,
         range_inv: 1.0 / (light.range.max - light.range.min),
-        parent_count: 1,
+        parent_count: light.parent_count,
         parent_ptr: light.parent_ptr,
     };
     write.write_struct(&light)?;
     // written as a result of parent_count, but is always 0 (= world node index)
-    write.write_u32(0)?;
+    for _ in 0..light.parent_count {
+        write.write_u32(0)?;
+    }
     Ok(())
 }
 
-pub(crate) fn size() -> u32 {
-    LightRcC::SIZE + 4
+pub(crate) fn size(light: &Light) -> u32 {
+    LightRcC::SIZE + 4 * light.parent_count
 }
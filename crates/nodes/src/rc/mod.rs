@@ -8,7 +8,18 @@ mod object3d;
 mod window;
 mod world;
 
+use crate::transform::{node_transforms, transformation_matrix_rc};
+use mech3ax_api_types::nodes::rc::NodeRc;
+
 pub use node::{
     assert_node_info_zero, read_node_data, read_node_info, size_node, write_node_data,
     write_node_info, NodeRcC, NodeVariantRc,
 };
+
+// RC object 3D transformations are always present, but have different kinds,
+// and the parent of a LOD node is optional.
+node_transforms!(
+    NodeRc,
+    |object3d| transformation_matrix_rc(&object3d.transformation),
+    |lod| lod.parent
+);
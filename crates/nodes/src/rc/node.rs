@@ -97,7 +97,7 @@ pub struct NodeRcC {
     unk164: BoundingBox,           // 164
     zero188: u32,                  // 188
 }
-impl_as_bytes!(NodeRcC, 192);
+impl_as_bytes!(NodeRcC, 192, LeStruct);
 
 impl NodeRcC {
     #[inline]
@@ -351,7 +351,7 @@ pub fn size_node(node: &NodeRc) -> u32 {
         NodeRc::Camera(_) => camera::size(),
         NodeRc::Empty(_) => empty::size(),
         NodeRc::Display(_) => display::size(),
-        NodeRc::Light(_) => light::size(),
+        NodeRc::Light(light) => light::size(light),
         NodeRc::Lod(lod) => lod::size(lod),
         NodeRc::Object3d(object3d) => object3d::size(object3d),
         NodeRc::Window(_) => window::size(),
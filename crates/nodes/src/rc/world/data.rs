@@ -55,7 +55,7 @@ struct WorldRcC {
     zero164: u32,                         // 164
     zero168: u32,                         // 168
 }
-impl_as_bytes!(WorldRcC, 172);
+impl_as_bytes!(WorldRcC, 172, LeStruct);
 
 #[derive(Debug, Clone, Copy, NoUninit, AnyBitPattern)]
 #[repr(C)]
@@ -78,7 +78,7 @@ struct PartitionRcC {
     count: u16,    // 58
     ptr: u32,      // 60
 }
-impl_as_bytes!(PartitionRcC, 64);
+impl_as_bytes!(PartitionRcC, 64, LeStruct);
 
 const FOG_STATE_LINEAR: u32 = 1;
 
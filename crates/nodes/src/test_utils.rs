@@ -0,0 +1,40 @@
+//! Fixtures shared by the tests of this crate and of other crates.
+use mech3ax_api_types::nodes::mw::Object3d;
+use mech3ax_api_types::nodes::{BoundingBox, NodeFlags};
+
+/// An object without a transformation, mesh, parent, or children.
+pub fn object3d() -> Object3d {
+    Object3d {
+        name: "object".to_string(),
+        transformation: None,
+        matrix_signs: 0,
+        flags: NodeFlags {
+            active: true,
+            altitude_surface: false,
+            intersect_surface: false,
+            intersect_bbox: false,
+            landmark: false,
+            unk08: false,
+            has_mesh: false,
+            unk10: false,
+            terrain: false,
+            can_modify: false,
+            clip_to: false,
+            tree_valid: true,
+            id_zone_check: true,
+            unk25: false,
+            unk28: false,
+        },
+        zone_id: 0,
+        area_partition: None,
+        mesh_index: -1,
+        parent: None,
+        children: Vec::new(),
+        data_ptr: 0,
+        parent_array_ptr: 0,
+        children_array_ptr: 0,
+        unk116: BoundingBox::EMPTY,
+        unk140: BoundingBox::EMPTY,
+        unk164: BoundingBox::EMPTY,
+    }
+}
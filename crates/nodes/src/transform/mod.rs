@@ -0,0 +1,152 @@
+//! Local and world transforms of nodes, as 4x4 matrices.
+//!
+//! The matrices are indexed `m[row][column]`, and transform column vectors,
+//! i.e. `p' = M * p`, with the translation in the last column. A world
+//! matrix is composed as `parent_world * local`, so the local transform is
+//! applied first. The game's coordinate system is left-handed and Z-up,
+//! which is kept as is.
+//!
+//! The game stores rotation matrices for row vectors (`p' = p * M`), so they
+//! are transposed here. This is also why the game negates the Euler angles
+//! when calculating the matrix.
+use crate::math::{euler_to_matrix, scale_to_matrix};
+use mech3ax_api_types::nodes::rc::Transformation as TransformationRc;
+use mech3ax_api_types::nodes::Transformation;
+use mech3ax_api_types::{Matrix, Vec3};
+use mech3ax_common::{assert_with_msg, Result};
+
+pub type Matrix4 = [[f32; 4]; 4];
+
+pub const IDENTITY: Matrix4 = [
+    [1.0, 0.0, 0.0, 0.0],
+    [0.0, 1.0, 0.0, 0.0],
+    [0.0, 0.0, 1.0, 0.0],
+    [0.0, 0.0, 0.0, 1.0],
+];
+
+/// The local matrix of an object 3D transformation. If the transformation
+/// has no matrix, it is calculated from the rotation.
+pub fn transformation_matrix(transformation: Option<&Transformation>) -> Matrix4 {
+    let Some(transformation) = transformation else {
+        return IDENTITY;
+    };
+    let m = transformation
+        .matrix
+        .unwrap_or_else(|| euler_to_matrix(&transformation.rotation));
+    local_matrix(&m, &transformation.translation)
+}
+
+/// The local matrix of a RC object 3D transformation. RC transformations
+/// only store the parts that are used, so the rest is the identity.
+pub fn transformation_matrix_rc(transformation: &TransformationRc) -> Matrix4 {
+    match transformation {
+        TransformationRc::None => IDENTITY,
+        TransformationRc::ScaleOnly(scale) => local_matrix(&scale_to_matrix(scale), &Vec3::DEFAULT),
+        TransformationRc::RotationTranslation(tr) => {
+            local_matrix(&euler_to_matrix(&tr.rotation), &tr.translation)
+        }
+        TransformationRc::TranslationOnly(tr) => {
+            local_matrix(&tr.matrix.unwrap_or(Matrix::IDENTITY), &tr.translation)
+        }
+    }
+}
+
+fn local_matrix(m: &Matrix, t: &Vec3) -> Matrix4 {
+    [
+        [m.a, m.d, m.g, t.x],
+        [m.b, m.e, m.h, t.y],
+        [m.c, m.f, m.i, t.z],
+        [0.0, 0.0, 0.0, 1.0],
+    ]
+}
+
+pub fn multiply(lhs: &Matrix4, rhs: &Matrix4) -> Matrix4 {
+    let mut result = [[0.0; 4]; 4];
+    for (row, result_row) in result.iter_mut().enumerate() {
+        for (column, value) in result_row.iter_mut().enumerate() {
+            *value = (0..4).map(|k| lhs[row][k] * rhs[k][column]).sum();
+        }
+    }
+    result
+}
+
+/// Compose the local matrices up the parent chain of a node.
+pub(crate) fn world_matrix<N>(
+    nodes: &[N],
+    index: usize,
+    local: fn(&N) -> Matrix4,
+    parent: fn(&N) -> Option<u32>,
+) -> Result<Matrix4> {
+    let count = nodes.len();
+    let node = nodes
+        .get(index)
+        .ok_or_else(|| assert_with_msg!("Expected node index < {}, but was {}", count, index))?;
+    let mut world = local(node);
+    let mut current = parent(node);
+    // a chain longer than the node count must have a cycle
+    let mut depth = 0;
+    while let Some(parent_index) = current {
+        depth += 1;
+        if depth > count {
+            return Err(assert_with_msg!(
+                "Expected node {} parent chain to end, but it has a cycle",
+                index
+            ));
+        }
+        let parent_node = usize::try_from(parent_index)
+            .ok()
+            .and_then(|parent_index| nodes.get(parent_index))
+            .ok_or_else(|| {
+                assert_with_msg!("Expected node parent < {}, but was {}", count, parent_index)
+            })?;
+        world = multiply(&local(parent_node), &world);
+        current = parent(parent_node);
+    }
+    Ok(world)
+}
+
+/// Local and world matrices over a list of nodes. Only object 3D nodes are
+/// transformed, and only object 3D and LOD nodes have parents.
+///
+/// By default, the object 3D transformation is optional and the LOD parent
+/// is required. Otherwise, the local matrix of an object 3D node and the
+/// parent of a LOD node are given as closure-like expressions.
+macro_rules! node_transforms {
+    ($node:ident) => {
+        $crate::transform::node_transforms!(
+            $node,
+            |object3d| $crate::transform::transformation_matrix(object3d.transformation.as_ref()),
+            |lod| Some(lod.parent)
+        );
+    };
+    ($node:ident, |$object3d:ident| $local:expr, |$lod:ident| $lod_parent:expr) => {
+        /// The local matrix of a node, relative to its parent.
+        pub fn node_local_matrix(node: &$node) -> $crate::transform::Matrix4 {
+            match node {
+                $node::Object3d($object3d) => $local,
+                _ => $crate::transform::IDENTITY,
+            }
+        }
+
+        fn node_parent(node: &$node) -> Option<u32> {
+            match node {
+                $node::Object3d(object3d) => object3d.parent,
+                $node::Lod($lod) => $lod_parent,
+                _ => None,
+            }
+        }
+
+        /// The world matrix of the node at the index, composed as
+        /// `parent_world * local` up the parent chain.
+        pub fn node_world_matrix(
+            nodes: &[$node],
+            index: usize,
+        ) -> ::mech3ax_common::Result<$crate::transform::Matrix4> {
+            $crate::transform::world_matrix(nodes, index, node_local_matrix, node_parent)
+        }
+    };
+}
+pub(crate) use node_transforms;
+
+#[cfg(test)]
+mod tests;
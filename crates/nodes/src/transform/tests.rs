@@ -0,0 +1,152 @@
+use super::{multiply, transformation_matrix, transformation_matrix_rc, Matrix4, IDENTITY};
+use crate::mw::{node_local_matrix, node_world_matrix};
+use crate::test_utils;
+use mech3ax_api_types::nodes::mw::{NodeMw, Object3d};
+use mech3ax_api_types::nodes::rc::{
+    RotationTranslation, Transformation as TransformationRc, TranslationOnly,
+};
+use mech3ax_api_types::nodes::Transformation;
+use mech3ax_api_types::Vec3;
+use mech3ax_common::Error;
+use std::f32::consts::FRAC_PI_2;
+
+fn object3d(rotation_z: f32, translation: Vec3, parent: Option<u32>) -> NodeMw {
+    NodeMw::Object3d(Object3d {
+        transformation: Some(Transformation {
+            rotation: Vec3 {
+                x: 0.0,
+                y: 0.0,
+                z: rotation_z,
+            },
+            translation,
+            matrix: None,
+        }),
+        parent,
+        ..test_utils::object3d()
+    })
+}
+
+fn vec3(x: f32, y: f32, z: f32) -> Vec3 {
+    Vec3 { x, y, z }
+}
+
+fn transform_point(m: &Matrix4, p: Vec3) -> Vec3 {
+    let apply = |row: &[f32; 4]| row[0] * p.x + row[1] * p.y + row[2] * p.z + row[3];
+    vec3(apply(&m[0]), apply(&m[1]), apply(&m[2]))
+}
+
+fn assert_close(actual: Vec3, expected: Vec3) {
+    let close = (actual.x - expected.x).abs() < 1e-5
+        && (actual.y - expected.y).abs() < 1e-5
+        && (actual.z - expected.z).abs() < 1e-5;
+    assert!(close, "expected {:?}, but was {:?}", expected, actual);
+}
+
+fn assert_message<T: std::fmt::Debug>(result: mech3ax_common::Result<T>, expected: &str) {
+    match result {
+        Err(Error::Assert(e)) => assert_eq!(e.to_string(), expected),
+        other => panic!("expected assertion error, got {:?}", other),
+    }
+}
+
+#[test]
+fn no_transformation_is_identity() {
+    assert_eq!(transformation_matrix(None), IDENTITY);
+}
+
+#[test]
+fn local_matrix_rotates_then_translates() {
+    let node = object3d(FRAC_PI_2, vec3(10.0, 0.0, 0.0), None);
+    let local = node_local_matrix(&node);
+    assert_eq!(local[3], [0.0, 0.0, 0.0, 1.0]);
+    // a positive rotation around Z rotates X towards Y
+    assert_close(
+        transform_point(&local, vec3(1.0, 0.0, 0.0)),
+        vec3(10.0, 1.0, 0.0),
+    );
+    assert_close(
+        transform_point(&local, vec3(0.0, 0.0, 1.0)),
+        vec3(10.0, 0.0, 1.0),
+    );
+}
+
+#[test]
+fn local_matrix_rc_by_transformation_kind() {
+    assert_eq!(transformation_matrix_rc(&TransformationRc::None), IDENTITY);
+
+    let scale = transformation_matrix_rc(&TransformationRc::ScaleOnly(vec3(2.0, 3.0, 4.0)));
+    assert_close(
+        transform_point(&scale, vec3(1.0, 1.0, 1.0)),
+        vec3(2.0, 3.0, 4.0),
+    );
+
+    let translation =
+        transformation_matrix_rc(&TransformationRc::TranslationOnly(TranslationOnly {
+            translation: vec3(1.0, 2.0, 3.0),
+            matrix: None,
+        }));
+    assert_close(
+        transform_point(&translation, vec3(1.0, 0.0, 0.0)),
+        vec3(2.0, 2.0, 3.0),
+    );
+
+    // the same as the other games
+    let rotation = vec3(0.0, 0.0, FRAC_PI_2);
+    let translation = vec3(10.0, 0.0, 0.0);
+    let rc = transformation_matrix_rc(&TransformationRc::RotationTranslation(
+        RotationTranslation {
+            rotation,
+            translation,
+        },
+    ));
+    let mw = transformation_matrix(Some(&Transformation {
+        rotation,
+        translation,
+        matrix: None,
+    }));
+    assert_eq!(rc, mw);
+}
+
+#[test]
+fn world_matrix_is_parent_times_local() {
+    let nodes = vec![
+        object3d(FRAC_PI_2, vec3(10.0, 0.0, 0.0), None),
+        object3d(0.0, vec3(1.0, 0.0, 0.0), Some(0)),
+    ];
+    let parent = node_local_matrix(&nodes[0]);
+    let local = node_local_matrix(&nodes[1]);
+
+    assert_eq!(node_world_matrix(&nodes, 0).unwrap(), parent);
+    let world = node_world_matrix(&nodes, 1).unwrap();
+    assert_eq!(world, multiply(&parent, &local));
+    // the child's origin is offset along the parent's rotated X axis
+    assert_close(
+        transform_point(&world, vec3(0.0, 0.0, 0.0)),
+        vec3(10.0, 1.0, 0.0),
+    );
+}
+
+#[test]
+fn world_matrix_parent_out_of_range() {
+    let nodes = vec![object3d(0.0, vec3(0.0, 0.0, 0.0), Some(5))];
+    assert_message(
+        node_world_matrix(&nodes, 0),
+        "Expected node parent < 1, but was 5",
+    );
+    assert_message(
+        node_world_matrix(&nodes, 1),
+        "Expected node index < 1, but was 1",
+    );
+}
+
+#[test]
+fn world_matrix_parent_cycle() {
+    let nodes = vec![
+        object3d(0.0, vec3(0.0, 0.0, 0.0), Some(1)),
+        object3d(0.0, vec3(0.0, 0.0, 0.0), Some(0)),
+    ];
+    assert_message(
+        node_world_matrix(&nodes, 0),
+        "Expected node 0 parent chain to end, but it has a cycle",
+    );
+}
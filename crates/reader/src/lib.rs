@@ -1,11 +1,16 @@
 #![warn(clippy::all, clippy::cargo)]
+mod query;
 mod read;
 mod write;
 
+pub use query::reader_get;
 pub use read::read_reader;
-pub use write::write_reader;
+pub use write::{write_reader, write_reader_as};
 
 const INT: u32 = 1;
 const FLOAT: u32 = 2;
 const STRING: u32 = 3;
 const LIST: u32 = 4;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,27 @@
+use serde_json::Value;
+
+/// Look up a value in a reader tree by a dotted path, e.g. `a.b.3.c`.
+///
+/// Reader files have no maps, only lists. Keys and values are stored in the
+/// same list, with each key string followed by its value. So a key segment
+/// finds the first matching key at an even index of a list, and returns the
+/// value after it. An index segment returns the list item at that index. An
+/// empty path returns the root.
+pub fn reader_get<'a>(root: &'a Value, path: &str) -> Option<&'a Value> {
+    if path.is_empty() {
+        return Some(root);
+    }
+    path.split('.')
+        .try_fold(root, |value, segment| get_segment(value, segment))
+}
+
+fn get_segment<'a>(value: &'a Value, segment: &str) -> Option<&'a Value> {
+    let items = value.as_array()?;
+    if let Ok(index) = segment.parse::<usize>() {
+        return items.get(index);
+    }
+    items
+        .chunks(2)
+        .find(|pair| pair[0].as_str() == Some(segment))
+        .and_then(|pair| pair.get(1))
+}
@@ -0,0 +1,230 @@
+use crate::{read_reader, reader_get, write_reader, write_reader_as, FLOAT, INT, LIST, STRING};
+use mech3ax_common::io_ext::{CountingReader, CountingWriter};
+use mech3ax_common::test_utils::assert_truncated_fails;
+use serde_json::{json, Value};
+
+fn float_list(values: &[f32]) -> Vec<u8> {
+    let mut buf = Vec::new();
+    buf.extend_from_slice(&LIST.to_le_bytes());
+    buf.extend_from_slice(&(values.len() as u32 + 1).to_le_bytes());
+    for value in values {
+        buf.extend_from_slice(&FLOAT.to_le_bytes());
+        buf.extend_from_slice(&value.to_le_bytes());
+    }
+    buf
+}
+
+fn round_trip(original: &[u8]) -> Vec<u8> {
+    let value = read_reader(&mut CountingReader::new(original)).unwrap();
+    // round-trip through JSON text, like `unzbd` and `rezbd`
+    let json = serde_json::to_string(&value).unwrap();
+    let value = serde_json::from_str(&json).unwrap();
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    write_reader(&mut write, &value).unwrap();
+    write.into_inner()
+}
+
+#[test]
+fn float_round_trip() {
+    let original = float_list(&[
+        0.1,
+        1.0,
+        -0.0,
+        1.0 / 3.0,
+        f32::MIN_POSITIVE,
+        f32::from_bits(1),
+        f32::MAX,
+        f32::MIN,
+        16_777_217.0,
+    ]);
+    assert_eq!(round_trip(&original), original);
+}
+
+#[test]
+fn float_round_trip_all_exponents() {
+    // one value per exponent, with a mantissa that isn't a short decimal
+    let values = (0..255u32)
+        .map(|exponent| f32::from_bits((exponent << 23) | 0x2A_AAAB))
+        .collect::<Vec<_>>();
+    let original = float_list(&values);
+    assert_eq!(round_trip(&original), original);
+}
+
+fn int(value: i32) -> Vec<u8> {
+    let mut buf = INT.to_le_bytes().to_vec();
+    buf.extend_from_slice(&value.to_le_bytes());
+    buf
+}
+
+fn string(value: &str) -> Vec<u8> {
+    let mut buf = STRING.to_le_bytes().to_vec();
+    buf.extend_from_slice(&(value.len() as u32).to_le_bytes());
+    buf.extend_from_slice(value.as_bytes());
+    buf
+}
+
+fn list(items: &[Vec<u8>]) -> Vec<u8> {
+    let mut buf = LIST.to_le_bytes().to_vec();
+    buf.extend_from_slice(&(items.len() as u32 + 1).to_le_bytes());
+    for item in items {
+        buf.extend_from_slice(item);
+    }
+    buf
+}
+
+#[test]
+fn int_round_trip() {
+    let original = list(&[int(0), int(1), int(-1), int(i32::MAX), int(i32::MIN)]);
+    assert_eq!(round_trip(&original), original);
+}
+
+#[test]
+fn string_round_trip() {
+    let original = list(&[string(""), string("madcat"), string("with spaces.txt")]);
+    assert_eq!(round_trip(&original), original);
+}
+
+#[test]
+fn empty_list_round_trip() {
+    // an empty list is read as null, and null is written as an empty list
+    let original = list(&[]);
+    let value = read_reader(&mut CountingReader::new(&original[..])).unwrap();
+    assert_eq!(value, Value::Null);
+    assert_eq!(round_trip(&original), original);
+
+    let original = list(&[string("empty"), list(&[])]);
+    assert_eq!(round_trip(&original), original);
+}
+
+#[test]
+fn mixed_nested_list_round_trip() {
+    let original = list(&[
+        string("mixed"),
+        list(&[int(-7), float_list(&[0.5]), string("x"), list(&[])]),
+        list(&[list(&[list(&[int(1)])])]),
+    ]);
+    assert_eq!(round_trip(&original), original);
+}
+
+#[test]
+fn read_unknown_value_type_fails() {
+    for value_type in [0, 5, u32::MAX] {
+        let data = list(&[int(1), value_type.to_le_bytes().to_vec()]);
+        let err = read_reader(&mut CountingReader::new(&data[..])).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "Expected value type to be 1, 2, 3 or 4, but was {} (at 16)",
+                value_type
+            )
+        );
+    }
+}
+
+fn tree() -> Value {
+    json!([
+        "name",
+        "madcat",
+        "weapons",
+        ["left", ["ammo", 20, "range", 400.0], "right", ["ammo", 10],],
+        "lights",
+        [1, 2, 3, ["color", "red"]],
+    ])
+}
+
+#[test]
+fn reader_get_hits() {
+    let root = tree();
+    assert_eq!(reader_get(&root, ""), Some(&root));
+    assert_eq!(reader_get(&root, "name"), Some(&json!("madcat")));
+    assert_eq!(reader_get(&root, "weapons.left.ammo"), Some(&json!(20)));
+    assert_eq!(reader_get(&root, "weapons.right.ammo"), Some(&json!(10)));
+    assert_eq!(reader_get(&root, "lights.1"), Some(&json!(2)));
+    assert_eq!(reader_get(&root, "lights.3.color"), Some(&json!("red")));
+    // an index also works on lists of keys and values
+    assert_eq!(reader_get(&root, "weapons.2"), Some(&json!("right")));
+}
+
+#[test]
+fn reader_get_misses() {
+    let root = tree();
+    assert_eq!(reader_get(&root, "missing"), None);
+    assert_eq!(reader_get(&root, "weapons.center.ammo"), None);
+    // values are not keys
+    assert_eq!(reader_get(&root, "madcat"), None);
+    // can't descend into a scalar
+    assert_eq!(reader_get(&root, "name.first"), None);
+    assert_eq!(reader_get(&root, "weapons..left"), None);
+}
+
+#[test]
+fn reader_get_index_out_of_range() {
+    let root = tree();
+    assert_eq!(reader_get(&root, "lights.4"), None);
+    assert_eq!(reader_get(&root, "lights.3.color.0"), None);
+    assert_eq!(reader_get(&root, "weapons.right.5"), None);
+}
+
+#[test]
+fn read_truncated_fails() {
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    write_reader(&mut write, &tree()).unwrap();
+    let data = write.into_inner();
+    assert_truncated_fails(&data, |data| read_reader(&mut CountingReader::new(data)));
+}
+
+fn write_as(value: &Value, expected: &Value) -> mech3ax_common::Result<Vec<u8>> {
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    write_reader_as(&mut write, value, expected)?;
+    Ok(write.into_inner())
+}
+
+#[test]
+fn write_as_coerces_numbers() {
+    let original = float_list(&[1.0, 2.5, -16_777_216.0]);
+    let expected = read_reader(&mut CountingReader::new(&original[..])).unwrap();
+    // hand-edited, the floats without a fraction became integers
+    let edited = json!([1, 2.5, -16_777_216]);
+    // without the expected types, they would be written as integers
+    assert_ne!(write_as(&edited, &Value::Null).unwrap(), original);
+    assert_eq!(write_as(&edited, &expected).unwrap(), original);
+
+    // and the other way around
+    let mut original = Vec::new();
+    original.extend_from_slice(&LIST.to_le_bytes());
+    original.extend_from_slice(&2u32.to_le_bytes());
+    original.extend_from_slice(&INT.to_le_bytes());
+    original.extend_from_slice(&20i32.to_le_bytes());
+    let expected = read_reader(&mut CountingReader::new(&original[..])).unwrap();
+    assert_eq!(write_as(&json!([20.0]), &expected).unwrap(), original);
+}
+
+#[test]
+fn write_as_string_for_number_fails() {
+    let mut edited = tree();
+    edited[3][1][1] = json!("20");
+    let err = write_as(&edited, &tree()).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Expected a number at `weapons.left.ammo`, but was the string \"20\""
+    );
+}
+
+#[test]
+fn write_as_inexact_number_fails() {
+    let mut edited = tree();
+    edited[3][1][1] = json!(20.5);
+    let err = write_as(&edited, &tree()).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Expected an integer at `weapons.left.ammo`, but was 20.5"
+    );
+
+    let mut edited = tree();
+    edited[3][1][3] = json!(16_777_217);
+    let err = write_as(&edited, &tree()).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Expected a float at `weapons.left.range`, but the integer 16777217 isn't exact as a float"
+    );
+}
@@ -9,6 +9,136 @@ pub fn write_reader(write: &mut CountingWriter<impl Write>, value: &Value) -> Re
     write_value(write, value)
 }
 
+/// Write the value, with the number types of the expected value.
+///
+/// JSON doesn't distinguish integers and floats, so a hand-edited value can
+/// easily change the type that is written. The expected value is usually the
+/// reader read from the original file. Where both have a number at the same
+/// position, an integer is written as a float if the expected value is a
+/// float and the integer is exact as one, and a float with no fraction is
+/// written as an integer if the expected value is an integer. Other numbers
+/// that don't fit fail, as does a string where a number is expected. The
+/// error includes the path of the value, as taken by [`reader_get`].
+///
+/// Any values not in the expected value (e.g. new list items) are written
+/// like [`write_reader`] does.
+///
+/// [`reader_get`]: crate::reader_get
+pub fn write_reader_as(
+    write: &mut CountingWriter<impl Write>,
+    value: &Value,
+    expected: &Value,
+) -> Result<()> {
+    let mut path = Vec::new();
+    write_value_as(write, value, Some(expected), &mut path)
+}
+
+/// The lists and item indices to a value.
+type Path<'a> = Vec<(&'a [Value], usize)>;
+
+fn write_value_as<'a>(
+    write: &mut CountingWriter<impl Write>,
+    value: &'a Value,
+    expected: Option<&Value>,
+    path: &mut Path<'a>,
+) -> Result<()> {
+    match (value, expected) {
+        (Value::Array(items), Some(Value::Array(expected))) => {
+            let count = assert_len!(u32, items.len() + 1, "reader list")?;
+            write.write_u32(LIST)?;
+            write.write_u32(count)?;
+            for (index, item) in items.iter().enumerate() {
+                path.push((items, index));
+                write_value_as(write, item, expected.get(index), path)?;
+                path.pop();
+            }
+            Ok(())
+        }
+        (Value::Number(number), Some(Value::Number(expected))) => {
+            write_number_as(write, number, expected, path)
+        }
+        (Value::String(value), Some(Value::Number(_))) => Err(assert_with_msg!(
+            "Expected a number at `{}`, but was the string {:?}",
+            format_path(path),
+            value
+        )),
+        _ => write_value(write, value),
+    }
+}
+
+fn write_number_as(
+    write: &mut CountingWriter<impl Write>,
+    value: &Number,
+    expected: &Number,
+    path: &Path<'_>,
+) -> Result<()> {
+    // the largest integer that all smaller integers are exact floats for
+    const EXACT: i64 = 1 << f32::MANTISSA_DIGITS;
+
+    match (value.is_f64(), expected.is_f64()) {
+        (false, true) => {
+            let int = value
+                .as_i64()
+                .filter(|int| (-EXACT..=EXACT).contains(int))
+                .ok_or_else(|| {
+                    assert_with_msg!(
+                        "Expected a float at `{}`, but the integer {} isn't exact as a float",
+                        format_path(path),
+                        value
+                    )
+                })?;
+            write.write_u32(FLOAT)?;
+            // Cast safety: the range was checked above
+            write.write_f32(int as f32)?;
+            Ok(())
+        }
+        (true, false) => {
+            let int = value
+                .as_f64()
+                .filter(|double| double.fract() == 0.0)
+                .and_then(|double| {
+                    // Cast safety: saturates, and is checked by the conversion
+                    i32::try_from(double as i64).ok()
+                })
+                .ok_or_else(|| {
+                    assert_with_msg!(
+                        "Expected an integer at `{}`, but was {}",
+                        format_path(path),
+                        value
+                    )
+                })?;
+            write.write_u32(INT)?;
+            write.write_i32(int)?;
+            Ok(())
+        }
+        _ => write_number(write, value),
+    }
+}
+
+/// Format the path as taken by `reader_get`. An item after a key uses the key
+/// name, if it is the first key with that name. Otherwise, the index is used.
+fn format_path(path: &Path<'_>) -> String {
+    path.iter()
+        .map(|&(items, index)| key_segment(items, index).unwrap_or_else(|| index.to_string()))
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+fn key_segment(items: &[Value], index: usize) -> Option<String> {
+    if index % 2 == 0 {
+        return None;
+    }
+    let key = items[index - 1].as_str()?;
+    // these would be parsed as an index, or split
+    if key.is_empty() || key.contains('.') || key.parse::<usize>().is_ok() {
+        return None;
+    }
+    let first = items
+        .chunks(2)
+        .position(|pair| pair[0].as_str() == Some(key))?;
+    (first == index / 2).then(|| key.to_string())
+}
+
 fn write_value(write: &mut CountingWriter<impl Write>, value: &Value) -> Result<()> {
     match value {
         Value::String(value) => write_string(write, value),
@@ -85,7 +215,9 @@ fn write_number(write: &mut CountingWriter<impl Write>, value: &Number) -> Resul
         write.write_i32(int)?;
         Ok(())
     } else if let Some(double) = value.as_f64() {
-        // this causes precision-loss, but might be necessary?
+        // floats are widened to a double when read, which is exact, and
+        // JSON numbers round-trip. so for read floats, this is also exact.
+        // for edited values, it rounds to the nearest float.
         let float = double as f32;
         write.write_u32(FLOAT)?;
         write.write_f32(float)?;
@@ -0,0 +1,108 @@
+//! Reconstruct a texture archive with one and with all CPUs decoding the
+//! images.
+//!
+//! By default, a synthetic texture set is used. To use a real one, set
+//! `REZBD_BENCH_TEXTURES` to a ZIP extracted by `unzbd textures`.
+use criterion::{criterion_group, criterion_main, Criterion};
+use image::{DynamicImage, ImageFormat, RgbImage};
+use mech3ax_api_types::image::{
+    TextureAlpha, TextureInfo, TextureManifest, TexturePalette, TextureStretch,
+};
+use std::io::{Cursor, Write as _};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+const TEXTURE_COUNT: u32 = 128;
+const SIZE: u32 = 256;
+
+/// Noisy images, so decoding them is work, roughly like a mech texture set.
+fn synthetic_textures(path: &Path) {
+    let file = std::fs::File::create(path).unwrap();
+    let mut zip = ZipWriter::new(file);
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Stored);
+
+    let mut texture_infos = Vec::new();
+    let mut seed = 0x1234_5678u32;
+    for index in 0..TEXTURE_COUNT {
+        let name = format!("texture{:03}", index);
+        let image = RgbImage::from_fn(SIZE, SIZE, |_x, _y| {
+            // xorshift
+            seed ^= seed << 13;
+            seed ^= seed >> 17;
+            seed ^= seed << 5;
+            let [r, g, b, _] = seed.to_le_bytes();
+            image::Rgb([r, g, b])
+        });
+        let mut buf = Cursor::new(Vec::new());
+        DynamicImage::ImageRgb8(image)
+            .write_to(&mut buf, ImageFormat::Png)
+            .unwrap();
+        zip.start_file(format!("{}.png", name), options).unwrap();
+        zip.write_all(buf.get_ref()).unwrap();
+
+        texture_infos.push(TextureInfo {
+            name,
+            index: None,
+            rename: None,
+            alpha: TextureAlpha::None,
+            width: SIZE as u16,
+            height: SIZE as u16,
+            stretch: TextureStretch::None,
+            image_loaded: false,
+            alpha_loaded: false,
+            palette_loaded: false,
+            palette: TexturePalette::None,
+        });
+    }
+    let manifest = TextureManifest {
+        texture_infos,
+        global_palettes: Vec::new(),
+        image_format: None,
+    };
+    zip.start_file("manifest.json", options).unwrap();
+    zip.write_all(&serde_json::to_vec(&manifest).unwrap())
+        .unwrap();
+    zip.finish().unwrap();
+}
+
+fn rezbd_textures(input: &Path, output: &Path, jobs: usize) {
+    let status = Command::new(env!("CARGO_BIN_EXE_rezbd"))
+        .arg("mw")
+        .arg("textures")
+        .arg(input)
+        .arg(output)
+        .arg("--jobs")
+        .arg(jobs.to_string())
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
+
+fn textures(c: &mut Criterion) {
+    let dir = std::env::temp_dir().join(format!("rezbd-bench-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let input = match std::env::var_os("REZBD_BENCH_TEXTURES") {
+        Some(path) => PathBuf::from(path),
+        None => {
+            let path = dir.join("textures.zip");
+            synthetic_textures(&path);
+            path
+        }
+    };
+    let output = dir.join("textures.zbd");
+
+    let mut group = c.benchmark_group("textures");
+    group.sample_size(10);
+    group.bench_function("serial", |b| b.iter(|| rezbd_textures(&input, &output, 1)));
+    group.bench_function("parallel", |b| {
+        b.iter(|| rezbd_textures(&input, &output, 0))
+    });
+    group.finish();
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+criterion_group!(benches, textures);
+criterion_main!(benches);
@@ -0,0 +1,179 @@
+use crate::{commands, AllOpts, GameZOpts, InterpOpts, MsgOpts, ReaderOpts, ZMapOpts, ZipOpts};
+use eyre::{bail, Context as _, Report, Result};
+use mech3ax_common::asset::{discover, Asset};
+use mech3ax_common::GameType;
+use std::path::{Path, PathBuf};
+
+fn repack(asset: Asset, game: GameType, input: String, dll: String, output: String) -> Result<()> {
+    match asset {
+        Asset::Sounds => commands::sounds(ZipOpts {
+            game,
+            input,
+            output,
+            verify_source: None,
+        }),
+        Asset::Interp => commands::interp(InterpOpts { input, output }),
+        Asset::Reader => commands::reader(ReaderOpts {
+            zip: ZipOpts {
+                game,
+                input,
+                output,
+                verify_source: None,
+            },
+            original: None,
+        }),
+        Asset::Messages => commands::messages(MsgOpts {
+            game,
+            input,
+            dll,
+            output,
+        }),
+        Asset::Textures => commands::textures(input, output, 0, None),
+        Asset::Motion => commands::motion(ZipOpts {
+            game,
+            input,
+            output,
+            verify_source: None,
+        }),
+        Asset::Mechlib => commands::mechlib(ZipOpts {
+            game,
+            input,
+            output,
+            verify_source: None,
+        }),
+        Asset::Gamez => commands::gamez(GameZOpts {
+            game,
+            input,
+            output,
+            fit_metadata: false,
+            verify_source: None,
+        }),
+        Asset::Anim => commands::anim(ZipOpts {
+            game,
+            input,
+            output,
+            verify_source: None,
+        }),
+        Asset::Zmap => commands::zmap(ZMapOpts {
+            game,
+            input,
+            output,
+        }),
+    }
+}
+
+struct Summary {
+    written: Vec<PathBuf>,
+    skipped: Vec<(PathBuf, &'static str)>,
+    failures: Vec<(PathBuf, Report)>,
+}
+
+/// Repack every known file in the extracted directory, grouped by kind. A
+/// file that fails to repack is recorded, and doesn't stop the others.
+///
+/// Messages can only be patched into the original DLL, so they are skipped
+/// without the original game directory.
+fn repack_all(
+    game: GameType,
+    extracted_dir: &Path,
+    out_dir: &Path,
+    original_dir: Option<&Path>,
+) -> Result<Summary> {
+    let mut files = Vec::new();
+    discover(extracted_dir, &mut files)
+        .with_context(|| format!("Failed to read directory `{}`", extracted_dir.display()))?;
+
+    let mut assets = files
+        .into_iter()
+        .filter_map(|path| {
+            let asset = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| Asset::from_extracted_file(name, game))?;
+            Some((asset, path))
+        })
+        .collect::<Vec<_>>();
+    // stable, so files of the same kind stay sorted by path
+    assets.sort_by_key(|(asset, _path)| *asset);
+
+    let mut summary = Summary {
+        written: Vec::new(),
+        skipped: Vec::new(),
+        failures: Vec::new(),
+    };
+    for (asset, path) in assets {
+        let relative = path.strip_prefix(extracted_dir).unwrap_or(&path);
+        let output = out_dir
+            .join(relative)
+            .with_extension(asset.game_extension());
+
+        let dll = match (asset, original_dir) {
+            (Asset::Messages, Some(original_dir)) => {
+                original_dir.join(relative).with_extension("dll")
+            }
+            (Asset::Messages, None) => {
+                log::warn!("ALL: Skipping `{}` without original", path.display());
+                summary.skipped.push((path, "the original DLL is required"));
+                continue;
+            }
+            _ => PathBuf::new(),
+        };
+
+        log::info!("ALL: Repacking `{}` ({:?})", path.display(), asset);
+        let result = output
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .context("Failed to create output directory")
+            .and_then(|()| {
+                repack(
+                    asset,
+                    game,
+                    path.to_string_lossy().into_owned(),
+                    dll.to_string_lossy().into_owned(),
+                    output.to_string_lossy().into_owned(),
+                )
+            });
+        match result {
+            Ok(()) => summary.written.push(output),
+            Err(e) => {
+                log::error!("ALL: `{}` failed: {:#}", path.display(), e);
+                summary.failures.push((path, e));
+            }
+        }
+    }
+    Ok(summary)
+}
+
+/// Repack every known file in an extracted directory, and report the result.
+pub(crate) fn all(opts: AllOpts) -> Result<()> {
+    let extracted_dir = Path::new(&opts.input);
+    let out_dir = Path::new(&opts.output);
+    let original_dir = opts.original.as_deref().map(Path::new);
+    let summary = repack_all(opts.game, extracted_dir, out_dir, original_dir)?;
+
+    for output in &summary.written {
+        println!("OK `{}`", output.display());
+    }
+    for (path, reason) in &summary.skipped {
+        println!("SKIP `{}`: {}", path.display(), reason);
+    }
+    for (path, e) in &summary.failures {
+        println!("FAIL `{}`: {:#}", path.display(), e);
+    }
+    let failed = summary.failures.len();
+    if failed > 0 {
+        bail!(
+            "{} of {} files in `{}` failed to repack",
+            failed,
+            failed + summary.written.len(),
+            opts.input
+        );
+    }
+    if summary.written.is_empty() && summary.skipped.is_empty() {
+        bail!("No known files found in `{}`", opts.input);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,80 @@
+use super::*;
+use mech3ax_api_types::archive::{ArchiveEntry, ArchiveEntryInfo, ArchiveEntryInfoInvalid};
+use mech3ax_archive::{write_archive, Version};
+use mech3ax_common::io_ext::CountingWriter;
+use mech3ax_common::test_utils::TempDir;
+use std::io::{Cursor, Write as _};
+use zip::write::{SimpleFileOptions, ZipWriter};
+
+const NAMES: [&str; 2] = ["alpha.wav", "bravo.wav"];
+
+fn entries() -> Vec<ArchiveEntry> {
+    NAMES
+        .iter()
+        .map(|name| ArchiveEntry {
+            name: name.to_string(),
+            rename: None,
+            flags: 0,
+            info: ArchiveEntryInfo::Invalid(ArchiveEntryInfoInvalid {
+                comment: vec![0; 64],
+                filetime: 0,
+            }),
+        })
+        .collect()
+}
+
+/// The sounds archive, as it would be in the game directory.
+fn sounds_zbd() -> Vec<u8> {
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    write_archive::<_, _, Report>(
+        &mut write,
+        &entries(),
+        |name, _offset| Ok(name.as_bytes().to_vec()),
+        Version::One,
+    )
+    .unwrap();
+    write.into_inner()
+}
+
+/// The sounds archive, as it would be extracted by `unzbd`.
+fn sounds_zip() -> Vec<u8> {
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default();
+    zip.start_file("manifest.json", options).unwrap();
+    zip.write_all(&serde_json::to_vec_pretty(&entries()).unwrap())
+        .unwrap();
+    for name in NAMES {
+        zip.start_file(name, options).unwrap();
+        zip.write_all(name.as_bytes()).unwrap();
+    }
+    zip.finish().unwrap().into_inner()
+}
+
+#[test]
+fn repack_all_reproduces_archive_and_isolates_failures() {
+    let base = TempDir::new("rezbd-all");
+    let extracted_dir = base.path().join("extracted");
+    let out_dir = base.path().join("out");
+    std::fs::create_dir_all(extracted_dir.join("zbd").join("c1")).unwrap();
+    std::fs::write(extracted_dir.join("zbd").join("sounds.zip"), sounds_zip()).unwrap();
+    std::fs::write(
+        extracted_dir.join("zbd").join("c1").join("gamez.zip"),
+        b"bad",
+    )
+    .unwrap();
+    std::fs::write(extracted_dir.join("Mech3Msg.json"), b"{}").unwrap();
+    std::fs::write(extracted_dir.join("readme.txt"), b"ignored").unwrap();
+
+    let summary = repack_all(GameType::MW, &extracted_dir, &out_dir, None).unwrap();
+
+    let sounds = out_dir.join("zbd").join("sounds.zbd");
+    assert_eq!(summary.written, [sounds.clone()]);
+    assert_eq!(std::fs::read(&sounds).unwrap(), sounds_zbd());
+    assert_eq!(summary.skipped.len(), 1);
+    assert_eq!(summary.skipped[0].0, extracted_dir.join("Mech3Msg.json"));
+    assert_eq!(summary.failures.len(), 1);
+    assert_eq!(
+        summary.failures[0].0,
+        extracted_dir.join("zbd").join("c1").join("gamez.zip")
+    );
+}
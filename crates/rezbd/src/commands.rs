@@ -1,5 +1,8 @@
-use crate::{InterpOpts, ZMapOpts, ZipOpts};
-use eyre::{bail, Context as _, Result};
+use crate::images::{
+    apply_embedded_format, texture_extension, texture_formats, BatchDecoder, EncodedImage,
+};
+use crate::{GameZOpts, InterpOpts, MsgOpts, ReaderOpts, ZMapOpts, ZipOpts};
+use eyre::{bail, eyre, Context as _, Result};
 use mech3ax_api_types::anim::AnimMetadata;
 use mech3ax_api_types::archive::ArchiveEntry;
 use mech3ax_api_types::gamez::materials::Material;
@@ -7,10 +10,11 @@ use mech3ax_api_types::gamez::mechlib::{ModelMw, ModelPm};
 use mech3ax_api_types::gamez::mesh::{MeshMw, MeshNg, MeshRc};
 use mech3ax_api_types::gamez::{
     GameZDataCs, GameZDataMw, GameZDataPm, GameZDataRc, GameZMetadataCs, GameZMetadataMw,
-    GameZMetadataPm, TextureName,
+    GameZMetadataPm, TextureInfoRc, TextureName,
 };
 use mech3ax_api_types::image::TextureManifest;
 use mech3ax_api_types::interp::Script;
+use mech3ax_api_types::messages::Messages;
 use mech3ax_api_types::motion::Motion;
 use mech3ax_api_types::nodes::cs::NodeCs;
 use mech3ax_api_types::nodes::mw::NodeMw;
@@ -18,17 +22,20 @@ use mech3ax_api_types::nodes::pm::NodePm;
 use mech3ax_api_types::nodes::rc::NodeRc;
 use mech3ax_api_types::saves::AnimActivation;
 use mech3ax_api_types::zmap::Zmap;
-use mech3ax_archive::{write_archive, Mode, Version};
-use mech3ax_common::io_ext::CountingWriter;
+use mech3ax_archive::{read_archive, write_archive, Mode, Version};
+use mech3ax_common::checksum::sha256_file;
+use mech3ax_common::io_ext::{CountingReader, CountingWriter};
 use mech3ax_common::GameType;
 use mech3ax_gamez::gamez;
 use mech3ax_gamez::mechlib::{self, write_format, write_materials, write_version};
-use mech3ax_image::write_textures;
+use mech3ax_image::{sort_textures, write_textures};
 use mech3ax_interp::write_interp;
+use mech3ax_messages::write_messages;
 use mech3ax_motion::write_motion;
-use mech3ax_reader::write_reader;
+use mech3ax_reader::{read_reader, write_reader, write_reader_as};
 use mech3ax_saves::{write_activation, write_save_header};
 use serde_json::Value;
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::{BufReader, BufWriter, Cursor, Read, Seek};
 use std::path::Path;
@@ -66,6 +73,17 @@ where
     serde_json::from_slice(&buf).with_context(|| format!("Failed to parse `{}` from Zip", name))
 }
 
+fn zip_json_opt<R, T>(zip: &mut ZipArchive<R>, name: &str) -> Result<Option<T>>
+where
+    R: Read + Seek,
+    T: serde::de::DeserializeOwned,
+{
+    if zip.index_for_name(name).is_none() {
+        return Ok(None);
+    }
+    zip_json(zip, name).map(Some)
+}
+
 pub(crate) fn interp(opts: InterpOpts) -> Result<()> {
     log::info!("INTERP: Reading `{}`", opts.input);
     let buf = std::fs::read(opts.input).context("Failed to open input")?;
@@ -77,9 +95,70 @@ pub(crate) fn interp(opts: InterpOpts) -> Result<()> {
     Ok(())
 }
 
+pub(crate) fn messages(opts: MsgOpts) -> Result<()> {
+    log::info!("MESSAGES: Reading `{}`", opts.input);
+    let buf = std::fs::read(opts.input).context("Failed to open input")?;
+    let messages: Messages = serde_json::from_slice(&buf).context("Failed to parse input")?;
+
+    log::info!("MESSAGES: Patching `{}`", opts.dll);
+    let mut dll = buf_reader(opts.dll)?;
+    let mut contents = Vec::new();
+    write_messages(&mut contents, &mut dll, &messages, opts.game)
+        .context("Failed to write message data")?;
+    std::fs::write(&opts.output, contents).context("Failed to write output")?;
+    log::info!("MESSAGES: Wrote `{}`", opts.output);
+    Ok(())
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct SourceChecksum {
+    sha256: String,
+}
+
+/// The checksums `unzbd` records, only the source is needed.
+#[derive(Debug, serde::Deserialize)]
+struct Checksums {
+    source: SourceChecksum,
+}
+
+/// Check the source file matches the checksums recorded when it was
+/// extracted.
+pub(crate) fn check_source(checksums: &[u8], source: &str) -> Result<()> {
+    let checksums: Checksums =
+        serde_json::from_slice(checksums).context("Failed to parse `checksums.json`")?;
+    let expected = checksums.source.sha256;
+    let actual = sha256_file(source).with_context(|| format!("Failed to hash `{}`", source))?;
+    if actual != expected {
+        bail!(
+            "Expected `{}` to have SHA-256 {}, but was {}",
+            source,
+            expected,
+            actual
+        );
+    }
+    Ok(())
+}
+
+/// Check the Zip was extracted from the source file, according to the
+/// checksums recorded when it was extracted.
+fn verify_source(zip: &mut ZipArchive<impl Read + Seek>, source: &str) -> Result<()> {
+    let checksums = zip_read(zip, "checksums.json")?;
+    check_source(&checksums, source)
+}
+
+fn open_zip(input: &str, source: Option<&str>) -> Result<ZipArchive<BufReader<File>>> {
+    let input = buf_reader(input)?;
+    let mut zip = ZipArchive::new(input).context("Failed to open input")?;
+    if let Some(source) = source {
+        verify_source(&mut zip, source)?;
+    }
+    Ok(zip)
+}
+
 fn _zarchive<F>(
     input: &str,
     output: &str,
+    source: Option<&str>,
     version: Version,
     context: &'static str,
     mut load_file: F,
@@ -87,8 +166,7 @@ fn _zarchive<F>(
 where
     F: FnMut(&mut ZipArchive<BufReader<File>>, &str, usize) -> Result<Vec<u8>>,
 {
-    let input = buf_reader(input)?;
-    let mut zip = ZipArchive::new(input).context("Failed to open input")?;
+    let mut zip = open_zip(input, source)?;
     let entries: Vec<ArchiveEntry> = zip_json(&mut zip, "manifest.json")?;
 
     let mut write = buf_writer(output)?;
@@ -108,6 +186,7 @@ pub(crate) fn sounds(opts: ZipOpts) -> Result<()> {
     _zarchive(
         &opts.input,
         &opts.output,
+        opts.verify_source.as_deref(),
         version,
         "Failed to write sounds data",
         |zip, name, _offset| zip_read(zip, name),
@@ -116,13 +195,49 @@ pub(crate) fn sounds(opts: ZipOpts) -> Result<()> {
     Ok(())
 }
 
-pub(crate) fn reader(opts: ZipOpts) -> Result<()> {
+/// Read the readers of the original archive, by entry name.
+fn read_original_readers(path: &str, version: Version) -> Result<HashMap<String, Value>> {
+    let mut read = CountingReader::new(buf_reader(path)?);
+    let mut readers = HashMap::new();
+    read_archive::<_, _, eyre::Report>(
+        &mut read,
+        |name, data, offset| {
+            let mut read = CountingReader::new(Cursor::new(data));
+            // translate to absolute offset
+            read.offset = offset;
+            let value = read_reader(&mut read)
+                .with_context(|| format!("Failed to read original reader data for `{}`", name))?;
+            readers.insert(name.to_string(), value);
+            Ok(())
+        },
+        version,
+    )
+    .context("Failed to read original reader data")?;
+    Ok(readers)
+}
+
+pub(crate) fn reader(opts: ReaderOpts) -> Result<()> {
+    let ReaderOpts {
+        zip: opts,
+        original,
+    } = opts;
     let version = opts.version(Mode::Reader);
 
+    // JSON doesn't keep whether a number was an integer or a float, so the
+    // original readers are used to write edited numbers with the same type
+    let originals = match original {
+        Some(path) => {
+            log::info!("READER: Reading original `{}`", path);
+            read_original_readers(&path, version)?
+        }
+        None => HashMap::new(),
+    };
+
     log::info!("READER: Reading `{}` ({})", opts.input, opts.game);
     _zarchive(
         &opts.input,
         &opts.output,
+        opts.verify_source.as_deref(),
         version,
         "Failed to write reader data",
         |zip, original, offset| {
@@ -130,8 +245,11 @@ pub(crate) fn reader(opts: ZipOpts) -> Result<()> {
             let value: Value = zip_json(zip, &name)?;
 
             let mut buf = CountingWriter::new(Vec::new(), offset);
-            write_reader(&mut buf, &value)
-                .with_context(|| format!("Failed to write reader data for `{}`", original))?;
+            match originals.get(original) {
+                Some(expected) => write_reader_as(&mut buf, &value, expected),
+                None => write_reader(&mut buf, &value),
+            }
+            .with_context(|| format!("Failed to write reader data for `{}`", original))?;
             Ok(buf.into_inner())
         },
     )?;
@@ -146,6 +264,7 @@ pub(crate) fn motion(opts: ZipOpts) -> Result<()> {
     _zarchive(
         &opts.input,
         &opts.output,
+        opts.verify_source.as_deref(),
         version,
         "Failed to write motion data",
         |zip, original, offset| {
@@ -169,12 +288,13 @@ pub(crate) fn mechlib(opts: ZipOpts) -> Result<()> {
         GameType::RC => bail!("Recoil does not have mechlib"),
         GameType::CS => bail!("Crimson Skies does not have mechlib"),
     };
-    let version = opts.version(Mode::Sounds);
+    let version = opts.version(Mode::Mechlib);
 
     log::info!("MECHLIB: Reading `{}` ({})", opts.input, opts.game);
     _zarchive(
         &opts.input,
         &opts.output,
+        opts.verify_source.as_deref(),
         version,
         "Failed to write mechlib data",
         |zip, name, offset| {
@@ -221,30 +341,57 @@ pub(crate) fn mechlib(opts: ZipOpts) -> Result<()> {
     Ok(())
 }
 
-pub(crate) fn textures(input: String, output: String) -> Result<()> {
+pub(crate) fn textures(
+    input: String,
+    output: String,
+    jobs: usize,
+    verify_source: Option<&str>,
+) -> Result<()> {
     log::info!("TEXTURES: Reading `{}`", input);
-    let input = buf_reader(input)?;
-    let mut zip = ZipArchive::new(input).context("Failed to open input")?;
-    let manifest: TextureManifest = zip_json(&mut zip, "manifest.json")?;
-
+    let mut zip = open_zip(&input, verify_source)?;
+    let mut manifest: TextureManifest = zip_json(&mut zip, "manifest.json")?;
+    sort_textures(&mut manifest).context("Failed to sort textures")?;
+    let formats = texture_formats(manifest.image_format);
+
+    // the images are read up front, since the format embedded in them can
+    // change how the textures are written
+    let images = manifest
+        .texture_infos
+        .iter_mut()
+        .map(|info| {
+            let original = info.rename.as_deref().unwrap_or(&info.name).to_string();
+            let (name, format) = formats
+                .iter()
+                .map(|&format| {
+                    (
+                        format!("{}.{}", original, texture_extension(format)),
+                        format,
+                    )
+                })
+                .find(|(name, _format)| zip.by_name(name).is_ok())
+                .ok_or_else(|| eyre!("Failed to find image for `{}` in Zip", original))?;
+            let buf = zip_read(&mut zip, &name)?;
+            apply_embedded_format(info, &buf, format)
+                .with_context(|| format!("Failed to read format of `{}`", original))?;
+            Ok(EncodedImage {
+                name: original,
+                buf,
+                format,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    // decoding is slow, but the textures are written in the manifest order,
+    // which is the order of the images
+    let mut decoder = BatchDecoder::new(images, jobs)?;
     let mut write = buf_writer(&output)?;
-    write_textures::<_, eyre::Report>(&mut write, &manifest, |original| {
-        let name = format!("{}.png", original);
-        let buf = zip_read(&mut zip, &name)?;
-
-        let mut reader = image::ImageReader::new(Cursor::new(buf));
-        reader.set_format(image::ImageFormat::Png);
-        let image = reader
-            .decode()
-            .with_context(|| format!("Failed to load image data for `{}`", original))?;
-        Ok(image)
-    })
-    .context("Failed to write texture data")?;
+    write_textures::<_, eyre::Report>(&mut write, &manifest, |original| decoder.take(original))
+        .context("Failed to write texture data")?;
     log::info!("TEXTURES: Wrote `{}`", output);
     Ok(())
 }
 
-pub(crate) fn gamez(opts: ZipOpts) -> Result<()> {
+pub(crate) fn gamez(opts: GameZOpts) -> Result<()> {
     log::info!("GAMEZ: Reading `{}` ({})", opts.input, opts.game);
     match opts.game {
         GameType::RC => gamez_rc(&opts)?,
@@ -256,9 +403,8 @@ pub(crate) fn gamez(opts: ZipOpts) -> Result<()> {
     Ok(())
 }
 
-fn gamez_mw(opts: &ZipOpts) -> Result<()> {
-    let input = buf_reader(&opts.input)?;
-    let mut zip = ZipArchive::new(input).context("Failed to open input")?;
+fn gamez_mw(opts: &GameZOpts) -> Result<()> {
+    let mut zip = open_zip(&opts.input, opts.verify_source.as_deref())?;
 
     let metadata: GameZMetadataMw = zip_json(&mut zip, "metadata.json")?;
     let textures: Vec<String> = zip_json(&mut zip, "textures.json")?;
@@ -268,21 +414,23 @@ fn gamez_mw(opts: &ZipOpts) -> Result<()> {
 
     drop(zip);
 
-    let gamez = GameZDataMw {
+    let mut gamez = GameZDataMw {
         metadata,
         textures,
         materials,
         meshes,
         nodes,
     };
+    if opts.fit_metadata {
+        gamez::mw::fit_metadata(&mut gamez);
+    }
 
     let mut write = buf_writer(&opts.output)?;
     gamez::mw::write_gamez(&mut write, &gamez).context("Failed to write gamez data")
 }
 
-fn gamez_pm(opts: &ZipOpts) -> Result<()> {
-    let input = buf_reader(&opts.input)?;
-    let mut zip = ZipArchive::new(input).context("Failed to open input")?;
+fn gamez_pm(opts: &GameZOpts) -> Result<()> {
+    let mut zip = open_zip(&opts.input, opts.verify_source.as_deref())?;
 
     let metadata: GameZMetadataPm = zip_json(&mut zip, "metadata.json")?;
     let textures: Vec<String> = zip_json(&mut zip, "textures.json")?;
@@ -292,21 +440,23 @@ fn gamez_pm(opts: &ZipOpts) -> Result<()> {
 
     drop(zip);
 
-    let gamez = GameZDataPm {
+    let mut gamez = GameZDataPm {
         metadata,
         textures,
         materials,
         meshes,
         nodes,
     };
+    if opts.fit_metadata {
+        gamez::pm::fit_metadata(&mut gamez);
+    }
 
     let mut write = buf_writer(&opts.output)?;
     gamez::pm::write_gamez(&mut write, &gamez).context("Failed to write gamez data")
 }
 
-fn gamez_cs(opts: &ZipOpts) -> Result<()> {
-    let input = buf_reader(&opts.input)?;
-    let mut zip = ZipArchive::new(input).context("Failed to open input")?;
+fn gamez_cs(opts: &GameZOpts) -> Result<()> {
+    let mut zip = open_zip(&opts.input, opts.verify_source.as_deref())?;
 
     let metadata: GameZMetadataCs = zip_json(&mut zip, "metadata.json")?;
     let textures: Vec<TextureName> = zip_json(&mut zip, "textures.json")?;
@@ -328,14 +478,14 @@ fn gamez_cs(opts: &ZipOpts) -> Result<()> {
     gamez::cs::write_gamez(&mut write, &gamez).context("Failed to write gamez data")
 }
 
-fn gamez_rc(opts: &ZipOpts) -> Result<()> {
-    let input = buf_reader(&opts.input)?;
-    let mut zip = ZipArchive::new(input).context("Failed to open input")?;
+fn gamez_rc(opts: &GameZOpts) -> Result<()> {
+    let mut zip = open_zip(&opts.input, opts.verify_source.as_deref())?;
 
     let textures: Vec<String> = zip_json(&mut zip, "textures.json")?;
     let materials: Vec<Material> = zip_json(&mut zip, "materials.json")?;
     let meshes: Vec<MeshRc> = zip_json(&mut zip, "meshes.json")?;
     let nodes: Vec<NodeRc> = zip_json(&mut zip, "nodes.json")?;
+    let texture_infos: Option<Vec<TextureInfoRc>> = zip_json_opt(&mut zip, "texture_infos.json")?;
 
     drop(zip);
 
@@ -344,6 +494,7 @@ fn gamez_rc(opts: &ZipOpts) -> Result<()> {
         materials,
         meshes,
         nodes,
+        texture_infos,
     };
 
     let mut write = buf_writer(&opts.output)?;
@@ -359,8 +510,7 @@ pub(crate) fn anim(opts: ZipOpts) -> Result<()> {
     }
 
     log::info!("ANIM: Reading `{}` ({})", opts.input, opts.game);
-    let input = buf_reader(&opts.input)?;
-    let mut zip = ZipArchive::new(input).context("Failed to open input")?;
+    let mut zip = open_zip(&opts.input, opts.verify_source.as_deref())?;
     let metadata: AnimMetadata = zip_json(&mut zip, "metadata.json")?;
 
     let mut write = buf_writer(&opts.output)?;
@@ -382,6 +532,7 @@ pub(crate) fn savegame(opts: ZipOpts) -> Result<()> {
     _zarchive(
         &opts.input,
         &opts.output,
+        opts.verify_source.as_deref(),
         version,
         "Failed to write savegame data",
         |zip, name, offset| match name {
@@ -440,3 +591,6 @@ Licensed under the European Union Public Licence (EUPL) 1.2.
     );
     Ok(())
 }
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,91 @@
+use super::*;
+use mech3ax_api_types::archive::{ArchiveEntryInfo, ArchiveEntryInfoInvalid};
+use mech3ax_common::test_utils::TempDir;
+use serde_json::json;
+use std::io::Write as _;
+use zip::write::{SimpleFileOptions, ZipWriter};
+
+const NAME: &str = "alpha.zrd";
+
+fn entries() -> Vec<ArchiveEntry> {
+    vec![ArchiveEntry {
+        name: NAME.to_string(),
+        rename: None,
+        flags: 0,
+        info: ArchiveEntryInfo::Invalid(ArchiveEntryInfoInvalid {
+            comment: vec![0; 64],
+            filetime: 0,
+        }),
+    }]
+}
+
+/// The reader archive, with a single reader.
+fn reader_zbd(value: &Value) -> Vec<u8> {
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    write_archive::<_, _, eyre::Report>(
+        &mut write,
+        &entries(),
+        |_name, offset| {
+            let mut buf = CountingWriter::new(Vec::new(), offset);
+            write_reader(&mut buf, value)?;
+            Ok(buf.into_inner())
+        },
+        Version::One,
+    )
+    .unwrap();
+    write.into_inner()
+}
+
+/// The reader archive, as it would be extracted by `unzbd`.
+fn reader_zip(value: &Value) -> Vec<u8> {
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default();
+    zip.start_file("manifest.json", options).unwrap();
+    zip.write_all(&serde_json::to_vec_pretty(&entries()).unwrap())
+        .unwrap();
+    zip.start_file("alpha.json", options).unwrap();
+    zip.write_all(&serde_json::to_vec_pretty(value).unwrap())
+        .unwrap();
+    zip.finish().unwrap().into_inner()
+}
+
+fn repack_reader(base: &TempDir, original: Option<String>) -> Value {
+    let input = base.path().join("reader.zip");
+    let output = base.path().join("reader.zbd");
+    // the float was edited by hand, and is now an integer
+    std::fs::write(&input, reader_zip(&json!(["radius", 2]))).unwrap();
+
+    reader(ReaderOpts {
+        zip: ZipOpts {
+            game: GameType::MW,
+            input: input.to_string_lossy().into_owned(),
+            output: output.to_string_lossy().into_owned(),
+            verify_source: None,
+        },
+        original,
+    })
+    .unwrap();
+
+    let path = output.to_string_lossy();
+    let mut readers = read_original_readers(&path, Version::One).unwrap();
+    readers.remove(NAME).unwrap()
+}
+
+#[test]
+fn reader_keeps_number_types_of_original() {
+    let base = TempDir::new("rezbd-reader-original");
+    let original = base.path().join("original.zbd");
+    std::fs::write(&original, reader_zbd(&json!(["radius", 1.5]))).unwrap();
+
+    let value = repack_reader(&base, Some(original.to_string_lossy().into_owned()));
+    assert_eq!(value, json!(["radius", 2.0]));
+    assert!(value[1].is_f64());
+}
+
+#[test]
+fn reader_without_original_writes_json_number_types() {
+    let base = TempDir::new("rezbd-reader");
+    let value = repack_reader(&base, None);
+    assert_eq!(value, json!(["radius", 2]));
+    assert!(value[1].is_i64());
+}
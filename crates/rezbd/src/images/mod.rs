@@ -0,0 +1,133 @@
+use eyre::{eyre, Context as _, Result};
+use image::{DynamicImage, ImageFormat, ImageReader};
+use mech3ax_api_types::image::{TextureFileFormat, TextureInfo};
+use mech3ax_image::{apply_texture_format, decode_png_format};
+use rayon::prelude::*;
+use rayon::{ThreadPool, ThreadPoolBuilder};
+use std::collections::{HashMap, VecDeque};
+use std::io::Cursor;
+
+/// The image formats accepted for textures, in lookup order. PNG is what
+/// `unzbd` writes by default, so it is found first.
+const TEXTURE_FORMATS: [ImageFormat; 4] = [
+    ImageFormat::Png,
+    ImageFormat::Tga,
+    ImageFormat::Bmp,
+    ImageFormat::Dds,
+];
+
+/// The image formats to look up textures in. The format recorded in the
+/// manifest is tried first, followed by the others.
+pub(crate) fn texture_formats(image_format: Option<TextureFileFormat>) -> Vec<ImageFormat> {
+    let first = match image_format {
+        None | Some(TextureFileFormat::Png) => ImageFormat::Png,
+        Some(TextureFileFormat::Tga) => ImageFormat::Tga,
+        Some(TextureFileFormat::Bmp) => ImageFormat::Bmp,
+    };
+    let mut formats = vec![first];
+    formats.extend(
+        TEXTURE_FORMATS
+            .into_iter()
+            .filter(|format| *format != first),
+    );
+    formats
+}
+
+/// The file extension used to look up textures of this format.
+pub(crate) fn texture_extension(format: ImageFormat) -> &'static str {
+    format.extensions_str()[0]
+}
+
+/// Decode an image, detecting the format from the data. Some formats (e.g.
+/// TGA) can't be detected, so the format from the file extension is used
+/// instead.
+pub(crate) fn decode_image(buf: Vec<u8>, format: ImageFormat) -> Result<DynamicImage> {
+    let mut reader = ImageReader::new(Cursor::new(buf))
+        .with_guessed_format()
+        .context("Failed to detect image format")?;
+    if reader.format().is_none() {
+        reader.set_format(format);
+    }
+    reader.decode().context("Failed to decode image")
+}
+
+/// Apply the original texture format embedded by `unzbd` to the texture
+/// info. Only PNGs can have the format embedded; other images, or PNGs
+/// without it (e.g. saved by an image editor), keep the manifest's format.
+pub(crate) fn apply_embedded_format(
+    info: &mut TextureInfo,
+    buf: &[u8],
+    format: ImageFormat,
+) -> Result<()> {
+    if format != ImageFormat::Png {
+        return Ok(());
+    }
+    if let Some(texture_format) = decode_png_format(buf).context("Failed to read PNG info")? {
+        apply_texture_format(info, &texture_format)?;
+    }
+    Ok(())
+}
+
+/// An image that hasn't been decoded yet.
+pub(crate) struct EncodedImage {
+    pub(crate) name: String,
+    pub(crate) buf: Vec<u8>,
+    pub(crate) format: ImageFormat,
+}
+
+/// Decode images in parallel, in batches.
+///
+/// The images are decoded in the order given, one batch at a time, when an
+/// image that hasn't been decoded yet is taken. A decoded RGBA image can be
+/// much larger than the encoded one, so only the images of about one batch
+/// are kept in memory.
+pub(crate) struct BatchDecoder {
+    pool: ThreadPool,
+    pending: VecDeque<EncodedImage>,
+    decoded: HashMap<String, DynamicImage>,
+}
+
+impl BatchDecoder {
+    /// Decode `jobs` images at once, or one per CPU if zero.
+    pub(crate) fn new(images: Vec<EncodedImage>, jobs: usize) -> Result<Self> {
+        let pool = ThreadPoolBuilder::new()
+            .num_threads(jobs)
+            .build()
+            .context("Failed to create thread pool")?;
+        Ok(Self {
+            pool,
+            pending: images.into(),
+            decoded: HashMap::new(),
+        })
+    }
+
+    /// Take the decoded image, decoding the next batches until it is found.
+    pub(crate) fn take(&mut self, name: &str) -> Result<DynamicImage> {
+        while !self.decoded.contains_key(name) && !self.pending.is_empty() {
+            self.decode_batch()?;
+        }
+        self.decoded
+            .remove(name)
+            .ok_or_else(|| eyre!("Failed to find image for `{}`", name))
+    }
+
+    fn decode_batch(&mut self) -> Result<()> {
+        let count = self.pool.current_num_threads().min(self.pending.len());
+        let batch: Vec<EncodedImage> = self.pending.drain(..count).collect();
+        let decoded = self.pool.install(|| {
+            batch
+                .into_par_iter()
+                .map(|EncodedImage { name, buf, format }| {
+                    let image = decode_image(buf, format)
+                        .with_context(|| format!("Failed to load image data for `{}`", name))?;
+                    Ok((name, image))
+                })
+                .collect::<Result<Vec<_>>>()
+        })?;
+        self.decoded.extend(decoded);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,133 @@
+use super::*;
+use image::{Rgb, RgbImage};
+use mech3ax_api_types::image::{
+    TextureAlpha, TextureInfo, TextureManifest, TexturePalette, TextureStretch,
+};
+use mech3ax_common::io_ext::CountingWriter;
+use mech3ax_image::write_textures;
+
+fn source() -> DynamicImage {
+    let image = RgbImage::from_fn(5, 3, |x, y| {
+        // Cast safety: the image is tiny
+        Rgb([(x * 50) as u8, (y * 80) as u8, ((x + y) * 20) as u8])
+    });
+    DynamicImage::ImageRgb8(image)
+}
+
+fn encode(format: ImageFormat) -> Vec<u8> {
+    let mut buf = Cursor::new(Vec::new());
+    source().write_to(&mut buf, format).unwrap();
+    buf.into_inner()
+}
+
+fn write(image: DynamicImage) -> Vec<u8> {
+    let manifest = TextureManifest {
+        texture_infos: vec![TextureInfo {
+            name: "texture".to_string(),
+            index: None,
+            rename: None,
+            alpha: TextureAlpha::None,
+            width: 5,
+            height: 3,
+            stretch: TextureStretch::None,
+            image_loaded: false,
+            alpha_loaded: false,
+            palette_loaded: false,
+            palette: TexturePalette::None,
+        }],
+        global_palettes: Vec::new(),
+        image_format: None,
+    };
+    let mut image = Some(image);
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    write_textures::<_, eyre::Report>(&mut write, &manifest, |_name| Ok(image.take().unwrap()))
+        .unwrap();
+    write.into_inner()
+}
+
+#[test]
+fn decode_tga_and_bmp_like_png() {
+    let png = decode_image(encode(ImageFormat::Png), ImageFormat::Png).unwrap();
+    let tga = decode_image(encode(ImageFormat::Tga), ImageFormat::Tga).unwrap();
+    let bmp = decode_image(encode(ImageFormat::Bmp), ImageFormat::Bmp).unwrap();
+
+    let expected = source().to_rgb8();
+    assert_eq!(png.to_rgb8(), expected);
+    assert_eq!(tga.to_rgb8(), expected);
+    assert_eq!(bmp.to_rgb8(), expected);
+
+    let expected = write(png);
+    assert_eq!(write(tga), expected);
+    assert_eq!(write(bmp), expected);
+}
+
+#[test]
+fn decode_detects_format_from_data() {
+    // the fallback format is only used if detection fails
+    let image = decode_image(encode(ImageFormat::Bmp), ImageFormat::Png).unwrap();
+    assert_eq!(image.to_rgb8(), source().to_rgb8());
+}
+
+fn encoded(name: &str, width: u32) -> EncodedImage {
+    let image = DynamicImage::ImageRgb8(RgbImage::new(width, 1));
+    let mut buf = Cursor::new(Vec::new());
+    image.write_to(&mut buf, ImageFormat::Png).unwrap();
+    EncodedImage {
+        name: name.to_string(),
+        buf: buf.into_inner(),
+        format: ImageFormat::Png,
+    }
+}
+
+#[test]
+fn batch_decoder_takes_in_any_order() {
+    let images = (1..=5)
+        .map(|width| encoded(&format!("t{}", width), width))
+        .collect();
+    let mut decoder = BatchDecoder::new(images, 2).unwrap();
+
+    assert_eq!(decoder.take("t1").unwrap().width(), 1);
+    // out of order decodes the batches in between
+    assert_eq!(decoder.take("t5").unwrap().width(), 5);
+    assert_eq!(decoder.take("t2").unwrap().width(), 2);
+    assert_eq!(decoder.take("t4").unwrap().width(), 4);
+    assert_eq!(decoder.take("t3").unwrap().width(), 3);
+    // each image can only be taken once
+    assert!(decoder.take("t3").is_err());
+}
+
+#[test]
+fn batch_decoder_fails_on_bad_image() {
+    let mut bad = encoded("bad", 1);
+    bad.buf.truncate(8);
+    let mut decoder = BatchDecoder::new(vec![encoded("good", 1), bad], 2).unwrap();
+    let err = decoder.take("good").unwrap_err();
+    assert_eq!(err.to_string(), "Failed to load image data for `bad`");
+}
+
+#[test]
+fn texture_formats_start_with_manifest_format() {
+    assert_eq!(texture_formats(None), TEXTURE_FORMATS);
+    assert_eq!(
+        texture_formats(Some(TextureFileFormat::Png)),
+        TEXTURE_FORMATS
+    );
+    assert_eq!(
+        texture_formats(Some(TextureFileFormat::Tga)),
+        [
+            ImageFormat::Tga,
+            ImageFormat::Png,
+            ImageFormat::Bmp,
+            ImageFormat::Dds
+        ]
+    );
+    assert_eq!(
+        texture_formats(Some(TextureFileFormat::Bmp)),
+        [
+            ImageFormat::Bmp,
+            ImageFormat::Png,
+            ImageFormat::Tga,
+            ImageFormat::Dds
+        ]
+    );
+}
@@ -1,4 +1,6 @@
+mod all;
 mod commands;
+mod images;
 mod modding;
 
 use clap::Parser as _;
@@ -58,15 +60,25 @@ struct ZipArgs {
     input: String,
     #[clap(help = "The destination ZBD path (will be overwritten)")]
     output: String,
+    #[clap(
+        long = "verify-source",
+        help = "Fail unless the ZIP was extracted from this ZBD file, according to 'checksums.json'"
+    )]
+    verify_source: Option<String>,
 }
 
 impl ZipArgs {
     fn opts(self, game: GameType) -> Result<ZipOpts> {
-        let Self { input, output } = self;
+        let Self {
+            input,
+            output,
+            verify_source,
+        } = self;
         Ok(ZipOpts {
             game,
             input,
             output,
+            verify_source,
         })
     }
 }
@@ -75,6 +87,7 @@ struct ZipOpts {
     game: GameType,
     input: String,
     output: String,
+    verify_source: Option<String>,
 }
 
 impl ZipOpts {
@@ -86,6 +99,76 @@ impl ZipOpts {
     }
 }
 
+#[derive(clap::Args)]
+struct ReaderArgs {
+    #[clap(flatten)]
+    zip: ZipArgs,
+    #[clap(
+        long = "original",
+        help = "The original ZBD path, to keep the number types of edited readers"
+    )]
+    original: Option<String>,
+}
+
+impl ReaderArgs {
+    fn opts(self, game: GameType) -> Result<ReaderOpts> {
+        let Self { zip, original } = self;
+        Ok(ReaderOpts {
+            zip: zip.opts(game)?,
+            original,
+        })
+    }
+}
+
+struct ReaderOpts {
+    zip: ZipOpts,
+    original: Option<String>,
+}
+
+#[derive(clap::Args)]
+struct GameZArgs {
+    #[clap(help = "The source ZIP path")]
+    input: String,
+    #[clap(help = "The destination ZBD path (will be overwritten)")]
+    output: String,
+    #[clap(
+        long = "fit-metadata",
+        help = "Grow the metadata array sizes to fit the meshes and nodes, instead of failing (MW, PM)"
+    )]
+    fit_metadata: bool,
+    #[clap(
+        long = "verify-source",
+        help = "Fail unless the ZIP was extracted from this ZBD file, according to 'checksums.json'"
+    )]
+    verify_source: Option<String>,
+}
+
+impl GameZArgs {
+    fn opts(self, game: GameType) -> Result<GameZOpts> {
+        let Self {
+            input,
+            output,
+            fit_metadata,
+            verify_source,
+        } = self;
+        Ok(GameZOpts {
+            game,
+            input,
+            output,
+            fit_metadata,
+            verify_source,
+        })
+    }
+}
+
+struct GameZOpts {
+    game: GameType,
+    input: String,
+    output: String,
+    fit_metadata: bool,
+    verify_source: Option<String>,
+}
+
 #[derive(clap::Args)]
 struct InterpOpts {
     #[clap(help = "The source JSON path")]
@@ -94,6 +177,35 @@ struct InterpOpts {
     output: String,
 }
 
+#[derive(clap::Args)]
+struct MsgArgs {
+    #[clap(help = "The source JSON path")]
+    input: String,
+    #[clap(help = "The original Mech3Msg.dll path")]
+    dll: String,
+    #[clap(help = "The destination DLL path (will be overwritten)")]
+    output: String,
+}
+
+impl MsgArgs {
+    fn opts(self, game: GameType) -> Result<MsgOpts> {
+        let Self { input, dll, output } = self;
+        Ok(MsgOpts {
+            game,
+            input,
+            dll,
+            output,
+        })
+    }
+}
+
+struct MsgOpts {
+    game: GameType,
+    input: String,
+    dll: String,
+    output: String,
+}
+
 #[derive(clap::Args)]
 struct TextureOpts {
     #[clap(help = "The source ZIP path")]
@@ -105,6 +217,17 @@ struct TextureOpts {
         long
     )]
     modding: bool,
+    #[clap(
+        long,
+        default_value_t = 0,
+        help = "The number of images to decode at once, or one per CPU if 0 (not with --modding)"
+    )]
+    jobs: usize,
+    #[clap(
+        long = "verify-source",
+        help = "Fail unless the textures were extracted from this ZBD file, according to 'checksums.json'"
+    )]
+    verify_source: Option<String>,
 }
 
 #[derive(clap::Args)]
@@ -140,6 +263,42 @@ struct ZMapOpts {
     output: String,
 }
 
+#[derive(clap::Args)]
+struct AllArgs {
+    #[clap(help = "The source directory, as extracted by 'unzbd all' (searched recursively)")]
+    input: String,
+    #[clap(help = "The destination directory (files will be overwritten)")]
+    output: String,
+    #[clap(
+        long = "original",
+        help = "The original game directory, required to patch messages into the original DLLs"
+    )]
+    original: Option<String>,
+}
+
+impl AllArgs {
+    fn opts(self, game: GameType) -> Result<AllOpts> {
+        let Self {
+            input,
+            output,
+            original,
+        } = self;
+        Ok(AllOpts {
+            game,
+            input,
+            output,
+            original,
+        })
+    }
+}
+
+struct AllOpts {
+    game: GameType,
+    input: String,
+    output: String,
+    original: Option<String>,
+}
+
 #[derive(clap::Subcommand)]
 enum SubCommand {
     #[clap(about = "Print license information")]
@@ -149,7 +308,9 @@ enum SubCommand {
     #[clap(about = "Reconstruct 'interp.zbd' files from JSON")]
     Interp(InterpOpts),
     #[clap(about = "Reconstruct 'reader*.zbd' archives from ZIP")]
-    Reader(ZipArgs),
+    Reader(ReaderArgs),
+    #[clap(about = "Reconstruct 'Mech3Msg.dll'/'messages.dll' files from JSON")]
+    Messages(MsgArgs),
     #[clap(
         about = "Reconstruct 'rimage.zbd', 'rmechtex*.zbd', 'rtexture*.zbd', 'texture*.zbd' archives from ZIP"
     )]
@@ -159,7 +320,7 @@ enum SubCommand {
     #[clap(about = "Reconstruct 'mechlib.zbd' archives from ZIP (MW, PM)")]
     Mechlib(ZipArgs),
     #[clap(about = "Reconstruct 'gamez.zbd' archives from ZIP")]
-    Gamez(ZipArgs),
+    Gamez(GameZArgs),
     #[clap(about = "Reconstruct 'anim.zbd' archives from ZIP (MW)")]
     Anim(ZipArgs),
     #[clap(about = "Reconstruct savegames '*.mw3' archives from ZIP (MW)")]
@@ -168,6 +329,8 @@ enum SubCommand {
     Zrd(ZrdOpts),
     #[clap(about = "Reconstruct map '*.zmap' files from JSON (RC)")]
     Zmap(ZMapArgs),
+    #[clap(about = "Reconstruct all known files in a directory extracted by 'unzbd all'")]
+    All(AllArgs),
 }
 
 fn main() -> Result<()> {
@@ -189,16 +352,21 @@ fn main() -> Result<()> {
         SubCommand::Sounds(args) => commands::sounds(args.opts(game)?),
         SubCommand::Interp(opts) => commands::interp(opts),
         SubCommand::Reader(args) => commands::reader(args.opts(game)?),
+        SubCommand::Messages(args) => commands::messages(args.opts(game)?),
         SubCommand::Textures(TextureOpts {
             input,
             output,
             modding: false,
-        }) => commands::textures(input, output),
+            jobs,
+            verify_source,
+        }) => commands::textures(input, output, jobs, verify_source.as_deref()),
         SubCommand::Textures(TextureOpts {
             input,
             output,
             modding: true,
-        }) => modding::textures(input, output),
+            jobs: _,
+            verify_source,
+        }) => modding::textures(input, output, verify_source.as_deref()),
         SubCommand::Motion(args) => commands::motion(args.opts(game)?),
         SubCommand::Mechlib(args) => commands::mechlib(args.opts(game)?),
         SubCommand::Gamez(args) => commands::gamez(args.opts(game)?),
@@ -206,6 +374,7 @@ fn main() -> Result<()> {
         SubCommand::Savegame(args) => commands::savegame(args.opts(game)?),
         SubCommand::Zrd(opts) => modding::zrd(opts),
         SubCommand::Zmap(args) => commands::zmap(args.opts(game)?),
+        SubCommand::All(args) => all::all(args.opts(game)?),
         SubCommand::License => commands::license(),
     }
 }
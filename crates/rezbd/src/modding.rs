@@ -1,16 +1,17 @@
-use crate::commands::buf_writer;
+use crate::commands::{buf_writer, check_source};
+use crate::images::{apply_embedded_format, decode_image, texture_extension, texture_formats};
 use crate::ZrdOpts;
 use eyre::{Context as _, OptionExt as _, Result};
-use image::{ColorType, DynamicImage, GenericImageView, ImageFormat};
+use image::{ColorType, DynamicImage, GenericImageView};
 use mech3ax_api_types::image::{TextureAlpha, TextureManifest};
 use mech3ax_common::assert_with_msg;
 use mech3ax_common::io_ext::CountingWriter;
-use mech3ax_image::write_textures;
+use mech3ax_image::{sort_textures, write_textures};
 use mech3ax_reader::write_reader;
 use serde_json::Value;
 use std::collections::HashMap;
 use std::fs::File;
-use std::io::{BufReader, BufWriter, ErrorKind};
+use std::io::{BufWriter, ErrorKind};
 use std::path::Path;
 
 fn convert_dim(value: u32, name: &str) -> mech3ax_common::Result<u16> {
@@ -24,14 +25,21 @@ fn convert_dim(value: u32, name: &str) -> mech3ax_common::Result<u16> {
     })
 }
 
-pub(crate) fn textures(input: String, output: String) -> Result<()> {
+pub(crate) fn textures(input: String, output: String, verify_source: Option<&str>) -> Result<()> {
     let path = Path::new(&input);
     let buf = std::fs::read(path).context("Failed to read input (manifest)")?;
     let mut manifest: TextureManifest =
         serde_json::from_slice(&buf).context("Failed to parse input (manifest)")?;
+    sort_textures(&mut manifest).context("Failed to sort textures")?;
+    let formats = texture_formats(manifest.image_format);
     let parent = path
         .parent()
         .ok_or_eyre("Failed to get input parent path")?;
+    if let Some(source) = verify_source {
+        let checksums = std::fs::read(parent.join("checksums.json"))
+            .context("Failed to read `checksums.json`")?;
+        check_source(&checksums, source)?;
+    }
 
     let mut images: HashMap<String, DynamicImage> = manifest
         .texture_infos
@@ -39,15 +47,18 @@ pub(crate) fn textures(input: String, output: String) -> Result<()> {
         .map(|info| {
             let mut path = parent.to_path_buf();
             path.push(info.name.clone());
-            path.set_extension("png");
+            let format = formats
+                .iter()
+                .copied()
+                .find(|format| path.with_extension(texture_extension(*format)).exists())
+                .unwrap_or(formats[0]);
+            path.set_extension(texture_extension(format));
 
-            let inner = File::open(&path)
+            let buf = std::fs::read(&path)
                 .with_context(|| format!("Failed to open image \"{:?}\"", &path))?;
-            let mut reader = image::ImageReader::new(BufReader::new(inner));
-            reader.set_format(ImageFormat::Png);
-
-            let mut image = reader
-                .decode()
+            apply_embedded_format(info, &buf, format)
+                .with_context(|| format!("Failed to read format of image \"{:?}\"", &path))?;
+            let mut image = decode_image(buf, format)
                 .with_context(|| format!("Failed to read image \"{:?}\"", &path))?;
 
             let (width, height) = image.dimensions();
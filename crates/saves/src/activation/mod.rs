@@ -25,4 +25,4 @@ struct AnimActivationC {
     pub unk86: u8,                 // 86
     pub unk87: u8,                 // 87
 }
-impl_as_bytes!(AnimActivationC, 88);
+impl_as_bytes!(AnimActivationC, 88, LeStruct);
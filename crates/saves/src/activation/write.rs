@@ -1,5 +1,6 @@
 use super::{AnimActivationC, VALUES_SIZE};
 use mech3ax_api_types::saves::{ActivationType, AnimActivation};
+use mech3ax_common::assert::assert_utf8;
 use mech3ax_common::io_ext::CountingWriter;
 use mech3ax_common::{assert_with_msg, Result};
 use mech3ax_types::Ascii;
@@ -28,7 +29,9 @@ pub fn write_activation(
             activation.node_states.len()
         )
     })?;
-    let name = Ascii::from_str_padded(&activation.name);
+    let name = assert_utf8("anim activation name", write.offset + 8, || {
+        Ascii::from_str_padded(&activation.name)
+    })?;
 
     let mut activ = AnimActivationC {
         type_,
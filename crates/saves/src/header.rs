@@ -7,7 +7,7 @@ const FORMAT: u32 = 2;
 
 pub fn read_save_header(read: &mut CountingReader<impl Read>) -> Result<()> {
     let version = read.read_u32()?;
-    assert_that!("save header version", version == VERSION_MW, read.prev)?;
+    assert_that!("save header version", version version == VERSION_MW, read.prev)?;
     let format = read.read_u32()?;
     assert_that!("save header format", format == FORMAT, read.prev)?;
     Ok(())
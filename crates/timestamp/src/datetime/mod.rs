@@ -105,5 +105,22 @@ impl<'de> de::Deserialize<'de> for DateTime {
     }
 }
 
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for DateTime {
+    fn is_referenceable() -> bool {
+        false
+    }
+
+    fn schema_name() -> String {
+        "DateTime".to_owned()
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        let mut schema = String::json_schema(gen).into_object();
+        schema.format = Some("date-time".to_owned());
+        schema.into()
+    }
+}
+
 #[cfg(test)]
 mod tests;
@@ -28,8 +28,34 @@ pub trait AsBytes: NoUninit + AnyBitPattern + std::fmt::Debug {
     fn as_bytes_mut(&mut self) -> &mut [u8];
 }
 
+/// A marker that a structure is stored in little-endian byte order.
+///
+/// Structures are read and written as raw bytes, so they are only correct if
+/// every field is stored in the native byte order, and the native byte order
+/// is little-endian (see above). The generic read and write helpers require
+/// this marker, so a structure must opt in where it is defined.
+///
+/// Do not implement this manually, instead use [`impl_as_bytes!`] with the
+/// `LeStruct` argument:
+///
+/// ```ignore
+/// #[derive(Debug, Clone, Copy, NoUninit, AnyBitPattern)]
+/// #[repr(C)]
+/// struct Marked(u32);
+/// impl_as_bytes!(Marked, 4, LeStruct);
+/// ```
+///
+/// A structure without the `LeStruct` argument doesn't implement the marker,
+/// so it can't be passed to the read and write helpers.
+pub trait LeStruct: AsBytes {}
+
 #[macro_export]
 macro_rules! impl_as_bytes {
+    ($type:ty, $size:literal, LeStruct) => {
+        $crate::impl_as_bytes!($type, $size);
+
+        impl $crate::LeStruct for $type {}
+    };
     ($type:ty, $size:literal) => {
         impl $crate::AsBytes for $type {
             #[allow(dead_code)]
@@ -1,5 +1,6 @@
 use super::Ascii;
 use crate::ConversionError;
+use mech3ax_encoding::{windows1252_decode, windows1252_encode};
 use std::result::Result as StdResult;
 
 pub type Result<T> = StdResult<T, ConversionError>;
@@ -22,10 +23,10 @@ fn is_ascii(v: &[u8]) -> Result<()> {
     if !v.is_ascii() {
         for (index, b) in v.iter().enumerate() {
             if b & 0x80 != 0 {
-                return Err(ConversionError::NonAscii(index));
+                return Err(ConversionError::NonAscii(index, v.to_vec()));
             }
         }
-        Err(ConversionError::NonAscii(v.len()))
+        Err(ConversionError::NonAscii(v.len(), v.to_vec()))
     } else {
         Ok(())
     }
@@ -93,17 +94,19 @@ impl<const N: usize> Ascii<N> {
 
     /// Converts a string to a zero-terminated, zero-padded fixed length buffer.
     ///
-    /// # Panics
-    ///
-    /// This function panics if the string is not ASCII.
-    pub fn from_str_padded(s: &str) -> Self {
-        let b = ensure_str_ascii!(s);
+    /// Fails if the string is not ASCII, or if the string and the zero
+    /// terminator don't fit, instead of truncating the string.
+    pub fn from_str_padded(s: &str) -> Result<Self> {
+        let b = str_to_ascii(s)?;
+        if b.len() >= N {
+            return Err(ConversionError::TooLong(b.len(), N.saturating_sub(1)));
+        }
 
         let mut s = Self::zero();
         s.copy_with_zero_space(b);
         // `s` is already zero terminated (being initialized with zeros)
 
-        s
+        Ok(s)
     }
 
     /// Converts a zero-terminated, zero-padded fixed length buffer to a string.
@@ -117,6 +120,35 @@ impl<const N: usize> Ascii<N> {
         }
     }
 
+    /// Converts a string to a zero-terminated, zero-padded fixed length buffer,
+    /// encoded as Windows-1252.
+    ///
+    /// Some localized builds store names in Windows-1252, instead of ASCII.
+    /// Fails if a character can't be encoded, or if the string and the zero
+    /// terminator don't fit.
+    pub fn from_str_padded_cp1252(s: &str) -> Result<Self> {
+        let b = windows1252_encode(s).map_err(ConversionError::NotWindows1252)?;
+        if b.len() >= N {
+            return Err(ConversionError::TooLong(b.len(), N.saturating_sub(1)));
+        }
+
+        let mut s = Self::zero();
+        s.copy_with_zero_space(&b);
+        Ok(s)
+    }
+
+    /// Converts a zero-terminated, zero-padded fixed length buffer encoded as
+    /// Windows-1252 to a string.
+    pub fn to_str_padded_cp1252(&self) -> Result<String> {
+        let index = self.find_first_zero()?;
+        let (s, padding) = self.0.split_at(index);
+        if padding.iter().copied().all(|b| b == 0) {
+            Ok(windows1252_decode(s).into_owned())
+        } else {
+            Err(ConversionError::PaddingError("zeroes"))
+        }
+    }
+
     /// Converts a string to a zero-terminated, default node name-padded fixed
     /// length buffer.
     ///
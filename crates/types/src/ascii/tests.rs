@@ -45,33 +45,47 @@ fn ascii_split_combine() {
 #[test]
 fn ascii_from_str_padded_valid() {
     let a = Ascii::<4>::from_str_padded("");
-    assert_eq!(a, ascii!(b"\0\0\0\0"));
+    assert_eq!(a, Ok(ascii!(b"\0\0\0\0")));
     let a = Ascii::<4>::from_str_padded("a");
-    assert_eq!(a, ascii!(b"a\0\0\0"));
+    assert_eq!(a, Ok(ascii!(b"a\0\0\0")));
     let a = Ascii::<4>::from_str_padded("ab");
-    assert_eq!(a, ascii!(b"ab\0\0"));
+    assert_eq!(a, Ok(ascii!(b"ab\0\0")));
     let a = Ascii::<4>::from_str_padded("abc");
-    assert_eq!(a, ascii!(b"abc\0"));
+    assert_eq!(a, Ok(ascii!(b"abc\0")));
+}
+
+#[test]
+fn ascii_from_str_padded_too_long() {
     let a = Ascii::<4>::from_str_padded("abcd");
-    assert_eq!(a, ascii!(b"abc\0"));
+    assert_eq!(a, Err(ConversionError::TooLong(4, 3)));
     let a = Ascii::<4>::from_str_padded("abcde");
-    assert_eq!(a, ascii!(b"abc\0"));
+    assert_eq!(a, Err(ConversionError::TooLong(5, 3)));
 }
 
 #[test]
-#[should_panic(expected = "non-ASCII string")]
 fn ascii_from_str_padded_invalid() {
-    Ascii::<0>::from_str_padded("spam🎅eggs");
+    let a = Ascii::<16>::from_str_padded("spam🎅eggs");
+    assert_eq!(
+        a,
+        Err(ConversionError::NonAscii(
+            4,
+            "spam🎅eggs".as_bytes().to_vec()
+        ))
+    );
 }
 
 #[test]
 fn ascii_from_str_padded_one() {
     let a = Ascii::<1>::from_str_padded("");
-    assert_eq!(a, ascii!(b"\0"));
+    assert_eq!(a, Ok(ascii!(b"\0")));
     let a = Ascii::<1>::from_str_padded("a");
-    assert_eq!(a, ascii!(b"\0"));
-    let a = Ascii::<1>::from_str_padded("ab");
-    assert_eq!(a, ascii!(b"\0"));
+    assert_eq!(a, Err(ConversionError::TooLong(1, 0)));
+}
+
+#[test]
+fn ascii_from_str_padded_zero() {
+    let a = Ascii::<0>::from_str_padded("");
+    assert_eq!(a, Err(ConversionError::TooLong(0, 0)));
 }
 
 #[test]
@@ -93,7 +107,32 @@ fn ascii_to_string_padded_invalid() {
     let s = ascii!(b"ab\0\x01").to_str_padded();
     assert_eq!(s, Err(ConversionError::PaddingError("zeroes")));
     let s = ascii!(b"a\xBBc\0").to_str_padded();
-    assert_eq!(s, Err(ConversionError::NonAscii(1)));
+    assert_eq!(s, Err(ConversionError::NonAscii(1, b"a\xBBc".to_vec())));
+}
+
+#[test]
+fn ascii_str_padded_cp1252_round_trip() {
+    let a = Ascii::<8>::from_str_padded_cp1252("Café ß").unwrap();
+    assert_eq!(a, ascii!(b"Caf\xE9 \xDF\0\0"));
+    assert_eq!(a.to_str_padded_cp1252(), ok!("Café ß"));
+    // ASCII is unchanged
+    let a = Ascii::<4>::from_str_padded_cp1252("abc").unwrap();
+    assert_eq!(a, Ascii::<4>::from_str_padded("abc").unwrap());
+    assert_eq!(a.to_str_padded_cp1252(), ok!("abc"));
+}
+
+#[test]
+fn ascii_str_padded_cp1252_invalid() {
+    let a = Ascii::<8>::from_str_padded_cp1252("spam🎅");
+    assert_eq!(a, Err(ConversionError::NotWindows1252('🎅')));
+    let a = Ascii::<4>::from_str_padded_cp1252("éßé");
+    assert_eq!(a, Ok(ascii!(b"\xE9\xDF\xE9\0")));
+    let a = Ascii::<4>::from_str_padded_cp1252("éßéß");
+    assert_eq!(a, Err(ConversionError::TooLong(4, 3)));
+    let s = ascii!(b"\xE9\xDF\xE9\xDF").to_str_padded_cp1252();
+    assert_eq!(s, Err(ConversionError::Unterminated));
+    let s = ascii!(b"\xE9\0\xDF\0").to_str_padded_cp1252();
+    assert_eq!(s, Err(ConversionError::PaddingError("zeroes")));
 }
 
 #[test]
@@ -154,7 +193,7 @@ fn ascii_to_str_node_name_invalid() {
     let s = ascii!(b"ab\0\0").to_str_node_name();
     assert_eq!(s, Err(ConversionError::PaddingError("node name")));
     let s = ascii!(b"a\xBBc\0").to_str_node_name();
-    assert_eq!(s, Err(ConversionError::NonAscii(1)));
+    assert_eq!(s, Err(ConversionError::NonAscii(1, b"a\xBBc".to_vec())));
 }
 
 #[test]
@@ -253,9 +292,9 @@ fn ascii_to_str_suffix_invalid() {
     assert_eq!(s, Err(ConversionError::PaddingError("zeroes")));
 
     let s = ascii!(b"a\xBBc\0").to_str_suffix();
-    assert_eq!(s, Err(ConversionError::NonAscii(1)));
+    assert_eq!(s, Err(ConversionError::NonAscii(1, b"a\xBBc".to_vec())));
     let s = ascii!(b"abc\0\xBB").to_str_suffix();
-    assert_eq!(s, Err(ConversionError::NonAscii(4)));
+    assert_eq!(s, Err(ConversionError::NonAscii(4, b"abc.\xBB".to_vec())));
 }
 
 #[test]
@@ -311,5 +350,5 @@ fn ascii_to_str_garbage_invalid() {
     assert_eq!(s, Err(ConversionError::Unterminated));
 
     let s = ascii!(b"\xAAbcd\0").to_str_garbage();
-    assert_eq!(s, Err(ConversionError::NonAscii(0)));
+    assert_eq!(s, Err(ConversionError::NonAscii(0, b"\xAAbcd".to_vec())));
 }
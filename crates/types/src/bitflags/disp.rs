@@ -1,4 +1,5 @@
 use super::display_set::DisplaySet;
+use super::FlagNames;
 use std::fmt;
 
 const FLAGS_RAW: &[&str; 32] = &[
@@ -32,6 +33,29 @@ fmt_flags!(format_flags_u8, u8, 8);
 fmt_flags!(format_flags_u16, u16, 16);
 fmt_flags!(format_flags_u32, u32, 32);
 
+macro_rules! names_flags {
+    ($name:ident, $ty:ty, $bits:literal) => {
+        #[inline]
+        pub fn $name(v: $ty, flags: &'static [Option<&'static str>; $bits]) -> FlagNames<$ty> {
+            let mut names = Vec::new();
+            let mut unknown = 0;
+            for index in 0..$bits {
+                if v & (1 << index) != 0 {
+                    match flags[index] {
+                        Some(flag) => names.push(flag),
+                        None => unknown |= 1 << index,
+                    }
+                }
+            }
+            FlagNames { names, unknown }
+        }
+    };
+}
+
+names_flags!(names_flags_u8, u8, 8);
+names_flags!(names_flags_u16, u16, 16);
+names_flags!(names_flags_u32, u32, 32);
+
 /*
 // TODO: replace `find_flag` with this when #57349 is stabilized
 const fn gather_flags<const N: usize>(
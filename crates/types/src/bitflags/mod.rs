@@ -4,7 +4,7 @@ mod display_set;
 use crate::maybe::{PrimitiveRepr, SupportsMaybe};
 pub use disp::{
     format_flags_u16, format_flags_u32, format_flags_u8, gather_flags_u16, gather_flags_u32,
-    gather_flags_u8,
+    gather_flags_u8, names_flags_u16, names_flags_u32, names_flags_u8,
 };
 use std::fmt;
 use std::ops::{BitOr, BitOrAssign};
@@ -29,6 +29,17 @@ where
         + 'static
         + SupportsMaybe<R>,
 {
+    /// The names of the flags set in the raw value, and any set bits without
+    /// a name.
+    fn names(v: R) -> FlagNames<R>;
+}
+
+/// The names of the flags set in a raw value, and any set bits without a
+/// name (unknown bits).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlagNames<R> {
+    pub names: Vec<&'static str>,
+    pub unknown: R,
 }
 
 #[macro_export]
@@ -157,7 +168,12 @@ macro_rules! bitflags {
             }
         }
 
-        impl $crate::bitflags::Bitflags<$ty> for $name {}
+        impl $crate::bitflags::Bitflags<$ty> for $name {
+            #[inline]
+            fn names(v: $ty) -> $crate::bitflags::FlagNames<$ty> {
+                bitflags!(@names $ty)(v, Self::FLAGS)
+            }
+        }
     };
     (@fmt u8) => {
         $crate::bitflags::format_flags_u8
@@ -168,6 +184,15 @@ macro_rules! bitflags {
     (@fmt u32) => {
         $crate::bitflags::format_flags_u32
     };
+    (@names u8) => {
+        $crate::bitflags::names_flags_u8
+    };
+    (@names u16) => {
+        $crate::bitflags::names_flags_u16
+    };
+    (@names u32) => {
+        $crate::bitflags::names_flags_u32
+    };
     (@flags u8) => {
         const FLAGS: &'static [::core::option::Option<&'static str>; 8] = &$crate::bitflags::gather_flags_u8(Self::VARIANTS);
     };
@@ -137,3 +137,22 @@ fn maybe_debug_alt() {
     let s = format!("{:#?}", Maybe::new(0b1000_1010));
     assert_eq!(s, "{FOO, BAR, 1 << 7}");
 }
+
+#[test]
+fn flag_names_known_and_unknown() {
+    use crate::bitflags::{Bitflags as _, FlagNames};
+
+    let names = TestFlags::names(0b1000_1000);
+    assert_eq!(
+        names,
+        FlagNames {
+            names: vec!["BAR"],
+            unknown: 0b1000_0000,
+        }
+    );
+    let names = Maybe::new(0b1000_1010).flag_names();
+    assert_eq!(names.names, vec!["FOO", "BAR"]);
+    assert_eq!(names.unknown, 0b1000_0000);
+    let names = VALID.maybe().flag_names();
+    assert_eq!(names.unknown, 0);
+}
@@ -14,7 +14,7 @@ pub mod primitive_enum;
 mod ptr;
 mod zeros;
 
-pub use as_bytes::AsBytes;
+pub use as_bytes::{AsBytes, LeStruct};
 pub use ascii::{str_from_ascii, str_to_ascii, string_from_ascii, Ascii};
 pub use bitflags::Bitflags;
 pub use boolean::{Bool, Bool16, Bool32, Bool8};
@@ -27,9 +27,15 @@ pub use primitive_enum::PrimitiveEnum;
 pub use ptr::Ptr;
 pub use zeros::Zeros;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ConversionError {
-    NonAscii(usize),
+    /// The index of the first non-ASCII byte, and the bytes that failed to
+    /// convert.
+    NonAscii(usize, Vec<u8>),
     PaddingError(&'static str),
     Unterminated,
+    /// The length of the string, and the maximum length that fits.
+    TooLong(usize, usize),
+    /// The first character that can't be encoded as Windows-1252.
+    NotWindows1252(char),
 }
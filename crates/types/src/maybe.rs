@@ -1,3 +1,4 @@
+use crate::bitflags::{Bitflags, FlagNames};
 use bytemuck::{AnyBitPattern, NoUninit, Zeroable};
 use std::fmt;
 use std::marker::PhantomData;
@@ -90,6 +91,15 @@ impl<R: PrimitiveRepr, F: SupportsMaybe<R>> Maybe<R, F> {
     }
 }
 
+impl<R: PrimitiveRepr, F: Bitflags<R>> Maybe<R, F> {
+    /// The names of the flags set in the raw value, and any set bits without
+    /// a name, even if the value isn't valid.
+    #[inline]
+    pub fn flag_names(self) -> FlagNames<R> {
+        F::names(self.value)
+    }
+}
+
 impl<R: PrimitiveRepr, F: SupportsMaybe<R>> fmt::Display for Maybe<R, F> {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
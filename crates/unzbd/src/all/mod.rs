@@ -0,0 +1,178 @@
+use crate::images::ImageFileFormat;
+use crate::{
+    commands, AllOpts, GamezOpts, InterpOpts, MechlibOpts, MsgOpts, ReaderOpts, ZMapOpts, ZipOpts,
+};
+use eyre::{bail, Context as _, Report, Result};
+use mech3ax_common::asset::{discover, Asset};
+use mech3ax_common::{GameType, ReadOptions};
+use std::path::{Path, PathBuf};
+
+fn extract(asset: Asset, game: GameType, input: String, output: String) -> Result<()> {
+    match asset {
+        Asset::Sounds => commands::sounds(ZipOpts {
+            game,
+            input,
+            output,
+            dir: false,
+            resume: false,
+            keep_going: false,
+            compact: false,
+        }),
+        Asset::Interp => commands::interp(InterpOpts {
+            input,
+            output,
+            compact: false,
+        }),
+        Asset::Reader => commands::reader(ReaderOpts {
+            game,
+            input,
+            output,
+            dir: false,
+            resume: false,
+            skip_crc: false,
+            keep_going: false,
+            compact: false,
+            ndjson: false,
+        }),
+        Asset::Messages => commands::messages(MsgOpts {
+            game,
+            input,
+            output,
+            compact: false,
+        }),
+        Asset::Textures => commands::textures(input, output, false, ImageFileFormat::Png, false),
+        Asset::Motion => commands::motion(ZipOpts {
+            game,
+            input,
+            output,
+            dir: false,
+            resume: false,
+            keep_going: false,
+            compact: false,
+        }),
+        Asset::Mechlib => commands::mechlib(MechlibOpts {
+            zip: ZipOpts {
+                game,
+                input,
+                output,
+                dir: false,
+                resume: false,
+                keep_going: false,
+                compact: false,
+            },
+            model: None,
+            with_bounds: false,
+        }),
+        Asset::Gamez => commands::gamez(GamezOpts {
+            zip: ZipOpts {
+                game,
+                input,
+                output,
+                dir: false,
+                resume: false,
+                keep_going: false,
+                compact: false,
+            },
+            options: ReadOptions::default(),
+        }),
+        Asset::Anim => commands::anim(ZipOpts {
+            game,
+            input,
+            output,
+            dir: false,
+            resume: false,
+            keep_going: false,
+            compact: false,
+        }),
+        Asset::Zmap => commands::zmap(ZMapOpts {
+            game,
+            input,
+            output,
+            compact: false,
+        }),
+    }
+}
+
+struct Summary {
+    written: Vec<PathBuf>,
+    failures: Vec<(PathBuf, Report)>,
+}
+
+/// Extract every known file in the game directory. A file that fails to
+/// extract is recorded, and doesn't stop the others.
+fn extract_all(game: GameType, game_dir: &Path, out_dir: &Path) -> Result<Summary> {
+    let mut files = Vec::new();
+    discover(game_dir, &mut files)
+        .with_context(|| format!("Failed to read directory `{}`", game_dir.display()))?;
+
+    let mut summary = Summary {
+        written: Vec::new(),
+        failures: Vec::new(),
+    };
+    for path in files {
+        let Some(asset) = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| Asset::from_game_file(name, game))
+        else {
+            continue;
+        };
+        // keep the directory structure, since e.g. every mission has a gamez
+        let relative = path.strip_prefix(game_dir).unwrap_or(&path);
+        let output = out_dir
+            .join(relative)
+            .with_extension(asset.extracted_extension());
+
+        log::info!("ALL: Extracting `{}` ({:?})", path.display(), asset);
+        let result = output
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .context("Failed to create output directory")
+            .and_then(|()| {
+                extract(
+                    asset,
+                    game,
+                    path.to_string_lossy().into_owned(),
+                    output.to_string_lossy().into_owned(),
+                )
+            });
+        match result {
+            Ok(()) => summary.written.push(output),
+            Err(e) => {
+                log::error!("ALL: `{}` failed: {:#}", path.display(), e);
+                summary.failures.push((path, e));
+            }
+        }
+    }
+    Ok(summary)
+}
+
+/// Extract every known file in a game directory, and report the result.
+pub(crate) fn all(opts: AllOpts) -> Result<()> {
+    let game_dir = Path::new(&opts.input);
+    let out_dir = Path::new(&opts.output);
+    let summary = extract_all(opts.game, game_dir, out_dir)?;
+
+    for output in &summary.written {
+        println!("OK `{}`", output.display());
+    }
+    for (path, e) in &summary.failures {
+        println!("FAIL `{}`: {:#}", path.display(), e);
+    }
+    let failed = summary.failures.len();
+    if failed > 0 {
+        bail!(
+            "{} of {} files in `{}` failed to extract",
+            failed,
+            failed + summary.written.len(),
+            opts.input
+        );
+    }
+    if summary.written.is_empty() {
+        bail!("No known files found in `{}`", opts.input);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,50 @@
+use super::*;
+use mech3ax_api_types::archive::{ArchiveEntry, ArchiveEntryInfo, ArchiveEntryInfoInvalid};
+use mech3ax_archive::{write_archive, Version};
+use mech3ax_common::io_ext::CountingWriter;
+use mech3ax_common::test_utils::TempDir;
+
+fn sounds() -> Vec<u8> {
+    let entries = ["alpha.wav", "bravo.wav"]
+        .iter()
+        .map(|name| ArchiveEntry {
+            name: name.to_string(),
+            rename: None,
+            flags: 0,
+            info: ArchiveEntryInfo::Invalid(ArchiveEntryInfoInvalid {
+                comment: vec![0; 64],
+                filetime: 0,
+            }),
+        })
+        .collect::<Vec<_>>();
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    write_archive::<_, _, Report>(
+        &mut write,
+        &entries,
+        |name, _offset| Ok(name.as_bytes().to_vec()),
+        Version::One,
+    )
+    .unwrap();
+    write.into_inner()
+}
+
+#[test]
+fn extract_all_isolates_failures() {
+    let base = TempDir::new("unzbd-all");
+    let game_dir = base.path().join("game");
+    let out_dir = base.path().join("out");
+    std::fs::create_dir_all(game_dir.join("zbd").join("c1")).unwrap();
+    std::fs::write(game_dir.join("zbd").join("sounds.zbd"), sounds()).unwrap();
+    std::fs::write(game_dir.join("zbd").join("c1").join("gamez.zbd"), b"bad").unwrap();
+    std::fs::write(game_dir.join("readme.txt"), b"ignored").unwrap();
+
+    let summary = extract_all(GameType::MW, &game_dir, &out_dir).unwrap();
+
+    assert_eq!(summary.written, [out_dir.join("zbd").join("sounds.zip")]);
+    assert!(summary.written[0].is_file());
+    assert_eq!(summary.failures.len(), 1);
+    assert_eq!(
+        summary.failures[0].0,
+        game_dir.join("zbd").join("c1").join("gamez.zbd")
+    );
+}
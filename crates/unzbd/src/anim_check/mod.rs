@@ -0,0 +1,137 @@
+use crate::commands::{buf_reader, log_warnings};
+use crate::input::SeekableInput;
+use crate::tree::{tree_nodes_mw, TreeNode};
+use crate::AnimCheckOpts;
+use eyre::{bail, Context as _, Result};
+use mech3ax_api_types::anim::AnimDef;
+use mech3ax_common::io_ext::CountingReader;
+use mech3ax_common::GameType;
+use mech3ax_gamez::gamez;
+use std::collections::HashSet;
+
+/// The node names in a GameZ file that anim defs can refer to.
+#[derive(Debug, Default)]
+pub(crate) struct GameZNames {
+    nodes: HashSet<String>,
+    lights: HashSet<String>,
+}
+
+impl GameZNames {
+    pub(crate) fn new(nodes: &[TreeNode]) -> Self {
+        let mut names = Self::default();
+        for node in nodes {
+            let Some(name) = &node.name else {
+                continue;
+            };
+            if node.kind == "Light" {
+                names.lights.insert(name.clone());
+            }
+            names.nodes.insert(name.clone());
+        }
+        names
+    }
+}
+
+/// The references of an anim def that aren't in the GameZ file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct DanglingRefs {
+    pub anim_def: String,
+    pub nodes: Vec<String>,
+    pub lights: Vec<String>,
+}
+
+/// Find the node and light references of an anim def that aren't in the
+/// GameZ file, or `None` if they all resolve.
+///
+/// Nodes the anim def lists as its own objects are created by the anim def,
+/// so they don't need to be in the GameZ file. Sounds are loaded from the
+/// sound archives, not the GameZ file, so they aren't checked.
+pub(crate) fn dangling_refs(anim_def: &AnimDef, names: &GameZNames) -> Option<DanglingRefs> {
+    let objects = anim_def
+        .objects
+        .iter()
+        .flatten()
+        .map(|object| object.name.as_str())
+        .collect::<HashSet<_>>();
+    let nodes = anim_def
+        .nodes
+        .iter()
+        .flatten()
+        .map(|node| &node.name)
+        .filter(|name| !names.nodes.contains(*name) && !objects.contains(name.as_str()))
+        .cloned()
+        .collect::<Vec<_>>();
+    let lights = anim_def
+        .lights
+        .iter()
+        .flatten()
+        .map(|light| &light.name)
+        .filter(|name| !names.lights.contains(*name))
+        .cloned()
+        .collect::<Vec<_>>();
+
+    if nodes.is_empty() && lights.is_empty() {
+        None
+    } else {
+        Some(DanglingRefs {
+            anim_def: anim_def.name.clone(),
+            nodes,
+            lights,
+        })
+    }
+}
+
+/// Check that the node and light references of every anim def resolve
+/// against a GameZ file.
+pub(crate) fn anim_check(opts: AnimCheckOpts) -> Result<()> {
+    match opts.game {
+        GameType::MW => {}
+        GameType::PM => bail!("Pirate's Moon support for Anim isn't implemented yet"),
+        GameType::RC => bail!("Recoil support for Anim isn't implemented yet"),
+        GameType::CS => bail!("Crimson Skies support for Anim isn't implemented yet"),
+    }
+
+    log::info!("ANIM CHECK: Reading `{}` ({})", opts.gamez, opts.game);
+    let mut input = CountingReader::new(SeekableInput::open(&opts.gamez)?);
+    let (gamez, warnings) =
+        gamez::mw::read_gamez(&mut input, opts.options).context("Failed to read gamez data")?;
+    log_warnings(&warnings);
+    let names = GameZNames::new(&tree_nodes_mw(&gamez.nodes));
+
+    log::info!("ANIM CHECK: Reading `{}` ({})", opts.anim, opts.game);
+    let mut input = CountingReader::new(buf_reader(&opts.anim)?);
+    let mut count = 0;
+    let mut dangling = Vec::new();
+    mech3ax_anim::mw::read_anim::<_, _, eyre::Report>(&mut input, |_name, anim_def| {
+        count += 1;
+        dangling.extend(dangling_refs(anim_def, &names));
+        Ok(())
+    })
+    .context("Failed to read anim data")?;
+
+    if !dangling.is_empty() {
+        for refs in &dangling {
+            for node in &refs.nodes {
+                println!("FAIL `{}`: node `{}` not found", refs.anim_def, node);
+            }
+            for light in &refs.lights {
+                println!("FAIL `{}`: light `{}` not found", refs.anim_def, light);
+            }
+        }
+        bail!(
+            "{} of {} anim defs in `{}` have references not in `{}`",
+            dangling.len(),
+            count,
+            opts.anim,
+            opts.gamez
+        );
+    }
+    println!(
+        "OK `{}`: {} anim defs resolve against `{}`",
+        opts.anim, count, opts.gamez
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests;
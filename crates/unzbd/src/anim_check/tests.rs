@@ -0,0 +1,94 @@
+use super::*;
+use mech3ax_api_types::anim::{AnimActivation, Execution, NamePad, NamePtr};
+
+fn name_pad(name: &str) -> NamePad {
+    NamePad {
+        name: name.to_string(),
+        pad: Vec::new(),
+    }
+}
+
+fn name_ptrs(names: &[&str]) -> Option<Vec<NamePtr>> {
+    let names = names
+        .iter()
+        .map(|name| NamePtr {
+            name: name.to_string(),
+            pointer: 0,
+        })
+        .collect();
+    Some(names)
+}
+
+fn anim_def(nodes: &[&str], lights: &[&str]) -> AnimDef {
+    AnimDef {
+        name: "test".to_string(),
+        anim_name: name_pad("test"),
+        anim_root: name_pad("test"),
+        file_name: "test.flt".to_string(),
+        auto_reset_node_states: true,
+        activation: AnimActivation::OnCall,
+        execution: Execution::None,
+        network_log: None,
+        save_log: None,
+        has_callbacks: false,
+        reset_time: None,
+        health: 0.0,
+        proximity_damage: true,
+        activ_prereq_min_to_satisfy: 0,
+        objects: None,
+        nodes: name_ptrs(nodes),
+        lights: name_ptrs(lights),
+        puffers: None,
+        dynamic_sounds: None,
+        static_sounds: None,
+        activ_prereqs: None,
+        anim_refs: None,
+        reset_state: None,
+        sequences: Vec::new(),
+    }
+}
+
+fn gamez_names() -> GameZNames {
+    GameZNames::new(&[
+        TreeNode::named("World", "world1"),
+        TreeNode::new("Camera"),
+        TreeNode::named("Object3d", "door"),
+        TreeNode::named("Light", "sunlight"),
+    ])
+}
+
+#[test]
+fn references_resolve() {
+    let anim_def = anim_def(&["world1", "door"], &["sunlight"]);
+    assert_eq!(dangling_refs(&anim_def, &gamez_names()), None);
+}
+
+#[test]
+fn missing_node_is_dangling() {
+    let anim_def = anim_def(&["door", "hatch"], &["sunlight"]);
+    let expected = DanglingRefs {
+        anim_def: "test".to_string(),
+        nodes: vec!["hatch".to_string()],
+        lights: Vec::new(),
+    };
+    assert_eq!(dangling_refs(&anim_def, &gamez_names()), Some(expected));
+}
+
+#[test]
+fn light_must_be_a_light_node() {
+    // `door` exists, but isn't a light
+    let anim_def = anim_def(&[], &["door"]);
+    let expected = DanglingRefs {
+        anim_def: "test".to_string(),
+        nodes: Vec::new(),
+        lights: vec!["door".to_string()],
+    };
+    assert_eq!(dangling_refs(&anim_def, &gamez_names()), Some(expected));
+}
+
+#[test]
+fn own_objects_are_not_dangling() {
+    let mut anim_def = anim_def(&["door", "hatch"], &[]);
+    anim_def.objects = Some(vec![name_pad("hatch")]);
+    assert_eq!(dangling_refs(&anim_def, &gamez_names()), None);
+}
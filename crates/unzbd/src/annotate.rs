@@ -0,0 +1,38 @@
+use crate::AnnotateOpts;
+use eyre::{bail, Context as _, Result};
+use mech3ax_gamez::annotate::{annotate as annotate_bytes, AnnotateType};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub(crate) enum Kind {
+    /// The GameZ header
+    Header,
+    /// A mesh light
+    Light,
+}
+
+impl Kind {
+    fn annotate_type(self) -> AnnotateType {
+        match self {
+            Self::Header => AnnotateType::Header,
+            Self::Light => AnnotateType::Light,
+        }
+    }
+}
+
+/// Print the fields of a known structure at an offset in the input.
+pub(crate) fn annotate(opts: AnnotateOpts) -> Result<()> {
+    let data =
+        std::fs::read(&opts.input).with_context(|| format!("Failed to open `{}`", opts.input))?;
+    let Some(bytes) = data.get(opts.at..) else {
+        bail!(
+            "Offset {} is past the end of `{}` ({} bytes)",
+            opts.at,
+            opts.input,
+            data.len()
+        );
+    };
+    let text = annotate_bytes(opts.kind.annotate_type(), opts.game, bytes)
+        .with_context(|| format!("Failed to annotate {:?} at {}", opts.kind, opts.at))?;
+    print!("{}", text);
+    Ok(())
+}
@@ -0,0 +1,248 @@
+use crate::commands::{buf_reader, mechlib_game, read_entries, read_mechlib_entry, EntryError};
+use crate::input::SeekableInput;
+use crate::CheckOpts;
+use eyre::{bail, Context as _, Report, Result};
+use mech3ax_archive::{Mode, Version};
+use mech3ax_common::io_ext::CountingReader;
+use mech3ax_common::{GameType, Warnings};
+use mech3ax_gamez::gamez;
+use mech3ax_image::read_textures;
+use mech3ax_interp::read_interp;
+use mech3ax_messages::read_messages;
+use mech3ax_motion::read_motion;
+use mech3ax_reader::read_reader;
+use mech3ax_saves::{read_activation, read_save_header};
+use std::io::Cursor;
+use std::time::Instant;
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub(crate) enum Kind {
+    Sounds,
+    Interp,
+    Reader,
+    Messages,
+    Textures,
+    Motion,
+    Mechlib,
+    Gamez,
+    Anim,
+    Savegame,
+    Zmap,
+}
+
+type EntryReader = CountingReader<Cursor<Vec<u8>>>;
+
+struct Summary {
+    entries: usize,
+    failures: Vec<EntryError>,
+    warnings: Warnings,
+}
+
+impl Summary {
+    fn single() -> Self {
+        Self {
+            entries: 1,
+            failures: Vec::new(),
+            warnings: Warnings::new(),
+        }
+    }
+}
+
+/// Read every entry of an archive, and record entries that fail to read
+/// instead of stopping at the first one.
+fn check_archive<F>(
+    input: &str,
+    version: Version,
+    context: &'static str,
+    check_entry: F,
+) -> Result<Summary>
+where
+    F: Fn(&str, &mut EntryReader) -> Result<()>,
+{
+    let mut input = CountingReader::new(buf_reader(input)?);
+    let (entries, failures) =
+        read_entries(&mut input, version, true, context, |name, data, offset| {
+            let mut read = CountingReader::new(Cursor::new(data));
+            // translate to absolute offset
+            read.offset = offset;
+            check_entry(name, &mut read)
+        })?;
+    Ok(Summary {
+        entries: entries.len(),
+        failures,
+        warnings: Warnings::new(),
+    })
+}
+
+fn check_kind(opts: &CheckOpts) -> Result<Summary> {
+    let game = opts.game;
+    let input = opts.input.as_str();
+    match opts.kind {
+        Kind::Sounds => check_archive(
+            input,
+            opts.version(Mode::Sounds),
+            "Failed to read sounds data",
+            |_name, _read| Ok(()),
+        ),
+        Kind::Interp => {
+            let mut read = CountingReader::new(buf_reader(input)?);
+            read_interp(&mut read).context("Failed to read interpreter data")?;
+            Ok(Summary::single())
+        }
+        Kind::Reader => check_archive(
+            input,
+            opts.version(Mode::Reader),
+            "Failed to read reader data",
+            |name, read| {
+                read_reader(read)
+                    .with_context(|| format!("Failed to read reader data for `{}`", name))?;
+                Ok(())
+            },
+        ),
+        Kind::Messages => {
+            let mut read = buf_reader(input)?;
+            read_messages(&mut read, game).context("Failed to read message data")?;
+            Ok(Summary::single())
+        }
+        Kind::Textures => {
+            let mut read = CountingReader::new(buf_reader(input)?);
+            let manifest = read_textures::<_, Report>(&mut read, |_name, _info, _image| Ok(()))
+                .context("Failed to read texture data")?;
+            Ok(Summary {
+                entries: manifest.texture_infos.len(),
+                failures: Vec::new(),
+                warnings: Warnings::new(),
+            })
+        }
+        Kind::Motion => {
+            match game {
+                GameType::MW | GameType::PM => {}
+                GameType::RC => bail!("Recoil does not have motion"),
+                GameType::CS => bail!("Crimson Skies does not have motion"),
+            }
+            check_archive(
+                input,
+                opts.version(Mode::Motion),
+                "Failed to read motion data",
+                |name, read| {
+                    read_motion(read)
+                        .with_context(|| format!("Failed to read motion data for `{}`", name))?;
+                    Ok(())
+                },
+            )
+        }
+        Kind::Mechlib => {
+            let game = mechlib_game(game)?;
+            check_archive(
+                input,
+                opts.version(Mode::Mechlib),
+                "Failed to read mechlib data",
+                |name, read| read_mechlib_entry(read, game, name).map(|_| ()),
+            )
+        }
+        Kind::Gamez => {
+            let mut read = CountingReader::new(SeekableInput::open(input)?);
+            let result = match game {
+                GameType::MW => gamez::mw::read_gamez(&mut read, opts.options).map(|(_, w)| w),
+                GameType::PM => gamez::pm::read_gamez(&mut read, opts.options).map(|(_, w)| w),
+                GameType::RC => gamez::rc::read_gamez(&mut read, opts.options).map(|(_, w)| w),
+                GameType::CS => gamez::cs::read_gamez(&mut read, opts.options).map(|(_, w)| w),
+            };
+            let warnings = result.context("Failed to read gamez data")?;
+            Ok(Summary {
+                warnings,
+                ..Summary::single()
+            })
+        }
+        Kind::Anim => {
+            match game {
+                GameType::MW => {}
+                GameType::PM => bail!("Pirate's Moon support for Anim isn't implemented yet"),
+                GameType::RC => bail!("Recoil support for Anim isn't implemented yet"),
+                GameType::CS => bail!("Crimson Skies support for Anim isn't implemented yet"),
+            }
+            let mut read = CountingReader::new(buf_reader(input)?);
+            let mut entries = 0;
+            mech3ax_anim::mw::read_anim::<_, _, Report>(&mut read, |_name, _anim_def| {
+                entries += 1;
+                Ok(())
+            })
+            .context("Failed to read anim data")?;
+            Ok(Summary {
+                entries,
+                failures: Vec::new(),
+                warnings: Warnings::new(),
+            })
+        }
+        Kind::Savegame => {
+            match game {
+                GameType::MW => {}
+                GameType::PM => bail!("Pirate's Moon support for Savegames isn't implemented yet"),
+                GameType::RC => bail!("Recoil support for Savegames isn't implemented yet"),
+                GameType::CS => bail!("Crimson Skies support for Savegames isn't implemented yet"),
+            }
+            check_archive(
+                input,
+                Version::One,
+                "Failed to read savegame data",
+                |name, read| match name {
+                    "zSaveHeader" => {
+                        read_save_header(read).context("Failed to read savegame header")
+                    }
+                    original => {
+                        read_activation(read).with_context(|| {
+                            format!("Failed to read anim activation `{}`", original)
+                        })?;
+                        Ok(())
+                    }
+                },
+            )
+        }
+        Kind::Zmap => {
+            match game {
+                GameType::RC => {}
+                GameType::MW => bail!("MechWarrior 3 does not have zmap"),
+                GameType::PM => bail!("Pirate's Moon does not have zmap"),
+                GameType::CS => bail!("Crimson Skies does not have zmap"),
+            }
+            let mut read = CountingReader::new(buf_reader(input)?);
+            mech3ax_zmap::read_map(&mut read).context("Failed to read zmap data")?;
+            Ok(Summary::single())
+        }
+    }
+}
+
+/// Read the input without writing any output, and report the result.
+pub(crate) fn check(opts: CheckOpts) -> Result<()> {
+    log::info!(
+        "CHECK: Reading `{}` ({}, {:?})",
+        opts.input,
+        opts.game,
+        opts.kind
+    );
+    let start = Instant::now();
+    let summary = check_kind(&opts)?;
+    let elapsed = start.elapsed();
+
+    for warning in &summary.warnings {
+        println!("WARN {}", warning);
+    }
+    let failed = summary.failures.len();
+    if failed > 0 {
+        for failure in &summary.failures {
+            println!("FAIL `{}`: {}", failure.name, failure.error);
+        }
+        bail!(
+            "{} of {} entries in `{}` failed to read ({:.2?})",
+            failed,
+            summary.entries,
+            opts.input,
+            elapsed
+        );
+    }
+    println!(
+        "OK `{}`: {} entries read ({:.2?})",
+        opts.input, summary.entries, elapsed
+    );
+    Ok(())
+}
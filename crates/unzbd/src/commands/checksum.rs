@@ -0,0 +1,66 @@
+use eyre::{Context as _, Result};
+use mech3ax_common::checksum::{sha256_file, sha256_hex};
+use std::collections::BTreeMap;
+use std::io;
+use std::path::Path;
+
+/// The name of the file listing the checksums of the source and the files
+/// written.
+pub(crate) const CHECKSUMS_NAME: &str = "checksums.json";
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub(crate) struct SourceChecksum {
+    /// The file name only, so the checksums don't depend on where the source
+    /// was.
+    pub name: String,
+    pub sha256: String,
+}
+
+/// The SHA-256 of the source file, and of each file written from it.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub(crate) struct Checksums {
+    pub source: SourceChecksum,
+    pub entries: BTreeMap<String, String>,
+}
+
+impl Checksums {
+    /// Hash the source file.
+    pub(crate) fn new(source: &str) -> Result<Self> {
+        let path = Path::new(source);
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let sha256 =
+            sha256_file(path).with_context(|| format!("Failed to hash `{}`", path.display()))?;
+        Ok(Self {
+            source: SourceChecksum { name, sha256 },
+            entries: BTreeMap::new(),
+        })
+    }
+
+    /// Keep the entries recorded by a previous run, e.g. when resuming, as
+    /// long as they were extracted from the same source.
+    pub(crate) fn merge_previous(&mut self, path: &Path) -> Result<()> {
+        let data = match std::fs::read(path) {
+            Ok(data) => data,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(e).context("Failed to read previous checksums"),
+        };
+        let previous: Self =
+            serde_json::from_slice(&data).context("Failed to parse previous checksums")?;
+        if previous.source.sha256 != self.source.sha256 {
+            log::warn!(
+                "Previous checksums are for a different source `{}`, ignoring",
+                previous.source.name
+            );
+            return Ok(());
+        }
+        self.entries = previous.entries;
+        Ok(())
+    }
+
+    pub(crate) fn add(&mut self, name: &str, data: &[u8]) {
+        self.entries.insert(name.to_string(), sha256_hex(data));
+    }
+}
@@ -0,0 +1,988 @@
+mod checksum;
+mod sink;
+
+use crate::images::ImageFileFormat;
+use crate::input::SeekableInput;
+use crate::{
+    GamezOpts, InterpDiffOpts, InterpOpts, MechlibOpts, MotionBvhOpts, MsgOpts, ReaderOpts,
+    ZMapOpts, ZipOpts,
+};
+use eyre::{bail, Context as _, Result};
+use mech3ax_api_types::archive::ArchiveEntry;
+use mech3ax_api_types::gamez::materials::Material;
+use mech3ax_api_types::gamez::mechlib::{ModelMw, ModelPm};
+use mech3ax_api_types::nodes::mw::NodeMw;
+use mech3ax_api_types::nodes::pm::NodePm;
+use mech3ax_api_types::Vec3;
+use mech3ax_archive::{read_archive, Mode, Version};
+use mech3ax_common::io_ext::CountingReader;
+use mech3ax_common::{GameType, ReadOptions, Warnings};
+use mech3ax_gamez::gamez;
+use mech3ax_gamez::mechlib::{
+    self, model_bounds_mw, model_bounds_pm, read_format, read_materials, read_version,
+};
+use mech3ax_image::{read_texture_manifest, read_textures};
+use mech3ax_interp::{diff_interp, read_interp, validate_interp, LineChange};
+use mech3ax_messages::read_messages;
+use mech3ax_motion::{motion_to_bvh, read_motion, BvhJoint};
+use mech3ax_reader::read_reader;
+use mech3ax_saves::{read_activation, read_save_header};
+use mech3ax_types::u32_to_usize;
+use serde_json::Value;
+use sink::{OutputSink, Sink};
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Cursor, Read, Seek, Write};
+use std::path::Path;
+use zip::CompressionMethod;
+
+pub(crate) fn buf_reader<P: AsRef<Path>>(path: P) -> Result<BufReader<File>> {
+    Ok(BufReader::new(
+        File::open(path).context("Failed to open input")?,
+    ))
+}
+
+fn buf_writer<P: AsRef<Path>>(path: P) -> Result<BufWriter<File>> {
+    Ok(BufWriter::new(
+        File::create(path).context("Failed to create output")?,
+    ))
+}
+
+/// Serialize the value to JSON, either compact or pretty-printed. Both are
+/// read back identically.
+fn to_json<T>(value: &T, compact: bool) -> Result<Vec<u8>>
+where
+    T: serde::ser::Serialize,
+{
+    let data = if compact {
+        serde_json::to_vec(value)?
+    } else {
+        serde_json::to_vec_pretty(value)?
+    };
+    Ok(data)
+}
+
+pub(crate) fn interp(opts: InterpOpts) -> Result<()> {
+    log::info!("INTERP: Reading `{}`", opts.input);
+    let mut input = CountingReader::new(buf_reader(opts.input)?);
+    let scripts = read_interp(&mut input).context("Failed to read interpreter data")?;
+    if let Err(e) = validate_interp(&scripts) {
+        log::warn!("INTERP: {}", e);
+    }
+    let contents = to_json(&scripts, opts.compact)?;
+    std::fs::write(&opts.output, contents).context("Failed to write output")?;
+    log::info!("INTERP: Wrote `{}`", opts.output);
+    Ok(())
+}
+
+pub(crate) fn interp_diff(opts: InterpDiffOpts) -> Result<()> {
+    log::info!("INTERP DIFF: Reading `{}`", opts.first);
+    let mut input = CountingReader::new(buf_reader(&opts.first)?);
+    let first = read_interp(&mut input).context("Failed to read first interpreter data")?;
+    log::info!("INTERP DIFF: Reading `{}`", opts.second);
+    let mut input = CountingReader::new(buf_reader(&opts.second)?);
+    let second = read_interp(&mut input).context("Failed to read second interpreter data")?;
+
+    let diff = diff_interp(&first, &second);
+    if diff.is_empty() {
+        println!("No differences");
+        return Ok(());
+    }
+    for name in &diff.removed {
+        println!("- script `{}`", name);
+    }
+    for name in &diff.added {
+        println!("+ script `{}`", name);
+    }
+    for changed in &diff.changed {
+        println!("~ script `{}`", changed.name);
+        for line in &changed.lines {
+            // line numbers are one-based, like in a text editor
+            match line {
+                LineChange::Removed { index, line } => println!("  - {:>4}: {}", index + 1, line),
+                LineChange::Added { index, line } => println!("  + {:>4}: {}", index + 1, line),
+            }
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn messages(opts: MsgOpts) -> Result<()> {
+    log::info!("MESSAGES: Reading `{}`", opts.input);
+    let mut input = buf_reader(opts.input)?;
+    let messages = read_messages(&mut input, opts.game).context("Failed to read message data")?;
+    let contents = to_json(&messages, opts.compact)?;
+    std::fs::write(&opts.output, contents).context("Failed to write output")?;
+    log::info!("MESSAGES: Wrote `{}`", opts.output);
+    Ok(())
+}
+
+#[derive(Debug, serde::Serialize)]
+pub(crate) struct EntryError {
+    pub(crate) name: String,
+    pub(crate) error: String,
+}
+
+#[derive(Debug, serde::Serialize)]
+struct WarningEntry<'a> {
+    offset: Option<usize>,
+    field: Option<&'a str>,
+    message: &'a str,
+}
+
+/// Log the warnings collected while reading the input.
+pub(crate) fn log_warnings(warnings: &Warnings) {
+    for warning in warnings {
+        log::warn!("{}", warning);
+    }
+}
+
+/// Log the offsets the reader recorded, if any (only with the `checkpoints`
+/// feature), to find where reading started to go wrong.
+pub(crate) fn log_checkpoints(input: &CountingReader<impl Read>) {
+    let checkpoints = input.dump_checkpoints();
+    if !checkpoints.is_empty() {
+        log::error!("Checkpoints before the failure:\n{}", checkpoints);
+    }
+}
+
+/// Log the warnings collected while reading the input, and list them in
+/// `warnings.json` if there are any.
+fn write_warnings<S: OutputSink>(out: &mut S, warnings: &Warnings) -> Result<()> {
+    if warnings.is_empty() {
+        return Ok(());
+    }
+    log_warnings(warnings);
+    let entries: Vec<WarningEntry<'_>> = warnings
+        .iter()
+        .map(|warning| WarningEntry {
+            offset: warning.offset,
+            field: warning.field.as_deref(),
+            message: &warning.message,
+        })
+        .collect();
+    out.write_json("warnings.json", &entries)
+}
+
+fn _zarchive<F>(
+    input: &str,
+    output: &str,
+    out: Sink,
+    version: Version,
+    keep_going: bool,
+    context: &'static str,
+    save_file: F,
+) -> Result<()>
+where
+    F: FnMut(&mut Sink, &str, Vec<u8>, usize) -> Result<()>,
+{
+    let mut input = CountingReader::new(buf_reader(input)?);
+    let (_, errors) = zarchive(&mut input, out, version, keep_going, context, save_file)?;
+    if !errors.is_empty() {
+        bail!(
+            "{} entries failed to read, see `errors.json` in `{}`",
+            errors.len(),
+            output
+        );
+    }
+    Ok(())
+}
+
+/// Read every entry in the archive, and pass it to `save_file`. With
+/// `keep_going`, entries that fail to save are recorded, instead of stopping
+/// the read.
+pub(crate) fn read_entries<R, F>(
+    input: &mut CountingReader<R>,
+    version: Version,
+    keep_going: bool,
+    context: &'static str,
+    mut save_file: F,
+) -> Result<(Vec<ArchiveEntry>, Vec<EntryError>)>
+where
+    R: Read + Seek,
+    F: FnMut(&str, Vec<u8>, usize) -> Result<()>,
+{
+    let mut errors = Vec::new();
+    let manifest = read_archive(
+        input,
+        |name, data, offset| match save_file(name, data, offset) {
+            Err(e) if keep_going => {
+                log::error!("Failed to save `{}`: {:#}", name, e);
+                errors.push(EntryError {
+                    name: name.to_owned(),
+                    error: format!("{:#}", e),
+                });
+                Ok(())
+            }
+            result => result,
+        },
+        version,
+    )
+    .context(context)?;
+    Ok((manifest, errors))
+}
+
+fn zarchive<R, S, F>(
+    input: &mut CountingReader<R>,
+    mut out: S,
+    version: Version,
+    keep_going: bool,
+    context: &'static str,
+    mut save_file: F,
+) -> Result<(S::Finished, Vec<EntryError>)>
+where
+    R: Read + Seek,
+    S: OutputSink,
+    F: FnMut(&mut S, &str, Vec<u8>, usize) -> Result<()>,
+{
+    let (manifest, errors) =
+        read_entries(input, version, keep_going, context, |name, data, offset| {
+            if out.is_done(name) {
+                log::debug!("Skipping `{}`, it was already written", name);
+                return Ok(());
+            }
+            let written = out.written();
+            save_file(&mut out, name, data, offset)?;
+            // entries the command skips (e.g. other mechlib models with
+            // `--model`) aren't done, so a later run can still write them
+            if out.written() == written {
+                return Ok(());
+            }
+            out.mark_done(name)
+        })?;
+
+    out.write_json("manifest.json", &manifest)?;
+    if !errors.is_empty() {
+        out.write_json("errors.json", &errors)?;
+    }
+    let output = out.finish()?;
+    Ok((output, errors))
+}
+
+#[derive(Debug, serde::Serialize)]
+struct NdjsonEntry<'a, T> {
+    name: &'a str,
+    data: &'a T,
+}
+
+/// Write one entry as a single line of JSON.
+fn ndjson_write<W, T>(write: &mut W, name: &str, data: &T) -> Result<()>
+where
+    W: Write,
+    T: serde::ser::Serialize,
+{
+    serde_json::to_writer(&mut *write, &NdjsonEntry { name, data })
+        .with_context(|| format!("Failed to write `{}` as JSON", name))?;
+    write
+        .write_all(b"\n")
+        .with_context(|| format!("Failed to write `{}` as JSON", name))
+}
+
+/// Like [`zarchive`], but write each entry to a newline-delimited JSON stream
+/// as soon as it is read, instead of to a Zip. The manifest isn't written.
+fn ndjson_archive<R, W, F>(
+    input: &mut CountingReader<R>,
+    mut write: W,
+    version: Version,
+    keep_going: bool,
+    context: &'static str,
+    mut save_file: F,
+) -> Result<(W, Vec<EntryError>)>
+where
+    R: Read + Seek,
+    W: Write,
+    F: FnMut(&mut W, &str, Vec<u8>, usize) -> Result<()>,
+{
+    let (_, errors) = read_entries(input, version, keep_going, context, |name, data, offset| {
+        save_file(&mut write, name, data, offset)
+    })?;
+    write.flush()?;
+    Ok((write, errors))
+}
+
+pub(crate) fn sounds(opts: ZipOpts) -> Result<()> {
+    let version = opts.version(Mode::Sounds);
+
+    log::info!("SOUNDS: Reading `{}` ({})", opts.input, opts.game);
+    let out = Sink::create(
+        &opts.input,
+        &opts.output,
+        opts.dir,
+        opts.resume,
+        CompressionMethod::Stored,
+        opts.compact,
+    )?;
+    _zarchive(
+        &opts.input,
+        &opts.output,
+        out,
+        version,
+        opts.keep_going,
+        "Failed to read sounds data",
+        |out, name, data, _offset| out.write_bytes(name, &data),
+    )?;
+    log::info!("SOUNDS: Wrote `{}`", opts.output);
+    Ok(())
+}
+
+fn read_reader_entry(name: &str, data: Vec<u8>, offset: usize) -> Result<Value> {
+    let mut read = CountingReader::new(Cursor::new(data));
+    // translate to absolute offset
+    read.offset = offset;
+    read_reader(&mut read).with_context(|| format!("Failed to read reader data for `{}`", name))
+}
+
+pub(crate) fn reader(opts: ReaderOpts) -> Result<()> {
+    let version = opts.version();
+    if opts.ndjson {
+        return reader_ndjson(opts, version);
+    }
+
+    log::info!("READER: Reading `{}`", opts.input);
+    let out = Sink::create(
+        &opts.input,
+        &opts.output,
+        opts.dir,
+        opts.resume,
+        CompressionMethod::Deflated,
+        opts.compact,
+    )?;
+    _zarchive(
+        &opts.input,
+        &opts.output,
+        out,
+        version,
+        opts.keep_going,
+        "Failed to read reader data",
+        |out, name, data, offset| {
+            let name = name.replace(".zrd", ".json");
+            let root = read_reader_entry(&name, data, offset)?;
+            out.write_json(&name, &root)
+        },
+    )?;
+    log::info!("READER: Wrote `{}`", opts.output);
+    Ok(())
+}
+
+fn reader_ndjson(opts: ReaderOpts, version: Version) -> Result<()> {
+    log::info!("READER: Reading `{}` (NDJSON)", opts.input);
+    let mut input = CountingReader::new(buf_reader(&opts.input)?);
+    let output = buf_writer(&opts.output)?;
+    let (_, errors) = ndjson_archive(
+        &mut input,
+        output,
+        version,
+        opts.keep_going,
+        "Failed to read reader data",
+        |write, name, data, offset| {
+            let root = read_reader_entry(name, data, offset)?;
+            ndjson_write(write, name, &root)
+        },
+    )?;
+    if !errors.is_empty() {
+        bail!(
+            "{} entries failed to read, see the log for details",
+            errors.len()
+        );
+    }
+    log::info!("READER: Wrote `{}`", opts.output);
+    Ok(())
+}
+
+pub(crate) fn motion(opts: ZipOpts) -> Result<()> {
+    match opts.game {
+        GameType::MW | GameType::PM => {}
+        GameType::RC => bail!("Recoil does not have motion"),
+        GameType::CS => bail!("Crimson Skies does not have motion"),
+    }
+    let version = opts.version(Mode::Motion);
+
+    log::info!("MOTION: Reading `{}` ({})", opts.input, opts.game);
+    let out = Sink::create(
+        &opts.input,
+        &opts.output,
+        opts.dir,
+        opts.resume,
+        CompressionMethod::Deflated,
+        opts.compact,
+    )?;
+    _zarchive(
+        &opts.input,
+        &opts.output,
+        out,
+        version,
+        opts.keep_going,
+        "Failed to read motion data",
+        |out, original, data, offset| {
+            let name = format!("{}.json", original);
+            let mut read = CountingReader::new(Cursor::new(data));
+            // translate to absolute offset
+            read.offset = offset;
+            let root = read_motion(&mut read)
+                .with_context(|| format!("Failed to read motion data for `{}`", original))?;
+
+            out.write_json(&name, &root)
+        },
+    )?;
+    log::info!("MOTION: Wrote `{}`", opts.output);
+    Ok(())
+}
+
+fn skeleton_mw(nodes: &[NodeMw]) -> Result<Vec<BvhJoint>> {
+    nodes
+        .iter()
+        .map(|node| match node {
+            NodeMw::Object3d(object3d) => Ok(BvhJoint {
+                name: object3d.name.clone(),
+                parent: object3d.parent.map(u32_to_usize),
+                offset: object3d
+                    .transformation
+                    .as_ref()
+                    .map(|tr| tr.translation)
+                    .unwrap_or(Vec3::DEFAULT),
+            }),
+            NodeMw::Lod(lod) => Ok(BvhJoint {
+                name: lod.name.clone(),
+                parent: Some(u32_to_usize(lod.parent)),
+                offset: Vec3::DEFAULT,
+            }),
+            _ => bail!("Unexpected node type in mechlib model"),
+        })
+        .collect()
+}
+
+fn skeleton_pm(nodes: &[NodePm]) -> Result<Vec<BvhJoint>> {
+    nodes
+        .iter()
+        .map(|node| match node {
+            NodePm::Object3d(object3d) => Ok(BvhJoint {
+                name: object3d.name.clone(),
+                parent: object3d.parent.map(u32_to_usize),
+                offset: object3d
+                    .transformation
+                    .as_ref()
+                    .map(|tr| tr.translation)
+                    .unwrap_or(Vec3::DEFAULT),
+            }),
+            NodePm::Lod(lod) => Ok(BvhJoint {
+                name: lod.name.clone(),
+                parent: Some(u32_to_usize(lod.parent)),
+                offset: Vec3::DEFAULT,
+            }),
+            _ => bail!("Unexpected node type in mechlib model"),
+        })
+        .collect()
+}
+
+fn read_skeleton(opts: &MotionBvhOpts, version: Version) -> Result<Vec<BvhJoint>> {
+    let mut input = CountingReader::new(buf_reader(&opts.mechlib)?);
+    let mut skeleton = None;
+    read_archive::<_, _, eyre::Report>(
+        &mut input,
+        |name, data, offset| {
+            if name != opts.model {
+                return Ok(());
+            }
+            let mut read = CountingReader::new(Cursor::new(data));
+            // translate to absolute offset
+            read.offset = offset;
+            let joints = match opts.game {
+                GameType::MW => skeleton_mw(&mechlib::mw::read_model(&mut read)?.nodes),
+                GameType::PM => skeleton_pm(&mechlib::pm::read_model(&mut read)?.nodes),
+                GameType::RC => unreachable!("Recoil does not have mechlib"),
+                GameType::CS => unreachable!("Crimson Skies does not have mechlib"),
+            }
+            .with_context(|| format!("Failed to read mechlib model for `{}`", name))?;
+            skeleton = Some(joints);
+            Ok(())
+        },
+        version,
+    )
+    .context("Failed to read mechlib data")?;
+    skeleton.ok_or_else(|| eyre::eyre!("Model `{}` not found in mechlib", opts.model))
+}
+
+pub(crate) fn motion_bvh(opts: MotionBvhOpts) -> Result<()> {
+    let (mechlib_version, motion_version) = match opts.game {
+        GameType::MW => (Version::One, Version::One),
+        GameType::PM => (Version::Two(Mode::Mechlib), Version::Two(Mode::Motion)),
+        GameType::RC => bail!("Recoil does not have motion"),
+        GameType::CS => bail!("Crimson Skies does not have motion"),
+    };
+
+    log::info!("MOTION: Reading `{}` ({})", opts.mechlib, opts.game);
+    let skeleton = read_skeleton(&opts, mechlib_version)?;
+    let joint_names: HashSet<&str> = skeleton.iter().map(|joint| joint.name.as_str()).collect();
+
+    log::info!("MOTION: Reading `{}` ({})", opts.input, opts.game);
+    let out = Sink::create(
+        &opts.input,
+        &opts.output,
+        false,
+        false,
+        CompressionMethod::Deflated,
+        false,
+    )?;
+    _zarchive(
+        &opts.input,
+        &opts.output,
+        out,
+        motion_version,
+        false,
+        "Failed to read motion data",
+        |out, original, data, offset| {
+            let mut read = CountingReader::new(Cursor::new(data));
+            // translate to absolute offset
+            read.offset = offset;
+            let motion = read_motion(&mut read)
+                .with_context(|| format!("Failed to read motion data for `{}`", original))?;
+            // the archive contains the motions for all models
+            if !motion
+                .parts
+                .iter()
+                .all(|part| joint_names.contains(part.name.as_str()))
+            {
+                log::debug!("Skipping motion `{}` for a different model", original);
+                return Ok(());
+            }
+
+            let name = format!("{}.bvh", original);
+            let mut data = Vec::new();
+            motion_to_bvh(&motion, &skeleton, &mut data)
+                .with_context(|| format!("Failed to export motion `{}`", original))?;
+            out.write_bytes(&name, &data)
+        },
+    )?;
+    log::info!("MOTION: Wrote `{}`", opts.output);
+    Ok(())
+}
+
+/// Check if an archive entry is the model, with or without the `.flt`
+/// extension.
+fn is_mechlib_model(name: &str, model: &str) -> bool {
+    name == model || name.strip_suffix(".flt") == Some(model)
+}
+
+/// Check if an archive entry is a model, and not the format, version, or
+/// materials.
+fn is_mechlib_model_entry(name: &str) -> bool {
+    !matches!(name, "format" | "version" | "materials")
+}
+
+/// Only MechWarrior 3 and Pirate's Moon have a mechlib.
+pub(crate) fn mechlib_game(game: GameType) -> Result<GameType> {
+    match game {
+        GameType::MW => Ok(GameType::MW),
+        GameType::PM => Ok(GameType::PM),
+        GameType::RC => bail!("Recoil does not have mechlib"),
+        GameType::CS => bail!("Crimson Skies does not have mechlib"),
+    }
+}
+
+pub(crate) enum MechlibEntry {
+    Format,
+    Version,
+    Materials(Vec<Material>),
+    ModelMw(ModelMw),
+    ModelPm(ModelPm),
+}
+
+/// Read a mechlib archive entry, depending on its name.
+pub(crate) fn read_mechlib_entry(
+    read: &mut CountingReader<impl Read>,
+    game: GameType,
+    name: &str,
+) -> Result<MechlibEntry> {
+    match name {
+        "format" => {
+            read_format(read).context("Failed to read mechlib format")?;
+            Ok(MechlibEntry::Format)
+        }
+        "version" => {
+            read_version(read, game).context("Failed to read mechlib version")?;
+            Ok(MechlibEntry::Version)
+        }
+        "materials" => {
+            let materials = read_materials(read).context("Failed to read mechlib materials")?;
+            Ok(MechlibEntry::Materials(materials))
+        }
+        original => {
+            let model = match game {
+                GameType::MW => mechlib::mw::read_model(read).map(MechlibEntry::ModelMw),
+                GameType::PM => mechlib::pm::read_model(read).map(MechlibEntry::ModelPm),
+                GameType::RC => unreachable!("Recoil does not have mechlib"),
+                GameType::CS => unreachable!("Crimson Skies does not have mechlib"),
+            };
+            model.with_context(|| format!("Failed to read mechlib model for `{}`", original))
+        }
+    }
+}
+
+fn mechlib_archive<R, S>(
+    input: &mut CountingReader<R>,
+    out: S,
+    game: GameType,
+    version: Version,
+    opts: &MechlibOpts,
+) -> Result<(S::Finished, Vec<EntryError>)>
+where
+    R: Read + Seek,
+    S: OutputSink,
+{
+    let mut found = false;
+    let result = zarchive(
+        input,
+        out,
+        version,
+        opts.zip.keep_going,
+        "Failed to read mechlib data",
+        |out, original, data, offset| {
+            // skip other models before reading them, which is the expensive
+            // part
+            if let Some(model) = &opts.model {
+                if is_mechlib_model_entry(original) {
+                    if !is_mechlib_model(original, model) {
+                        log::debug!("Skipping mechlib model `{}`", original);
+                        return Ok(());
+                    }
+                    found = true;
+                }
+            }
+            let mut read = CountingReader::new(Cursor::new(data));
+            // translate to absolute offset
+            read.offset = offset;
+            let bounds = match read_mechlib_entry(&mut read, game, original)? {
+                MechlibEntry::Format | MechlibEntry::Version => return Ok(()),
+                MechlibEntry::Materials(materials) => {
+                    return out.write_json("materials.json", &materials);
+                }
+                MechlibEntry::ModelMw(root) => {
+                    out.write_json(&original.replace(".flt", ".json"), &root)?;
+                    model_bounds_mw(&root)
+                }
+                MechlibEntry::ModelPm(root) => {
+                    out.write_json(&original.replace(".flt", ".json"), &root)?;
+                    model_bounds_pm(&root)
+                }
+            };
+            if opts.with_bounds {
+                let name = original.replace(".flt", ".bounds.json");
+                out.write_json(&name, &bounds)?;
+            }
+            Ok(())
+        },
+    )?;
+    if let Some(model) = &opts.model {
+        if !found {
+            bail!("Model `{}` not found in mechlib", model);
+        }
+    }
+    Ok(result)
+}
+
+pub(crate) fn mechlib(opts: MechlibOpts) -> Result<()> {
+    let game = mechlib_game(opts.zip.game)?;
+    let version = opts.zip.version(Mode::Mechlib);
+
+    log::info!("MECHLIB: Reading `{}` ({})", opts.zip.input, opts.zip.game);
+    let mut input = CountingReader::new(buf_reader(&opts.zip.input)?);
+    let out = Sink::create(
+        &opts.zip.input,
+        &opts.zip.output,
+        opts.zip.dir,
+        opts.zip.resume,
+        CompressionMethod::Deflated,
+        opts.zip.compact,
+    )?;
+    let (_, errors) = mechlib_archive(&mut input, out, game, version, &opts)?;
+    if !errors.is_empty() {
+        bail!(
+            "{} entries failed to read, see `errors.json` in `{}`",
+            errors.len(),
+            opts.zip.output
+        );
+    }
+    log::info!("MECHLIB: Wrote `{}`", opts.zip.output);
+    Ok(())
+}
+
+pub(crate) fn textures(
+    input: String,
+    output: String,
+    dir: bool,
+    image_format: ImageFileFormat,
+    compact: bool,
+) -> Result<()> {
+    log::info!("TEXTURES: Reading `{}`", input);
+    let mut out = Sink::create(
+        &input,
+        &output,
+        dir,
+        false,
+        CompressionMethod::Stored,
+        compact,
+    )?;
+    let mut input = CountingReader::new(buf_reader(input)?);
+
+    let mut manifest = read_textures::<_, eyre::Report>(&mut input, |original, info, image| {
+        let name = format!("{}.{}", original, image_format.extension());
+        let data = image_format
+            .encode(&image, info)
+            .with_context(|| format!("Failed to write image data for `{}`", original))?;
+
+        out.write_bytes(&name, &data)
+    })
+    .context("Failed to read texture data")?;
+    manifest.image_format = image_format.manifest_format();
+
+    out.write_json("manifest.json", &manifest)?;
+    out.finish()?;
+    log::info!("TEXTURES: Wrote `{}`", output);
+    Ok(())
+}
+
+pub(crate) fn texture_manifest(input: String, output: String, compact: bool) -> Result<()> {
+    log::info!("TEXTURES: Reading `{}` (manifest only)", input);
+    let mut input = CountingReader::new(SeekableInput::open(input)?);
+    let manifest = read_texture_manifest(&mut input).context("Failed to read texture data")?;
+    let contents = to_json(&manifest, compact)?;
+    std::fs::write(&output, contents).context("Failed to write output")?;
+    log::info!("TEXTURES: Wrote `{}`", output);
+    Ok(())
+}
+
+pub(crate) fn gamez(opts: GamezOpts) -> Result<()> {
+    let GamezOpts { zip: opts, options } = opts;
+    log::info!("GAMEZ: Reading `{}` ({})", opts.input, opts.game);
+    match opts.game {
+        GameType::RC => gamez_rc(&opts, options)?,
+        GameType::MW => gamez_mw(&opts, options)?,
+        GameType::PM => gamez_pm(&opts, options)?,
+        GameType::CS => gamez_cs(&opts, options)?,
+    }
+    log::info!("GAMEZ: Wrote `{}`", opts.output);
+    Ok(())
+}
+
+fn gamez_mw(opts: &ZipOpts, options: ReadOptions) -> Result<()> {
+    let mut input = CountingReader::new(SeekableInput::open(&opts.input)?);
+    let (gamez, warnings) = gamez::mw::read_gamez(&mut input, options)
+        .inspect_err(|_| log_checkpoints(&input))
+        .context("Failed to read gamez data")?;
+    drop(input);
+
+    let mut out = Sink::create(
+        &opts.input,
+        &opts.output,
+        opts.dir,
+        opts.resume,
+        CompressionMethod::Deflated,
+        opts.compact,
+    )?;
+
+    out.write_json("metadata.json", &gamez.metadata)?;
+    out.write_json("textures.json", &gamez.textures)?;
+    out.write_json("materials.json", &gamez.materials)?;
+    out.write_json("meshes.json", &gamez.meshes)?;
+    out.write_json("nodes.json", &gamez.nodes)?;
+    write_warnings(&mut out, &warnings)?;
+
+    out.finish()?;
+    Ok(())
+}
+
+fn gamez_pm(opts: &ZipOpts, options: ReadOptions) -> Result<()> {
+    let mut input = CountingReader::new(SeekableInput::open(&opts.input)?);
+    let (gamez, warnings) = gamez::pm::read_gamez(&mut input, options)
+        .inspect_err(|_| log_checkpoints(&input))
+        .context("Failed to read gamez data")?;
+    drop(input);
+
+    let mut out = Sink::create(
+        &opts.input,
+        &opts.output,
+        opts.dir,
+        opts.resume,
+        CompressionMethod::Deflated,
+        opts.compact,
+    )?;
+
+    out.write_json("metadata.json", &gamez.metadata)?;
+    out.write_json("textures.json", &gamez.textures)?;
+    out.write_json("materials.json", &gamez.materials)?;
+    out.write_json("meshes.json", &gamez.meshes)?;
+    out.write_json("nodes.json", &gamez.nodes)?;
+    write_warnings(&mut out, &warnings)?;
+
+    out.finish()?;
+    Ok(())
+}
+
+fn gamez_cs(opts: &ZipOpts, options: ReadOptions) -> Result<()> {
+    let mut input = CountingReader::new(SeekableInput::open(&opts.input)?);
+    let (gamez, warnings) = gamez::cs::read_gamez(&mut input, options)
+        .inspect_err(|_| log_checkpoints(&input))
+        .context("Failed to read gamez data")?;
+    drop(input);
+
+    let mut out = Sink::create(
+        &opts.input,
+        &opts.output,
+        opts.dir,
+        opts.resume,
+        CompressionMethod::Deflated,
+        opts.compact,
+    )?;
+
+    out.write_json("metadata.json", &gamez.metadata)?;
+    out.write_json("textures.json", &gamez.textures)?;
+    out.write_json("materials.json", &gamez.materials)?;
+    out.write_json("meshes.json", &gamez.meshes)?;
+    out.write_json("nodes.json", &gamez.nodes)?;
+    write_warnings(&mut out, &warnings)?;
+
+    out.finish()?;
+    Ok(())
+}
+
+fn gamez_rc(opts: &ZipOpts, options: ReadOptions) -> Result<()> {
+    let mut input = CountingReader::new(SeekableInput::open(&opts.input)?);
+    let (gamez, warnings) = gamez::rc::read_gamez(&mut input, options)
+        .inspect_err(|_| log_checkpoints(&input))
+        .context("Failed to read gamez data")?;
+    drop(input);
+
+    let mut out = Sink::create(
+        &opts.input,
+        &opts.output,
+        opts.dir,
+        opts.resume,
+        CompressionMethod::Deflated,
+        opts.compact,
+    )?;
+
+    out.write_json("textures.json", &gamez.textures)?;
+    out.write_json("materials.json", &gamez.materials)?;
+    out.write_json("meshes.json", &gamez.meshes)?;
+    out.write_json("nodes.json", &gamez.nodes)?;
+    if let Some(texture_infos) = &gamez.texture_infos {
+        out.write_json("texture_infos.json", texture_infos)?;
+    }
+    write_warnings(&mut out, &warnings)?;
+
+    out.finish()?;
+    Ok(())
+}
+
+pub(crate) fn anim(opts: ZipOpts) -> Result<()> {
+    match opts.game {
+        GameType::MW => {}
+        GameType::PM => bail!("Pirate's Moon support for Anim isn't implemented yet"),
+        GameType::RC => bail!("Recoil support for Anim isn't implemented yet"),
+        GameType::CS => bail!("Crimson Skies support for Anim isn't implemented yet"),
+    }
+
+    log::info!("ANIM: Reading `{}` ({})", opts.input, opts.game);
+    let mut input = CountingReader::new(buf_reader(&opts.input)?);
+
+    let mut out = Sink::create(
+        &opts.input,
+        &opts.output,
+        opts.dir,
+        opts.resume,
+        CompressionMethod::Deflated,
+        opts.compact,
+    )?;
+    let metadata =
+        mech3ax_anim::mw::read_anim(&mut input, |name, anim_def| out.write_json(name, anim_def))
+            .context("Failed to read anim data")?;
+
+    out.write_json("metadata.json", &metadata)?;
+    out.finish()?;
+    log::info!("ANIM: Wrote `{}`", opts.output);
+    Ok(())
+}
+
+pub(crate) fn savegame(opts: ZipOpts) -> Result<()> {
+    let version = match opts.game {
+        GameType::MW => Version::One,
+        GameType::PM => bail!("Pirate's Moon support for Savegames isn't implemented yet"),
+        GameType::RC => bail!("Recoil support for Savegames isn't implemented yet"),
+        GameType::CS => bail!("Crimson Skies support for Savegames isn't implemented yet"),
+    };
+
+    log::info!("SAVEGAME: Reading `{}` ({})", opts.input, opts.game);
+    let out = Sink::create(
+        &opts.input,
+        &opts.output,
+        opts.dir,
+        opts.resume,
+        CompressionMethod::Deflated,
+        opts.compact,
+    )?;
+    _zarchive(
+        &opts.input,
+        &opts.output,
+        out,
+        version,
+        opts.keep_going,
+        "Failed to read savegame data",
+        |out, name, data, offset| {
+            let mut read = CountingReader::new(Cursor::new(data));
+            // translate to absolute offset
+            read.offset = offset;
+            match name {
+                "zSaveHeader" => {
+                    read_save_header(&mut read).context("Failed to read savegame header")
+                }
+                original => {
+                    let name = format!("{}.json", original);
+                    let value = read_activation(&mut read).with_context(|| {
+                        format!("Failed to read anim activation `{}`", original)
+                    })?;
+                    out.write_json(&name, &value)
+                }
+            }
+        },
+    )?;
+    log::info!("SAVEGAME: Wrote `{}`", opts.output);
+    Ok(())
+}
+
+pub(crate) fn zmap(opts: ZMapOpts) -> Result<()> {
+    match opts.game {
+        GameType::RC => {}
+        GameType::MW => bail!("MechWarrior 3 does not have zmap"),
+        GameType::PM => bail!("Pirate's Moon does not have zmap"),
+        GameType::CS => bail!("Crimson Skies does not have zmap"),
+    }
+
+    log::info!("ZMAP: Reading `{}`", opts.input);
+    let mut input = CountingReader::new(buf_reader(opts.input)?);
+    let map = mech3ax_zmap::read_map(&mut input).context("Failed to read zmap data")?;
+    let contents = to_json(&map, opts.compact)?;
+    std::fs::write(&opts.output, contents).context("Failed to write output")?;
+    log::info!("ZMAP: Wrote `{}`", opts.output);
+    Ok(())
+}
+
+pub(crate) fn license() -> Result<()> {
+    print!(
+        "mech3ax extracts assets from certain games developed by Zipper
+Interactive (tm).
+
+Zipper Interactive (tm) was trademark or registered trademark
+of Sony Computer Entertainment America LLC. This project is not
+endorsed by or affiliated with any previous or current
+rightsholders.
+
+Copyright (C) 2015-2024  Toby Fleming
+
+Licensed under the European Union Public Licence (EUPL) 1.2.
+"
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,306 @@
+use super::checksum::{Checksums, CHECKSUMS_NAME};
+use super::{buf_writer, to_json};
+use crate::raw::entry_path;
+use eyre::{Context as _, Result};
+use std::collections::HashSet;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufWriter, Seek, Write};
+use std::path::{Path, PathBuf};
+use zip::write::{SimpleFileOptions, ZipWriter};
+use zip::{CompressionMethod, DateTime};
+
+/// Where the extracted files are written.
+pub(crate) trait OutputSink {
+    /// What is left once all files are written, e.g. the Zip's writer.
+    type Finished;
+
+    /// Write the data as a file.
+    fn write_bytes(&mut self, name: &str, data: &[u8]) -> Result<()>;
+
+    /// Write the value as a JSON file.
+    fn write_json<T>(&mut self, name: &str, value: &T) -> Result<()>
+    where
+        T: serde::ser::Serialize;
+
+    /// Whether the archive entry was written by a previous run, and can be
+    /// skipped.
+    fn is_done(&self, _entry: &str) -> bool {
+        false
+    }
+
+    /// The number of files written so far.
+    fn written(&self) -> usize;
+
+    /// Record that the archive entry was written.
+    fn mark_done(&mut self, _entry: &str) -> Result<()> {
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Self::Finished>;
+}
+
+/// Write the files into a Zip.
+pub(crate) struct ZipSink<W: Write + Seek> {
+    zip: ZipWriter<W>,
+    method: CompressionMethod,
+    compact: bool,
+    written: usize,
+}
+
+impl<W: Write + Seek> ZipSink<W> {
+    /// Files written as bytes use the compression method, JSON files are
+    /// always deflated.
+    pub(crate) fn new(write: W, method: CompressionMethod, compact: bool) -> Self {
+        Self {
+            zip: ZipWriter::new(write),
+            method,
+            compact,
+            written: 0,
+        }
+    }
+
+    fn write(&mut self, method: CompressionMethod, name: &str, data: &[u8]) -> Result<()> {
+        // a fixed modification time (1980-01-01, the earliest possible), so the
+        // same input always produces the same output
+        let options = SimpleFileOptions::default()
+            .compression_method(method)
+            .last_modified_time(DateTime::default());
+        self.zip
+            .start_file(name, options)
+            .with_context(|| format!("Failed to write `{}` to Zip", name))?;
+        self.zip
+            .write_all(data)
+            .with_context(|| format!("Failed to write `{}` to Zip", name))?;
+        self.written += 1;
+        Ok(())
+    }
+}
+
+impl<W: Write + Seek> OutputSink for ZipSink<W> {
+    type Finished = W;
+
+    fn write_bytes(&mut self, name: &str, data: &[u8]) -> Result<()> {
+        self.write(self.method, name, data)
+    }
+
+    fn write_json<T>(&mut self, name: &str, value: &T) -> Result<()>
+    where
+        T: serde::ser::Serialize,
+    {
+        let data = to_json(value, self.compact)?;
+        self.write(CompressionMethod::Deflated, name, &data)
+    }
+
+    fn written(&self) -> usize {
+        self.written
+    }
+
+    fn finish(self) -> Result<W> {
+        Ok(self.zip.finish()?)
+    }
+}
+
+/// The file in the output directory listing the archive entries that were
+/// written, one per line.
+pub(crate) const PROGRESS_NAME: &str = ".done";
+
+/// Write the files into a directory.
+///
+/// Written archive entries are recorded, so an interrupted run can be
+/// resumed.
+pub(crate) struct DirSink {
+    dir: PathBuf,
+    compact: bool,
+    done: HashSet<String>,
+    progress: Option<File>,
+    written: usize,
+}
+
+impl DirSink {
+    /// Create the directory, if it doesn't exist. When resuming, the entries
+    /// written by the previous run are skipped, otherwise they are forgotten.
+    pub(crate) fn create(dir: impl Into<PathBuf>, compact: bool, resume: bool) -> Result<Self> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir).context("Failed to create output directory")?;
+
+        let path = dir.join(PROGRESS_NAME);
+        let done = if resume {
+            match std::fs::read_to_string(&path) {
+                Ok(contents) => contents.lines().map(str::to_string).collect(),
+                Err(e) if e.kind() == io::ErrorKind::NotFound => HashSet::new(),
+                Err(e) => return Err(e).context("Failed to read resume progress"),
+            }
+        } else {
+            match std::fs::remove_file(&path) {
+                Ok(()) => {}
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+                Err(e) => return Err(e).context("Failed to remove resume progress"),
+            }
+            HashSet::new()
+        };
+        Ok(Self {
+            dir,
+            compact,
+            done,
+            progress: None,
+            written: 0,
+        })
+    }
+}
+
+impl OutputSink for DirSink {
+    type Finished = ();
+
+    fn write_bytes(&mut self, name: &str, data: &[u8]) -> Result<()> {
+        let path = entry_path(&self.dir, name)?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).context("Failed to create output directory")?;
+        }
+        std::fs::write(&path, data).with_context(|| format!("Failed to write `{}`", name))?;
+        self.written += 1;
+        Ok(())
+    }
+
+    fn write_json<T>(&mut self, name: &str, value: &T) -> Result<()>
+    where
+        T: serde::ser::Serialize,
+    {
+        let data = to_json(value, self.compact)?;
+        self.write_bytes(name, &data)
+    }
+
+    fn written(&self) -> usize {
+        self.written
+    }
+
+    fn is_done(&self, entry: &str) -> bool {
+        self.done.contains(entry)
+    }
+
+    fn mark_done(&mut self, entry: &str) -> Result<()> {
+        let progress = match &mut self.progress {
+            Some(progress) => progress,
+            None => {
+                // unbuffered, so the progress survives an interrupted run
+                let progress = OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(self.dir.join(PROGRESS_NAME))
+                    .context("Failed to open resume progress")?;
+                self.progress.insert(progress)
+            }
+        };
+        writeln!(progress, "{}", entry).context("Failed to write resume progress")?;
+        self.done.insert(entry.to_string());
+        Ok(())
+    }
+
+    fn finish(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// A Zip or a directory.
+enum SinkKind {
+    Zip(Box<ZipSink<BufWriter<File>>>),
+    Dir(DirSink),
+}
+
+/// A Zip or a directory, as chosen on the command line.
+///
+/// The checksums of the source and every file written are recorded in
+/// `checksums.json`. When resuming, the checksums of files written by a
+/// previous run are kept.
+pub(crate) struct Sink {
+    kind: SinkKind,
+    checksums: Checksums,
+}
+
+impl Sink {
+    /// Resuming is only possible with a directory.
+    pub(crate) fn create(
+        source: &str,
+        path: &str,
+        dir: bool,
+        resume: bool,
+        method: CompressionMethod,
+        compact: bool,
+    ) -> Result<Self> {
+        let mut checksums = Checksums::new(source)?;
+        let kind = if dir {
+            if resume {
+                // files skipped by this run keep their previous checksums
+                checksums.merge_previous(&Path::new(path).join(CHECKSUMS_NAME))?;
+            }
+            SinkKind::Dir(DirSink::create(path, compact, resume)?)
+        } else {
+            let write = buf_writer(path)?;
+            SinkKind::Zip(Box::new(ZipSink::new(write, method, compact)))
+        };
+        Ok(Self { kind, checksums })
+    }
+
+    fn compact(&self) -> bool {
+        match &self.kind {
+            SinkKind::Zip(zip) => zip.compact,
+            SinkKind::Dir(dir) => dir.compact,
+        }
+    }
+}
+
+impl OutputSink for Sink {
+    type Finished = ();
+
+    fn write_bytes(&mut self, name: &str, data: &[u8]) -> Result<()> {
+        match &mut self.kind {
+            SinkKind::Zip(zip) => zip.write_bytes(name, data)?,
+            SinkKind::Dir(dir) => dir.write_bytes(name, data)?,
+        }
+        self.checksums.add(name, data);
+        Ok(())
+    }
+
+    fn write_json<T>(&mut self, name: &str, value: &T) -> Result<()>
+    where
+        T: serde::ser::Serialize,
+    {
+        let data = to_json(value, self.compact())?;
+        self.write_bytes(name, &data)
+    }
+
+    fn written(&self) -> usize {
+        match &self.kind {
+            SinkKind::Zip(zip) => zip.written(),
+            SinkKind::Dir(dir) => dir.written(),
+        }
+    }
+
+    fn is_done(&self, entry: &str) -> bool {
+        match &self.kind {
+            SinkKind::Zip(zip) => zip.is_done(entry),
+            SinkKind::Dir(dir) => dir.is_done(entry),
+        }
+    }
+
+    fn mark_done(&mut self, entry: &str) -> Result<()> {
+        match &mut self.kind {
+            SinkKind::Zip(zip) => zip.mark_done(entry),
+            SinkKind::Dir(dir) => dir.mark_done(entry),
+        }
+    }
+
+    fn finish(self) -> Result<()> {
+        let Self {
+            mut kind,
+            checksums,
+        } = self;
+        match &mut kind {
+            SinkKind::Zip(zip) => zip.write_json(CHECKSUMS_NAME, &checksums)?,
+            SinkKind::Dir(dir) => dir.write_json(CHECKSUMS_NAME, &checksums)?,
+        }
+        match kind {
+            SinkKind::Zip(zip) => zip.finish().map(drop),
+            SinkKind::Dir(dir) => dir.finish(),
+        }
+    }
+}
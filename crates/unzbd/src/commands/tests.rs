@@ -0,0 +1,512 @@
+use super::checksum::CHECKSUMS_NAME;
+use super::sink::{DirSink, ZipSink, PROGRESS_NAME};
+use super::*;
+use mech3ax_api_types::archive::{ArchiveEntry, ArchiveEntryInfo, ArchiveEntryInfoInvalid};
+use mech3ax_api_types::gamez::mechlib::ModelMw;
+use mech3ax_api_types::nodes::BoundingBox;
+use mech3ax_archive::write_archive;
+use mech3ax_common::io_ext::CountingWriter;
+use mech3ax_common::test_utils::TempDir;
+use mech3ax_gamez::mechlib::{write_format, write_materials, write_version};
+use mech3ax_reader::write_reader;
+use serde_json::json;
+use sha2::Digest as _;
+use std::collections::BTreeMap;
+use zip::{DateTime, ZipArchive};
+
+fn archive(names: &[&str]) -> Vec<u8> {
+    archive_with(names, |name| name.as_bytes().to_vec())
+}
+
+fn archive_with(names: &[&str], data: impl Fn(&str) -> Vec<u8>) -> Vec<u8> {
+    let entries = names
+        .iter()
+        .map(|name| ArchiveEntry {
+            name: name.to_string(),
+            rename: None,
+            flags: 0,
+            info: ArchiveEntryInfo::Invalid(ArchiveEntryInfoInvalid {
+                comment: vec![0; 64],
+                filetime: 0,
+            }),
+        })
+        .collect::<Vec<_>>();
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    write_archive::<_, _, eyre::Report>(
+        &mut write,
+        &entries,
+        |name, _offset| Ok(data(name)),
+        Version::One,
+    )
+    .unwrap();
+    write.into_inner()
+}
+
+fn extract_into<S>(out: S, keep_going: bool) -> Result<(S::Finished, Vec<EntryError>)>
+where
+    S: OutputSink,
+{
+    let data = archive(&["alpha", "bravo", "charlie"]);
+    let mut input = CountingReader::new(Cursor::new(data));
+    zarchive(
+        &mut input,
+        out,
+        Version::One,
+        keep_going,
+        "Failed to read test data",
+        |out, name, data, _offset| {
+            if name == "bravo" {
+                bail!("bad entry");
+            }
+            out.write_bytes(name, &data)
+        },
+    )
+}
+
+fn extract(keep_going: bool) -> Result<(Vec<u8>, Vec<EntryError>)> {
+    let out = ZipSink::new(Cursor::new(Vec::new()), CompressionMethod::Stored, false);
+    let (output, errors) = extract_into(out, keep_going)?;
+    Ok((output.into_inner(), errors))
+}
+
+#[test]
+fn zarchive_stops_on_first_error() {
+    assert!(extract(false).is_err());
+}
+
+#[test]
+fn zarchive_keep_going() {
+    let (output, errors) = extract(true).unwrap();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].name, "bravo");
+    assert_eq!(errors[0].error, "bad entry");
+
+    let zip = ZipArchive::new(Cursor::new(output)).unwrap();
+    let mut names = zip.file_names().collect::<Vec<_>>();
+    names.sort();
+    assert_eq!(names, ["alpha", "charlie", "errors.json", "manifest.json"]);
+}
+
+#[test]
+fn zarchive_output_is_reproducible() {
+    let (first, _errors) = extract(true).unwrap();
+    let (second, _errors) = extract(true).unwrap();
+    assert_eq!(first, second);
+
+    let mut zip = ZipArchive::new(Cursor::new(first)).unwrap();
+    let file = zip.by_name("alpha").unwrap();
+    assert_eq!(file.last_modified(), Some(DateTime::default()));
+}
+
+#[test]
+fn sink_resume_keeps_previous_checksums() {
+    let tmp = TempDir::new("unzbd-checksums-resume");
+    let dir = tmp.path();
+    let source = dir.join("sounds.zbd");
+    std::fs::write(&source, archive(&["alpha"])).unwrap();
+    let out_dir = dir.join("out");
+
+    let run = |resume: bool, name: &str| {
+        let mut out = Sink::create(
+            source.to_str().unwrap(),
+            out_dir.to_str().unwrap(),
+            true,
+            resume,
+            CompressionMethod::Stored,
+            false,
+        )
+        .unwrap();
+        out.write_bytes(name, name.as_bytes()).unwrap();
+        out.mark_done(name).unwrap();
+        out.finish().unwrap();
+        let data = std::fs::read(out_dir.join(CHECKSUMS_NAME)).unwrap();
+        let checksums: Value = serde_json::from_slice(&data).unwrap();
+        let entries = checksums["entries"].as_object().unwrap();
+        entries.keys().cloned().collect::<Vec<_>>()
+    };
+
+    assert_eq!(run(false, "alpha"), ["alpha"]);
+    assert_eq!(run(true, "bravo"), ["alpha", "bravo"]);
+    // without resuming, the previous checksums are forgotten
+    assert_eq!(run(false, "charlie"), ["charlie"]);
+}
+
+fn zip_files(data: Vec<u8>) -> BTreeMap<String, Vec<u8>> {
+    let mut zip = ZipArchive::new(Cursor::new(data)).unwrap();
+    (0..zip.len())
+        .map(|index| {
+            let mut file = zip.by_index(index).unwrap();
+            let mut data = Vec::new();
+            file.read_to_end(&mut data).unwrap();
+            (file.name().to_string(), data)
+        })
+        .collect()
+}
+
+/// The files in the directory, except the resume progress.
+fn dir_files(dir: &Path) -> BTreeMap<String, Vec<u8>> {
+    std::fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| {
+            let entry = entry.unwrap();
+            let name = entry.file_name().into_string().unwrap();
+            (name, std::fs::read(entry.path()).unwrap())
+        })
+        .filter(|(name, _data)| name != PROGRESS_NAME)
+        .collect()
+}
+
+#[test]
+fn zarchive_zip_and_dir_sinks_write_the_same_files() {
+    let (output, _errors) = extract(true).unwrap();
+    let expected = zip_files(output);
+
+    let tmp = TempDir::new("unzbd-zarchive");
+    let dir = tmp.path();
+    let out = DirSink::create(dir, false, false).unwrap();
+    let ((), errors) = extract_into(out, true).unwrap();
+    assert_eq!(errors.len(), 1);
+    let actual = dir_files(dir);
+
+    assert_eq!(actual, expected);
+}
+
+fn extract_names(out: DirSink) -> Vec<String> {
+    let data = archive(&["alpha", "bravo", "charlie"]);
+    let mut input = CountingReader::new(Cursor::new(data));
+    let mut names = Vec::new();
+    zarchive(
+        &mut input,
+        out,
+        Version::One,
+        true,
+        "Failed to read test data",
+        |out, name, data, _offset| {
+            names.push(name.to_string());
+            if name == "bravo" {
+                bail!("bad entry");
+            }
+            out.write_bytes(name, &data)
+        },
+    )
+    .unwrap();
+    names
+}
+
+#[test]
+fn zarchive_resume_skips_done_entries() {
+    let tmp = TempDir::new("unzbd-resume");
+    let dir = tmp.path();
+    std::fs::write(dir.join(PROGRESS_NAME), "alpha\n").unwrap();
+
+    let out = DirSink::create(dir, false, true).unwrap();
+    let names = extract_names(out);
+    assert_eq!(names, ["bravo", "charlie"]);
+    // failed entries aren't done, so they are retried
+    let progress = std::fs::read_to_string(dir.join(PROGRESS_NAME)).unwrap();
+    assert_eq!(progress, "alpha\ncharlie\n");
+
+    let out = DirSink::create(dir, false, true).unwrap();
+    let names = extract_names(out);
+    assert_eq!(names, ["bravo"]);
+
+    // without resuming, the progress is forgotten
+    let out = DirSink::create(dir, false, false).unwrap();
+    let names = extract_names(out);
+    assert_eq!(names, ["alpha", "bravo", "charlie"]);
+}
+
+#[test]
+fn sink_records_checksums() {
+    let tmp = TempDir::new("unzbd-checksums");
+    let dir = tmp.path();
+    let source = dir.join("sounds.zbd");
+    std::fs::write(&source, archive(&["alpha"])).unwrap();
+    let out_dir = dir.join("out");
+
+    let mut out = Sink::create(
+        source.to_str().unwrap(),
+        out_dir.to_str().unwrap(),
+        true,
+        false,
+        CompressionMethod::Stored,
+        false,
+    )
+    .unwrap();
+    out.write_bytes("alpha", b"alpha").unwrap();
+    out.write_json("manifest.json", &json!(["alpha"])).unwrap();
+    out.finish().unwrap();
+
+    let mut files = dir_files(&out_dir);
+    let source_data = std::fs::read(&source).unwrap();
+
+    let checksums: Value = serde_json::from_slice(&files.remove(CHECKSUMS_NAME).unwrap()).unwrap();
+    let hex = |data: &[u8]| format!("{:x}", sha2::Sha256::digest(data));
+    assert_eq!(checksums["source"]["name"], "sounds.zbd");
+    assert_eq!(checksums["source"]["sha256"], hex(&source_data));
+    let entries = checksums["entries"].as_object().unwrap();
+    assert_eq!(entries.len(), files.len());
+    for (name, data) in &files {
+        assert_eq!(entries[name], hex(data), "{}", name);
+    }
+}
+
+#[test]
+fn compact_and_pretty_json_read_back_identically() {
+    let value = vec![
+        Vec3::DEFAULT,
+        Vec3 {
+            x: 1.0,
+            y: -2.5,
+            z: 3.0,
+        },
+    ];
+    let pretty = to_json(&value, false).unwrap();
+    let compact = to_json(&value, true).unwrap();
+    assert!(compact.len() < pretty.len());
+    assert!(!compact.contains(&b'\n'));
+
+    let pretty: Vec<Vec3> = serde_json::from_slice(&pretty).unwrap();
+    let compact: Vec<Vec3> = serde_json::from_slice(&compact).unwrap();
+    assert_eq!(pretty, value);
+    assert_eq!(compact, value);
+}
+
+fn reader_data(name: &str) -> Vec<u8> {
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    write_reader(&mut write, &json!(["name", name, "count", 3])).unwrap();
+    write.into_inner()
+}
+
+#[test]
+fn ndjson_archive_writes_one_line_per_entry() {
+    let names = ["alpha.zrd", "bravo.zrd", "charlie.zrd"];
+    let data = archive_with(&names, |name| {
+        if name == "bravo.zrd" {
+            // not valid reader data
+            vec![0xFF; 4]
+        } else {
+            reader_data(name)
+        }
+    });
+
+    let mut input = CountingReader::new(Cursor::new(data.clone()));
+    let err = ndjson_archive(
+        &mut input,
+        Vec::new(),
+        Version::One,
+        false,
+        "Failed to read test data",
+        |write, name, data, offset| {
+            let root = read_reader_entry(name, data, offset)?;
+            ndjson_write(write, name, &root)
+        },
+    )
+    .unwrap_err();
+    assert_eq!(format!("{}", err), "Failed to read test data");
+
+    let mut input = CountingReader::new(Cursor::new(data));
+    let (output, errors) = ndjson_archive(
+        &mut input,
+        Vec::new(),
+        Version::One,
+        true,
+        "Failed to read test data",
+        |write, name, data, offset| {
+            let root = read_reader_entry(name, data, offset)?;
+            ndjson_write(write, name, &root)
+        },
+    )
+    .unwrap();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].name, "bravo.zrd");
+
+    let output = String::from_utf8(output).unwrap();
+    let lines = output.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), names.len() - errors.len());
+    for (line, name) in lines.iter().zip(["alpha.zrd", "charlie.zrd"]) {
+        let value: Value = serde_json::from_str(line).unwrap();
+        assert_eq!(
+            value,
+            json!({"name": name, "data": ["name", name, "count", 3]})
+        );
+    }
+}
+
+fn mechlib_model(name: &str) -> Vec<u8> {
+    let mut model: ModelMw = serde_json::from_value(json!({
+        "nodes": [{"Object3d": {
+            "name": name,
+            "transformation": null,
+            "matrix_signs": 0,
+            "flags": {"unk25": true},
+            "zone_id": 255,
+            "area_partition": null,
+            "mesh_index": -1,
+            "parent": null,
+            "children": [],
+            "data_ptr": 0x1000,
+            "parent_array_ptr": 0,
+            "children_array_ptr": 0,
+            "unk116": BoundingBox::EMPTY,
+            "unk140": BoundingBox::EMPTY,
+            "unk164": BoundingBox::EMPTY,
+        }}],
+        "meshes": [],
+        "mesh_ptrs": [],
+    }))
+    .unwrap();
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    mechlib::mw::write_model(&mut write, &mut model).unwrap();
+    write.into_inner()
+}
+
+fn mechlib_opts(model: Option<&str>, with_bounds: bool) -> MechlibOpts {
+    MechlibOpts {
+        zip: ZipOpts {
+            game: GameType::MW,
+            input: String::new(),
+            output: String::new(),
+            dir: false,
+            resume: false,
+            keep_going: false,
+            compact: false,
+        },
+        model: model.map(str::to_string),
+        with_bounds,
+    }
+}
+
+#[test]
+fn mechlib_extracts_one_model() {
+    let names = ["format", "version", "materials", "torso.flt", "legs.flt"];
+    let data = archive_with(&names, |name| {
+        let mut write = CountingWriter::new(Vec::new(), 0);
+        match name {
+            "format" => write_format(&mut write).unwrap(),
+            "version" => write_version(&mut write, GameType::MW).unwrap(),
+            "materials" => write_materials(&mut write, &[]).unwrap(),
+            "torso.flt" => return mechlib_model("torso"),
+            // not a valid model, so this fails if it isn't skipped
+            _ => return vec![0xFF; 4],
+        }
+        write.into_inner()
+    });
+
+    for model in ["torso.flt", "torso"] {
+        let mut input = CountingReader::new(Cursor::new(data.clone()));
+        let out = ZipSink::new(Cursor::new(Vec::new()), CompressionMethod::Deflated, false);
+        let opts = mechlib_opts(Some(model), false);
+        let (output, errors) =
+            mechlib_archive(&mut input, out, GameType::MW, Version::One, &opts).unwrap();
+        assert!(errors.is_empty());
+
+        let mut zip = ZipArchive::new(Cursor::new(output.into_inner())).unwrap();
+        let mut names = zip.file_names().collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(names, ["manifest.json", "materials.json", "torso.json"]);
+        let model: ModelMw = serde_json::from_reader(zip.by_name("torso.json").unwrap()).unwrap();
+        let NodeMw::Object3d(object3d) = &model.nodes[0] else {
+            panic!("expected an Object3d node");
+        };
+        assert_eq!(object3d.name, "torso");
+    }
+
+    let mut input = CountingReader::new(Cursor::new(data));
+    let out = ZipSink::new(Cursor::new(Vec::new()), CompressionMethod::Deflated, false);
+    let opts = mechlib_opts(Some("arms.flt"), false);
+    let err = mechlib_archive(&mut input, out, GameType::MW, Version::One, &opts).unwrap_err();
+    assert_eq!(format!("{}", err), "Model `arms.flt` not found in mechlib");
+}
+
+fn mechlib_data() -> Vec<u8> {
+    let names = ["format", "version", "materials", "torso.flt"];
+    archive_with(&names, |name| {
+        let mut write = CountingWriter::new(Vec::new(), 0);
+        match name {
+            "format" => write_format(&mut write).unwrap(),
+            "version" => write_version(&mut write, GameType::MW).unwrap(),
+            "materials" => write_materials(&mut write, &[]).unwrap(),
+            _ => return mechlib_model("torso"),
+        }
+        write.into_inner()
+    })
+}
+
+#[test]
+fn mechlib_with_bounds() {
+    let data = mechlib_data();
+    let mut input = CountingReader::new(Cursor::new(data));
+    let out = ZipSink::new(Cursor::new(Vec::new()), CompressionMethod::Deflated, false);
+    let opts = mechlib_opts(None, true);
+    let (output, _errors) =
+        mechlib_archive(&mut input, out, GameType::MW, Version::One, &opts).unwrap();
+
+    let mut zip = ZipArchive::new(Cursor::new(output.into_inner())).unwrap();
+    let bounds: Value = serde_json::from_reader(zip.by_name("torso.bounds.json").unwrap()).unwrap();
+    // the model has no mesh
+    assert_eq!(bounds["empty"], json!(true));
+}
+
+#[test]
+fn mechlib_zip_and_dir_sinks_write_the_same_files() {
+    let opts = mechlib_opts(None, true);
+
+    let mut input = CountingReader::new(Cursor::new(mechlib_data()));
+    let out = ZipSink::new(Cursor::new(Vec::new()), CompressionMethod::Deflated, false);
+    let (output, _errors) =
+        mechlib_archive(&mut input, out, GameType::MW, Version::One, &opts).unwrap();
+    let expected = zip_files(output.into_inner());
+
+    let tmp = TempDir::new("unzbd-mechlib");
+    let dir = tmp.path();
+    let mut input = CountingReader::new(Cursor::new(mechlib_data()));
+    let out = DirSink::create(dir, false, false).unwrap();
+    mechlib_archive(&mut input, out, GameType::MW, Version::One, &opts).unwrap();
+    let actual = dir_files(dir);
+
+    assert_eq!(
+        actual.keys().collect::<Vec<_>>(),
+        [
+            "manifest.json",
+            "materials.json",
+            "torso.bounds.json",
+            "torso.json"
+        ]
+    );
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn mechlib_resume_only_marks_written_models_done() {
+    let names = ["format", "version", "materials", "torso.flt", "legs.flt"];
+    let data = archive_with(&names, |name| {
+        let mut write = CountingWriter::new(Vec::new(), 0);
+        match name {
+            "format" => write_format(&mut write).unwrap(),
+            "version" => write_version(&mut write, GameType::MW).unwrap(),
+            "materials" => write_materials(&mut write, &[]).unwrap(),
+            "torso.flt" => return mechlib_model("torso"),
+            _ => return mechlib_model("legs"),
+        }
+        write.into_inner()
+    });
+    let tmp = TempDir::new("unzbd-mechlib-resume");
+    let dir = tmp.path();
+
+    let mut input = CountingReader::new(Cursor::new(data.clone()));
+    let out = DirSink::create(dir, false, true).unwrap();
+    let opts = mechlib_opts(Some("torso.flt"), false);
+    mechlib_archive(&mut input, out, GameType::MW, Version::One, &opts).unwrap();
+    // the skipped model isn't done
+    let progress = std::fs::read_to_string(dir.join(PROGRESS_NAME)).unwrap();
+    assert_eq!(progress, "materials\ntorso.flt\n");
+
+    // so resuming without `--model` still writes it
+    let mut input = CountingReader::new(Cursor::new(data));
+    let out = DirSink::create(dir, false, true).unwrap();
+    let opts = mechlib_opts(None, false);
+    mechlib_archive(&mut input, out, GameType::MW, Version::One, &opts).unwrap();
+    let files = dir_files(dir);
+    assert!(files.contains_key("legs.json"), "{:?}", files.keys());
+}
@@ -0,0 +1,52 @@
+use eyre::Result;
+use image::{DynamicImage, ImageFormat};
+use mech3ax_api_types::image::{TextureFileFormat, TextureInfo};
+use mech3ax_image::encode_png;
+use std::io::Cursor;
+
+/// The image format textures are extracted as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub(crate) enum ImageFileFormat {
+    Png,
+    Tga,
+    Bmp,
+}
+
+impl ImageFileFormat {
+    fn image_format(self) -> ImageFormat {
+        match self {
+            Self::Png => ImageFormat::Png,
+            Self::Tga => ImageFormat::Tga,
+            Self::Bmp => ImageFormat::Bmp,
+        }
+    }
+
+    /// The format recorded in the manifest. PNG is the default, and isn't
+    /// recorded so the manifest stays the same as before.
+    pub(crate) fn manifest_format(self) -> Option<TextureFileFormat> {
+        match self {
+            Self::Png => None,
+            Self::Tga => Some(TextureFileFormat::Tga),
+            Self::Bmp => Some(TextureFileFormat::Bmp),
+        }
+    }
+
+    /// The file extension of images in this format.
+    pub(crate) fn extension(self) -> &'static str {
+        self.image_format().extensions_str()[0]
+    }
+
+    /// Encode the texture's image. Only PNGs can have the original texture
+    /// format embedded, for other formats `rezbd` uses the manifest.
+    pub(crate) fn encode(self, image: &DynamicImage, info: &TextureInfo) -> Result<Vec<u8>> {
+        if self == Self::Png {
+            return Ok(encode_png(image, info)?);
+        }
+        let mut buf = Cursor::new(Vec::new());
+        image.write_to(&mut buf, self.image_format())?;
+        Ok(buf.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,83 @@
+use super::*;
+use image::{Rgb, RgbImage, Rgba, RgbaImage};
+use mech3ax_api_types::image::{TextureAlpha, TexturePalette, TextureStretch};
+use mech3ax_image::decode_png_format;
+
+const FORMATS: [ImageFileFormat; 3] = [
+    ImageFileFormat::Png,
+    ImageFileFormat::Tga,
+    ImageFileFormat::Bmp,
+];
+
+fn info(alpha: TextureAlpha) -> TextureInfo {
+    TextureInfo {
+        name: "texture".to_string(),
+        index: None,
+        rename: None,
+        alpha,
+        width: 5,
+        height: 3,
+        stretch: TextureStretch::None,
+        image_loaded: false,
+        alpha_loaded: false,
+        palette_loaded: false,
+        palette: TexturePalette::None,
+    }
+}
+
+fn rgb() -> DynamicImage {
+    let image = RgbImage::from_fn(5, 3, |x, y| {
+        // Cast safety: the image is tiny
+        Rgb([(x * 50) as u8, (y * 80) as u8, ((x + y) * 20) as u8])
+    });
+    DynamicImage::ImageRgb8(image)
+}
+
+fn rgba() -> DynamicImage {
+    let image = RgbaImage::from_fn(5, 3, |x, y| {
+        // Cast safety: the image is tiny
+        Rgba([(x * 50) as u8, (y * 80) as u8, 7, ((x + y) * 40) as u8])
+    });
+    DynamicImage::ImageRgba8(image)
+}
+
+fn round_trip(format: ImageFileFormat, image: &DynamicImage, alpha: TextureAlpha) -> DynamicImage {
+    let buf = format.encode(image, &info(alpha)).unwrap();
+    image::load_from_memory_with_format(&buf, format.image_format()).unwrap()
+}
+
+#[test]
+fn each_format_round_trips_rgb() {
+    let image = rgb();
+    for format in FORMATS {
+        let decoded = round_trip(format, &image, TextureAlpha::None);
+        assert_eq!(decoded.to_rgb8(), image.to_rgb8(), "{:?}", format);
+    }
+}
+
+#[test]
+fn each_format_round_trips_rgba() {
+    let image = rgba();
+    for format in FORMATS {
+        let decoded = round_trip(format, &image, TextureAlpha::Full);
+        assert_eq!(decoded.to_rgba8(), image.to_rgba8(), "{:?}", format);
+    }
+}
+
+#[test]
+fn png_embeds_texture_format() {
+    let image = rgb();
+    let info = info(TextureAlpha::None);
+    let png = ImageFileFormat::Png.encode(&image, &info).unwrap();
+    assert!(decode_png_format(&png).unwrap().is_some());
+    assert_eq!(ImageFileFormat::Png.manifest_format(), None);
+
+    assert_eq!(
+        ImageFileFormat::Tga.manifest_format(),
+        Some(TextureFileFormat::Tga)
+    );
+    assert_eq!(
+        ImageFileFormat::Bmp.manifest_format(),
+        Some(TextureFileFormat::Bmp)
+    );
+}
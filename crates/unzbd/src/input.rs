@@ -0,0 +1,71 @@
+use eyre::{Context as _, Result};
+use std::fs::File;
+use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// An input file that is read with random seeks (e.g. GameZ files).
+///
+/// With the `memmap` feature, the file is memory-mapped, so seeking doesn't
+/// discard and refill the read buffer. If the file can't be mapped, it falls
+/// back to a buffered reader.
+pub(crate) enum SeekableInput {
+    #[cfg(feature = "memmap")]
+    Mapped(std::io::Cursor<memmap2::Mmap>),
+    Buffered(BufReader<File>),
+}
+
+impl SeekableInput {
+    pub(crate) fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let file = File::open(path).context("Failed to open input")?;
+        Ok(Self::new(file))
+    }
+
+    #[cfg(feature = "memmap")]
+    fn new(file: File) -> Self {
+        // SAFETY: the input file must not be modified while it is mapped.
+        // this is the same expectation as for any other reader.
+        match unsafe { memmap2::Mmap::map(&file) } {
+            Ok(mmap) => Self::Mapped(std::io::Cursor::new(mmap)),
+            Err(e) => {
+                log::warn!("Failed to map input, falling back to buffered: {}", e);
+                Self::Buffered(BufReader::new(file))
+            }
+        }
+    }
+
+    #[cfg(not(feature = "memmap"))]
+    fn new(file: File) -> Self {
+        Self::Buffered(BufReader::new(file))
+    }
+}
+
+impl Read for SeekableInput {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match self {
+            #[cfg(feature = "memmap")]
+            Self::Mapped(cursor) => cursor.read(buf),
+            Self::Buffered(reader) => reader.read(buf),
+        }
+    }
+
+    #[inline]
+    fn read_exact(&mut self, buf: &mut [u8]) -> std::io::Result<()> {
+        match self {
+            #[cfg(feature = "memmap")]
+            Self::Mapped(cursor) => cursor.read_exact(buf),
+            Self::Buffered(reader) => reader.read_exact(buf),
+        }
+    }
+}
+
+impl Seek for SeekableInput {
+    #[inline]
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        match self {
+            #[cfg(feature = "memmap")]
+            Self::Mapped(cursor) => cursor.seek(pos),
+            Self::Buffered(reader) => reader.seek(pos),
+        }
+    }
+}
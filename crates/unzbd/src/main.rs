@@ -1,10 +1,20 @@
+mod all;
+mod anim_check;
+mod annotate;
+mod check;
 mod commands;
+mod images;
+mod input;
+mod raw;
+mod schema;
+mod stats;
+mod tree;
 
 use clap::Parser as _;
 use env_logger::Env;
 use eyre::Result;
 use mech3ax_archive::{Mode, Version};
-use mech3ax_common::GameType;
+use mech3ax_common::{GameType, ReadOptions};
 use mech3ax_version::VERSION;
 
 #[derive(clap::Parser)]
@@ -55,33 +65,145 @@ impl Into<GameType> for Game {
 struct ZipArgs {
     #[clap(help = "The source ZBD path")]
     input: String,
-    #[clap(help = "The destination ZIP path (will be overwritten)")]
+    #[clap(help = "The destination ZIP path, or directory with `--dir` (will be overwritten)")]
     output: String,
+    #[clap(
+        long = "dir",
+        help = "Write the files into a directory, instead of a ZIP"
+    )]
+    dir: bool,
+    #[clap(
+        long = "resume",
+        help = "Skip archive entries written to the directory by a previous run",
+        requires = "dir"
+    )]
+    resume: bool,
+    #[clap(
+        long = "keep-going",
+        help = "Continue past archive entries that fail to read, and list them in 'errors.json'"
+    )]
+    keep_going: bool,
+    #[clap(
+        long = "compact",
+        help = "Write compact JSON instead of pretty-printed JSON"
+    )]
+    compact: bool,
 }
 
 impl ZipArgs {
     fn opts(self, game: GameType) -> Result<ZipOpts> {
-        let Self { input, output } = self;
+        let Self {
+            input,
+            output,
+            dir,
+            resume,
+            keep_going,
+            compact,
+        } = self;
         Ok(ZipOpts {
             game,
             input,
             output,
+            dir,
+            resume,
+            keep_going,
+            compact,
+        })
+    }
+}
+
+#[derive(clap::Args)]
+struct MechlibArgs {
+    #[clap(flatten)]
+    zip: ZipArgs,
+    #[clap(
+        long = "model",
+        help = "Only extract this model (e.g. 'madcat.flt'), along with the materials"
+    )]
+    model: Option<String>,
+    #[clap(
+        long = "with-bounds",
+        help = "Also write the bounding box of each model (e.g. 'madcat.bounds.json')"
+    )]
+    with_bounds: bool,
+}
+
+impl MechlibArgs {
+    fn opts(self, game: GameType) -> Result<MechlibOpts> {
+        let Self {
+            zip,
+            model,
+            with_bounds,
+        } = self;
+        Ok(MechlibOpts {
+            zip: zip.opts(game)?,
+            model,
+            with_bounds,
         })
     }
 }
 
+#[derive(clap::Args)]
+struct GamezArgs {
+    #[clap(flatten)]
+    zip: ZipArgs,
+    #[clap(
+        long = "lenient",
+        help = "Log unexpected but harmless values as warnings, instead of failing"
+    )]
+    lenient: bool,
+}
+
+impl GamezArgs {
+    fn opts(self, game: GameType) -> Result<GamezOpts> {
+        let Self { zip, lenient } = self;
+        Ok(GamezOpts {
+            zip: zip.opts(game)?,
+            options: read_options(lenient),
+        })
+    }
+}
+
+struct GamezOpts {
+    zip: ZipOpts,
+    options: ReadOptions,
+}
+
+fn read_options(lenient: bool) -> ReadOptions {
+    if lenient {
+        ReadOptions::LENIENT
+    } else {
+        ReadOptions::STRICT
+    }
+}
+
+struct MechlibOpts {
+    zip: ZipOpts,
+    model: Option<String>,
+    with_bounds: bool,
+}
+
 struct ZipOpts {
     game: GameType,
     input: String,
     output: String,
+    dir: bool,
+    resume: bool,
+    keep_going: bool,
+    compact: bool,
+}
+
+/// Only Pirate's Moon archives depend on the mode.
+fn archive_version(game: GameType, mode: Mode) -> Version {
+    match game {
+        GameType::MW | GameType::RC | GameType::CS => Version::One,
+        GameType::PM => Version::Two(mode),
+    }
 }
 
 impl ZipOpts {
     fn version(&self, mode: Mode) -> Version {
-        match self.game {
-            GameType::MW | GameType::RC | GameType::CS => Version::One,
-            GameType::PM => Version::Two(mode),
-        }
+        archive_version(self.game, mode)
     }
 }
 
@@ -89,14 +211,43 @@ impl ZipOpts {
 struct ReaderArgs {
     #[clap(help = "The source ZBD path")]
     input: String,
-    #[clap(help = "The destination ZIP path (will be overwritten)")]
+    #[clap(
+        help = "The destination ZIP path, or directory with `--dir`, or NDJSON path with `--ndjson` (will be overwritten)"
+    )]
     output: String,
+    #[clap(
+        long = "dir",
+        help = "Write the files into a directory, instead of a ZIP",
+        conflicts_with = "ndjson"
+    )]
+    dir: bool,
+    #[clap(
+        long = "resume",
+        help = "Skip archive entries written to the directory by a previous run",
+        requires = "dir"
+    )]
+    resume: bool,
     #[clap(
         long = "skip-crc",
         help = "Skip the CRC check (only for PM)",
         hide = true
     )]
     skip_crc: bool,
+    #[clap(
+        long = "keep-going",
+        help = "Continue past archive entries that fail to read, and list them in 'errors.json'"
+    )]
+    keep_going: bool,
+    #[clap(
+        long = "compact",
+        help = "Write compact JSON instead of pretty-printed JSON"
+    )]
+    compact: bool,
+    #[clap(
+        long = "ndjson",
+        help = "Write each entry as a line of JSON as it is read, instead of a ZIP"
+    )]
+    ndjson: bool,
 }
 
 impl ReaderArgs {
@@ -104,13 +255,23 @@ impl ReaderArgs {
         let Self {
             input,
             output,
+            dir,
+            resume,
             skip_crc,
+            keep_going,
+            compact,
+            ndjson,
         } = self;
         Ok(ReaderOpts {
             game,
             input,
             output,
+            dir,
+            resume,
             skip_crc,
+            keep_going,
+            compact,
+            ndjson,
         })
     }
 }
@@ -119,7 +280,12 @@ struct ReaderOpts {
     game: GameType,
     input: String,
     output: String,
+    dir: bool,
+    resume: bool,
     skip_crc: bool,
+    keep_going: bool,
+    compact: bool,
+    ndjson: bool,
 }
 
 impl ReaderOpts {
@@ -138,14 +304,53 @@ struct InterpOpts {
     input: String,
     #[clap(help = "The destination JSON path (will be overwritten)")]
     output: String,
+    #[clap(
+        long = "compact",
+        help = "Write compact JSON instead of pretty-printed JSON"
+    )]
+    compact: bool,
+}
+
+#[derive(clap::Args)]
+struct InterpDiffOpts {
+    #[clap(help = "The first source ZBD path")]
+    first: String,
+    #[clap(help = "The second source ZBD path")]
+    second: String,
 }
 
 #[derive(clap::Args)]
 struct TextureOpts {
     #[clap(help = "The source ZBD path")]
     input: String,
-    #[clap(help = "The destination ZIP path (will be overwritten)")]
+    #[clap(
+        help = "The destination ZIP path, or directory with `--dir`, or JSON path with `--manifest-only` (will be overwritten)"
+    )]
     output: String,
+    #[clap(
+        long = "dir",
+        help = "Write the files into a directory, instead of a ZIP",
+        conflicts_with = "manifest_only"
+    )]
+    dir: bool,
+    #[clap(
+        long = "manifest-only",
+        help = "Only write the manifest as JSON, without decoding the images"
+    )]
+    manifest_only: bool,
+    #[arg(
+        long = "image-format",
+        value_enum,
+        default_value = "png",
+        help = "The image format to write the textures as",
+        conflicts_with = "manifest_only"
+    )]
+    image_format: images::ImageFileFormat,
+    #[clap(
+        long = "compact",
+        help = "Write compact JSON instead of pretty-printed JSON"
+    )]
+    compact: bool,
 }
 
 #[derive(clap::Args)]
@@ -154,15 +359,25 @@ struct MsgArgs {
     input: String,
     #[clap(help = "The destination JSON path (will be overwritten)")]
     output: String,
+    #[clap(
+        long = "compact",
+        help = "Write compact JSON instead of pretty-printed JSON"
+    )]
+    compact: bool,
 }
 
 impl MsgArgs {
     fn opts(self, game: GameType) -> Result<MsgOpts> {
-        let Self { input, output } = self;
+        let Self {
+            input,
+            output,
+            compact,
+        } = self;
         Ok(MsgOpts {
             game,
             input,
             output,
+            compact,
         })
     }
 }
@@ -171,6 +386,7 @@ struct MsgOpts {
     game: GameType,
     input: String,
     output: String,
+    compact: bool,
 }
 
 #[derive(clap::Args)]
@@ -179,15 +395,25 @@ struct ZMapArgs {
     input: String,
     #[clap(help = "The destination JSON path (will be overwritten)")]
     output: String,
+    #[clap(
+        long = "compact",
+        help = "Write compact JSON instead of pretty-printed JSON"
+    )]
+    compact: bool,
 }
 
 impl ZMapArgs {
     fn opts(self, game: GameType) -> Result<ZMapOpts> {
-        let Self { input, output } = self;
+        let Self {
+            input,
+            output,
+            compact,
+        } = self;
         Ok(ZMapOpts {
             game,
             input,
             output,
+            compact,
         })
     }
 }
@@ -196,6 +422,263 @@ struct ZMapOpts {
     game: GameType,
     input: String,
     output: String,
+    compact: bool,
+}
+
+#[derive(clap::Args)]
+struct MotionBvhArgs {
+    #[clap(help = "The source 'motion.zbd' path")]
+    input: String,
+    #[clap(help = "The source 'mechlib.zbd' path")]
+    mechlib: String,
+    #[clap(help = "The mechlib model to use as the skeleton (e.g. 'madcat.flt')")]
+    model: String,
+    #[clap(help = "The destination ZIP path (will be overwritten)")]
+    output: String,
+}
+
+impl MotionBvhArgs {
+    fn opts(self, game: GameType) -> Result<MotionBvhOpts> {
+        let Self {
+            input,
+            mechlib,
+            model,
+            output,
+        } = self;
+        Ok(MotionBvhOpts {
+            game,
+            input,
+            mechlib,
+            model,
+            output,
+        })
+    }
+}
+
+struct MotionBvhOpts {
+    game: GameType,
+    input: String,
+    mechlib: String,
+    model: String,
+    output: String,
+}
+
+#[derive(clap::Args)]
+struct AnimCheckArgs {
+    #[clap(help = "The source 'anim.zbd' path")]
+    anim: String,
+    #[clap(help = "The source 'gamez.zbd' path to resolve the references against")]
+    gamez: String,
+    #[clap(
+        long = "lenient",
+        help = "Log unexpected but harmless values as warnings, instead of failing"
+    )]
+    lenient: bool,
+}
+
+impl AnimCheckArgs {
+    fn opts(self, game: GameType) -> Result<AnimCheckOpts> {
+        let Self {
+            anim,
+            gamez,
+            lenient,
+        } = self;
+        Ok(AnimCheckOpts {
+            game,
+            anim,
+            gamez,
+            options: read_options(lenient),
+        })
+    }
+}
+
+struct AnimCheckOpts {
+    game: GameType,
+    anim: String,
+    gamez: String,
+    options: ReadOptions,
+}
+
+#[derive(clap::Args)]
+struct CheckArgs {
+    #[clap(help = "The source path")]
+    input: String,
+    #[arg(long, value_enum, help = "The kind of file to read")]
+    kind: check::Kind,
+    #[clap(
+        long = "lenient",
+        help = "Log unexpected but harmless values as warnings, instead of failing"
+    )]
+    lenient: bool,
+}
+
+impl CheckArgs {
+    fn opts(self, game: GameType) -> Result<CheckOpts> {
+        let Self {
+            input,
+            kind,
+            lenient,
+        } = self;
+        Ok(CheckOpts {
+            game,
+            input,
+            kind,
+            options: read_options(lenient),
+        })
+    }
+}
+
+struct CheckOpts {
+    game: GameType,
+    input: String,
+    kind: check::Kind,
+    options: ReadOptions,
+}
+
+impl CheckOpts {
+    fn version(&self, mode: Mode) -> Version {
+        archive_version(self.game, mode)
+    }
+}
+
+#[derive(clap::Args)]
+struct AllArgs {
+    #[clap(help = "The source game directory (searched recursively)")]
+    input: String,
+    #[clap(help = "The destination directory (files will be overwritten)")]
+    output: String,
+}
+
+impl AllArgs {
+    fn opts(self, game: GameType) -> Result<AllOpts> {
+        let Self { input, output } = self;
+        Ok(AllOpts {
+            game,
+            input,
+            output,
+        })
+    }
+}
+
+struct AllOpts {
+    game: GameType,
+    input: String,
+    output: String,
+}
+
+#[derive(clap::Args)]
+struct SchemaArgs {
+    #[arg(value_enum, help = "The kind of JSON structure")]
+    kind: schema::Kind,
+}
+
+impl SchemaArgs {
+    fn opts(self, game: GameType) -> Result<SchemaOpts> {
+        let Self { kind } = self;
+        Ok(SchemaOpts { game, kind })
+    }
+}
+
+struct SchemaOpts {
+    game: GameType,
+    kind: schema::Kind,
+}
+
+#[derive(clap::Args)]
+struct RawArgs {
+    #[clap(help = "The source ZBD archive path")]
+    input: String,
+    #[clap(help = "The destination directory (files will be overwritten)")]
+    output: String,
+    #[arg(
+        long,
+        value_enum,
+        help = "The archive version (defaults to the game's version)"
+    )]
+    version: Option<raw::RawVersion>,
+}
+
+impl RawArgs {
+    fn opts(self, game: GameType) -> Result<RawOpts> {
+        let Self {
+            input,
+            output,
+            version,
+        } = self;
+        let version = version.unwrap_or_else(|| raw::RawVersion::from_game(game));
+        Ok(RawOpts {
+            input,
+            output,
+            version,
+        })
+    }
+}
+
+struct RawOpts {
+    input: String,
+    output: String,
+    version: raw::RawVersion,
+}
+
+#[derive(clap::Args)]
+struct AnnotateArgs {
+    #[clap(help = "The source path")]
+    input: String,
+    #[arg(
+        long = "type",
+        value_enum,
+        help = "The structure to annotate the bytes with"
+    )]
+    kind: annotate::Kind,
+    #[clap(long = "at", help = "The offset of the structure in the file")]
+    at: usize,
+}
+
+impl AnnotateArgs {
+    fn opts(self, game: GameType) -> Result<AnnotateOpts> {
+        let Self { input, kind, at } = self;
+        Ok(AnnotateOpts {
+            game,
+            input,
+            kind,
+            at,
+        })
+    }
+}
+
+struct AnnotateOpts {
+    game: GameType,
+    input: String,
+    kind: annotate::Kind,
+    at: usize,
+}
+
+#[derive(clap::Args)]
+struct GamezInfoArgs {
+    #[clap(help = "The source ZBD path")]
+    input: String,
+    #[clap(
+        long = "lenient",
+        help = "Log unexpected but harmless values as warnings, instead of failing"
+    )]
+    lenient: bool,
+}
+
+impl GamezInfoArgs {
+    fn opts(self, game: GameType) -> Result<GamezInfoOpts> {
+        let Self { input, lenient } = self;
+        Ok(GamezInfoOpts {
+            game,
+            input,
+            options: read_options(lenient),
+        })
+    }
+}
+
+struct GamezInfoOpts {
+    game: GameType,
+    input: String,
+    options: ReadOptions,
 }
 
 #[derive(clap::Subcommand)]
@@ -206,6 +689,8 @@ enum SubCommand {
     Sounds(ZipArgs),
     #[clap(about = "Extract 'interp.zbd' files to JSON")]
     Interp(InterpOpts),
+    #[clap(about = "Compare the scripts in two 'interp.zbd' files")]
+    InterpDiff(InterpDiffOpts),
     #[clap(about = "Extract 'reader*.zbd'/'zrdr.zbd' archives to ZIP")]
     Reader(ReaderArgs),
     #[clap(about = "Extract 'Mech3Msg.dll'/'messages.dll' files to JSON")]
@@ -214,16 +699,36 @@ enum SubCommand {
     Textures(TextureOpts),
     #[clap(about = "Extract 'motion.zbd' archives to ZIP (MW, PM)")]
     Motion(ZipArgs),
+    #[clap(about = "Export 'motion.zbd' archives to BVH using a mechlib model (MW, PM)")]
+    MotionBvh(MotionBvhArgs),
     #[clap(about = "Extract 'mechlib.zbd' archives to ZIP (MW, PM)")]
-    Mechlib(ZipArgs),
+    Mechlib(MechlibArgs),
     #[clap(about = "Extract 'gamez.zbd' archives to ZIP")]
-    Gamez(ZipArgs),
+    Gamez(GamezArgs),
+    #[clap(
+        about = "Print mesh, material, texture, and node counts of 'gamez.zbd' archives as JSON"
+    )]
+    GamezStats(GamezInfoArgs),
+    #[clap(about = "Print the node hierarchy of 'gamez.zbd' archives as a tree")]
+    NodesTree(GamezInfoArgs),
     #[clap(about = "Extract 'anim.zbd' archives to ZIP (MW)")]
     Anim(ZipArgs),
+    #[clap(about = "Check anim def node and light references against a 'gamez.zbd' (MW)")]
+    AnimCheck(AnimCheckArgs),
     #[clap(about = "Extract savegames '*.mw3' archives to ZIP (MW)")]
     Savegame(ZipArgs),
     #[clap(about = "Extract map '*.zmap' files to JSON (RC)")]
     Zmap(ZMapArgs),
+    #[clap(about = "Read a file without writing any output, and report any errors")]
+    Check(CheckArgs),
+    #[clap(about = "Extract all known files in a game directory")]
+    All(AllArgs),
+    #[clap(about = "Print the JSON schema of an extracted JSON structure")]
+    Schema(SchemaArgs),
+    #[clap(about = "Dump the raw entries of any archive, without parsing them")]
+    Raw(RawArgs),
+    #[clap(about = "Print the fields of a known structure at an offset in a file")]
+    Annotate(AnnotateArgs),
 }
 
 fn main() -> Result<()> {
@@ -244,15 +749,40 @@ fn main() -> Result<()> {
     match cli.subcmd {
         SubCommand::Sounds(args) => commands::sounds(args.opts(game)?),
         SubCommand::Interp(opts) => commands::interp(opts),
+        SubCommand::InterpDiff(opts) => commands::interp_diff(opts),
         SubCommand::Reader(args) => commands::reader(args.opts(game)?),
         SubCommand::Messages(args) => commands::messages(args.opts(game)?),
-        SubCommand::Textures(TextureOpts { input, output }) => commands::textures(input, output),
+        SubCommand::Textures(TextureOpts {
+            input,
+            output,
+            dir,
+            manifest_only: false,
+            image_format,
+            compact,
+        }) => commands::textures(input, output, dir, image_format, compact),
+        SubCommand::Textures(TextureOpts {
+            input,
+            output,
+            dir: _,
+            manifest_only: true,
+            image_format: _,
+            compact,
+        }) => commands::texture_manifest(input, output, compact),
         SubCommand::Motion(args) => commands::motion(args.opts(game)?),
+        SubCommand::MotionBvh(args) => commands::motion_bvh(args.opts(game)?),
         SubCommand::Mechlib(args) => commands::mechlib(args.opts(game)?),
         SubCommand::Gamez(args) => commands::gamez(args.opts(game)?),
+        SubCommand::GamezStats(args) => stats::stats(args.opts(game)?),
+        SubCommand::NodesTree(args) => tree::nodes_tree(args.opts(game)?),
         SubCommand::Anim(args) => commands::anim(args.opts(game)?),
+        SubCommand::AnimCheck(args) => anim_check::anim_check(args.opts(game)?),
         SubCommand::Savegame(args) => commands::savegame(args.opts(game)?),
         SubCommand::Zmap(args) => commands::zmap(args.opts(game)?),
+        SubCommand::Check(args) => check::check(args.opts(game)?),
+        SubCommand::All(args) => all::all(args.opts(game)?),
+        SubCommand::Schema(args) => schema::schema(args.opts(game)?),
+        SubCommand::Raw(args) => raw::raw(args.opts(game)?),
+        SubCommand::Annotate(args) => annotate::annotate(args.opts(game)?),
         SubCommand::License => commands::license(),
     }
 }
@@ -0,0 +1,83 @@
+use crate::commands::buf_reader;
+use crate::RawOpts;
+use eyre::{bail, Context as _, Report, Result};
+use mech3ax_archive::{read_archive, Mode, Version};
+use mech3ax_common::io_ext::CountingReader;
+use mech3ax_common::GameType;
+use std::io::{Read, Seek};
+use std::path::{Component, Path};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub(crate) enum RawVersion {
+    One,
+    Two,
+}
+
+impl RawVersion {
+    pub(crate) fn from_game(game: GameType) -> Self {
+        match game {
+            GameType::MW | GameType::RC | GameType::CS => Self::One,
+            GameType::PM => Self::Two,
+        }
+    }
+
+    fn version(self) -> Version {
+        match self {
+            Self::One => Version::One,
+            // the format of the entries is unknown, so don't check the
+            // reader checksum or any other mode specific values
+            Self::Two => Version::Two(Mode::ReaderBypass),
+        }
+    }
+}
+
+/// Entry names are used as file names, so they must stay inside the output
+/// directory.
+pub(crate) fn entry_path(out_dir: &Path, name: &str) -> Result<std::path::PathBuf> {
+    let relative = Path::new(name);
+    let is_normal = relative
+        .components()
+        .all(|component| matches!(component, Component::Normal(_)));
+    if !is_normal || name.is_empty() {
+        bail!("Entry name `{}` is not a valid file name", name);
+    }
+    Ok(out_dir.join(relative))
+}
+
+/// Write each entry's bytes to a file named after the entry, and the
+/// manifest to `manifest.json`.
+fn dump_archive<R>(read: &mut CountingReader<R>, out_dir: &Path, version: Version) -> Result<usize>
+where
+    R: Read + Seek,
+{
+    std::fs::create_dir_all(out_dir).context("Failed to create output directory")?;
+    let manifest = read_archive::<_, _, Report>(
+        read,
+        |name, data, _offset| {
+            let path = entry_path(out_dir, name)?;
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent).context("Failed to create output directory")?;
+            }
+            std::fs::write(&path, data).with_context(|| format!("Failed to write entry `{}`", name))
+        },
+        version,
+    )
+    .context("Failed to read archive data")?;
+
+    let contents = serde_json::to_vec_pretty(&manifest)?;
+    std::fs::write(out_dir.join("manifest.json"), contents).context("Failed to write manifest")?;
+    Ok(manifest.len())
+}
+
+/// Dump the raw entries of any archive, without parsing them.
+pub(crate) fn raw(opts: RawOpts) -> Result<()> {
+    let version = opts.version.version();
+    log::info!("RAW: Reading `{}` ({:?})", opts.input, version);
+    let mut input = CountingReader::new(buf_reader(&opts.input)?);
+    let count = dump_archive(&mut input, Path::new(&opts.output), version)?;
+    log::info!("RAW: Wrote {} entries to `{}`", count, opts.output);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,83 @@
+use super::*;
+use mech3ax_api_types::archive::{ArchiveEntry, ArchiveEntryInfo, ArchiveEntryInfoInvalid};
+use mech3ax_archive::write_archive;
+use mech3ax_common::io_ext::CountingWriter;
+use mech3ax_common::test_utils::TempDir;
+use std::io::Cursor;
+
+fn entry(name: &str) -> ArchiveEntry {
+    ArchiveEntry {
+        name: name.to_string(),
+        rename: None,
+        flags: 0,
+        info: ArchiveEntryInfo::Invalid(ArchiveEntryInfoInvalid {
+            comment: vec![0; 64],
+            filetime: 0,
+        }),
+    }
+}
+
+fn payload(name: &str) -> Vec<u8> {
+    // not text, and different lengths
+    name.bytes().chain(0..=255).collect()
+}
+
+fn archive(entries: &[ArchiveEntry], version: Version) -> Vec<u8> {
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    write_archive::<_, _, Report>(
+        &mut write,
+        entries,
+        |name, _offset| Ok(payload(name)),
+        version,
+    )
+    .unwrap();
+    write.into_inner()
+}
+
+#[test]
+fn dumped_bytes_match_payloads() {
+    let entries = [entry("alpha.bin"), entry("bravo"), entry("charlie.dat")];
+    let data = archive(&entries, Version::One);
+    let tmp = TempDir::new("unzbd-raw-one");
+    let out_dir = tmp.path();
+
+    let mut read = CountingReader::new(Cursor::new(data));
+    let count = dump_archive(&mut read, out_dir, Version::One).unwrap();
+
+    assert_eq!(count, 3);
+    for entry in &entries {
+        let dumped = std::fs::read(out_dir.join(&entry.name)).unwrap();
+        assert_eq!(dumped, payload(&entry.name), "{}", entry.name);
+    }
+    let manifest = std::fs::read(out_dir.join("manifest.json")).unwrap();
+    let manifest: Vec<ArchiveEntry> = serde_json::from_slice(&manifest).unwrap();
+    let names = manifest.iter().map(|e| e.name.as_str()).collect::<Vec<_>>();
+    assert_eq!(names, ["alpha.bin", "bravo", "charlie.dat"]);
+}
+
+#[test]
+fn dump_version_two() {
+    let entries = [entry("alpha.bin")];
+    let data = archive(&entries, Version::Two(Mode::Sounds));
+    let tmp = TempDir::new("unzbd-raw-two");
+    let out_dir = tmp.path();
+
+    let mut read = CountingReader::new(Cursor::new(data));
+    let version = RawVersion::Two.version();
+    dump_archive(&mut read, out_dir, version).unwrap();
+
+    let dumped = std::fs::read(out_dir.join("alpha.bin")).unwrap();
+    assert_eq!(dumped, payload("alpha.bin"));
+}
+
+#[test]
+fn entry_names_stay_in_output_dir() {
+    let out_dir = Path::new("out");
+    assert_eq!(
+        entry_path(out_dir, "sub/file.bin").unwrap(),
+        out_dir.join("sub").join("file.bin")
+    );
+    assert!(entry_path(out_dir, "../file.bin").is_err());
+    assert!(entry_path(out_dir, "/file.bin").is_err());
+    assert!(entry_path(out_dir, "").is_err());
+}
@@ -0,0 +1,96 @@
+use crate::SchemaOpts;
+use eyre::{bail, Result};
+use mech3ax_api_types::anim::{AnimDef, AnimMetadata};
+use mech3ax_api_types::archive::ArchiveEntry;
+use mech3ax_api_types::gamez::mechlib::{ModelMw, ModelPm};
+use mech3ax_api_types::gamez::{GameZDataCs, GameZDataMw, GameZDataPm, GameZDataRc};
+use mech3ax_api_types::image::TextureManifest;
+use mech3ax_api_types::interp::Script;
+use mech3ax_api_types::messages::Messages;
+use mech3ax_api_types::motion::Motion;
+use mech3ax_api_types::saves::AnimActivation;
+use mech3ax_api_types::zmap::Zmap;
+use mech3ax_common::GameType;
+use schemars::schema::RootSchema;
+use schemars::schema_for;
+
+/// The JSON structures written by the extract commands. Reader files are
+/// not included, since they are untyped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub(crate) enum Kind {
+    /// The archive `manifest.json` (e.g. sounds)
+    Manifest,
+    Interp,
+    Messages,
+    /// The textures `manifest.json`
+    Textures,
+    Motion,
+    /// A mechlib model
+    Mechlib,
+    /// The GameZ data (each field is a JSON file in the ZIP)
+    Gamez,
+    /// The anim `metadata.json`
+    AnimMetadata,
+    /// An anim definition
+    AnimDef,
+    /// A savegame anim activation
+    Savegame,
+    Zmap,
+}
+
+pub(crate) fn schema_for_kind(kind: Kind, game: GameType) -> Result<RootSchema> {
+    let schema = match (kind, game) {
+        (Kind::Manifest, _) => schema_for!(Vec<ArchiveEntry>),
+        (Kind::Interp, _) => schema_for!(Vec<Script>),
+        (Kind::Messages, _) => schema_for!(Messages),
+        (Kind::Textures, _) => schema_for!(TextureManifest),
+        (Kind::Motion, GameType::MW | GameType::PM) => schema_for!(Motion),
+        (Kind::Motion, GameType::RC) => bail!("Recoil does not have motion"),
+        (Kind::Motion, GameType::CS) => bail!("Crimson Skies does not have motion"),
+        (Kind::Mechlib, GameType::MW) => schema_for!(ModelMw),
+        (Kind::Mechlib, GameType::PM) => schema_for!(ModelPm),
+        (Kind::Mechlib, GameType::RC) => bail!("Recoil does not have mechlib"),
+        (Kind::Mechlib, GameType::CS) => bail!("Crimson Skies does not have mechlib"),
+        (Kind::Gamez, GameType::MW) => schema_for!(GameZDataMw),
+        (Kind::Gamez, GameType::PM) => schema_for!(GameZDataPm),
+        (Kind::Gamez, GameType::RC) => schema_for!(GameZDataRc),
+        (Kind::Gamez, GameType::CS) => schema_for!(GameZDataCs),
+        (Kind::AnimMetadata, GameType::MW) => schema_for!(AnimMetadata),
+        (Kind::AnimDef, GameType::MW) => schema_for!(AnimDef),
+        (Kind::AnimMetadata | Kind::AnimDef, GameType::PM) => {
+            bail!("Pirate's Moon support for Anim isn't implemented yet")
+        }
+        (Kind::AnimMetadata | Kind::AnimDef, GameType::RC) => {
+            bail!("Recoil support for Anim isn't implemented yet")
+        }
+        (Kind::AnimMetadata | Kind::AnimDef, GameType::CS) => {
+            bail!("Crimson Skies support for Anim isn't implemented yet")
+        }
+        (Kind::Savegame, GameType::MW) => schema_for!(AnimActivation),
+        (Kind::Savegame, GameType::PM) => {
+            bail!("Pirate's Moon support for Savegames isn't implemented yet")
+        }
+        (Kind::Savegame, GameType::RC) => {
+            bail!("Recoil support for Savegames isn't implemented yet")
+        }
+        (Kind::Savegame, GameType::CS) => {
+            bail!("Crimson Skies support for Savegames isn't implemented yet")
+        }
+        (Kind::Zmap, GameType::RC) => schema_for!(Zmap),
+        (Kind::Zmap, GameType::MW) => bail!("MechWarrior 3 does not have zmap"),
+        (Kind::Zmap, GameType::PM) => bail!("Pirate's Moon does not have zmap"),
+        (Kind::Zmap, GameType::CS) => bail!("Crimson Skies does not have zmap"),
+    };
+    Ok(schema)
+}
+
+/// Print the JSON schema of the structure written for a kind of file.
+pub(crate) fn schema(opts: SchemaOpts) -> Result<()> {
+    let schema = schema_for_kind(opts.kind, opts.game)?;
+    let contents = serde_json::to_string_pretty(&schema)?;
+    println!("{}", contents);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,83 @@
+use super::*;
+use clap::ValueEnum as _;
+use serde_json::Value;
+
+const GAMES: [GameType; 4] = [GameType::MW, GameType::PM, GameType::RC, GameType::CS];
+
+fn collect_refs<'a>(value: &'a Value, refs: &mut Vec<&'a str>) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                match (key.as_str(), value) {
+                    ("$ref", Value::String(reference)) => refs.push(reference),
+                    _ => collect_refs(value, refs),
+                }
+            }
+        }
+        Value::Array(values) => {
+            for value in values {
+                collect_refs(value, refs);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn assert_valid(kind: Kind, game: GameType, schema: &RootSchema) {
+    let value = serde_json::to_value(schema).unwrap();
+    assert_eq!(
+        value["$schema"], "http://json-schema.org/draft-07/schema#",
+        "{:?} {}",
+        kind, game
+    );
+    assert!(value["title"].is_string(), "{:?} {}", kind, game);
+
+    let definitions = &value["definitions"];
+    let mut refs = Vec::new();
+    collect_refs(&value, &mut refs);
+    for reference in refs {
+        let name = reference
+            .strip_prefix("#/definitions/")
+            .unwrap_or_else(|| panic!("{:?} {}: unexpected ref `{}`", kind, game, reference));
+        assert!(
+            definitions.get(name).is_some(),
+            "{:?} {}: ref `{}` not found",
+            kind,
+            game,
+            reference
+        );
+    }
+}
+
+#[test]
+fn every_schema_is_valid() {
+    let mut generated = 0;
+    for &kind in Kind::value_variants() {
+        for game in GAMES {
+            if let Ok(schema) = schema_for_kind(kind, game) {
+                assert_valid(kind, game, &schema);
+                generated += 1;
+            }
+        }
+    }
+    // manifest, interp, messages, textures, and gamez for all games, plus
+    // motion, mechlib (MW, PM), anim metadata, anim def, savegame (MW),
+    // and zmap (RC)
+    assert_eq!(generated, 5 * 4 + 2 + 2 + 1 + 1 + 1 + 1);
+}
+
+#[test]
+fn schema_for_unsupported_game_fails() {
+    let err = schema_for_kind(Kind::Zmap, GameType::MW).unwrap_err();
+    assert_eq!(err.to_string(), "MechWarrior 3 does not have zmap");
+}
+
+#[test]
+fn schema_titles_match_types() {
+    let schema = schema_for_kind(Kind::Gamez, GameType::RC).unwrap();
+    let value = serde_json::to_value(&schema).unwrap();
+    assert_eq!(value["title"], "GameZDataRc");
+    let schema = schema_for_kind(Kind::Interp, GameType::MW).unwrap();
+    let value = serde_json::to_value(&schema).unwrap();
+    assert_eq!(value["title"], "Array_of_Script");
+}
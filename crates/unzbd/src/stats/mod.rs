@@ -0,0 +1,263 @@
+use crate::commands::log_warnings;
+use crate::input::SeekableInput;
+use crate::GamezInfoOpts;
+use eyre::{Context as _, Result};
+use mech3ax_api_types::gamez::materials::Material;
+use mech3ax_api_types::gamez::mesh::{MeshMw, MeshNg, MeshPolygons, MeshRc};
+use mech3ax_api_types::gamez::{GameZDataCs, GameZDataMw, GameZDataPm, GameZDataRc};
+use mech3ax_api_types::nodes::cs::NodeCs;
+use mech3ax_api_types::nodes::mw::NodeMw;
+use mech3ax_api_types::nodes::pm::NodePm;
+use mech3ax_api_types::nodes::rc::NodeRc;
+use mech3ax_common::io_ext::CountingReader;
+use mech3ax_common::GameType;
+use mech3ax_gamez::gamez;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(crate) struct MeshStats {
+    pub index: usize,
+    pub vertices: usize,
+    pub polygons: usize,
+    pub triangles: usize,
+}
+
+/// A summary of the complexity of GameZ data.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
+pub(crate) struct GameZStats {
+    pub mesh_count: usize,
+    pub vertex_count: usize,
+    pub polygon_count: usize,
+    pub triangle_count: usize,
+    pub meshes: Vec<MeshStats>,
+    /// The number of polygons using each material index.
+    pub material_usage: BTreeMap<u32, usize>,
+    /// The number of polygons using each texture, via their materials.
+    pub texture_usage: BTreeMap<String, usize>,
+    /// The number of nodes of each type.
+    pub node_types: BTreeMap<&'static str, usize>,
+}
+
+trait StatsMesh: MeshPolygons {
+    fn vertex_count(&self) -> usize;
+    /// The material indices of each polygon.
+    fn polygon_material_indices(&self) -> Vec<Vec<u32>>;
+}
+
+impl StatsMesh for MeshMw {
+    fn vertex_count(&self) -> usize {
+        self.vertices.len()
+    }
+
+    fn polygon_material_indices(&self) -> Vec<Vec<u32>> {
+        self.polygons
+            .iter()
+            .map(|polygon| vec![polygon.material_index])
+            .collect()
+    }
+}
+
+impl StatsMesh for MeshRc {
+    fn vertex_count(&self) -> usize {
+        self.vertices.len()
+    }
+
+    fn polygon_material_indices(&self) -> Vec<Vec<u32>> {
+        self.polygons
+            .iter()
+            .map(|polygon| vec![polygon.material_index])
+            .collect()
+    }
+}
+
+impl StatsMesh for MeshNg {
+    fn vertex_count(&self) -> usize {
+        self.vertices.len()
+    }
+
+    fn polygon_material_indices(&self) -> Vec<Vec<u32>> {
+        self.polygons
+            .iter()
+            .map(|polygon| {
+                polygon
+                    .materials
+                    .iter()
+                    .map(|material| material.material_index)
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+pub(crate) fn node_type_mw(node: &NodeMw) -> &'static str {
+    match node {
+        NodeMw::Camera(_) => "Camera",
+        NodeMw::Display(_) => "Display",
+        NodeMw::Empty(_) => "Empty",
+        NodeMw::Light(_) => "Light",
+        NodeMw::Lod(_) => "Lod",
+        NodeMw::Object3d(_) => "Object3d",
+        NodeMw::Window(_) => "Window",
+        NodeMw::World(_) => "World",
+    }
+}
+
+pub(crate) fn node_type_pm(node: &NodePm) -> &'static str {
+    match node {
+        NodePm::Camera(_) => "Camera",
+        NodePm::Display(_) => "Display",
+        NodePm::Light(_) => "Light",
+        NodePm::Lod(_) => "Lod",
+        NodePm::Object3d(_) => "Object3d",
+        NodePm::Window(_) => "Window",
+        NodePm::World(_) => "World",
+    }
+}
+
+pub(crate) fn node_type_cs(node: &NodeCs) -> &'static str {
+    match node {
+        NodeCs::Camera(_) => "Camera",
+        NodeCs::Display(_) => "Display",
+        NodeCs::Light(_) => "Light",
+        NodeCs::Lod(_) => "Lod",
+        NodeCs::Object3d(_) => "Object3d",
+        NodeCs::Window(_) => "Window",
+        NodeCs::World(_) => "World",
+    }
+}
+
+pub(crate) fn node_type_rc(node: &NodeRc) -> &'static str {
+    match node {
+        NodeRc::Camera(_) => "Camera",
+        NodeRc::Display(_) => "Display",
+        NodeRc::Empty(_) => "Empty",
+        NodeRc::Light(_) => "Light",
+        NodeRc::Lod(_) => "Lod",
+        NodeRc::Object3d(_) => "Object3d",
+        NodeRc::Window(_) => "Window",
+        NodeRc::World(_) => "World",
+    }
+}
+
+fn gamez_stats<'a, M: StatsMesh + 'a>(
+    materials: &[Material],
+    meshes: impl Iterator<Item = (usize, &'a M)>,
+    node_types: impl Iterator<Item = &'static str>,
+) -> GameZStats {
+    let mut stats = GameZStats::default();
+    for (index, mesh) in meshes {
+        let vertices = mesh.vertex_count();
+        let triangles = mesh
+            .polygon_triangles()
+            .map(|triangles| triangles.len())
+            .sum();
+        let polygon_materials = mesh.polygon_material_indices();
+
+        for material_index in polygon_materials.iter().flatten().copied() {
+            *stats.material_usage.entry(material_index).or_default() += 1;
+            // out of range indices are still counted above, but can't be
+            // resolved to a texture
+            let material = usize::try_from(material_index)
+                .ok()
+                .and_then(|index| materials.get(index));
+            if let Some(Material::Textured(textured)) = material {
+                *stats
+                    .texture_usage
+                    .entry(textured.texture.clone())
+                    .or_default() += 1;
+            }
+        }
+
+        stats.vertex_count += vertices;
+        stats.polygon_count += polygon_materials.len();
+        stats.triangle_count += triangles;
+        stats.meshes.push(MeshStats {
+            index,
+            vertices,
+            polygons: polygon_materials.len(),
+            triangles,
+        });
+    }
+    stats.mesh_count = stats.meshes.len();
+    for node_type in node_types {
+        *stats.node_types.entry(node_type).or_default() += 1;
+    }
+    stats
+}
+
+pub(crate) fn gamez_stats_mw(gamez: &GameZDataMw) -> GameZStats {
+    gamez_stats(
+        &gamez.materials,
+        gamez.meshes.iter().enumerate(),
+        gamez.nodes.iter().map(node_type_mw),
+    )
+}
+
+pub(crate) fn gamez_stats_pm(gamez: &GameZDataPm) -> GameZStats {
+    gamez_stats(
+        &gamez.materials,
+        gamez.meshes.iter().enumerate(),
+        gamez.nodes.iter().map(node_type_pm),
+    )
+}
+
+pub(crate) fn gamez_stats_cs(gamez: &GameZDataCs) -> GameZStats {
+    // the mesh array can have gaps, which are skipped
+    let meshes = gamez
+        .meshes
+        .iter()
+        .enumerate()
+        .filter_map(|(index, mesh)| mesh.as_ref().map(|mesh| (index, mesh)));
+    gamez_stats(
+        &gamez.materials,
+        meshes,
+        gamez.nodes.iter().map(node_type_cs),
+    )
+}
+
+pub(crate) fn gamez_stats_rc(gamez: &GameZDataRc) -> GameZStats {
+    gamez_stats(
+        &gamez.materials,
+        gamez.meshes.iter().enumerate(),
+        gamez.nodes.iter().map(node_type_rc),
+    )
+}
+
+/// Print a summary of the complexity of a GameZ file as JSON.
+pub(crate) fn stats(opts: GamezInfoOpts) -> Result<()> {
+    log::info!("GAMEZ STATS: Reading `{}` ({})", opts.input, opts.game);
+    let mut input = CountingReader::new(SeekableInput::open(&opts.input)?);
+    let stats = match opts.game {
+        GameType::MW => {
+            let (gamez, warnings) = gamez::mw::read_gamez(&mut input, opts.options)
+                .context("Failed to read gamez data")?;
+            log_warnings(&warnings);
+            gamez_stats_mw(&gamez)
+        }
+        GameType::PM => {
+            let (gamez, warnings) = gamez::pm::read_gamez(&mut input, opts.options)
+                .context("Failed to read gamez data")?;
+            log_warnings(&warnings);
+            gamez_stats_pm(&gamez)
+        }
+        GameType::CS => {
+            let (gamez, warnings) = gamez::cs::read_gamez(&mut input, opts.options)
+                .context("Failed to read gamez data")?;
+            log_warnings(&warnings);
+            gamez_stats_cs(&gamez)
+        }
+        GameType::RC => {
+            let (gamez, warnings) = gamez::rc::read_gamez(&mut input, opts.options)
+                .context("Failed to read gamez data")?;
+            log_warnings(&warnings);
+            gamez_stats_rc(&gamez)
+        }
+    };
+    let contents = serde_json::to_string_pretty(&stats)?;
+    println!("{}", contents);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,72 @@
+use super::*;
+use mech3ax_api_types::gamez::mesh::PolygonMw;
+use mech3ax_api_types::gamez::GameZMetadataMw;
+use mech3ax_api_types::Vec3;
+use mech3ax_gamez::test_utils::{self, colored, empty, textured};
+
+fn polygon(vertex_count: u32, material_index: u32) -> PolygonMw {
+    PolygonMw {
+        vertex_indices: (0..vertex_count).collect(),
+        ..test_utils::polygon(material_index)
+    }
+}
+
+fn mesh(vertex_count: usize, polygons: Vec<PolygonMw>) -> MeshMw {
+    MeshMw {
+        vertices: vec![Vec3::DEFAULT; vertex_count],
+        ..test_utils::mesh(polygons)
+    }
+}
+
+#[test]
+fn stats_mw() {
+    let gamez = GameZDataMw {
+        textures: vec!["sky".to_string(), "ground".to_string()],
+        materials: vec![textured("sky"), colored(), textured("ground")],
+        meshes: vec![
+            // a quad and a triangle
+            mesh(4, vec![polygon(4, 0), polygon(3, 1)]),
+            mesh(3, vec![polygon(3, 2), polygon(3, 0), polygon(3, 5)]),
+        ],
+        nodes: vec![empty("a"), empty("b")],
+        metadata: GameZMetadataMw {
+            meshes_array_size: 16,
+            node_array_size: 16,
+            node_data_count: 0,
+            texture_infos: None,
+        },
+    };
+    let stats = gamez_stats_mw(&gamez);
+
+    assert_eq!(stats.mesh_count, 2);
+    assert_eq!(stats.vertex_count, 7);
+    assert_eq!(stats.polygon_count, 5);
+    assert_eq!(stats.triangle_count, 6);
+    assert_eq!(
+        stats.meshes,
+        vec![
+            MeshStats {
+                index: 0,
+                vertices: 4,
+                polygons: 2,
+                triangles: 3,
+            },
+            MeshStats {
+                index: 1,
+                vertices: 3,
+                polygons: 3,
+                triangles: 3,
+            },
+        ]
+    );
+    // the out of range material index is counted, but has no texture
+    assert_eq!(
+        stats.material_usage,
+        BTreeMap::from([(0, 2), (1, 1), (2, 1), (5, 1)])
+    );
+    assert_eq!(
+        stats.texture_usage,
+        BTreeMap::from([("ground".to_string(), 1), ("sky".to_string(), 2)])
+    );
+    assert_eq!(stats.node_types, BTreeMap::from([("Empty", 2)]));
+}
@@ -0,0 +1,290 @@
+use crate::commands::log_warnings;
+use crate::input::SeekableInput;
+use crate::stats::{node_type_cs, node_type_mw, node_type_pm, node_type_rc};
+use crate::GamezInfoOpts;
+use eyre::{Context as _, Result};
+use mech3ax_api_types::nodes::cs::NodeCs;
+use mech3ax_api_types::nodes::mw::NodeMw;
+use mech3ax_api_types::nodes::pm::NodePm;
+use mech3ax_api_types::nodes::rc::NodeRc;
+use mech3ax_common::io_ext::CountingReader;
+use mech3ax_common::GameType;
+use mech3ax_gamez::gamez;
+use mech3ax_types::u32_to_usize;
+use std::collections::HashSet;
+
+/// The parts of a node needed to show the hierarchy, for any game.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TreeNode {
+    pub name: Option<String>,
+    pub kind: &'static str,
+    pub mesh_index: Option<i32>,
+    /// The parent the node claims to have. Empty nodes are never listed as
+    /// children, so their parent isn't included.
+    pub parent: Option<u32>,
+    pub children: Vec<u32>,
+}
+
+impl TreeNode {
+    pub(crate) fn new(kind: &'static str) -> Self {
+        Self {
+            name: None,
+            kind,
+            mesh_index: None,
+            parent: None,
+            children: Vec::new(),
+        }
+    }
+
+    pub(crate) fn named(kind: &'static str, name: &str) -> Self {
+        Self {
+            name: Some(name.to_string()),
+            ..Self::new(kind)
+        }
+    }
+}
+
+fn mesh_index(mesh_index: i32) -> Option<i32> {
+    (mesh_index >= 0).then_some(mesh_index)
+}
+
+pub(crate) fn tree_nodes_mw(nodes: &[NodeMw]) -> Vec<TreeNode> {
+    nodes
+        .iter()
+        .map(|node| {
+            let kind = node_type_mw(node);
+            match node {
+                NodeMw::Camera(_) | NodeMw::Display(_) | NodeMw::Window(_) => TreeNode::new(kind),
+                NodeMw::Empty(empty) => TreeNode::named(kind, &empty.name),
+                NodeMw::Light(light) => TreeNode::named(kind, &light.name),
+                NodeMw::Lod(lod) => TreeNode {
+                    parent: Some(lod.parent),
+                    children: lod.children.clone(),
+                    ..TreeNode::named(kind, &lod.name)
+                },
+                NodeMw::Object3d(object3d) => TreeNode {
+                    mesh_index: mesh_index(object3d.mesh_index),
+                    parent: object3d.parent,
+                    children: object3d.children.clone(),
+                    ..TreeNode::named(kind, &object3d.name)
+                },
+                NodeMw::World(world) => TreeNode {
+                    children: world.children.clone(),
+                    ..TreeNode::named(kind, &world.name)
+                },
+            }
+        })
+        .collect()
+}
+
+pub(crate) fn tree_nodes_pm(nodes: &[NodePm]) -> Vec<TreeNode> {
+    nodes
+        .iter()
+        .map(|node| {
+            let kind = node_type_pm(node);
+            match node {
+                NodePm::Camera(_) | NodePm::Display(_) | NodePm::Window(_) => TreeNode::new(kind),
+                NodePm::Light(light) => TreeNode::named(kind, &light.name),
+                NodePm::Lod(lod) => TreeNode {
+                    parent: Some(lod.parent),
+                    children: lod.children.clone(),
+                    ..TreeNode::named(kind, &lod.name)
+                },
+                NodePm::Object3d(object3d) => TreeNode {
+                    mesh_index: mesh_index(object3d.mesh_index),
+                    parent: object3d.parent,
+                    children: object3d.children.clone(),
+                    ..TreeNode::named(kind, &object3d.name)
+                },
+                NodePm::World(world) => TreeNode {
+                    children: world.children.clone(),
+                    ..TreeNode::named(kind, &world.name)
+                },
+            }
+        })
+        .collect()
+}
+
+pub(crate) fn tree_nodes_cs(nodes: &[NodeCs]) -> Vec<TreeNode> {
+    nodes
+        .iter()
+        .map(|node| {
+            let kind = node_type_cs(node);
+            match node {
+                NodeCs::Camera(camera) => TreeNode::named(kind, &camera.name),
+                NodeCs::Display(_) => TreeNode::new(kind),
+                NodeCs::Light(light) => TreeNode::named(kind, &light.name),
+                NodeCs::Lod(lod) => TreeNode {
+                    parent: Some(lod.parent),
+                    children: lod.children.clone(),
+                    ..TreeNode::named(kind, &lod.name)
+                },
+                NodeCs::Object3d(object3d) => TreeNode {
+                    mesh_index: mesh_index(object3d.mesh_index),
+                    parent: object3d.parent,
+                    children: object3d.children.clone(),
+                    ..TreeNode::named(kind, &object3d.name)
+                },
+                NodeCs::Window(window) => TreeNode::named(kind, &window.name),
+                NodeCs::World(world) => TreeNode {
+                    children: world.children.clone(),
+                    ..TreeNode::named(kind, &world.name)
+                },
+            }
+        })
+        .collect()
+}
+
+pub(crate) fn tree_nodes_rc(nodes: &[NodeRc]) -> Vec<TreeNode> {
+    nodes
+        .iter()
+        .map(|node| {
+            let kind = node_type_rc(node);
+            match node {
+                NodeRc::Camera(_) | NodeRc::Display(_) | NodeRc::Window(_) => TreeNode::new(kind),
+                NodeRc::Empty(empty) => TreeNode::named(kind, &empty.name),
+                NodeRc::Light(light) => TreeNode::named(kind, &light.name),
+                NodeRc::Lod(lod) => TreeNode {
+                    parent: lod.parent,
+                    children: lod.children.clone(),
+                    ..TreeNode::named(kind, &lod.name)
+                },
+                NodeRc::Object3d(object3d) => TreeNode {
+                    mesh_index: mesh_index(object3d.mesh_index),
+                    parent: object3d.parent,
+                    children: object3d.children.clone(),
+                    ..TreeNode::named(kind, &object3d.name)
+                },
+                NodeRc::World(world) => TreeNode {
+                    children: world.children.clone(),
+                    ..TreeNode::named(kind, &world.name)
+                },
+            }
+        })
+        .collect()
+}
+
+struct Renderer<'a> {
+    nodes: &'a [TreeNode],
+    visited: HashSet<u32>,
+    path: Vec<u32>,
+    out: String,
+}
+
+impl Renderer<'_> {
+    fn line(&mut self, depth: usize, index: u32, marker: Option<&str>) {
+        let mut line = format!("{:indent$}{}", "", index, indent = depth * 2);
+        match self.nodes.get(u32_to_usize(index)) {
+            Some(node) => {
+                line.push(' ');
+                line.push_str(node.kind);
+                if let Some(name) = &node.name {
+                    line.push_str(&format!(" `{}`", name));
+                }
+                if let Some(mesh_index) = node.mesh_index {
+                    line.push_str(&format!(" mesh {}", mesh_index));
+                }
+            }
+            None => line.push_str(" [missing]"),
+        }
+        if let Some(marker) = marker {
+            line.push_str(&format!(" [{}]", marker));
+        }
+        self.out.push_str(&line);
+        self.out.push('\n');
+    }
+
+    fn render(&mut self, depth: usize, index: u32, marker: Option<&str>) {
+        let Some(node) = self.nodes.get(u32_to_usize(index)) else {
+            self.line(depth, index, None);
+            return;
+        };
+        if self.path.contains(&index) {
+            self.line(depth, index, Some("cycle"));
+            return;
+        }
+        if !self.visited.insert(index) {
+            self.line(depth, index, Some("repeated"));
+            return;
+        }
+        self.line(depth, index, marker);
+        self.path.push(index);
+        for &child in &node.children {
+            self.render(depth + 1, child, None);
+        }
+        self.path.pop();
+    }
+}
+
+/// Render the node hierarchy as an indented tree.
+///
+/// Every node that isn't a child of another node is a root. A root that
+/// claims a parent is marked as an orphan. Children that aren't in the
+/// node list are marked as missing, and children that lead back to a node
+/// on the current path are marked as a cycle, and aren't followed. Nodes
+/// that can't be reached from any root are rendered last.
+pub(crate) fn render_tree(nodes: &[TreeNode]) -> String {
+    let mut is_child = vec![false; nodes.len()];
+    for node in nodes {
+        for &child in &node.children {
+            if let Some(is_child) = is_child.get_mut(u32_to_usize(child)) {
+                *is_child = true;
+            }
+        }
+    }
+    let mut renderer = Renderer {
+        nodes,
+        visited: HashSet::new(),
+        path: Vec::new(),
+        out: String::new(),
+    };
+    for ((index, node), is_child) in (0u32..).zip(nodes).zip(&is_child) {
+        if !is_child {
+            let marker = node.parent.map(|_| "orphan");
+            renderer.render(0, index, marker);
+        }
+    }
+    for (index, _) in (0u32..).zip(nodes) {
+        if !renderer.visited.contains(&index) {
+            renderer.render(0, index, Some("unreachable"));
+        }
+    }
+    renderer.out
+}
+
+/// Print the node hierarchy of a GameZ file as a tree.
+pub(crate) fn nodes_tree(opts: GamezInfoOpts) -> Result<()> {
+    log::info!("NODES TREE: Reading `{}` ({})", opts.input, opts.game);
+    let mut input = CountingReader::new(SeekableInput::open(&opts.input)?);
+    let nodes = match opts.game {
+        GameType::MW => {
+            let (gamez, warnings) = gamez::mw::read_gamez(&mut input, opts.options)
+                .context("Failed to read gamez data")?;
+            log_warnings(&warnings);
+            tree_nodes_mw(&gamez.nodes)
+        }
+        GameType::PM => {
+            let (gamez, warnings) = gamez::pm::read_gamez(&mut input, opts.options)
+                .context("Failed to read gamez data")?;
+            log_warnings(&warnings);
+            tree_nodes_pm(&gamez.nodes)
+        }
+        GameType::CS => {
+            let (gamez, warnings) = gamez::cs::read_gamez(&mut input, opts.options)
+                .context("Failed to read gamez data")?;
+            log_warnings(&warnings);
+            tree_nodes_cs(&gamez.nodes)
+        }
+        GameType::RC => {
+            let (gamez, warnings) = gamez::rc::read_gamez(&mut input, opts.options)
+                .context("Failed to read gamez data")?;
+            log_warnings(&warnings);
+            tree_nodes_rc(&gamez.nodes)
+        }
+    };
+    print!("{}", render_tree(&nodes));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,55 @@
+use super::*;
+
+fn node(kind: &'static str, name: &str, parent: Option<u32>, children: &[u32]) -> TreeNode {
+    TreeNode {
+        parent,
+        children: children.to_vec(),
+        ..TreeNode::named(kind, name)
+    }
+}
+
+#[test]
+fn render_hierarchy() {
+    let nodes = vec![
+        node("World", "world1", None, &[2]),
+        TreeNode::new("Camera"),
+        node("Object3d", "body", Some(0), &[3, 4]),
+        TreeNode {
+            mesh_index: Some(7),
+            ..node("Object3d", "arm", Some(2), &[])
+        },
+        node("Lod", "head", Some(2), &[]),
+    ];
+    let expected = "\
+0 World `world1`
+  2 Object3d `body`
+    3 Object3d `arm` mesh 7
+    4 Lod `head`
+1 Camera
+";
+    assert_eq!(render_tree(&nodes), expected);
+}
+
+#[test]
+fn render_orphans_and_cycles() {
+    let nodes = vec![
+        node("World", "world1", None, &[1, 9]),
+        node("Object3d", "shared", Some(0), &[]),
+        // claims a parent, but isn't its child
+        node("Object3d", "orphan", Some(0), &[1]),
+        // only reachable from each other
+        node("Object3d", "ping", Some(4), &[4]),
+        node("Object3d", "pong", Some(3), &[3]),
+    ];
+    let expected = "\
+0 World `world1`
+  1 Object3d `shared`
+  9 [missing]
+2 Object3d `orphan` [orphan]
+  1 Object3d `shared` [repeated]
+3 Object3d `ping` [unreachable]
+  4 Object3d `pong`
+    3 Object3d `ping` [cycle]
+";
+    assert_eq!(render_tree(&nodes), expected);
+}
@@ -18,8 +18,11 @@ struct MapHeaderC {
     zero24: u32,  // 24
     max_y: f32,   // 28
 }
-impl_as_bytes!(MapHeaderC, 32);
+impl_as_bytes!(MapHeaderC, 32, LeStruct);
 const MAP_VERSION: u32 = 5;
 
 pub use read::read_map;
 pub use write::write_map;
+
+#[cfg(test)]
+mod tests;
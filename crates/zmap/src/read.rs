@@ -40,7 +40,7 @@ pub fn read_map(read: &mut CountingReader<impl Read>) -> Result<Zmap> {
 
     assert_that!(
         "map header version",
-        header.version == MAP_VERSION,
+        version header.version == MAP_VERSION,
         read.prev + 0
     )?;
     assert_that!("map header unk04", 1 <= header.unk04 <= 31, read.prev + 4)?;
@@ -53,8 +53,19 @@ pub fn read_map(read: &mut CountingReader<impl Read>) -> Result<Zmap> {
     let mut features = Vec::new();
     for index in 0.. {
         trace!("Reading map feature {}", index);
-        match read.read_struct::<MapColor>() {
-            Ok(color) => {
+        // only the end of the file before a feature is valid, so read the
+        // first byte on its own. otherwise, a file truncated in the middle
+        // of a color would be read as if it ended cleanly
+        let mut r = [0u8; 1];
+        match read.read_exact(&mut r) {
+            Ok(()) => {
+                let mut gb = [0u8; 2];
+                read.read_exact(&mut gb)?;
+                let color = MapColor {
+                    r: r[0],
+                    g: gb[0],
+                    b: gb[1],
+                };
                 let feature = read_map_feature(read, header.max_x, header.max_y, color, index)?;
                 features.push(feature);
             }
@@ -0,0 +1,45 @@
+use crate::{read_map, write_map};
+use mech3ax_api_types::zmap::{MapColor, MapFeature, MapVertex, Zmap};
+use mech3ax_common::io_ext::{CountingReader, CountingWriter};
+use mech3ax_common::test_utils::assert_truncated_fails_except;
+
+fn feature(count: usize) -> MapFeature {
+    MapFeature {
+        color: MapColor {
+            r: 255,
+            g: 128,
+            b: 0,
+        },
+        vertices: vec![
+            MapVertex {
+                x: 1.0,
+                z: 2.0,
+                y: 3.0,
+            };
+            count
+        ],
+        objective: -1,
+    }
+}
+
+#[test]
+fn read_truncated_fails() {
+    let map = Zmap {
+        unk04: 1,
+        max_x: 10.0,
+        max_y: 10.0,
+        features: vec![feature(2), feature(1)],
+    };
+    let mut write = CountingWriter::new(Vec::new(), 0);
+    write_map(&mut write, &map).unwrap();
+    let data = write.into_inner();
+
+    // the number of features isn't stored, so the file can end after any
+    // complete feature
+    let header = 32;
+    let first = header + 3 + 4 + 2 * 12 + 4;
+    let read = |data: &[u8]| read_map(&mut CountingReader::new(data));
+    assert_eq!(read(&data[..header]).unwrap().features.len(), 0);
+    assert_eq!(read(&data[..first]).unwrap().features.len(), 1);
+    assert_truncated_fails_except(&data, &[header, first], read);
+}
@@ -443,6 +443,56 @@ class Tester:
             # since planes uses similar code to gamez, so we have coverage.
             self.compare(input_zbd, output_zbd, limit=100)
 
+    def test_all(self) -> None:
+        print("--- ALL ---")
+        for name, zbd_dir, output_base in self.versions:
+            game = name_to_game(name)
+            game_dir = zbd_dir.parent
+
+            output_dir = output_base / "all"
+            output_dir.mkdir(exist_ok=True)
+
+            extracted_dir = output_dir / "extracted"
+            repacked_dir = output_dir / "repacked"
+            read_log = output_dir / "all-read.log"
+            write_log = output_dir / "all-write.log"
+
+            print(game, name, "all")
+
+            env = {"RUST_LOG": "trace"}
+            cmd = [
+                str(self.unzbd_exe),
+                game,
+                "all",
+                str(game_dir),
+                str(extracted_dir),
+            ]
+            with read_log.open("wb") as f:
+                subprocess.run(cmd, check=True, env=env, stderr=f)
+            cmd = [
+                str(self.rezbd_exe),
+                game,
+                "all",
+                str(extracted_dir),
+                str(repacked_dir),
+                "--original",
+                str(game_dir),
+            ]
+            with write_log.open("wb") as f:
+                subprocess.run(cmd, check=True, env=env, stderr=f)
+
+            # the repacked extensions are lowercase, the originals may not be
+            originals = {
+                str(path.relative_to(game_dir)).lower(): path
+                for path in game_dir.rglob("*")
+                if path.is_file()
+            }
+            for output_path in sorted(repacked_dir.rglob("*")):
+                if not output_path.is_file():
+                    continue
+                relative = str(output_path.relative_to(repacked_dir)).lower()
+                self.compare(originals[relative], output_path)
+
 
 def main() -> None:
     parser = ArgumentParser()
@@ -471,6 +521,7 @@ def main() -> None:
     tester.test_anim()
     tester.test_zmap()
     tester.test_planes()
+    tester.test_all()
     tester.print_miscompares()
 
 